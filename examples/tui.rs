@@ -0,0 +1,35 @@
+#[cfg(feature = "tui")]
+mod example {
+    use lminc::{assembler, runner::tui::Tui};
+
+    /// Imported assembly
+    const ASSEMBLY: &str = include_str!("fib.txt");
+
+    pub fn main() {
+        // Assemble the assembly
+        let memory = assembler::assemble_from_text(ASSEMBLY)
+            .expect("failed to parse")
+            .expect("failed to assemble");
+
+        // Create the terminal front-end, which also initialises the computer
+        let tui = Tui::new(memory);
+
+        // Run it until the user quits
+        match tui.run() {
+            Ok(state) => println!("The computer {state}"),
+            Err(error) => eprintln!("{error}"),
+        }
+    }
+}
+
+#[cfg(not(feature = "tui"))]
+mod example {
+    pub fn main() {
+        eprintln!("To run this example, the `tui` feature must be enabled!");
+    }
+}
+
+/// Shows the Fibonacci program running in a full-screen terminal view
+fn main() {
+    example::main();
+}
@@ -3,12 +3,14 @@ use core::fmt;
 use crate::{
     assembly::{Instruction, NumberOrLabel},
     computer::Memory,
-    errors::{self, InstructionNumber, LineNumber},
+    errors::{self, Code, ErrorCode, InstructionNumber, LineNumber},
+    num2::TwoDigitNumber,
     num3::ThreeDigitNumber,
     parser::{self, Parser},
 };
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 /// Assembler Errors
 pub enum Error {
     /// Failed to resolve a label
@@ -26,6 +28,15 @@ impl fmt::Display for Error {
     }
 }
 
+impl Code for Error {
+    fn code(&self) -> ErrorCode {
+        match self {
+            Self::LabelResolve(error) => error.code(),
+            Self::AddressTooLarge => ErrorCode(101),
+        }
+    }
+}
+
 #[cfg(feature = "std")]
 impl std::error::Error for Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
@@ -52,52 +63,39 @@ pub fn assemble_instruction(
     instruction: Instruction<NumberOrLabel>,
     parser: &Parser,
 ) -> Result<ThreeDigitNumber, Error> {
-    let op_code = {
-        let op_code = instruction.op_code();
-        if u16::from(op_code) == 1 {
-            0
-        } else {
-            op_code.into()
+    let op_code = instruction.op_code();
+
+    Ok(match instruction {
+        Instruction::ADD(data)
+        | Instruction::SUB(data)
+        | Instruction::STO(data)
+        | Instruction::LDA(data)
+        | Instruction::BR(data)
+        | Instruction::BRZ(data)
+        | Instruction::BRP(data) => {
+            let data = match data {
+                NumberOrLabel::Label(label) => parser.resolve_label(label)?,
+                NumberOrLabel::Number(number) => TwoDigitNumber::try_from(number)
+                    .map_err(|_| Error::AddressTooLarge)?
+                    .into(),
+            };
+
+            op_code + u16::from(data)
+        }
+
+        Instruction::IN | Instruction::OUT | Instruction::HLT => op_code,
+
+        #[cfg(feature = "extended")]
+        Instruction::INA | Instruction::OUTA | Instruction::EXT => op_code,
+
+        Instruction::DAT(data) => {
+            let data: ThreeDigitNumber = match data {
+                NumberOrLabel::Label(label) => parser.resolve_label(label)?,
+                NumberOrLabel::Number(number) => number,
+            };
+
+            op_code + u16::from(data)
         }
-    };
-
-    Ok(unsafe {
-        ThreeDigitNumber::from_unchecked(match instruction {
-            Instruction::ADD(data)
-            | Instruction::SUB(data)
-            | Instruction::STO(data)
-            | Instruction::LDA(data)
-            | Instruction::BR(data)
-            | Instruction::BRZ(data)
-            | Instruction::BRP(data) => {
-                let data = match data {
-                    NumberOrLabel::Label(label) => parser.resolve_label(label)?,
-                    NumberOrLabel::Number(number) => {
-                        if number.is_2_digit() {
-                            number
-                        } else {
-                            return Err(Error::AddressTooLarge);
-                        }
-                    }
-                };
-
-                op_code + u16::from(data)
-            }
-
-            Instruction::IN | Instruction::OUT | Instruction::HLT => op_code,
-
-            #[cfg(feature = "extended")]
-            Instruction::INA | Instruction::OUTA | Instruction::EXT => op_code,
-
-            Instruction::DAT(data) => {
-                let data: ThreeDigitNumber = match data {
-                    NumberOrLabel::Label(label) => parser.resolve_label(label)?,
-                    NumberOrLabel::Number(number) => number,
-                };
-
-                op_code + u16::from(data)
-            }
-        })
     })
 }
 
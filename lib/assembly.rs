@@ -1,6 +1,10 @@
 use core::fmt;
 
-use crate::{helper::case_insensitive::Str, num3::ThreeDigitNumber};
+use crate::{
+    errors::{Code, ErrorCode},
+    helper::case_insensitive::Str,
+    num3::ThreeDigitNumber,
+};
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 #[repr(u16)]
@@ -50,7 +54,7 @@ pub enum Instruction<Data> {
 impl<Data> Instruction<Data> {
     /// Get the op-code of the [Instruction]
     pub const fn op_code(&self) -> ThreeDigitNumber {
-        let op_code = unsafe { *(self as *const Self).cast::<u16>() };
+        let op_code = unsafe { *core::ptr::from_ref(self).cast::<u16>() };
         if op_code == 1 {
             ThreeDigitNumber::ZERO
         } else {
@@ -73,7 +77,17 @@ pub enum NumberOrLabel<'a> {
     Label(&'a str),
 }
 
+impl fmt::Display for NumberOrLabel<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(number) => fmt::Display::fmt(number, f),
+            Self::Label(label) => fmt::Display::fmt(label, f),
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum InvalidInstructionError {
     InvalidInstruction,
 }
@@ -89,37 +103,41 @@ impl fmt::Display for InvalidInstructionError {
 #[cfg(feature = "std")]
 impl std::error::Error for InvalidInstructionError {}
 
+/// The mnemonics that the assembler reads, in op-code order.
+/// The first mnemonic of each instruction is the one it is written as,
+///  and any after it are alternatives (like `STA` for `STO`)
+pub const MNEMONICS: &[(&str, Instruction<()>)] = &[
+    ("ADD", Instruction::ADD(())),
+    ("SUB", Instruction::SUB(())),
+    ("STO", Instruction::STO(())),
+    ("STA", Instruction::STO(())),
+    ("LDA", Instruction::LDA(())),
+    ("BR", Instruction::BR(())),
+    ("BRA", Instruction::BR(())),
+    ("BRZ", Instruction::BRZ(())),
+    ("BRP", Instruction::BRP(())),
+    ("IN", Instruction::IN),
+    ("INP", Instruction::IN),
+    ("OUT", Instruction::OUT),
+    #[cfg(feature = "extended")]
+    ("INA", Instruction::INA),
+    #[cfg(feature = "extended")]
+    ("OTA", Instruction::OUTA),
+    ("HLT", Instruction::HLT),
+    #[cfg(feature = "extended")]
+    ("EXT", Instruction::EXT),
+    ("DAT", Instruction::DAT(())),
+];
+
 impl TryFrom<&str> for Instruction<()> {
     type Error = InvalidInstructionError;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        match Str::from(value) {
-            i if i == "ADD" => Ok(Self::ADD(())),
-            i if i == "SUB" => Ok(Self::SUB(())),
-
-            i if i == "STO" || i == "STA" => Ok(Self::STO(())),
-            i if i == "LDA" => Ok(Self::LDA(())),
-
-            i if i == "BR" || i == "BRA" => Ok(Self::BR(())),
-            i if i == "BRZ" => Ok(Self::BRZ(())),
-            i if i == "BRP" => Ok(Self::BRP(())),
-
-            i if i == "IN" || i == "INP" => Ok(Self::IN),
-            i if i == "OUT" => Ok(Self::OUT),
-            #[cfg(feature = "extended")]
-            i if i == "INA" => Ok(Self::INA),
-            #[cfg(feature = "extended")]
-            i if i == "OTA" => Ok(Self::OUTA),
-
-            i if i == "HLT" => Ok(Self::HLT),
-
-            #[cfg(feature = "extended")]
-            i if i == "EXT" => Ok(Self::EXT),
-
-            i if i == "DAT" => Ok(Self::DAT(())),
-
-            _ => Err(InvalidInstructionError::InvalidInstruction),
-        }
+        MNEMONICS
+            .iter()
+            .find(|(mnemonic, _)| Str::from(value) == *mnemonic)
+            .map(|(_, instruction)| *instruction)
+            .ok_or(InvalidInstructionError::InvalidInstruction)
     }
 }
 
@@ -134,6 +152,7 @@ impl<'a> From<&'a str> for NumberOrLabel<'a> {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 /// A data presence error
 pub enum Error {
     /// The instruction expected data but did not receive any
@@ -151,6 +170,15 @@ impl fmt::Display for Error {
     }
 }
 
+impl Code for Error {
+    fn code(&self) -> ErrorCode {
+        match self {
+            Self::ExpectedData => ErrorCode(6),
+            Self::UnexpectedData => ErrorCode(7),
+        }
+    }
+}
+
 #[cfg(feature = "std")]
 impl std::error::Error for Error {}
 
@@ -214,11 +242,252 @@ impl Instruction<()> {
 }
 
 impl<Data> Instruction<Data> {
+    #[must_use]
+    /// Get the mnemonic that the instruction is written as, see [`MNEMONICS`]
+    pub const fn mnemonic(&self) -> &'static str {
+        match self {
+            Self::ADD(_) => "ADD",
+            Self::SUB(_) => "SUB",
+
+            Self::STO(_) => "STO",
+            Self::LDA(_) => "LDA",
+
+            Self::BR(_) => "BR",
+            Self::BRZ(_) => "BRZ",
+            Self::BRP(_) => "BRP",
+
+            Self::IN => "IN",
+            Self::OUT => "OUT",
+            #[cfg(feature = "extended")]
+            Self::INA => "INA",
+            #[cfg(feature = "extended")]
+            Self::OUTA => "OTA",
+
+            Self::HLT => "HLT",
+
+            #[cfg(feature = "extended")]
+            Self::EXT => "EXT",
+
+            Self::DAT(_) => "DAT",
+        }
+    }
+
+    #[must_use]
+    /// Get what the instruction does, where `xx` is the address / label
+    ///  (or `xxx` the number, for [`Instruction::DAT`])
+    pub const fn description(&self) -> &'static str {
+        match self {
+            Self::ADD(_) => "Add the contents of the memory at address xx to the register",
+            Self::SUB(_) => "Subtract the contents of the memory at address xx from the register, setting the negative flag if the result underflows, otherwise clearing it",
+
+            Self::STO(_) => "Store the register in the memory at address xx",
+            Self::LDA(_) => "Load the memory at address xx into the register",
+
+            Self::BR(_) => "Go to address xx",
+            Self::BRZ(_) => "If the register is zero, go to address xx",
+            Self::BRP(_) => "If the negative flag is not set, go to address xx",
+
+            Self::IN => "Take an input and store it in the register",
+            Self::OUT => "Output the register",
+            #[cfg(feature = "extended")]
+            Self::INA => "Take a char input and store it in the register (in extended mode)",
+            #[cfg(feature = "extended")]
+            Self::OUTA => "Output the register as a char (in extended mode)",
+
+            Self::HLT => "Halt the computer",
+
+            #[cfg(feature = "extended")]
+            Self::EXT => "Enable extended mode, for char input and output",
+
+            Self::DAT(_) => "Store the number xxx, which is not run as an instruction",
+        }
+    }
+
+    #[must_use]
+    /// Get the label / number of the instruction, if it has one
+    pub const fn data(&self) -> Option<&Data> {
+        match self {
+            Self::ADD(data)
+            | Self::SUB(data)
+            | Self::STO(data)
+            | Self::LDA(data)
+            | Self::BR(data)
+            | Self::BRZ(data)
+            | Self::BRP(data)
+            | Self::DAT(data) => Some(data),
+            _ => None,
+        }
+    }
+
+    /// Change the label / number of the instruction, keeping the instruction
+    ///
+    /// ```
+    /// # use lminc::assembly::Instruction;
+    /// assert_eq!(Instruction::ADD(5).map_data(|data| data * 2), Instruction::ADD(10));
+    /// assert_eq!(Instruction::<u8>::HLT.map_data(|data| data * 2), Instruction::HLT);
+    /// ```
+    pub fn map_data<NewData>(self, f: impl FnOnce(Data) -> NewData) -> Instruction<NewData> {
+        match self {
+            Self::ADD(data) => Instruction::ADD(f(data)),
+            Self::SUB(data) => Instruction::SUB(f(data)),
+
+            Self::STO(data) => Instruction::STO(f(data)),
+            Self::LDA(data) => Instruction::LDA(f(data)),
+
+            Self::BR(data) => Instruction::BR(f(data)),
+            Self::BRZ(data) => Instruction::BRZ(f(data)),
+            Self::BRP(data) => Instruction::BRP(f(data)),
+
+            Self::IN => Instruction::IN,
+            Self::OUT => Instruction::OUT,
+            #[cfg(feature = "extended")]
+            Self::INA => Instruction::INA,
+            #[cfg(feature = "extended")]
+            Self::OUTA => Instruction::OUTA,
+
+            Self::HLT => Instruction::HLT,
+
+            #[cfg(feature = "extended")]
+            Self::EXT => Instruction::EXT,
+
+            Self::DAT(data) => Instruction::DAT(f(data)),
+        }
+    }
+
     /// Add a label to an instruction
-    pub const fn add_label(self, label: Option<&str>) -> InstructionWithLabel<Data> {
+    pub const fn add_label(self, label: Option<&str>) -> InstructionWithLabel<'_, Data> {
         InstructionWithLabel {
             label,
             instruction: self,
         }
     }
 }
+
+impl<Data: fmt::Display> fmt::Display for Instruction<Data> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.data() {
+            Some(data) => write!(f, "{} {data}", self.mnemonic()),
+            None => f.write_str(self.mnemonic()),
+        }
+    }
+}
+
+impl RawInstruction {
+    #[must_use]
+    /// Decode a number into the instruction that [`Computer::execute`](crate::computer::Computer::execute)
+    ///  runs it as, or `None` if it is an invalid instruction (`4xx` and the other `9xx`).
+    /// Unlike [`From`], every `0xx` is [`Instruction::HLT`] (apart from `EXT`),
+    ///  as that is what the computer does with them
+    pub fn executed(value: ThreeDigitNumber) -> Option<Self> {
+        match Self::from(value) {
+            Self::DAT(_) if value.hundreds() == 0 => Some(Self::HLT),
+            Self::DAT(_) => None,
+            instruction => Some(instruction),
+        }
+    }
+}
+
+impl From<ThreeDigitNumber> for RawInstruction {
+    /// Decode a number into the instruction it is written as,
+    ///  numbers that are not instructions are decoded as [`Instruction::DAT`].
+    /// See [`RawInstruction::executed`] for what the computer runs them as
+    fn from(value: ThreeDigitNumber) -> Self {
+        let number = u16::from(value);
+        let (op_code, address) = value.split_op();
+        let data = ThreeDigitNumber::from(address);
+
+        match op_code {
+            1 => Self::ADD(data),
+            2 => Self::SUB(data),
+            3 => Self::STO(data),
+            5 => Self::LDA(data),
+            6 => Self::BR(data),
+            7 => Self::BRZ(data),
+            8 => Self::BRP(data),
+            _ => match number {
+                901 => Self::IN,
+                902 => Self::OUT,
+                #[cfg(feature = "extended")]
+                911 => Self::INA,
+                #[cfg(feature = "extended")]
+                912 => Self::OUTA,
+                0 => Self::HLT,
+                #[cfg(feature = "extended")]
+                10 => Self::EXT,
+                _ => Self::DAT(value),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::num3::ThreeDigitNumber;
+
+    use super::{Instruction, RawInstruction, MNEMONICS};
+
+    #[test]
+    fn mnemonics() {
+        for (mnemonic, instruction) in MNEMONICS {
+            assert_eq!(
+                Instruction::try_from(*mnemonic),
+                Ok(*instruction),
+                "Failed to read the mnemonic {mnemonic}!"
+            );
+            assert_eq!(
+                Instruction::try_from(mnemonic.to_lowercase().as_str()),
+                Ok(*instruction),
+                "Failed to read the mnemonic {mnemonic} in lower case!"
+            );
+        }
+
+        // The mnemonic of each instruction is its first one in the table
+        for (index, (mnemonic, instruction)) in MNEMONICS.iter().enumerate() {
+            if MNEMONICS[..index]
+                .iter()
+                .all(|(_, other)| other != instruction)
+            {
+                assert_eq!(
+                    instruction.mnemonic(),
+                    *mnemonic,
+                    "Got the wrong mnemonic for {instruction:?}!"
+                );
+            }
+        }
+
+        assert!(
+            Instruction::try_from("NOP").is_err(),
+            "Read an unknown mnemonic!"
+        );
+        assert_eq!(
+            RawInstruction::from(
+                ThreeDigitNumber::try_from(316_u16).expect("failed to create the number")
+            )
+            .to_string(),
+            "STO 16",
+            "Displayed the wrong instruction!"
+        );
+    }
+
+    #[test]
+    fn executed() {
+        let executed = |value: u16| {
+            RawInstruction::executed(
+                ThreeDigitNumber::try_from(value).expect("failed to create the number"),
+            )
+        };
+
+        assert_eq!(
+            executed(316),
+            Some(Instruction::STO(ThreeDigitNumber::new::<16>())),
+            "Decoded the wrong instruction!"
+        );
+        assert_eq!(
+            executed(1),
+            Some(Instruction::HLT),
+            "Failed to halt on 001!"
+        );
+        assert_eq!(executed(405), None, "Failed to reject 405!");
+        assert_eq!(executed(903), None, "Failed to reject 903!");
+    }
+}
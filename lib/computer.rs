@@ -1,13 +1,20 @@
-use core::fmt;
+use core::{
+    fmt,
+    ops::{Index, IndexMut},
+};
 
-use crate::num3::ThreeDigitNumber;
+use crate::{
+    errors::{Code, ErrorCode},
+    num2::TwoDigitNumber,
+    num3::ThreeDigitNumber,
+};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 /// The computer that runs programs
 pub struct Computer {
     state: State,
     memory: Memory,
-    counter: usize,
+    counter: Option<TwoDigitNumber>,
     register: ThreeDigitNumber,
     negative_flag: bool,
     #[cfg(feature = "extended")]
@@ -16,7 +23,23 @@ pub struct Computer {
 
 pub type Memory = [ThreeDigitNumber; 100];
 
+impl Index<TwoDigitNumber> for Memory {
+    type Output = ThreeDigitNumber;
+
+    fn index(&self, index: TwoDigitNumber) -> &Self::Output {
+        // Two digit numbers are always valid addresses
+        &self[usize::from(index)]
+    }
+}
+
+impl IndexMut<TwoDigitNumber> for Memory {
+    fn index_mut(&mut self, index: TwoDigitNumber) -> &mut Self::Output {
+        &mut self[usize::from(index)]
+    }
+}
+
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 /// The states for [Computer]s
 pub enum State {
     #[default]
@@ -50,6 +73,7 @@ impl fmt::Display for State {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 /// Errors for [Computer] Io
 pub enum Error {
     /// The computer was not waiting for an input, but one was given
@@ -89,26 +113,21 @@ impl fmt::Display for Error {
     }
 }
 
-#[cfg(feature = "std")]
-impl std::error::Error for Error {}
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-/// Errors for `set_counter`
-pub enum SetCounterError {
-    /// The given value is too large (> 99)
-    TooLarge,
-}
-
-impl fmt::Display for SetCounterError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl Code for Error {
+    fn code(&self) -> ErrorCode {
         match self {
-            Self::TooLarge => write!(f, "The given value for the counter was too large (> 99)!"),
+            Self::UnexpectedInput => ErrorCode(401),
+            Self::NoOutput => ErrorCode(402),
+            #[cfg(feature = "extended")]
+            Self::UnexpectedCharInput => ErrorCode(403),
+            #[cfg(feature = "extended")]
+            Self::NoCharOutput => ErrorCode(404),
         }
     }
 }
 
 #[cfg(feature = "std")]
-impl std::error::Error for SetCounterError {}
+impl std::error::Error for Error {}
 
 impl Computer {
     #[must_use]
@@ -117,7 +136,7 @@ impl Computer {
         Self {
             state: State::Running,
             memory,
-            counter: 0,
+            counter: Some(TwoDigitNumber::ZERO),
             register: ThreeDigitNumber::ZERO,
             negative_flag: false,
             #[cfg(feature = "extended")]
@@ -131,56 +150,63 @@ impl Computer {
             return self.state;
         }
 
-        if self.counter == 100 {
+        let Some(counter) = self.counter else {
             self.state = State::ReachedEnd;
             return self.state;
+        };
+
+        self.execute(self.memory[counter])
+    }
+
+    /// Run the given instruction as if it were in memory at the counter
+    pub fn execute(&mut self, instruction: ThreeDigitNumber) -> State {
+        if self.state != State::Running {
+            return self.state;
         }
 
-        let instruction = u16::from(self.memory[self.counter]);
-        let op_code = instruction / 100;
-        let data = instruction % 100;
+        let (op_code, address) = instruction.split_op();
 
         match op_code {
             // ADD
             1 => {
-                self.register += self.memory[data as usize];
+                self.register += self.memory[address];
             }
             // SUB
             2 => {
-                let (register, negative_flag) = self.register - self.memory[data as usize];
+                let (register, negative_flag) = self.register - self.memory[address];
                 self.register = register;
                 self.negative_flag = negative_flag;
             }
             // STO
             3 => {
-                self.memory[data as usize] = self.register;
+                self.memory[address] = self.register;
             }
             // LDA
             5 => {
-                self.register = self.memory[data as usize];
+                self.register = self.memory[address];
             }
             // BR
             6 => {
-                self.counter = data as usize;
+                self.counter = Some(address);
                 return self.state;
             }
             // BRZ
             7 => {
                 if self.register == ThreeDigitNumber::ZERO {
-                    self.counter = data as usize;
+                    self.counter = Some(address);
                     return self.state;
                 }
             }
             // BRP
             8 => {
                 if !self.negative_flag {
-                    self.counter = data as usize;
+                    self.counter = Some(address);
                     return self.state;
                 }
             }
             // IO
             9 => {
-                match data {
+                match u8::from(address) {
                     // IN
                     1 => {
                         self.state = State::AwaitingInput;
@@ -209,7 +235,7 @@ impl Computer {
             // HLT
             0 => {
                 #[cfg(feature = "extended")]
-                if data == 10 {
+                if u8::from(address) == 10 {
                     self.extended_mode_flag = true;
                 } else {
                     self.state = State::Halted;
@@ -226,7 +252,7 @@ impl Computer {
             }
         }
 
-        self.counter += 1;
+        self.counter = self.counter.and_then(TwoDigitNumber::checked_next);
         self.state
     }
 
@@ -303,14 +329,14 @@ impl Computer {
     }
 
     /// Set a [Computer]'s [State]
-    pub fn set_state(computer: &mut Self, value: State) {
+    pub const fn set_state(computer: &mut Self, value: State) {
         computer.state = value;
     }
 
     /// Reset the [Computer] without resetting the [Memory]
-    pub fn reset(&mut self) {
+    pub const fn reset(&mut self) {
         self.state = State::Running;
-        self.counter = 0;
+        self.counter = Some(TwoDigitNumber::ZERO);
         self.register = ThreeDigitNumber::ZERO;
         self.negative_flag = false;
         #[cfg(feature = "extended")]
@@ -326,27 +352,20 @@ impl Computer {
     }
 
     /// Mutably get a [Computer]'s [Memory]
-    pub fn get_memory_mut(computer: &mut Self) -> &mut Memory {
+    pub const fn get_memory_mut(computer: &mut Self) -> &mut Memory {
         &mut computer.memory
     }
 
     #[must_use]
-    /// Get the [Computer]'s counter
-    pub const fn counter(&self) -> usize {
+    /// Get the [Computer]'s counter, the address of the next instruction,
+    ///  or [`None`] if it has run past the end of its [Memory]
+    pub const fn counter(&self) -> Option<TwoDigitNumber> {
         self.counter
     }
 
     /// Set a [Computer]'s counter
-    ///
-    /// # Errors
-    /// See [`SetCounterError`]
-    pub fn set_counter(computer: &mut Self, value: usize) -> Result<(), SetCounterError> {
-        if value > 100 {
-            Err(SetCounterError::TooLarge)
-        } else {
-            computer.counter = value;
-            Ok(())
-        }
+    pub const fn set_counter(computer: &mut Self, value: TwoDigitNumber) {
+        computer.counter = Some(value);
     }
 
     #[must_use]
@@ -356,7 +375,7 @@ impl Computer {
     }
 
     /// Set a [Computer]'s register
-    pub fn set_register(computer: &mut Self, value: ThreeDigitNumber) {
+    pub const fn set_register(computer: &mut Self, value: ThreeDigitNumber) {
         computer.register = value;
     }
 
@@ -367,7 +386,7 @@ impl Computer {
     }
 
     /// Set a [Computer]'s negative flag
-    pub fn set_negative_flag(computer: &mut Self, value: bool) {
+    pub const fn set_negative_flag(computer: &mut Self, value: bool) {
         computer.negative_flag = value;
     }
 
@@ -380,7 +399,7 @@ impl Computer {
 
     #[cfg(feature = "extended")]
     /// Set a [Computer]'s extended mode flag
-    pub fn set_extended_mode_flag(computer: &mut Self, value: bool) {
+    pub const fn set_extended_mode_flag(computer: &mut Self, value: bool) {
         computer.extended_mode_flag = value;
     }
 }
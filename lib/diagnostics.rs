@@ -0,0 +1,399 @@
+use core::{fmt, iter, ops::Range};
+
+use crate::{
+    assembler,
+    assembly::{self, Instruction},
+    errors::{LineNumber, Severity, Span},
+    lint::{Lint, LintWithLineNumber},
+    parser,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind<'a> {
+    Parse(parser::Error),
+    Assemble(assembler::Error),
+    Lint(Lint<'a>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// A parse or assemble error (or a lint) with the line of source it came from,
+///  displayed with a caret under the offending word and a suggestion
+///
+/// ```
+/// # use lminc::{assembler::assemble_from_text, diagnostics::Diagnostic};
+/// let assembly = "start LDA one\n      OUT\n      HLT\nonce  DAT 1";
+/// let error = assemble_from_text(assembly)
+///     .expect("failed to parse")
+///     .expect_err("assembled an unknown label");
+/// let diagnostic = Diagnostic::from_assembler_error(assembly, &error).expect("no line");
+///
+/// assert_eq!(
+///     diagnostic.to_string(),
+///     "error: Unknown label!
+///  --> line 1
+///   |
+/// 1 | start LDA one
+///   |           ^^^
+///   = help: labels are defined by writing them before an instruction
+/// "
+/// );
+/// ```
+pub struct Diagnostic<'a> {
+    kind: Kind<'a>,
+    line_number: usize,
+    line: &'a str,
+    span: Range<usize>,
+    color: bool,
+}
+
+/// The ANSI escape codes used by [`Diagnostic::with_color`]
+mod style {
+    pub const RESET: &str = "\x1b[0m";
+    pub const BOLD: &str = "\x1b[1m";
+    pub const ERROR: &str = "\x1b[1;31m";
+    pub const WARNING: &str = "\x1b[1;33m";
+    pub const GUTTER: &str = "\x1b[1;34m";
+}
+
+impl<'a> Diagnostic<'a> {
+    #[must_use]
+    /// Make a diagnostic for a parse error from the text that was parsed,
+    ///  returning `None` if the text does not have the error's line
+    pub fn from_parse_error(
+        text: &'a str,
+        error: &parser::ErrorWithLocation<LineNumber>,
+    ) -> Option<Self> {
+        let line = text.lines().nth(error.location().0.checked_sub(1)?)?;
+
+        Some(Self {
+            kind: Kind::Parse(*error.error()),
+            line_number: error.location().0,
+            line,
+            span: parse_error_span(line, *error.error())?,
+            color: false,
+        })
+    }
+
+    #[must_use]
+    /// Make a diagnostic for an assembler error from the text that was assembled,
+    ///  returning `None` if the text does not have the error's instruction
+    pub fn from_assembler_error(
+        text: &'a str,
+        error: &assembler::ErrorWithInstructionNumber,
+    ) -> Option<Self> {
+        // Find the line of the instruction, skipping lines without code
+        let (index, line) = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| words(line).next().is_some())
+            .nth(error.location().0.checked_sub(1)?)?;
+
+        let span = match *error.error() {
+            assembler::Error::LabelResolve(error) => parse_error_span(line, error)?,
+            assembler::Error::AddressTooLarge => words(line).last()?,
+        };
+
+        Some(Self {
+            kind: Kind::Assemble(*error.error()),
+            line_number: index + 1,
+            line,
+            span,
+            color: false,
+        })
+    }
+
+    #[must_use]
+    /// Make a diagnostic for a lint from the text that was linted,
+    ///  returning `None` if the text does not have the lint's line
+    pub fn from_lint(text: &'a str, warning: &LintWithLineNumber<'a>) -> Option<Self> {
+        let line = text.lines().nth(warning.location().0.checked_sub(1)?)?;
+
+        Some(Self {
+            kind: Kind::Lint(*warning.error()),
+            line_number: warning.location().0,
+            line,
+            // All lints are about the label, which is the first word
+            span: words(line).next()?,
+            color: false,
+        })
+    }
+
+    #[must_use]
+    /// Color the diagnostic with ANSI escape codes when it is displayed, for terminals
+    pub const fn with_color(mut self) -> Self {
+        self.color = true;
+        self
+    }
+
+    #[must_use]
+    /// Get whether this is an error or a warning (lints are warnings)
+    pub const fn severity(&self) -> Severity {
+        match self.kind {
+            Kind::Parse(_) | Kind::Assemble(_) => Severity::Error,
+            Kind::Lint(_) => Severity::Warning,
+        }
+    }
+
+    #[must_use]
+    /// Get the line number (starting at 1) of the error
+    pub const fn line_number(&self) -> usize {
+        self.line_number
+    }
+
+    #[must_use]
+    /// Get the byte range of the offending word(s) in the line
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    #[must_use]
+    /// Get where the offending word(s) are in the source
+    pub fn location(&self) -> Span {
+        Span::from_line(LineNumber(self.line_number), self.line, self.span())
+    }
+
+    #[must_use]
+    /// Get the suggestion for fixing the error
+    pub const fn help(&self) -> &'static str {
+        let error = match self.kind {
+            Kind::Parse(error) | Kind::Assemble(assembler::Error::LabelResolve(error)) => error,
+            Kind::Assemble(assembler::Error::AddressTooLarge) => {
+                return "addresses are from 0 to 99, use DAT to store larger numbers"
+            }
+            Kind::Lint(Lint::UnusedLabel(_)) => return "remove the label, or use it as an address",
+            Kind::Lint(Lint::DuplicateLabel(_)) => {
+                return "labels must be unique, rename or remove this one"
+            }
+        };
+
+        match error {
+            parser::Error::TooManyWords => {
+                "a line can only have a label, an instruction and a label / number"
+            }
+            parser::Error::TooManyInstructions => "the memory only has space for 100 instructions",
+            parser::Error::MultipleInstructions => "put each instruction on its own line",
+            parser::Error::UnexpectedNumber => "labels cannot be numbers",
+            parser::Error::NoInstruction => {
+                "add an instruction, or start a comment with '#' or ';'"
+            }
+            parser::Error::DataPresence(assembly::Error::ExpectedData) => {
+                "add the address or label that this instruction uses"
+            }
+            parser::Error::DataPresence(assembly::Error::UnexpectedData) => {
+                "this instruction does not use a label / number"
+            }
+            parser::Error::UnknownLabel => {
+                "labels are defined by writing them before an instruction"
+            }
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let line_number = self.line_number;
+        // The number of digits in the line number
+        let gutter = iter::successors(Some(line_number), |number| {
+            (*number >= 10).then_some(number / 10)
+        })
+        .count();
+
+        let severity = self.severity();
+        // The severity and carets are colored by the severity, and the gutter is blue
+        let (accent, bold, blue, reset) = match (self.color, severity) {
+            (false, _) => ("", "", "", ""),
+            (true, Severity::Error) => (style::ERROR, style::BOLD, style::GUTTER, style::RESET),
+            (true, Severity::Warning) => (style::WARNING, style::BOLD, style::GUTTER, style::RESET),
+        };
+
+        write!(f, "{accent}{severity}{reset}{bold}: ")?;
+        match self.kind {
+            Kind::Parse(error) => write!(f, "{error}")?,
+            Kind::Assemble(error) => write!(f, "{error}")?,
+            Kind::Lint(lint) => write!(f, "{lint}")?,
+        }
+        writeln!(f, "{reset}")?;
+        writeln!(f, "{blue}{:gutter$}-->{reset} line {line_number}", "")?;
+        writeln!(f, "{blue}{:gutter$} |{reset}", "")?;
+        writeln!(f, "{blue}{line_number} |{reset} {}", self.line)?;
+
+        // Copy tabs from the line so that the caret lines up
+        write!(f, "{blue}{:gutter$} |{reset} ", "")?;
+        for character in self.line[..self.span.start].chars() {
+            f.write_str(if character == '\t' { "\t" } else { " " })?;
+        }
+        f.write_str(accent)?;
+        for _ in self.line[self.span.clone()].chars() {
+            f.write_str("^")?;
+        }
+        writeln!(f, "{reset}")?;
+
+        writeln!(
+            f,
+            "{blue}{:gutter$} ={reset} {bold}help{reset}: {}",
+            "",
+            self.help()
+        )
+    }
+}
+
+/// Get the byte ranges of the words in a line, before any comments
+fn words(line: &str) -> impl Iterator<Item = Range<usize>> + '_ {
+    let code = line.split(&['#', ';'][..]).next().unwrap_or_default();
+
+    let mut start = None;
+    code.char_indices()
+        .chain(iter::once((code.len(), ' ')))
+        .filter_map(
+            move |(index, character)| match (start, character.is_whitespace()) {
+                (None, false) => {
+                    start = Some(index);
+                    None
+                }
+                (Some(word_start), true) => {
+                    start = None;
+                    Some(word_start..index)
+                }
+                _ => None,
+            },
+        )
+}
+
+/// Find the word(s) in a line that caused a parse error
+fn parse_error_span(line: &str, error: parser::Error) -> Option<Range<usize>> {
+    let is_instruction =
+        |span: &Range<usize>| Instruction::<()>::try_from(&line[span.clone()]).is_ok();
+    let mut words = words(line);
+
+    match error {
+        parser::Error::UnexpectedNumber => words.next(),
+        parser::Error::MultipleInstructions => words.filter(is_instruction).nth(1),
+        // Either there are more than 3 words, or the third word is a second label / number
+        parser::Error::TooManyWords => {
+            let third = words.nth(2)?;
+            Some(third.start..words.last().unwrap_or(third).end)
+        }
+        parser::Error::TooManyInstructions | parser::Error::NoInstruction => {
+            let first = words.next()?;
+            Some(first.start..words.last().unwrap_or(first).end)
+        }
+        parser::Error::DataPresence(assembly::Error::ExpectedData) => words.find(is_instruction),
+        parser::Error::DataPresence(assembly::Error::UnexpectedData) => {
+            words.find(is_instruction)?;
+            words.next()
+        }
+        parser::Error::UnknownLabel => words.last(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{assembler::assemble_from_text, parser::Parser};
+    #[cfg(feature = "alloc")]
+    use crate::{errors::Severity, lint::lint};
+
+    use super::Diagnostic;
+
+    fn parse_span(text: &str) -> (usize, &str) {
+        let error = Parser::parse_text(text).expect_err("parsed invalid assembly");
+        let diagnostic = Diagnostic::from_parse_error(text, &error).expect("failed to find line");
+        let line = text
+            .lines()
+            .nth(diagnostic.line_number() - 1)
+            .expect("failed to find line");
+
+        (diagnostic.line_number(), &line[diagnostic.span()])
+    }
+
+    #[test]
+    fn spans() {
+        assert_eq!(
+            parse_span("IN\n  5 OUT"),
+            (2, "5"),
+            "Failed to find a number used as a label!"
+        );
+        assert_eq!(
+            parse_span("IN OUT # HLT"),
+            (1, "OUT"),
+            "Failed to find the second instruction!"
+        );
+        assert_eq!(
+            parse_span("a DAT 1 2 3"),
+            (1, "1 2 3"),
+            "Failed to find the extra words!"
+        );
+        assert_eq!(
+            parse_span("\tHLT 5"),
+            (1, "5"),
+            "Failed to find unexpected data!"
+        );
+        assert_eq!(
+            parse_span("\n\nlabel ADD"),
+            (3, "ADD"),
+            "Failed to find the instruction missing data!"
+        );
+
+        let text = "# comment\n\n  ADD 100\n";
+        let error = assemble_from_text(text)
+            .expect("failed to parse")
+            .expect_err("assembled a large address");
+        let diagnostic =
+            Diagnostic::from_assembler_error(text, &error).expect("failed to find line");
+        assert_eq!(
+            (diagnostic.line_number(), diagnostic.span()),
+            (3, 6..9),
+            "Failed to find a large address!"
+        );
+        assert_eq!(
+            diagnostic.location().to_string(),
+            "line 3, columns 7..10",
+            "Failed to make the span of a large address!"
+        );
+    }
+
+    #[test]
+    fn render() {
+        let text = "\tIN\n\tSTO\n\tHLT";
+        let error = Parser::parse_text(text).expect_err("parsed invalid assembly");
+        let diagnostic = Diagnostic::from_parse_error(text, &error).expect("failed to find line");
+
+        assert_eq!(
+            diagnostic.to_string(),
+            "error: Expected label / number!\n --> line 2\n  |\n2 | \tSTO\n  | \t^^^\n  = help: add the address or label that this instruction uses\n",
+            "Failed to render the diagnostic!"
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn render_lint() {
+        let text = "  IN
+loop OUT
+  HLT";
+        let lints = lint(text).expect("failed to lint");
+        let diagnostic = Diagnostic::from_lint(text, &lints[0]).expect("failed to find line");
+
+        assert_eq!(
+            diagnostic.severity(),
+            Severity::Warning,
+            "Lint was not a warning!"
+        );
+        assert_eq!(
+            diagnostic.to_string(),
+            "warning: Label 'loop' is never used!\n --> line 2\n  |\n2 | loop OUT\n  | ^^^^\n  = help: remove the label, or use it as an address\n",
+            "Failed to render the lint!"
+        );
+
+        assert_eq!(
+            diagnostic.with_color().to_string(),
+            "\x1b[1;33mwarning\x1b[0m\x1b[1m: Label 'loop' is never used!\x1b[0m
+\x1b[1;34m -->\x1b[0m line 2
+\x1b[1;34m  |\x1b[0m
+\x1b[1;34m2 |\x1b[0m loop OUT
+\x1b[1;34m  |\x1b[0m \x1b[1;33m^^^^\x1b[0m
+\x1b[1;34m  =\x1b[0m \x1b[1mhelp\x1b[0m: remove the label, or use it as an address
+",
+            "Failed to render the lint with color!"
+        );
+    }
+}
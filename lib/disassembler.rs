@@ -0,0 +1,352 @@
+#[cfg(feature = "alloc")]
+extern crate alloc;
+use core::fmt::{self, Write};
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+use crate::{assembly::RawInstruction, computer::Memory, num3::ThreeDigitNumber};
+
+/// Get the length of the program, without the zeros after the last non-zero number
+fn program_length(memory: &Memory) -> usize {
+    memory
+        .iter()
+        .rposition(|number| *number != ThreeDigitNumber::ZERO)
+        .map_or(0, |index| index + 1)
+}
+
+/// Write the [Memory] as assembly, one instruction per line.
+///
+/// Numbers that are not instructions are written as `DAT`,
+///  and the zeros after the last non-zero number are left out.
+/// Assembling the output gives the same memory
+///
+/// # Errors
+/// [`fmt::Error`] - writer error
+pub fn write_disassembly(out: &mut impl Write, memory: Memory) -> fmt::Result {
+    for number in &memory[..program_length(&memory)] {
+        writeln!(out, "{}", RawInstruction::from(*number))?;
+    }
+
+    Ok(())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// The kinds of label made up by [`write_labelled_disassembly`]
+enum LabelKind {
+    /// An address that is branched to
+    Branch,
+    /// An address that is added, subtracted, stored or loaded
+    Data,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// A made up label, like `l05` or `d05`
+struct Label(LabelKind, usize);
+
+impl fmt::Display for Label {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            LabelKind::Branch => write!(f, "l{:02}", self.1),
+            LabelKind::Data => write!(f, "d{:02}", self.1),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// The label / number of a disassembled instruction
+enum Operand {
+    Number(ThreeDigitNumber),
+    Label(Label),
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(number) => fmt::Display::fmt(number, f),
+            Self::Label(label) => fmt::Display::fmt(label, f),
+        }
+    }
+}
+
+/// Get the address that an instruction uses, and the kind of label it should have
+const fn target(instruction: RawInstruction) -> Option<(ThreeDigitNumber, LabelKind)> {
+    match instruction {
+        RawInstruction::ADD(address)
+        | RawInstruction::SUB(address)
+        | RawInstruction::STO(address)
+        | RawInstruction::LDA(address) => Some((address, LabelKind::Data)),
+        RawInstruction::BR(address)
+        | RawInstruction::BRZ(address)
+        | RawInstruction::BRP(address) => Some((address, LabelKind::Branch)),
+        _ => None,
+    }
+}
+
+/// Write the [Memory] as assembly like [`write_disassembly`], but with labels made up
+///  for the addresses that instructions use, so that the program is easier to follow.
+///
+/// Branch targets are labelled `lNN` and other addresses `dNN`, where `NN` is the address.
+/// Addresses after the end of the program are left as numbers
+///
+/// # Errors
+/// [`fmt::Error`] - writer error
+pub fn write_labelled_disassembly(out: &mut impl Write, memory: Memory) -> fmt::Result {
+    let length = program_length(&memory);
+
+    let mut labels = [None; 100];
+    for number in &memory[..length] {
+        if let Some((address, kind)) = target(RawInstruction::from(*number)) {
+            let address = usize::from(address);
+            // Branch labels take priority, as they show the control flow
+            if address < length && labels[address] != Some(LabelKind::Branch) {
+                labels[address] = Some(kind);
+            }
+        }
+    }
+
+    for (address, number) in memory[..length].iter().enumerate() {
+        match labels[address] {
+            Some(kind) => write!(out, "{} ", Label(kind, address))?,
+            None => write!(out, "    ")?,
+        }
+
+        let instruction = RawInstruction::from(*number);
+        // The data of DAT is a value, not an address
+        let uses_address = target(instruction).is_some();
+        let instruction = instruction.map_data(|data| {
+            let address = usize::from(data);
+            match labels.get(address).copied().flatten() {
+                Some(kind) if uses_address => Operand::Label(Label(kind, address)),
+                _ => Operand::Number(data),
+            }
+        });
+        writeln!(out, "{instruction}")?;
+    }
+
+    Ok(())
+}
+
+/// A writer that only counts the length of what is written, for padding
+struct Length(usize);
+
+impl Write for Length {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.chars().count();
+        Ok(())
+    }
+}
+
+/// Write the [Memory] as a table of 10 rows of 10, with `cell` giving what is written for
+///  each non-zero number, right-aligned to `width`
+fn write_table_with<T: fmt::Display>(
+    out: &mut impl Write,
+    memory: Memory,
+    width: usize,
+    cell: impl Fn(ThreeDigitNumber) -> T,
+) -> fmt::Result {
+    write!(out, "   |")?;
+    for column in 0..10 {
+        write!(out, " {column:>width$}")?;
+    }
+    writeln!(out)?;
+    writeln!(out, "---+{:-<1$}", "", 10 * (width + 1))?;
+
+    let mut skipping = false;
+    for (row, numbers) in memory.chunks(10).enumerate() {
+        // Write one '...' for each run of rows of zeros
+        if numbers
+            .iter()
+            .all(|number| *number == ThreeDigitNumber::ZERO)
+        {
+            if !skipping {
+                writeln!(out, "...")?;
+            }
+            skipping = true;
+            continue;
+        }
+        skipping = false;
+
+        write!(out, "{:02} |", row * 10)?;
+        for number in numbers {
+            if *number == ThreeDigitNumber::ZERO {
+                write!(out, " {:>width$}", ".")?;
+                continue;
+            }
+
+            let value = cell(*number);
+            let mut length = Length(0);
+            write!(length, "{value}")?;
+            write!(out, " {:1$}{value}", "", width.saturating_sub(length.0))?;
+        }
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
+
+/// Write the [Memory] as a table of 10 rows of 10 numbers, with the addresses
+///  down the side and across the top.
+///
+/// Zeros are written as `.`, and rows of zeros are left out, with `...` in their place
+///
+/// # Errors
+/// [`fmt::Error`] - writer error
+pub fn write_table(out: &mut impl Write, memory: Memory) -> fmt::Result {
+    write_table_with(out, memory, 3, |number| number)
+}
+
+/// Write the [Memory] as a table like [`write_table`], but with each number
+///  decoded into the instruction it runs as (numbers that are not instructions are `DAT`)
+///
+/// # Errors
+/// [`fmt::Error`] - writer error
+pub fn write_decoded_table(out: &mut impl Write, memory: Memory) -> fmt::Result {
+    write_table_with(out, memory, 7, RawInstruction::from)
+}
+
+#[cfg(feature = "alloc")]
+#[must_use]
+/// Disassemble the [Memory] to assembly, see [`write_disassembly`]
+///
+/// ```
+/// # use lminc::{assembler::assemble_from_text, disassembler::disassemble};
+/// let memory = assemble_from_text("IN\nSTO 99\nOUT\nHLT")??;
+/// assert_eq!(disassemble(memory), "IN\nSTO 99\nOUT\n");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn disassemble(memory: Memory) -> String {
+    let mut text = String::new();
+    // Writing to a String does not fail
+    let _ = write_disassembly(&mut text, memory);
+    text
+}
+
+#[cfg(feature = "alloc")]
+#[must_use]
+/// Disassemble the [Memory] to assembly with made up labels, see [`write_labelled_disassembly`]
+///
+/// ```
+/// # use lminc::{assembler::assemble_from_text, disassembler::disassemble_with_labels};
+/// let memory = assemble_from_text("loop IN\nBRZ end\nSUB one\nBR loop\nend OUT\nHLT\none DAT 1")??;
+/// assert_eq!(
+///     disassemble_with_labels(memory),
+///     "\
+/// l00 IN
+///     BRZ l04
+///     SUB d06
+///     BR l00
+/// l04 OUT
+///     HLT
+/// d06 DAT 1
+/// "
+/// );
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn disassemble_with_labels(memory: Memory) -> String {
+    let mut text = String::new();
+    // Writing to a String does not fail
+    let _ = write_labelled_disassembly(&mut text, memory);
+    text
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{assembler::assemble_from_text, computer::Memory, num3::ThreeDigitNumber};
+
+    use super::{disassemble, disassemble_with_labels, write_decoded_table, write_table};
+
+    #[test]
+    fn round_trip() {
+        let memory = assemble_from_text(
+            "\
+        IN
+loop    OUT
+        SUB one
+        BRP loop
+        BRZ end
+        BR loop
+end     HLT
+one     DAT 1
+        DAT 999
+        DAT 401",
+        )
+        .expect("failed to parse the assembly")
+        .expect("failed to assemble the assembly");
+
+        assert_eq!(
+            assemble_from_text(&disassemble_with_labels(memory))
+                .expect("failed to parse the labelled disassembly")
+                .expect("failed to assemble the labelled disassembly"),
+            memory,
+            "Labelled disassembly did not assemble to the same memory!"
+        );
+
+        let text = disassemble(memory);
+        assert_eq!(
+            text, "IN\nOUT\nSUB 7\nBRP 1\nBRZ 6\nBR 1\nHLT\nDAT 1\nDAT 999\nDAT 401\n",
+            "Disassembled the wrong assembly!"
+        );
+        assert_eq!(
+            assemble_from_text(&text)
+                .expect("failed to parse the disassembly")
+                .expect("failed to assemble the disassembly"),
+            memory,
+            "Disassembly did not assemble to the same memory!"
+        );
+
+        // Every number assembles back to itself
+        let mut every: Memory = [ThreeDigitNumber::ZERO; 100];
+        for start in (0_u16..1000).step_by(100) {
+            for (cell, number) in every.iter_mut().zip(start..) {
+                *cell = ThreeDigitNumber::try_from(number).expect("failed to create the number");
+            }
+            assert_eq!(
+                assemble_from_text(&disassemble(every))
+                    .expect("failed to parse the disassembly")
+                    .expect("failed to assemble the disassembly"),
+                every,
+                "Disassembly of {start}..{} did not assemble to the same memory!",
+                start + 100
+            );
+            assert_eq!(
+                assemble_from_text(&disassemble_with_labels(every))
+                    .expect("failed to parse the labelled disassembly")
+                    .expect("failed to assemble the labelled disassembly"),
+                every,
+                "Labelled disassembly of {start}..{} did not assemble to the same memory!",
+                start + 100
+            );
+        }
+    }
+
+    #[test]
+    fn tables() {
+        let memory = assemble_from_text(
+            "IN\nOUT\nHLT\nDAT 1\nDAT 1\nDAT 1\nDAT 1\nDAT 1\nDAT 1\nDAT 1\nDAT 999\nSTO 5",
+        )
+        .expect("failed to parse the assembly")
+        .expect("failed to assemble the assembly");
+
+        let mut text = String::new();
+        write_table(&mut text, memory).expect("failed to write the table");
+        assert_eq!(
+            text,
+            "   |   0   1   2   3   4   5   6   7   8   9
+---+----------------------------------------
+00 | 901 902   .   1   1   1   1   1   1   1
+10 | 999 305   .   .   .   .   .   .   .   .
+...
+",
+            "Wrote the wrong table!"
+        );
+
+        let mut text = String::new();
+        write_decoded_table(&mut text, memory).expect("failed to write the decoded table");
+        assert_eq!(
+            text.lines().nth(3),
+            Some("10 | DAT 999   STO 5       .       .       .       .       .       .       .       ."),
+            "Wrote the wrong decoded table!"
+        );
+    }
+}
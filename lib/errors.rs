@@ -1,4 +1,18 @@
-use core::fmt;
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt, ops::Range};
+
+#[cfg(feature = "alloc")]
+use crate::machine;
+#[cfg(feature = "std")]
+use crate::runner::stdio;
+use crate::{assembler, computer, file, number_assembler, parser, runner::tester};
 
 #[doc(hidden)]
 #[macro_export]
@@ -9,6 +23,7 @@ macro_rules! create_location_type {
         $s:ident => $($arg:tt)*
     ) => {
         #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #[cfg_attr(feature = "defmt", derive(defmt::Format))]
         $(#[doc = $doc])?
         pub struct $name $(< $( $lt )*, >)? ($($v $t),*);
 
@@ -22,19 +37,175 @@ macro_rules! create_location_type {
 }
 
 create_location_type!(
-    "A line number for use with [ErrorWithLocation]":
+    "A line number for use with [`ErrorWithLocation`]":
     LineNumber(pub usize): line => "line {}", line.0
 );
 
 create_location_type!(
-    "An instruction number for use with [ErrorWithLocation] (usually when [`LineNumber`] cannot be used)":
+    "An instruction number for use with [`ErrorWithLocation`] (usually when [`LineNumber`] cannot be used)":
     InstructionNumber(pub usize): number => "instruction {}", number.0
 );
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// A range in the source for use with [`ErrorWithLocation`], which can span multiple lines.
+///
+/// Lines and columns start at 1, columns count chars, and the end column is exclusive
+pub struct Span {
+    pub start_line: usize,
+    pub start_col: usize,
+    pub end_line: usize,
+    pub end_col: usize,
+}
+
+impl Span {
+    #[must_use]
+    /// Make a span from a byte range in a line of the source
+    ///
+    /// # Panics
+    /// Panics if the range is not on char boundaries in the line
+    pub fn from_line(line_number: LineNumber, line: &str, range: Range<usize>) -> Self {
+        let start_col = line[..range.start].chars().count() + 1;
+
+        Self {
+            start_line: line_number.0,
+            start_col,
+            end_line: line_number.0,
+            end_col: start_col + line[range].chars().count(),
+        }
+    }
+
+    #[must_use]
+    /// Make a span from a byte range in the whole source, which can include line breaks
+    ///
+    /// # Panics
+    /// Panics if the range is not on char boundaries in the text
+    ///
+    /// ```
+    /// # use lminc::errors::Span;
+    /// let text = "start IN\n      OUT\n      HLT";
+    /// let span = Span::from_range(text, 6..18);
+    /// assert_eq!(span.to_string(), "line 1, column 7 to line 2, column 10");
+    /// ```
+    pub fn from_range(text: &str, range: Range<usize>) -> Self {
+        let position = |index: usize| {
+            let before = &text[..index];
+            let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+            (
+                before.matches('\n').count() + 1,
+                before[line_start..].chars().count() + 1,
+            )
+        };
+        let (start_line, start_col) = position(range.start);
+        let (end_line, end_col) = position(range.end);
+
+        Self {
+            start_line,
+            start_col,
+            end_line,
+            end_col,
+        }
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.start_line == self.end_line {
+            write!(
+                f,
+                "line {}, columns {}..{}",
+                self.start_line, self.start_col, self.end_col
+            )
+        } else {
+            write!(
+                f,
+                "line {}, column {} to line {}, column {}",
+                self.start_line, self.start_col, self.end_line, self.end_col
+            )
+        }
+    }
+}
+
+impl From<Span> for LineNumber {
+    /// Get the line that the span starts on
+    fn from(value: Span) -> Self {
+        Self(value.start_line)
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 /// An error with a location in the source
 pub struct ErrorWithLocation<Error, Location>(pub Location, pub Error);
 
+impl<Error, Location> ErrorWithLocation<Error, Location> {
+    #[must_use]
+    /// Get the error
+    pub const fn error(&self) -> &Error {
+        &self.1
+    }
+
+    #[must_use]
+    /// Get the location of the error
+    pub const fn location(&self) -> &Location {
+        &self.0
+    }
+
+    // This cannot be const as the destructor for Location may not be const
+    #[allow(clippy::missing_const_for_fn)]
+    #[must_use]
+    /// Get the error, dropping the location
+    pub fn into_inner(self) -> Error {
+        self.1
+    }
+
+    #[must_use]
+    /// Change the error, keeping the location
+    ///
+    /// ```
+    /// # use lminc::errors::{ErrorWithLocation, LineNumber};
+    /// let error = ErrorWithLocation(LineNumber(3), 5);
+    /// assert_eq!(error.map_err(|error| error * 2), ErrorWithLocation(LineNumber(3), 10));
+    /// ```
+    pub fn map_err<NewError>(
+        self,
+        f: impl FnOnce(Error) -> NewError,
+    ) -> ErrorWithLocation<NewError, Location> {
+        ErrorWithLocation(self.0, f(self.1))
+    }
+
+    #[must_use]
+    /// Change the location, keeping the error
+    ///
+    /// ```
+    /// # use lminc::errors::{ErrorWithLocation, InstructionNumber, LineNumber};
+    /// let error = ErrorWithLocation(InstructionNumber(2), "error");
+    /// assert_eq!(
+    ///     error.map_location(|number| LineNumber(number.0 + 1)),
+    ///     ErrorWithLocation(LineNumber(3), "error")
+    /// );
+    /// ```
+    pub fn map_location<NewLocation>(
+        self,
+        f: impl FnOnce(Location) -> NewLocation,
+    ) -> ErrorWithLocation<Error, NewLocation> {
+        ErrorWithLocation(f(self.0), self.1)
+    }
+}
+
+impl<Error, Location> From<(Location, Error)> for ErrorWithLocation<Error, Location> {
+    /// Add a location to an error
+    fn from((location, error): (Location, Error)) -> Self {
+        Self(location, error)
+    }
+}
+
+impl<Error, Location> From<ErrorWithLocation<Error, Location>> for (Location, Error) {
+    fn from(value: ErrorWithLocation<Error, Location>) -> Self {
+        (value.0, value.1)
+    }
+}
+
 impl<Error, Location> fmt::Display for ErrorWithLocation<Error, Location>
 where
     Error: fmt::Display,
@@ -45,6 +216,24 @@ where
     }
 }
 
+impl<Error, Location> Code for ErrorWithLocation<Error, Location>
+where
+    Error: Code,
+    Location: fmt::Display,
+{
+    fn code(&self) -> ErrorCode {
+        self.1.code()
+    }
+
+    fn fmt_message(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.1.fmt_message(f)
+    }
+
+    fn location(&self) -> Option<&dyn fmt::Display> {
+        Some(&self.0)
+    }
+}
+
 #[cfg(feature = "std")]
 impl<Error, Location> std::error::Error for ErrorWithLocation<Error, Location>
 where
@@ -55,3 +244,666 @@ where
         self.1.source()
     }
 }
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// A stable code for an error, displayed like `E0012`.
+///
+/// Codes are never changed or reused, so tools can match on them instead of on messages.
+/// They are grouped by the hundred:
+/// - `E00xx`: parsing assembly
+/// - `E01xx`: assembling
+/// - `E02xx`: assembling numbers
+/// - `E03xx`: loading files
+/// - `E04xx`: running
+/// - `E05xx`: failed tests
+/// - `E06xx` to `E09xx`: reading CSV, inline, JSON and TOML tests
+/// - `E10xx`: lints (warnings)
+pub struct ErrorCode(pub u16);
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "E{:04}", self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ErrorCode {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for ErrorCode {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(f, "E{=u16:04}", self.0);
+    }
+}
+
+/// An error with a stable [`ErrorCode`]
+pub trait Code: fmt::Display {
+    /// Get the stable code of the error
+    fn code(&self) -> ErrorCode;
+
+    /// Write the message of the error, without its location
+    ///
+    /// # Errors
+    /// Returns an error if the formatter does
+    fn fmt_message(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+
+    /// Get where the error happened, if it has a location
+    fn location(&self) -> Option<&dyn fmt::Display> {
+        None
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// Displays the message of an error, see [`Code::fmt_message`]
+struct Message<'a, E: ?Sized>(&'a E);
+
+#[cfg(feature = "alloc")]
+impl<E: Code + ?Sized> fmt::Display for Message<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_message(f)
+    }
+}
+
+#[cfg(feature = "json")]
+#[must_use]
+/// Render an error as a JSON object with its `code`, `message` and `location`
+///  (`null` if it does not have one)
+///
+/// ```
+/// # use lminc::{assembler::assemble_from_text, errors::to_json};
+/// let error = assemble_from_text("LDA one").expect("failed to parse").expect_err("assembled");
+/// assert_eq!(
+///     to_json(&error),
+///     r#"{"code":"E0008","message":"Unknown label!","location":"instruction 1"}"#
+/// );
+/// ```
+pub fn to_json<E: Code + ?Sized>(error: &E) -> String {
+    #[derive(serde::Serialize)]
+    struct Rendering {
+        code: ErrorCode,
+        message: String,
+        location: Option<String>,
+    }
+
+    serde_json::to_string(&Rendering {
+        code: error.code(),
+        message: Message(error).to_string(),
+        location: error.location().map(ToString::to_string),
+    })
+    // Serialising strings cannot fail
+    .unwrap_or_default()
+}
+
+#[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
+/// An error from any stage of the crate, so that errors from parsing, assembling,
+///  loading, running and testing can all be propagated with `?`
+///
+/// ```
+/// # use lminc::{assembler::assemble_from_text, file::{load_from_buffer, save_to_buffer, MAX_FILE_SIZE}};
+/// fn round_trip(assembly: &str) -> Result<(), lminc::Error> {
+///     let memory = assemble_from_text(assembly)??;
+///     let mut buffer = [0; MAX_FILE_SIZE];
+///     assert_eq!(load_from_buffer(save_to_buffer(&mut buffer, memory))?, memory);
+///     Ok(())
+/// }
+///
+/// assert!(round_trip("IN\nOUT\nHLT").is_ok());
+/// assert!(matches!(round_trip("ADD 100"), Err(lminc::Error::AssemblerError(_))));
+/// ```
+pub enum Error {
+    /// See [`parser::Error`]
+    ParseError(parser::ErrorWithLocation<LineNumber>),
+    /// See [`assembler::Error`]
+    AssemblerError(assembler::ErrorWithInstructionNumber),
+    /// See [`number_assembler::FromNumbersError`]
+    NumberAssemblerError(number_assembler::ErrorWithLineNumber),
+    /// See [`file::Error`]
+    LoadError(file::Error),
+    #[cfg(feature = "std")]
+    /// See [`file::FromFileError`]
+    FromFileError(file::FromFileError),
+    /// See [`computer::Error`]
+    ComputerError(computer::Error),
+    #[cfg(feature = "std")]
+    /// See [`stdio::Error`]
+    RunnerError(stdio::Error),
+    #[cfg(feature = "alloc")]
+    /// See [`machine::Error`]
+    MachineError(machine::Error),
+    /// See [`tester::TestError`]
+    TestError(tester::ErrorWithCycles),
+    #[cfg(feature = "alloc")]
+    /// See [`tester::CSVError`]
+    CSVError(tester::CSVErrorWithLineNumber),
+    #[cfg(feature = "alloc")]
+    /// See [`tester::InlineTestError`]
+    InlineTestError(tester::InlineTestErrorWithLineNumber),
+    #[cfg(feature = "json")]
+    /// See [`tester::JSONError`]
+    JSONError(tester::JSONErrorWithTestNumber),
+    #[cfg(feature = "toml")]
+    /// See [`tester::SuiteError`]
+    SuiteError(tester::SuiteErrorWithTestNumber),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ParseError(error) => write!(f, "Error parsing assembly: {error}"),
+            Self::AssemblerError(error) => write!(f, "Error assembling: {error}"),
+            Self::NumberAssemblerError(error) => write!(f, "Error assembling numbers: {error}"),
+            Self::LoadError(error) => write!(f, "Error loading memory: {error}"),
+            #[cfg(feature = "std")]
+            Self::FromFileError(error) => write!(f, "Error loading file: {error}"),
+            Self::ComputerError(error) => write!(f, "Error running: {error}"),
+            #[cfg(feature = "std")]
+            Self::RunnerError(error) => write!(f, "Error running: {error}"),
+            #[cfg(feature = "alloc")]
+            Self::MachineError(error) => write!(f, "Error running: {error}"),
+            Self::TestError(error) => write!(f, "Error testing: {error}"),
+            #[cfg(feature = "alloc")]
+            Self::CSVError(error) => write!(f, "Error reading CSV tests: {error}"),
+            #[cfg(feature = "alloc")]
+            Self::InlineTestError(error) => write!(f, "Error reading inline tests: {error}"),
+            #[cfg(feature = "json")]
+            Self::JSONError(error) => write!(f, "Error reading JSON tests: {error}"),
+            #[cfg(feature = "toml")]
+            Self::SuiteError(error) => write!(f, "Error reading TOML tests: {error}"),
+        }
+    }
+}
+
+impl Error {
+    /// Get the error from the stage that failed
+    fn inner(&self) -> &dyn Code {
+        match self {
+            Self::ParseError(error) => error,
+            Self::AssemblerError(error) => error,
+            Self::NumberAssemblerError(error) => error,
+            Self::LoadError(error) => error,
+            #[cfg(feature = "std")]
+            Self::FromFileError(error) => error,
+            Self::ComputerError(error) => error,
+            #[cfg(feature = "std")]
+            Self::RunnerError(error) => error,
+            #[cfg(feature = "alloc")]
+            Self::MachineError(error) => error,
+            Self::TestError(error) => error,
+            #[cfg(feature = "alloc")]
+            Self::CSVError(error) => error,
+            #[cfg(feature = "alloc")]
+            Self::InlineTestError(error) => error,
+            #[cfg(feature = "json")]
+            Self::JSONError(error) => error,
+            #[cfg(feature = "toml")]
+            Self::SuiteError(error) => error,
+        }
+    }
+}
+
+impl Code for Error {
+    fn code(&self) -> ErrorCode {
+        self.inner().code()
+    }
+
+    fn fmt_message(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner().fmt_message(f)
+    }
+
+    fn location(&self) -> Option<&dyn fmt::Display> {
+        self.inner().location()
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::ParseError(error) => Some(error),
+            Self::AssemblerError(error) => Some(error),
+            Self::NumberAssemblerError(error) => Some(error),
+            Self::LoadError(error) => Some(error),
+            Self::FromFileError(error) => Some(error),
+            Self::ComputerError(error) => Some(error),
+            Self::RunnerError(error) => Some(error),
+            #[cfg(feature = "alloc")]
+            Self::MachineError(error) => Some(error),
+            Self::TestError(error) => Some(error),
+            #[cfg(feature = "alloc")]
+            Self::CSVError(error) => Some(error),
+            #[cfg(feature = "alloc")]
+            Self::InlineTestError(error) => Some(error),
+            #[cfg(feature = "json")]
+            Self::JSONError(error) => Some(error),
+            #[cfg(feature = "toml")]
+            Self::SuiteError(error) => Some(error),
+        }
+    }
+}
+
+macro_rules! from_impl {
+    ( $( $( #[$attr:meta] )? $error:ty => $variant:ident ),* $(,)? ) => {
+        $(
+            $( #[$attr] )?
+            impl From<$error> for Error {
+                fn from(value: $error) -> Self {
+                    Self::$variant(value)
+                }
+            }
+        )*
+    };
+}
+
+from_impl!(
+    parser::ErrorWithLocation<LineNumber> => ParseError,
+    assembler::ErrorWithInstructionNumber => AssemblerError,
+    number_assembler::ErrorWithLineNumber => NumberAssemblerError,
+    file::Error => LoadError,
+    #[cfg(feature = "std")] file::FromFileError => FromFileError,
+    computer::Error => ComputerError,
+    #[cfg(feature = "std")] stdio::Error => RunnerError,
+    #[cfg(feature = "alloc")] machine::Error => MachineError,
+    tester::ErrorWithCycles => TestError,
+    #[cfg(feature = "alloc")] tester::CSVErrorWithLineNumber => CSVError,
+    #[cfg(feature = "alloc")] tester::InlineTestErrorWithLineNumber => InlineTestError,
+    #[cfg(feature = "json")] tester::JSONErrorWithTestNumber => JSONError,
+    #[cfg(feature = "toml")] tester::SuiteErrorWithTestNumber => SuiteError,
+);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+/// How serious a [`Diagnostics`] entry is
+pub enum Severity {
+    /// The input is valid, but probably not what was meant
+    Warning,
+    /// The input is not valid
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Warning => write!(f, "warning"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+/// A warning or error collected by [`Diagnostics`]
+pub struct Entry {
+    pub severity: Severity,
+    pub code: ErrorCode,
+    /// The message, without the location
+    pub message: String,
+    pub location: Option<String>,
+}
+
+#[cfg(feature = "alloc")]
+impl Entry {
+    #[must_use]
+    /// Make an entry from an error with a [`Code`]
+    pub fn new<E: Code + ?Sized>(severity: Severity, error: &E) -> Self {
+        Self {
+            severity,
+            code: error.code(),
+            message: Message(error).to_string(),
+            location: error.location().map(ToString::to_string),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}[{}]: {}", self.severity, self.code, self.message)?;
+        if let Some(location) = &self.location {
+            write!(f, " ({location})")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+/// Warnings and errors collected across passes (parsing, assembling, linting),
+///  so that all of them can be reported instead of only the first
+///
+/// ```
+/// # use lminc::{errors::{Diagnostics, LineNumber}, parser::Parser};
+/// let mut diagnostics = Diagnostics::new();
+/// for (index, line) in ["IN", "OUT 5", "HLT", "5 DAT"].into_iter().enumerate() {
+///     diagnostics.collect(
+///         Parser::parse_text(line).map_err(|error| error.map_location(|_| LineNumber(index + 1))),
+///     );
+/// }
+///
+/// assert!(diagnostics.has_errors());
+/// assert_eq!(
+///     diagnostics.to_string(),
+///     "error[E0007]: Unexpected label / number! (line 2)
+/// error[E0004]: Expected a label not a number! (line 4)
+/// 2 errors, 0 warnings
+/// "
+/// );
+/// ```
+pub struct Diagnostics {
+    entries: Vec<Entry>,
+}
+
+#[cfg(feature = "alloc")]
+impl Diagnostics {
+    #[must_use]
+    /// Create an empty collection
+    pub const fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Add an entry
+    pub fn push(&mut self, entry: Entry) {
+        self.entries.push(entry);
+    }
+
+    /// Add an error
+    pub fn push_error<E: Code + ?Sized>(&mut self, error: &E) {
+        self.push(Entry::new(Severity::Error, error));
+    }
+
+    /// Add a warning
+    pub fn push_warning<E: Code + ?Sized>(&mut self, warning: &E) {
+        self.push(Entry::new(Severity::Warning, warning));
+    }
+
+    /// Add the error from a result (if it is one), returning the value otherwise
+    pub fn collect<T, E: Code>(&mut self, result: Result<T, E>) -> Option<T> {
+        result.map_err(|error| self.push_error(&error)).ok()
+    }
+
+    #[must_use]
+    /// Get the number of entries
+    pub const fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[must_use]
+    /// Check if there are no entries
+    pub const fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    #[must_use]
+    /// Check if any of the entries are errors
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+
+    /// Iterate over the entries, in the order they were added
+    pub fn iter(&self) -> core::slice::Iter<'_, Entry> {
+        self.entries.iter()
+    }
+
+    /// Iterate over the errors
+    pub fn errors(&self) -> impl Iterator<Item = &Entry> {
+        self.with_severity(Severity::Error)
+    }
+
+    /// Iterate over the warnings
+    pub fn warnings(&self) -> impl Iterator<Item = &Entry> {
+        self.with_severity(Severity::Warning)
+    }
+
+    #[must_use]
+    /// Get a count of the errors and warnings, displayed like `1 error, 2 warnings`
+    pub const fn summary(&self) -> Summary<'_> {
+        Summary(self)
+    }
+
+    fn with_severity(&self, severity: Severity) -> impl Iterator<Item = &Entry> {
+        self.iter().filter(move |entry| entry.severity == severity)
+    }
+
+    #[must_use]
+    /// Split the entries into (warnings, errors)
+    pub fn partition(self) -> (Vec<Entry>, Vec<Entry>) {
+        self.entries
+            .into_iter()
+            .partition(|entry| entry.severity == Severity::Warning)
+    }
+
+    /// Move the entries from another collection onto the end of this one
+    pub fn append(&mut self, other: &mut Self) {
+        self.entries.append(&mut other.entries);
+    }
+
+    /// Return `Err(self)` if there are any errors, otherwise `Ok(self)` with the warnings
+    ///
+    /// # Errors
+    /// If any of the entries are errors
+    pub fn into_result(self) -> Result<Self, Self> {
+        if self.has_errors() {
+            Err(self)
+        } else {
+            Ok(self)
+        }
+    }
+
+    #[cfg(feature = "json")]
+    #[must_use]
+    /// Render the entries as a JSON array of objects with their `severity`, `code`,
+    ///  `message` and `location` (`null` if they do not have one)
+    pub fn to_json(&self) -> String {
+        // Serialising strings cannot fail
+        serde_json::to_string(&self.entries).unwrap_or_default()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for Diagnostics {
+    /// Writes each entry on its own line, then the [summary](Diagnostics::summary)
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in self {
+            writeln!(f, "{entry}")?;
+        }
+
+        writeln!(f, "{}", self.summary())
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// The count of errors and warnings in [`Diagnostics`], see [`Diagnostics::summary`]
+pub struct Summary<'a>(&'a Diagnostics);
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for Summary<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let errors = self.0.errors().count();
+        let warnings = self.0.warnings().count();
+        write!(
+            f,
+            "{errors} error{}, {warnings} warning{}",
+            if errors == 1 { "" } else { "s" },
+            if warnings == 1 { "" } else { "s" }
+        )
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> IntoIterator for &'a Diagnostics {
+    type Item = &'a Entry;
+    type IntoIter = core::slice::Iter<'a, Entry>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl IntoIterator for Diagnostics {
+    type Item = Entry;
+    type IntoIter = alloc::vec::IntoIter<Entry>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Extend<Entry> for Diagnostics {
+    fn extend<T: IntoIterator<Item = Entry>>(&mut self, iter: T) {
+        self.entries.extend(iter);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Code, ErrorCode, LineNumber, Span};
+    use crate::{assembler::assemble_from_text, parser::Parser};
+
+    #[test]
+    fn codes() {
+        assert_eq!(
+            ErrorCode(12).to_string(),
+            "E0012",
+            "Failed to display an error code!"
+        );
+
+        let error = Parser::parse_text("IN\nOUT 5").expect_err("parsed invalid assembly");
+        assert_eq!(
+            error.code(),
+            ErrorCode(7),
+            "Failed to get the code of a parse error!"
+        );
+        assert_eq!(
+            Code::location(&error).map(ToString::to_string),
+            Some("line 2".to_string()),
+            "Failed to get the location of a parse error!"
+        );
+
+        let error: super::Error = assemble_from_text("ADD 100")
+            .expect("failed to parse")
+            .expect_err("assembled a large address")
+            .into();
+        assert_eq!(
+            error.code(),
+            ErrorCode(101),
+            "Failed to get the code of a wrapped error!"
+        );
+        assert!(
+            error.location().is_some(),
+            "Failed to get the location of a wrapped error!"
+        );
+    }
+
+    #[test]
+    fn spans() {
+        let text = "a DAT 1\n\u{e9}\u{e9} ADD a";
+        assert_eq!(
+            Span::from_range(text, 13..18),
+            Span {
+                start_line: 2,
+                start_col: 4,
+                end_line: 2,
+                end_col: 9,
+            },
+            "Failed to count chars in a span!"
+        );
+        assert_eq!(
+            Span::from_line(LineNumber(2), "\u{e9}\u{e9} ADD a", 5..8),
+            Span::from_range(text, 13..16),
+            "Failed to make the same span from a line!"
+        );
+        assert_eq!(
+            Span::from_range(text, 2..text.len()).to_string(),
+            "line 1, column 3 to line 2, column 9",
+            "Failed to display a span over multiple lines!"
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json() {
+        let error: super::Error = Parser::parse_text("5 HLT")
+            .expect_err("parsed invalid assembly")
+            .into();
+        assert_eq!(
+            super::to_json(&error),
+            r#"{"code":"E0004","message":"Expected a label not a number!","location":"line 1"}"#,
+            "Failed to render an error as JSON!"
+        );
+        assert_eq!(
+            super::to_json(&crate::computer::Error::NoOutput),
+            r#"{"code":"E0402","message":"The computer was not waiting to output, but one was requested!","location":null}"#,
+            "Failed to render an error without a location as JSON!"
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn diagnostics() {
+        use super::{Diagnostics, Entry, Severity};
+        use crate::computer;
+
+        let mut diagnostics = Diagnostics::new();
+        assert!(
+            diagnostics.collect(Parser::parse_text("IN\nHLT")).is_some(),
+            "Failed to return the value of a result!"
+        );
+        diagnostics.push_warning(&computer::Error::NoOutput);
+        diagnostics.collect(Parser::parse_text("OUT 5"));
+
+        assert_eq!(diagnostics.len(), 2, "Failed to collect the entries!");
+        assert!(
+            diagnostics.clone().into_result().is_err(),
+            "Failed to find the error!"
+        );
+
+        let (warnings, errors) = diagnostics.partition();
+        assert_eq!(
+            warnings.iter().map(|entry| entry.code).collect::<Vec<_>>(),
+            [ErrorCode(402)],
+            "Failed to partition the warnings!"
+        );
+        assert_eq!(
+            (errors[0].severity, errors[0].location.as_deref()),
+            (Severity::Error, Some("line 1")),
+            "Failed to partition the errors!"
+        );
+
+        let mut warnings = Diagnostics::new();
+        warnings.extend([Entry::new(Severity::Warning, &computer::Error::NoOutput)]);
+        assert_eq!(
+            warnings.to_string(),
+            "warning[E0402]: The computer was not waiting to output, but one was requested!\n0 errors, 1 warning\n",
+            "Failed to render the diagnostics!"
+        );
+        assert!(
+            warnings.into_result().is_ok(),
+            "Failed to accept only warnings!"
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn diagnostics_json() {
+        let mut diagnostics = super::Diagnostics::new();
+        diagnostics.collect(Parser::parse_text("5 HLT"));
+        assert_eq!(
+            diagnostics.to_json(),
+            r#"[{"severity":"error","code":"E0004","message":"Expected a label not a number!","location":"line 1"}]"#,
+            "Failed to render the diagnostics as JSON!"
+        );
+    }
+}
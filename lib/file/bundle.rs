@@ -0,0 +1,375 @@
+extern crate alloc;
+use core::{fmt, str};
+#[cfg(feature = "std")]
+use std::{fs, io};
+
+use alloc::{string::String, vec::Vec};
+
+#[cfg(feature = "std")]
+use super::FromFileError;
+use super::{
+    container::{metadata_section_len, split_container},
+    load_with_metadata_from_buffer, save_container_with_metadata, Metadata, MetadataError,
+};
+use crate::computer::Memory;
+
+/// The magic bytes at the start of a bundle
+pub const BUNDLE_MAGIC: [u8; 4] = *b"LMNB";
+/// The bundle format version written by this version of lminc
+pub const BUNDLE_VERSION: u8 = 1;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+/// The formats of test suites in bundles
+pub enum TestsFormat {
+    /// CSV tests, see `StdTest::from_csv`
+    CSV = 1,
+    /// JSON tests, see `StdTest::from_json` (with the `json` feature)
+    JSON = 2,
+    /// A TOML test suite, see `TestSuite::from_toml` (with the `toml` feature)
+    TOML = 3,
+}
+
+impl TestsFormat {
+    #[must_use]
+    /// Get the format of a test file from its extension, where anything else is CSV
+    pub fn from_extension(extension: &str) -> Self {
+        match extension {
+            "json" => Self::JSON,
+            "toml" => Self::TOML,
+            _ => Self::CSV,
+        }
+    }
+
+    /// Get the format from its tag in a bundle
+    const fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            1 => Some(Self::CSV),
+            2 => Some(Self::JSON),
+            3 => Some(Self::TOML),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Errors for saving and loading [Bundle]s
+pub enum BundleError {
+    /// The program or its metadata could not be saved or loaded, see [`MetadataError`]
+    InvalidProgram(MetadataError),
+    /// The buffer does not start with the [`BUNDLE_MAGIC`] bytes
+    NotBundle,
+    /// The bundle was saved with an unsupported format version
+    UnsupportedVersion(u8),
+    /// The bundle ends part way through a section
+    Truncated,
+    /// The source or tests are too long to save (> 4 GiB)
+    TooLong,
+    /// The source or tests are not valid UTF-8
+    InvalidText,
+    /// The tests are in an unknown format
+    UnknownTestsFormat(u8),
+    /// There are bytes after the end of the bundle, from the byte offset
+    TrailingData(usize),
+}
+
+impl fmt::Display for BundleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidProgram(error) => fmt::Display::fmt(error, f),
+            Self::NotBundle => write!(f, "Not a bundle!"),
+            Self::UnsupportedVersion(version) => write!(
+                f,
+                "The bundle format version is not supported (version {version})!"
+            ),
+            Self::Truncated => write!(f, "The bundle is incomplete!"),
+            Self::TooLong => write!(f, "The bundle source or tests are too long!"),
+            Self::InvalidText => write!(f, "The bundle source or tests are not valid UTF-8!"),
+            Self::UnknownTestsFormat(format) => {
+                write!(f, "The bundle tests are in an unknown format ({format})!")
+            }
+            Self::TrailingData(offset) => write!(
+                f,
+                "The bundle has unexpected data after the end (from byte {offset})!"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BundleError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidProgram(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<MetadataError> for BundleError {
+    fn from(value: MetadataError) -> Self {
+        Self::InvalidProgram(value)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// The test suite in a [Bundle], kept as text so it can be read in its format
+pub struct Tests {
+    /// The format of the test suite
+    pub format: TestsFormat,
+    /// The test suite
+    pub text: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// A program with its metadata, and optionally its source and a test suite,
+///  so an exercise can be distributed as a single file.
+///
+/// It is saved as the [`BUNDLE_MAGIC`] bytes and the [`BUNDLE_VERSION`],
+///  the program in a container with its metadata, then the source and the tests
+///
+/// ```
+/// # use lminc::{assembler::assemble_from_text, file::{self, Bundle, Metadata, Tests, TestsFormat}};
+/// let source = "IN\nOUT\nHLT";
+/// let bundle = Bundle::new(assemble_from_text(source)??, Metadata::default())
+///     .with_source(source.to_string())
+///     .with_tests(Tests {
+///         format: TestsFormat::CSV,
+///         text: "5;5".to_string(),
+///     });
+///
+/// let saved = file::save_bundle_to_vec(&bundle)?;
+/// assert_eq!(file::load_bundle_from_buffer(&saved)?, bundle);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct Bundle {
+    /// The assembled program
+    pub memory: Memory,
+    /// Information about the program
+    pub metadata: Metadata,
+    /// The assembly the program was assembled from, if it is included
+    pub source: Option<String>,
+    /// The test suite for the program, if it is included
+    pub tests: Option<Tests>,
+}
+
+impl Bundle {
+    #[must_use]
+    /// Create a bundle of a program, without its source or tests
+    pub const fn new(memory: Memory, metadata: Metadata) -> Self {
+        Self {
+            memory,
+            metadata,
+            source: None,
+            tests: None,
+        }
+    }
+
+    #[must_use]
+    /// Include the source of the program
+    pub fn with_source(mut self, source: String) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    #[must_use]
+    /// Include a test suite for the program
+    pub fn with_tests(mut self, tests: Tests) -> Self {
+        self.tests = Some(tests);
+        self
+    }
+}
+
+/// Add a length (big endian `u32`) and the text to the buffer
+fn push_text(buffer: &mut Vec<u8>, text: &str) -> Result<(), BundleError> {
+    let length = u32::try_from(text.len()).map_err(|_| BundleError::TooLong)?;
+    buffer.extend_from_slice(&length.to_be_bytes());
+    buffer.extend_from_slice(text.as_bytes());
+    Ok(())
+}
+
+/// Save a [Bundle] to a buffer
+///
+/// # Errors
+/// See [`BundleError`]
+pub fn save_bundle_to_vec(bundle: &Bundle) -> Result<Vec<u8>, BundleError> {
+    let mut buffer = Vec::from(BUNDLE_MAGIC);
+    buffer.push(BUNDLE_VERSION);
+    buffer.extend(save_container_with_metadata(
+        bundle.memory,
+        &bundle.metadata,
+    )?);
+
+    match &bundle.source {
+        Some(source) => {
+            buffer.push(1);
+            push_text(&mut buffer, source)?;
+        }
+        None => buffer.push(0),
+    }
+
+    match &bundle.tests {
+        Some(tests) => {
+            buffer.push(tests.format as u8);
+            push_text(&mut buffer, &tests.text)?;
+        }
+        None => buffer.push(0),
+    }
+
+    Ok(buffer)
+}
+
+/// Take a byte from the start of the buffer
+fn take_byte(buffer: &mut &[u8]) -> Result<u8, BundleError> {
+    let (&byte, rest) = buffer.split_first().ok_or(BundleError::Truncated)?;
+    *buffer = rest;
+    Ok(byte)
+}
+
+/// Take a length (big endian `u32`) and the text from the start of the buffer
+fn take_text(buffer: &mut &[u8]) -> Result<String, BundleError> {
+    let (length, rest) = buffer.split_first_chunk().ok_or(BundleError::Truncated)?;
+    let length = usize::try_from(u32::from_be_bytes(*length)).map_err(|_| BundleError::TooLong)?;
+    let (text, rest) = rest
+        .split_at_checked(length)
+        .ok_or(BundleError::Truncated)?;
+    *buffer = rest;
+
+    str::from_utf8(text)
+        .map(String::from)
+        .map_err(|_| BundleError::InvalidText)
+}
+
+/// Load a [Bundle] from a saved buffer
+///
+/// # Errors
+/// See [`BundleError`]
+pub fn load_bundle_from_buffer(buffer: &[u8]) -> Result<Bundle, BundleError> {
+    let Some(contents) = buffer.strip_prefix(&BUNDLE_MAGIC) else {
+        return Err(BundleError::NotBundle);
+    };
+    let mut contents = contents;
+    let version = take_byte(&mut contents)?;
+    if version != BUNDLE_VERSION {
+        return Err(BundleError::UnsupportedVersion(version));
+    }
+
+    // The container is followed by its metadata section, if it has one
+    let (_, rest) = split_container(contents).map_err(MetadataError::LoadError)?;
+    let metadata_length = metadata_section_len(rest).ok_or(BundleError::Truncated)?;
+    let container_length = contents.len() - rest.len() + metadata_length;
+    let (container, rest) = contents
+        .split_at_checked(container_length)
+        .ok_or(BundleError::Truncated)?;
+    let (memory, metadata) = load_with_metadata_from_buffer(container)?;
+    contents = rest;
+
+    let source = match take_byte(&mut contents)? {
+        0 => None,
+        _ => Some(take_text(&mut contents)?),
+    };
+    let tests = match take_byte(&mut contents)? {
+        0 => None,
+        tag => {
+            let format = TestsFormat::from_tag(tag).ok_or(BundleError::UnknownTestsFormat(tag))?;
+            Some(Tests {
+                format,
+                text: take_text(&mut contents)?,
+            })
+        }
+    };
+
+    if !contents.is_empty() {
+        return Err(BundleError::TrailingData(buffer.len() - contents.len()));
+    }
+
+    Ok(Bundle {
+        memory,
+        metadata: metadata.unwrap_or_default(),
+        source,
+        tests,
+    })
+}
+
+#[cfg(feature = "std")]
+/// Save a [Bundle] to a file given the path str
+///
+/// # Errors
+/// [`io::Error`] - file system error, or [`io::ErrorKind::InvalidInput`] with a [`BundleError`]
+pub fn save_bundle(path: &str, bundle: &Bundle) -> io::Result<()> {
+    let buffer = save_bundle_to_vec(bundle)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+    fs::write(path, buffer)
+}
+
+#[cfg(feature = "std")]
+/// Load a [Bundle] from a file given the path str.
+///
+/// Unlike other files, bundles are not limited to
+///  [`MAX_FILE_LOAD_SIZE`](super::MAX_FILE_LOAD_SIZE) bytes, as the source and tests can be long
+///
+/// # Errors
+/// See [`FromFileError`]
+pub fn load_bundle(path: &str) -> Result<Bundle, FromFileError> {
+    load_bundle_from_buffer(&fs::read(path)?).map_err(FromFileError::from)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{assembler::assemble_from_text, file::Metadata};
+
+    use super::{
+        load_bundle_from_buffer, save_bundle_to_vec, Bundle, BundleError, Tests, TestsFormat,
+    };
+
+    #[test]
+    fn bundle() {
+        let source = "IN\nSTO 99\nIN\nADD 99\nOUT\nHLT";
+        let memory = assemble_from_text(source)
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+        let metadata = Metadata {
+            name: Some("Add".to_string()),
+            ..Metadata::default()
+        };
+
+        let bundle = Bundle::new(memory, metadata.clone())
+            .with_source(source.to_string())
+            .with_tests(Tests {
+                format: TestsFormat::CSV,
+                text: "5,6;11\n1,2;3".to_string(),
+            });
+        let saved = save_bundle_to_vec(&bundle).expect("failed to save the bundle");
+        assert_eq!(
+            load_bundle_from_buffer(&saved),
+            Ok(bundle),
+            "Failed to load the saved bundle!"
+        );
+
+        // Without the source and tests
+        let bundle = Bundle::new(memory, metadata);
+        let mut saved = save_bundle_to_vec(&bundle).expect("failed to save the bundle");
+        assert_eq!(
+            load_bundle_from_buffer(&saved),
+            Ok(bundle),
+            "Failed to load the saved bundle without source or tests!"
+        );
+
+        assert_eq!(
+            load_bundle_from_buffer(&saved[..saved.len() - 1]),
+            Err(BundleError::Truncated),
+            "Failed to reject a truncated bundle!"
+        );
+        saved.push(0);
+        assert_eq!(
+            load_bundle_from_buffer(&saved),
+            Err(BundleError::TrailingData(saved.len() - 1)),
+            "Failed to reject trailing data!"
+        );
+        assert_eq!(
+            load_bundle_from_buffer(b"LMNC"),
+            Err(BundleError::NotBundle),
+            "Failed to reject a buffer that is not a bundle!"
+        );
+    }
+}
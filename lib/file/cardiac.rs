@@ -0,0 +1,496 @@
+extern crate alloc;
+use core::fmt;
+
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use crate::{
+    computer::Memory,
+    errors::{self, LineNumber},
+    num2::TwoDigitNumber,
+    num3::ThreeDigitNumber,
+};
+
+/// The bootstrap cards at the start of a deck, which load the loader into cells 2 and 3
+const BOOTSTRAP: [i16; 2] = [2, 800];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Errors for reading CARDIAC decks and converting them to and from [Memory]
+pub enum CardiacError {
+    /// A card was not a number from -999 to 999
+    InvalidCard,
+    /// The deck did not start with the bootstrap cards (`002` and `800`)
+    MissingBootstrap,
+    /// A card was not an address card (`0aa`) or the jump to the program (`8aa`)
+    ExpectedAddressCard(i16),
+    /// An address card was the last card, so it had no value card
+    MissingValueCard,
+    /// The deck did not end with the jump to the program (`8aa`)
+    MissingJump,
+    /// The instruction at the address has no LMC equivalent
+    Unsupported(usize, i16),
+    /// The data at the address is negative, which the LMC cannot store
+    NegativeData(usize, i16),
+    /// The address (of a cell or the entry) is not in memory (> 99)
+    AddressOutOfRange(usize),
+    /// The memory does not start with a jump to the program (`6aa`),
+    ///  which is needed as cell 0 is read only on the CARDIAC
+    MissingEntryJump,
+}
+
+impl fmt::Display for CardiacError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidCard => write!(f, "Invalid card (should be from -999 to 999)!"),
+            Self::MissingBootstrap => write!(f, "Deck does not start with the bootstrap cards!"),
+            Self::ExpectedAddressCard(card) => {
+                write!(
+                    f,
+                    "Expected an address card (0aa) or a jump (8aa), found {card:03}!"
+                )
+            }
+            Self::MissingValueCard => write!(f, "Address card has no value card!"),
+            Self::MissingJump => write!(f, "Deck does not end with a jump to the program!"),
+            Self::Unsupported(address, word) => write!(
+                f,
+                "Instruction has no LMC equivalent (address {address}, {word:03})!"
+            ),
+            Self::NegativeData(address, word) => {
+                write!(
+                    f,
+                    "Negative data cannot be stored (address {address}, {word})!"
+                )
+            }
+            Self::AddressOutOfRange(address) => {
+                write!(f, "Address is not in memory (address {address}, > 99)!")
+            }
+            Self::MissingEntryJump => write!(
+                f,
+                "Memory does not start with a jump to the program (6aa), which the CARDIAC needs!"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CardiacError {}
+
+pub type CardiacErrorWithLineNumber = errors::ErrorWithLocation<CardiacError, LineNumber>;
+
+/// Read a card, an optionally signed number from -999 to 999
+fn read_card(card: &str) -> Result<i16, CardiacError> {
+    card.parse()
+        .ok()
+        .filter(|card: &i16| (-999..=999).contains(card))
+        .ok_or(CardiacError::InvalidCard)
+}
+
+/// Get a memory address, if it is in memory
+fn address(address: usize) -> Result<TwoDigitNumber, CardiacError> {
+    TwoDigitNumber::try_from(address).map_err(|_| CardiacError::AddressOutOfRange(address))
+}
+
+/// Get the card for a word in memory
+fn card(word: ThreeDigitNumber) -> i16 {
+    // The word is at most 999, so it fits
+    #[allow(clippy::cast_possible_wrap)]
+    {
+        u16::from(word) as i16
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+/// A deck of cards for the CARDIAC cardboard computer, using its standard loader.
+///
+/// The deck is the bootstrap cards (`002` and `800`), then an address card (`0aa`)
+///  and a value card for each cell, then a jump to the program (`8aa`).
+/// One card is written per line, and blank lines and comments after `#` or `;` are ignored
+///
+/// ```
+/// # use lminc::file::CardiacDeck;
+/// let deck = CardiacDeck::from_text("002\n800\n010\n110\n011\n910\n811")?;
+/// assert_eq!(deck.entry, 11);
+/// assert_eq!(deck.to_string(), "002\n800\n010\n110\n011\n910\n811\n");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct CardiacDeck {
+    /// The words loaded into each address
+    pub cells: BTreeMap<usize, i16>,
+    /// The address the program starts at
+    pub entry: usize,
+}
+
+impl CardiacDeck {
+    /// Read a deck from its text, one card per line
+    ///
+    /// # Errors
+    /// See [`CardiacError`]
+    pub fn from_text(text: &str) -> Result<Self, CardiacErrorWithLineNumber> {
+        let mut cards = text
+            .lines()
+            .enumerate()
+            .filter_map(|(line_number, line)| {
+                let card = line.split(['#', ';']).next().unwrap_or_default().trim();
+                (!card.is_empty()).then_some((LineNumber(line_number + 1), card))
+            })
+            .map(|(line_number, card)| {
+                read_card(card)
+                    .map(|card| (line_number, card))
+                    .map_err(|error| errors::ErrorWithLocation(line_number, error))
+            })
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter();
+
+        let end = LineNumber(text.lines().count());
+
+        for expected in BOOTSTRAP {
+            match cards.next() {
+                Some((_, card)) if card == expected => (),
+                Some((line_number, _)) => {
+                    return Err(errors::ErrorWithLocation(
+                        line_number,
+                        CardiacError::MissingBootstrap,
+                    ))
+                }
+                None => {
+                    return Err(errors::ErrorWithLocation(
+                        end,
+                        CardiacError::MissingBootstrap,
+                    ))
+                }
+            }
+        }
+
+        let mut deck = Self::default();
+        loop {
+            let Some((line_number, card)) = cards.next() else {
+                return Err(errors::ErrorWithLocation(end, CardiacError::MissingJump));
+            };
+
+            // Cards are at most 999, so the address is always in memory
+            #[allow(clippy::cast_sign_loss)]
+            let address = (card % 100) as usize;
+            match card / 100 {
+                _ if card < 0 => {
+                    return Err(errors::ErrorWithLocation(
+                        line_number,
+                        CardiacError::ExpectedAddressCard(card),
+                    ))
+                }
+                0 => {
+                    let Some((_, value)) = cards.next() else {
+                        return Err(errors::ErrorWithLocation(
+                            line_number,
+                            CardiacError::MissingValueCard,
+                        ));
+                    };
+                    deck.cells.insert(address, value);
+                }
+                8 => {
+                    deck.entry = address;
+                    return Ok(deck);
+                }
+                _ => {
+                    return Err(errors::ErrorWithLocation(
+                        line_number,
+                        CardiacError::ExpectedAddressCard(card),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Convert the deck to [Memory] for the LMC.
+    ///
+    /// The instructions that the program can reach from the entry are translated
+    ///  (`CLA` to `LDA`, `ADD`, `SUB`, `STO`, `JMP` to `BR` and `HRS` to `HLT`),
+    ///  the other cells are copied as data, and cell 0 jumps to the entry.
+    /// `INP`, `OUT`, `TAC` and `SFT` have no LMC equivalent, so they cannot be converted
+    ///
+    /// # Errors
+    /// See [`CardiacError`]
+    pub fn to_memory(&self) -> Result<Memory, CardiacError> {
+        let entry = address(self.entry)?;
+        let mut memory = [ThreeDigitNumber::ZERO; 100];
+
+        // Copy the data
+        for (address, word) in &self.cells {
+            let cell = memory
+                .get_mut(*address)
+                .ok_or(CardiacError::AddressOutOfRange(*address))?;
+            if !(-999..=999).contains(word) {
+                return Err(CardiacError::InvalidCard);
+            }
+            *cell = u16::try_from(*word)
+                .ok()
+                .and_then(|word| ThreeDigitNumber::try_from(word).ok())
+                .ok_or(CardiacError::NegativeData(*address, *word))?;
+        }
+
+        // Translate the instructions reachable from the entry
+        let mut translated = [false; 100];
+        let mut next = Vec::from([entry]);
+        while let Some(address) = next.pop() {
+            let index = usize::from(address);
+            if translated[index] {
+                continue;
+            }
+            translated[index] = true;
+
+            let (op_code, operand) = memory[index].split_op();
+            let (op_code, continues) = match op_code {
+                // CLA, ADD, STO and SUB
+                1 => (5, true),
+                2 => (1, true),
+                6 => (3, true),
+                7 => (2, true),
+                // JMP
+                8 => {
+                    next.push(operand);
+                    (6, false)
+                }
+                // HRS
+                9 => (0, false),
+                _ => return Err(CardiacError::Unsupported(index, card(memory[index]))),
+            };
+
+            memory[index] = ThreeDigitNumber::compose(op_code, operand)
+                .ok_or_else(|| CardiacError::Unsupported(index, card(memory[index])))?;
+            if let Some(address) = address.checked_next().filter(|_| continues) {
+                next.push(address);
+            }
+        }
+
+        // The LMC starts at 0, which is read only on the CARDIAC, so it is free for the jump
+        memory[0] = ThreeDigitNumber::compose(6, entry)
+            .ok_or(CardiacError::AddressOutOfRange(self.entry))?;
+
+        Ok(memory)
+    }
+
+    /// Convert [Memory] from the LMC to a deck, the inverse of [`Self::to_memory`].
+    ///
+    /// Cell 0 must jump to the program (`6aa`), which becomes the deck's entry.
+    /// The instructions that the program can reach from the entry are translated
+    ///  (`LDA` to `CLA`, `ADD`, `SUB`, `STO`, `BR` to `JMP` and `HLT` to `HRS`),
+    ///  the other cells are copied as data, and cells holding 0 are left out.
+    /// The other instructions have no CARDIAC equivalent, so they cannot be converted
+    ///
+    /// # Errors
+    /// See [`CardiacError`]
+    pub fn from_memory(memory: &Memory) -> Result<Self, CardiacError> {
+        let entry = match memory[0].split_op() {
+            (6, entry) if entry != TwoDigitNumber::ZERO => entry,
+            _ => return Err(CardiacError::MissingEntryJump),
+        };
+
+        let mut words = *memory;
+        words[0] = ThreeDigitNumber::ZERO;
+
+        // Translate the instructions reachable from the entry
+        let mut translated = [false; 100];
+        translated[0] = true;
+        let mut next = Vec::from([entry]);
+        while let Some(address) = next.pop() {
+            let index = usize::from(address);
+            if translated[index] {
+                continue;
+            }
+            translated[index] = true;
+
+            let (op_code, operand) = memory[index].split_op();
+            let (op_code, operand, continues) = match op_code {
+                // LDA, ADD, STO and SUB
+                5 => (1, operand, true),
+                1 => (2, operand, true),
+                3 => (6, operand, true),
+                2 => (7, operand, true),
+                // BR
+                6 => {
+                    next.push(operand);
+                    (8, operand, false)
+                }
+                // HLT
+                0 => (9, TwoDigitNumber::ZERO, false),
+                _ => return Err(CardiacError::Unsupported(index, card(memory[index]))),
+            };
+
+            words[index] = ThreeDigitNumber::compose(op_code, operand)
+                .ok_or_else(|| CardiacError::Unsupported(index, card(memory[index])))?;
+            if let Some(address) = address.checked_next().filter(|_| continues) {
+                next.push(address);
+            }
+        }
+
+        Ok(Self {
+            cells: words
+                .into_iter()
+                .enumerate()
+                .filter(|(_, word)| *word != ThreeDigitNumber::ZERO)
+                .map(|(address, word)| (address, card(word)))
+                .collect(),
+            entry: usize::from(entry),
+        })
+    }
+}
+
+impl fmt::Display for CardiacDeck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for card in BOOTSTRAP {
+            writeln!(f, "{card:03}")?;
+        }
+        for (address, word) in &self.cells {
+            writeln!(f, "{address:03}")?;
+            if *word < 0 {
+                writeln!(f, "-{:03}", word.unsigned_abs())?;
+            } else {
+                writeln!(f, "{word:03}")?;
+            }
+        }
+        writeln!(f, "{}", 800 + self.entry)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::alloc::collections::BTreeMap;
+
+    use crate::{
+        computer::{Computer, State},
+        errors::{ErrorWithLocation, LineNumber},
+        num3::ThreeDigitNumber,
+        three_digit,
+    };
+
+    use super::{CardiacDeck, CardiacError};
+
+    #[test]
+    fn cardiac() {
+        // Adds the numbers in cells 20 and 21 into cell 22
+        let text = "\
+002
+800
+# The program
+010
+120 ; CLA 20
+011
+221 ; ADD 21
+012
+622 ; STO 22
+013
+900 ; HRS
+# The data
+020
+005
+021
+006
+810";
+        let deck = CardiacDeck::from_text(text).expect("failed to read the deck");
+        assert_eq!(deck.entry, 10, "Read the wrong entry!");
+        assert_eq!(
+            CardiacDeck::from_text(&deck.to_string()),
+            Ok(deck.clone()),
+            "Failed to read the written deck!"
+        );
+
+        let memory = deck.to_memory().expect("failed to convert the deck");
+        let mut computer = Computer::new(memory);
+        while computer.step() == State::Running {}
+        assert_eq!(computer.state(), State::Halted, "Program did not halt!");
+        assert_eq!(
+            u16::from(computer.get_memory()[22]),
+            11,
+            "Program did not add the numbers!"
+        );
+
+        assert_eq!(
+            CardiacDeck::from_memory(&memory),
+            Ok(deck),
+            "Failed to round trip the deck through memory!"
+        );
+
+        assert_eq!(
+            CardiacDeck::from_text("002\n800\n010\n510\n810")
+                .expect("failed to read the deck")
+                .to_memory(),
+            Err(CardiacError::Unsupported(10, 510)),
+            "Failed to reject an instruction with no LMC equivalent!"
+        );
+        assert_eq!(
+            CardiacDeck::from_text("002\n800\n010"),
+            Err(ErrorWithLocation(
+                LineNumber(3),
+                CardiacError::MissingValueCard
+            )),
+            "Failed to reject an address card without a value!"
+        );
+        assert_eq!(
+            CardiacDeck::from_text("010\n110"),
+            Err(ErrorWithLocation(
+                LineNumber(1),
+                CardiacError::MissingBootstrap
+            )),
+            "Failed to reject a deck without the bootstrap!"
+        );
+    }
+
+    #[test]
+    fn out_of_range() {
+        let deck = CardiacDeck {
+            cells: BTreeMap::from([(10, 900)]),
+            entry: 400,
+        };
+        assert_eq!(
+            deck.to_memory(),
+            Err(CardiacError::AddressOutOfRange(400)),
+            "Failed to reject an entry out of memory!"
+        );
+
+        let deck = CardiacDeck {
+            cells: BTreeMap::from([(100, 900)]),
+            entry: 10,
+        };
+        assert_eq!(
+            deck.to_memory(),
+            Err(CardiacError::AddressOutOfRange(100)),
+            "Failed to reject a cell out of memory!"
+        );
+    }
+
+    #[test]
+    fn from_memory() {
+        let mut memory = [ThreeDigitNumber::ZERO; 100];
+        assert_eq!(
+            CardiacDeck::from_memory(&memory),
+            Err(CardiacError::MissingEntryJump),
+            "Failed to reject memory that does not jump to the program!"
+        );
+
+        // BR 10, then INP
+        memory[0] = three_digit!(610);
+        memory[10] = three_digit!(901);
+        assert_eq!(
+            CardiacDeck::from_memory(&memory),
+            Err(CardiacError::Unsupported(10, 901)),
+            "Failed to reject an instruction with no CARDIAC equivalent!"
+        );
+
+        // LDA 20, SUB 21, HLT, with data that looks like an instruction
+        memory[10] = three_digit!(520);
+        memory[11] = three_digit!(221);
+        memory[12] = three_digit!(0);
+        memory[20] = three_digit!(901);
+        memory[21] = three_digit!(1);
+        let deck = CardiacDeck::from_memory(&memory).expect("failed to convert the memory");
+        assert_eq!(
+            deck.to_string(),
+            "002\n800\n010\n120\n011\n721\n012\n900\n020\n901\n021\n001\n810\n",
+            "Converted the memory to the wrong deck!"
+        );
+        assert_eq!(
+            deck.to_memory(),
+            Ok(memory),
+            "Failed to round trip the memory through a deck!"
+        );
+    }
+}
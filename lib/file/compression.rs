@@ -0,0 +1,124 @@
+extern crate alloc;
+#[cfg(feature = "std")]
+use std::{
+    fs::File,
+    io::{self, Write},
+};
+
+use alloc::vec::Vec;
+
+use miniz_oxide::{deflate::compress_to_vec, inflate::decompress_to_vec_with_limit};
+
+#[cfg(feature = "std")]
+use crate::computer::Memory;
+
+use super::{
+    container::is_compressed, Error, COMPRESSED_MAGIC, MAX_CONTAINER_SIZE, MAX_METADATA_SIZE,
+};
+#[cfg(feature = "std")]
+use super::{save_container_with_metadata, Metadata};
+
+/// The compression level, from 0 (none) to 10 (smallest)
+const LEVEL: u8 = 10;
+
+#[must_use]
+/// Compress a saved buffer, such as a container with metadata.
+///
+/// Compressed buffers start with the [`COMPRESSED_MAGIC`] bytes,
+///  and are detected and decompressed by [`load_from_buffer`](super::load_from_buffer)
+///
+/// ```
+/// # use lminc::{assembler::assemble_from_text, file};
+/// let memory = assemble_from_text("IN\nOUT\nHLT")??;
+/// let metadata = file::Metadata { name: Some("echo".to_string()), ..Default::default() };
+///
+/// let saved = file::save_container_with_metadata(memory, &metadata)?;
+/// let compressed = file::compress(&saved);
+/// assert_eq!(file::load_from_buffer(&compressed)?, memory);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn compress(buffer: &[u8]) -> Vec<u8> {
+    let mut compressed = COMPRESSED_MAGIC.to_vec();
+    compressed.extend(compress_to_vec(buffer, LEVEL));
+    compressed
+}
+
+/// Decompress a buffer compressed with [`compress`]
+///
+/// # Errors
+/// [`Error::InvalidCompression`] - if the buffer is not compressed, could not be decompressed,
+///  or is too large when decompressed
+pub fn decompress(buffer: &[u8]) -> Result<Vec<u8>, Error> {
+    let Some(compressed) = buffer.strip_prefix(&COMPRESSED_MAGIC) else {
+        return Err(Error::InvalidCompression);
+    };
+
+    let decompressed =
+        decompress_to_vec_with_limit(compressed, MAX_CONTAINER_SIZE + MAX_METADATA_SIZE)
+            .map_err(|_| Error::InvalidCompression)?;
+
+    // Only compress once
+    if is_compressed(&decompressed) {
+        return Err(Error::InvalidCompression);
+    }
+
+    Ok(decompressed)
+}
+
+#[cfg(feature = "std")]
+/// Save the [Memory] to a file in a compressed container with the [Metadata] given the path str
+///
+/// # Errors
+/// [`io::Error`] - file system error, or [`io::ErrorKind::InvalidInput`] with a
+///  [`MetadataError`](super::MetadataError)
+pub fn save_compressed(path: &str, memory: Memory, metadata: &Metadata) -> io::Result<()> {
+    let buffer = save_container_with_metadata(memory, metadata)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+
+    File::create(path)?.write_all(&compress(&buffer))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        assembler::assemble_from_text,
+        file::{load_with_metadata_from_buffer, save_container_with_metadata, Error, Metadata},
+    };
+
+    use super::{compress, decompress};
+
+    #[test]
+    fn compression() {
+        let memory = assemble_from_text("IN\nSTO 99\nIN\nADD 99\nOUT\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+        let metadata = Metadata {
+            description: Some("Adds two numbers. ".repeat(10)),
+            ..Metadata::default()
+        };
+
+        let saved =
+            save_container_with_metadata(memory, &metadata).expect("failed to save the metadata");
+        let compressed = compress(&saved);
+        assert!(
+            compressed.len() < saved.len(),
+            "Failed to compress the container!"
+        );
+        assert_eq!(
+            load_with_metadata_from_buffer(&compressed),
+            Ok((memory, Some(metadata))),
+            "Failed to load the compressed container!"
+        );
+
+        assert_eq!(
+            decompress(&compress(&compressed)),
+            Err(Error::InvalidCompression),
+            "Failed to reject a buffer compressed twice!"
+        );
+        assert_eq!(
+            decompress(&compressed[..compressed.len() / 2]),
+            Err(Error::InvalidCompression),
+            "Failed to reject a truncated compressed buffer!"
+        );
+    }
+}
@@ -0,0 +1,267 @@
+#[cfg(feature = "std")]
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::PathBuf,
+};
+
+use crate::computer::Memory;
+
+use super::{save_to_buffer, Error, MAX_FILE_SIZE};
+
+/// The magic bytes at the start of a container.
+///
+/// Legacy files have no header, so one starting with the numbers 305, 212, 912
+///  and a number from 768 also starts with these bytes, and is loaded as a container
+///  (which fails). Such files can be loaded with
+///  [`load_legacy_from_buffer`](super::load_legacy_from_buffer)
+pub const MAGIC: [u8; 4] = *b"LMNC";
+/// The magic bytes at the start of a compressed buffer, see `compress` (with the `compression` feature)
+pub const COMPRESSED_MAGIC: [u8; 4] = *b"LMNZ";
+/// The container format version written by this version of lminc
+pub const VERSION: u8 = 1;
+/// The size of the container header in bytes
+///
+/// The header is the [`MAGIC`] bytes, the [`VERSION`], the length of the saved memory
+///  and the CRC-32 of the saved memory (big endian)
+pub const HEADER_SIZE: usize = MAGIC.len() + 1 + 1 + 4;
+/// The maximum size of a container in bytes
+pub const MAX_CONTAINER_SIZE: usize = HEADER_SIZE + MAX_FILE_SIZE;
+/// The size of the metadata section header in bytes, the length (big endian `u16`)
+///  and the CRC-32 (big endian `u32`) of the records
+pub const METADATA_HEADER_SIZE: usize = 2 + 4;
+
+/// Get the CRC-32 (IEEE) checksum of the bytes
+pub(super) const fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0_u32;
+
+    let mut index = 0;
+    while index < bytes.len() {
+        crc ^= bytes[index] as u32;
+
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 0 {
+                crc >> 1
+            } else {
+                (crc >> 1) ^ 0xEDB8_8320
+            };
+            bit += 1;
+        }
+
+        index += 1;
+    }
+
+    !crc
+}
+
+/// Check if the buffer starts with the [`COMPRESSED_MAGIC`] bytes
+pub(super) fn is_compressed(buffer: &[u8]) -> bool {
+    buffer.starts_with(&COMPRESSED_MAGIC)
+}
+
+/// Get the length of the metadata section at the start of the buffer from its header,
+///  if the buffer is long enough for the header
+pub(super) fn metadata_section_len(buffer: &[u8]) -> Option<usize> {
+    let header = buffer.get(..METADATA_HEADER_SIZE)?;
+    Some(METADATA_HEADER_SIZE + usize::from(u16::from_be_bytes([header[0], header[1]])))
+}
+
+/// Split a container into the saved memory and anything after it (the metadata), checking the header.
+///
+/// Legacy buffers, which do not start with the [`MAGIC`] bytes, are all saved memory.
+/// A buffer that starts with them but fails the checks is not loaded as a legacy buffer
+///  instead, as corrupt containers would then load as the wrong memory, see [`MAGIC`]
+pub(super) fn split_container(buffer: &[u8]) -> Result<(&[u8], &[u8]), Error> {
+    if !buffer.starts_with(&MAGIC) {
+        return Ok((buffer, &[]));
+    }
+
+    let Some((header, contents)) = buffer.split_at_checked(HEADER_SIZE) else {
+        return Err(Error::TruncatedHeader);
+    };
+
+    let version = header[4];
+    if version != VERSION {
+        return Err(Error::UnsupportedVersion(version));
+    }
+
+    let length = usize::from(header[5]);
+    let Some((contents, rest)) = contents.split_at_checked(length) else {
+        return Err(Error::WrongLength(length, contents.len()));
+    };
+
+    let checksum = u32::from_be_bytes([header[6], header[7], header[8], header[9]]);
+    let actual = crc32(contents);
+    if checksum != actual {
+        return Err(Error::WrongChecksum(checksum, actual));
+    }
+
+    Ok((contents, rest))
+}
+
+#[allow(clippy::module_name_repetitions)]
+/// Save the [Memory] to the buffer in a container and return a trimmed version of it.
+///
+/// Unlike [`save_to_buffer`], the container has a header with a version and checksum,
+///  so wrong or corrupt files can be detected when loading
+pub fn save_container_to_buffer(buffer: &mut [u8; MAX_CONTAINER_SIZE], memory: Memory) -> &[u8] {
+    let (header, contents) = buffer.split_at_mut(HEADER_SIZE);
+
+    // Save the memory after the header
+    let mut saved = [0; MAX_FILE_SIZE];
+    let saved = save_to_buffer(&mut saved, memory);
+    contents[..saved.len()].copy_from_slice(saved);
+
+    header[..MAGIC.len()].copy_from_slice(&MAGIC);
+    header[4] = VERSION;
+    // The saved memory is never more than MAX_FILE_SIZE bytes, so it fits in a u8
+    #[allow(clippy::cast_possible_truncation)]
+    {
+        header[5] = saved.len() as u8;
+    }
+    header[6..].copy_from_slice(&crc32(saved).to_be_bytes());
+
+    &buffer[..HEADER_SIZE + saved.len()]
+}
+
+#[cfg(feature = "std")]
+#[allow(clippy::module_name_repetitions)]
+/// Save the [Memory] to the given writer in a container
+///
+/// # Errors
+/// [`io::Error`] - writer error
+pub fn save_container_to_writer(writer: &mut impl Write, memory: Memory) -> io::Result<()> {
+    let mut buffer = [0; MAX_CONTAINER_SIZE];
+    writer.write_all(save_container_to_buffer(&mut buffer, memory))
+}
+
+#[cfg(feature = "std")]
+#[allow(clippy::module_name_repetitions)]
+/// Save the [Memory] to the given file in a container
+///
+/// This function will move the cursor inside file,
+/// unless you know what you are doing, do not use `file` after calling this function
+///
+/// # Errors
+/// [`io::Error`] - file system error
+pub fn save_container_to_file(file: &mut File, memory: Memory) -> io::Result<()> {
+    save_container_to_writer(file, memory)
+}
+
+#[cfg(feature = "std")]
+#[allow(clippy::module_name_repetitions)]
+/// Save the [Memory] to a file in a container given the path str
+///
+/// # Errors
+/// [`io::Error`] - file system error
+pub fn save_container(path: &str, memory: Memory) -> io::Result<()> {
+    save_container_to_file(&mut File::create(path)?, memory)
+}
+
+#[cfg(feature = "std")]
+#[allow(clippy::module_name_repetitions)]
+/// Save the [Memory] to a file in a container given the path
+///
+/// # Errors
+/// [`io::Error`] - file system error
+pub fn save_container_to_path(path: PathBuf, memory: Memory) -> io::Result<()> {
+    save_container_to_file(&mut File::create(path)?, memory)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        assembler::assemble_from_text,
+        computer::Memory,
+        file::{load_from_buffer, load_legacy_from_buffer, save_to_buffer, Error, MAX_FILE_SIZE},
+        num3::ThreeDigitNumber,
+        three_digit,
+    };
+
+    use super::{crc32, save_container_to_buffer, HEADER_SIZE, MAGIC, MAX_CONTAINER_SIZE};
+
+    #[test]
+    fn checksum() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926, "Got the wrong checksum!");
+    }
+
+    #[test]
+    fn container() {
+        let memory = assemble_from_text("IN\nSTO 99\nIN\nADD 99\nOUT\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        let mut buffer = [0; MAX_CONTAINER_SIZE];
+        let saved = save_container_to_buffer(&mut buffer, memory).to_vec();
+        assert_eq!(
+            load_from_buffer(&saved),
+            Ok(memory),
+            "Failed to load the saved container!"
+        );
+
+        // Legacy files still load
+        let mut legacy = [0; MAX_FILE_SIZE];
+        assert_eq!(
+            load_from_buffer(save_to_buffer(&mut legacy, memory)),
+            Ok(memory),
+            "Failed to load the legacy file!"
+        );
+
+        let mut corrupt = saved.clone();
+        corrupt[HEADER_SIZE] ^= 1;
+        assert!(
+            matches!(load_from_buffer(&corrupt), Err(Error::WrongChecksum(..))),
+            "Failed to reject a corrupt container!"
+        );
+
+        assert_eq!(
+            load_from_buffer(&saved[..saved.len() - 1]),
+            Err(Error::WrongLength(
+                saved.len() - HEADER_SIZE,
+                saved.len() - HEADER_SIZE - 1
+            )),
+            "Failed to reject a truncated container!"
+        );
+        assert_eq!(
+            load_from_buffer(&saved[..HEADER_SIZE - 1]),
+            Err(Error::TruncatedHeader),
+            "Failed to reject a truncated header!"
+        );
+
+        let mut future = saved;
+        future[4] = 2;
+        assert_eq!(
+            load_from_buffer(&future),
+            Err(Error::UnsupportedVersion(2)),
+            "Failed to reject an unsupported version!"
+        );
+    }
+
+    #[test]
+    fn magic_collision() {
+        let mut memory: Memory = [ThreeDigitNumber::ZERO; 100];
+        memory[..4].copy_from_slice(&[
+            three_digit!(305),
+            three_digit!(212),
+            three_digit!(912),
+            three_digit!(800),
+        ]);
+
+        let mut buffer = [0; MAX_FILE_SIZE];
+        let saved = save_to_buffer(&mut buffer, memory);
+        assert!(
+            saved.starts_with(&MAGIC),
+            "Legacy file does not start with the magic bytes!"
+        );
+        assert!(
+            load_from_buffer(saved).is_err(),
+            "Loaded a legacy file that starts with the magic bytes!"
+        );
+        assert_eq!(
+            load_legacy_from_buffer(saved),
+            Ok(memory),
+            "Failed to load the legacy file!"
+        );
+    }
+}
@@ -0,0 +1,304 @@
+extern crate alloc;
+use core::{fmt, num::ParseIntError};
+
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+};
+
+use crate::{
+    errors::{self, LineNumber},
+    parser::{self, Parser},
+};
+
+use super::Metadata;
+
+/// The first line of a debug info file, followed by the format version
+const DEBUG_INFO_HEADER: &str = "lminc-debug";
+/// The debug info format version written by this version of lminc
+pub const DEBUG_INFO_VERSION: u32 = 1;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Errors for reading debug info
+pub enum DebugInfoError {
+    /// The first line was not the debug info header
+    MissingHeader,
+    /// The debug info was written with an unsupported format version
+    UnsupportedVersion(u32),
+    /// A line did not start with a known entry
+    UnknownEntry(String),
+    /// An entry was missing a value
+    MissingValue,
+    /// An address or line number was not a valid number
+    InvalidNumber(ParseIntError),
+    /// An address was too large (> 99)
+    AddressTooLarge(usize),
+}
+
+impl fmt::Display for DebugInfoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingHeader => write!(f, "Not a debug info file!"),
+            Self::UnsupportedVersion(version) => write!(
+                f,
+                "The debug info format version is not supported (version {version})!"
+            ),
+            Self::UnknownEntry(entry) => write!(f, "Unknown debug info entry ({entry:?})!"),
+            Self::MissingValue => write!(f, "Debug info entry is missing a value!"),
+            Self::InvalidNumber(_) => write!(f, "Invalid number in debug info!"),
+            Self::AddressTooLarge(address) => {
+                write!(f, "Address too large ({address} should be < 100)!")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DebugInfoError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidNumber(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseIntError> for DebugInfoError {
+    fn from(value: ParseIntError) -> Self {
+        Self::InvalidNumber(value)
+    }
+}
+
+pub type DebugInfoErrorWithLineNumber = errors::ErrorWithLocation<DebugInfoError, LineNumber>;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+/// Debug info for an assembled program, a source map and the symbols,
+///  so addresses can be translated back to source lines and labels.
+///
+/// It is written as text, one entry per line:
+/// ```text
+/// lminc-debug 1
+/// source add.txt
+/// line 0 1
+/// symbol 0 start
+/// ```
+///
+/// ```
+/// # use lminc::{errors::LineNumber, file::DebugInfo};
+/// let debug_info = DebugInfo::from_assembly("# Adds two numbers\nstart IN\nSTO 99")?;
+/// assert_eq!(debug_info.line(1), Some(LineNumber(3)));
+/// assert_eq!(debug_info.label(0), Some("start"));
+///
+/// assert_eq!(DebugInfo::from_text(&debug_info.to_string())?, debug_info);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct DebugInfo {
+    /// The path of the source file, if it is known
+    pub source: Option<String>,
+    /// The source line number of the instruction at each address
+    pub lines: BTreeMap<usize, usize>,
+    /// The labels in the program and their addresses
+    pub symbols: BTreeMap<String, usize>,
+}
+
+impl DebugInfo {
+    /// Create the debug info for assembly text
+    ///
+    /// # Errors
+    /// See [`parser::Error`]
+    pub fn from_assembly(text: &str) -> Result<Self, parser::ErrorWithLocation<LineNumber>> {
+        let mut parser = Parser::new();
+        let mut lines = BTreeMap::new();
+
+        for (line_number, line) in text.lines().enumerate() {
+            let address = parser.len();
+            parser
+                .parse_line(line)
+                .map_err(|error| error.map_location(|_| LineNumber(line_number + 1)))?;
+
+            // Only lines with an instruction add to the parser
+            if parser.len() != address {
+                lines.insert(address, line_number + 1);
+            }
+        }
+
+        Ok(Self {
+            source: None,
+            lines,
+            symbols: Metadata::from_parser(&parser).symbols,
+        })
+    }
+
+    #[must_use]
+    /// Get the source line of the instruction at an address
+    pub fn line(&self, address: usize) -> Option<LineNumber> {
+        self.lines.get(&address).copied().map(LineNumber)
+    }
+
+    #[must_use]
+    /// Get the address of the instruction on a source line
+    pub fn address(&self, line: LineNumber) -> Option<usize> {
+        self.lines
+            .iter()
+            .find_map(|(address, line_number)| (*line_number == line.0).then_some(*address))
+    }
+
+    #[must_use]
+    /// Get the label for an address, if it has one.
+    /// If there are multiple, this is the first alphabetically
+    pub fn label(&self, address: usize) -> Option<&str> {
+        self.symbols.iter().find_map(|(label, label_address)| {
+            (*label_address == address).then_some(label.as_str())
+        })
+    }
+
+    /// Read one line of debug info
+    fn read_line(&mut self, line: &str) -> Result<(), DebugInfoError> {
+        let address = |address: &str| {
+            let address = address.parse()?;
+            if address < 100 {
+                Ok(address)
+            } else {
+                Err(DebugInfoError::AddressTooLarge(address))
+            }
+        };
+
+        let (entry, value) = line.split_once(' ').unwrap_or((line, ""));
+        let mut values = value.split_whitespace();
+        let mut next = || values.next().ok_or(DebugInfoError::MissingValue);
+
+        match entry {
+            "source" => self.source = Some(value.to_string()),
+            "line" => {
+                let address = address(next()?)?;
+                self.lines.insert(address, next()?.parse()?);
+            }
+            "symbol" => {
+                let address = address(next()?)?;
+                self.symbols.insert(next()?.to_string(), address);
+            }
+            _ => return Err(DebugInfoError::UnknownEntry(entry.to_string())),
+        }
+
+        Ok(())
+    }
+
+    /// Read debug info from its text format
+    ///
+    /// # Errors
+    /// See [`DebugInfoError`]
+    pub fn from_text(text: &str) -> Result<Self, DebugInfoErrorWithLineNumber> {
+        let mut lines = text.lines().enumerate();
+
+        let header = lines.next().map(|(_, line)| line.split_once(' '));
+        let Some(Some((DEBUG_INFO_HEADER, version))) = header else {
+            return Err(errors::ErrorWithLocation(
+                LineNumber(1),
+                DebugInfoError::MissingHeader,
+            ));
+        };
+        let version = version.parse().map_err(|error| {
+            errors::ErrorWithLocation(LineNumber(1), DebugInfoError::from(error))
+        })?;
+        if version != DEBUG_INFO_VERSION {
+            return Err(errors::ErrorWithLocation(
+                LineNumber(1),
+                DebugInfoError::UnsupportedVersion(version),
+            ));
+        }
+
+        let mut debug_info = Self::default();
+        for (line_number, line) in lines.filter(|(_, line)| !line.trim().is_empty()) {
+            debug_info
+                .read_line(line)
+                .map_err(|error| errors::ErrorWithLocation(LineNumber(line_number + 1), error))?;
+        }
+
+        Ok(debug_info)
+    }
+}
+
+impl fmt::Display for DebugInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{DEBUG_INFO_HEADER} {DEBUG_INFO_VERSION}")?;
+
+        if let Some(source) = &self.source {
+            writeln!(f, "source {source}")?;
+        }
+        for (address, line) in &self.lines {
+            writeln!(f, "line {address} {line}")?;
+        }
+        for (label, address) in &self.symbols {
+            writeln!(f, "symbol {address} {label}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::errors::{ErrorWithLocation, LineNumber};
+
+    use super::{DebugInfo, DebugInfoError};
+
+    #[test]
+    fn debug_info() {
+        let mut debug_info = DebugInfo::from_assembly(
+            "\
+# Counts down from the input
+        IN
+loop    OUT
+
+        SUB one
+        BRP loop
+        HLT
+one     DAT 1",
+        )
+        .expect("failed to parse the assembly");
+        debug_info.source = Some("count down.txt".to_string());
+
+        assert_eq!(
+            debug_info.line(2),
+            Some(LineNumber(5)),
+            "Got the wrong line!"
+        );
+        assert_eq!(
+            debug_info.address(LineNumber(8)),
+            Some(5),
+            "Got the wrong address!"
+        );
+        assert_eq!(debug_info.label(5), Some("one"), "Got the wrong label!");
+
+        let text = debug_info.to_string();
+        assert_eq!(
+            text,
+            "lminc-debug 1\nsource count down.txt\n\
+             line 0 2\nline 1 3\nline 2 5\nline 3 6\nline 4 7\nline 5 8\n\
+             symbol 1 loop\nsymbol 5 one\n",
+            "Wrote the wrong debug info!"
+        );
+        assert_eq!(
+            DebugInfo::from_text(&text),
+            Ok(debug_info),
+            "Failed to read the written debug info!"
+        );
+
+        assert_eq!(
+            DebugInfo::from_text("lminc-debug 1\nline 100 1"),
+            Err(ErrorWithLocation(
+                LineNumber(2),
+                DebugInfoError::AddressTooLarge(100)
+            )),
+            "Failed to reject an address that was too large!"
+        );
+        assert_eq!(
+            DebugInfo::from_text("line 0 1"),
+            Err(ErrorWithLocation(
+                LineNumber(1),
+                DebugInfoError::MissingHeader
+            )),
+            "Failed to reject text without a header!"
+        );
+    }
+}
@@ -0,0 +1,189 @@
+use core::{fmt, str};
+#[cfg(feature = "std")]
+use std::{fs::File, io::Read};
+
+#[cfg(feature = "std")]
+use crate::computer::Memory;
+
+#[cfg(feature = "std")]
+use super::{load::read_to_limit, load_from_buffer, load_intel_hex, load_numbers, FromFileError};
+use super::{COMPRESSED_MAGIC, FRAGMENT_MAGIC, MAGIC};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// The formats that [Memory](crate::computer::Memory) can be saved in
+pub enum Format {
+    /// The packed numbers on their own, see `save`
+    Legacy,
+    /// The packed numbers in a container, see `save_container`
+    Container,
+    /// A compressed container, see `compress`
+    Compressed,
+    /// A fragment of memory, see [`Fragment`](super::Fragment)
+    Fragment,
+    /// JSON, see `save_json`
+    JSON,
+    /// Intel HEX, see [`write_intel_hex`](super::write_intel_hex)
+    IntelHex,
+    /// One number per line, see [`write_numbers`](super::write_numbers)
+    Numbers,
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Legacy => write!(f, "legacy binary"),
+            Self::Container => write!(f, "container"),
+            Self::Compressed => write!(f, "compressed container"),
+            Self::Fragment => write!(f, "fragment"),
+            Self::JSON => write!(f, "JSON"),
+            Self::IntelHex => write!(f, "Intel HEX"),
+            Self::Numbers => write!(f, "numbers"),
+        }
+    }
+}
+
+/// Check if a line of text is empty or a single number, ignoring comments
+fn is_number_line(line: &str) -> bool {
+    line.split(['#', ';'])
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .bytes()
+        .all(|byte| byte.is_ascii_digit())
+}
+
+#[must_use]
+/// Detect the format of saved memory from its contents.
+///
+/// Binary formats are detected by their magic bytes, and text formats by their syntax.
+/// Anything else is treated as the legacy format, which has no header
+///
+/// ```
+/// # use lminc::file::{detect_format, Format};
+/// assert_eq!(detect_format(b"901\n902 # Output\n"), Format::Numbers);
+/// assert_eq!(detect_format(b"[901, 902]"), Format::JSON);
+/// assert_eq!(detect_format(&[0xe1, 0x7a, 0x40]), Format::Legacy);
+/// ```
+pub fn detect_format(buffer: &[u8]) -> Format {
+    if buffer.starts_with(&MAGIC) {
+        return Format::Container;
+    }
+    if buffer.starts_with(&COMPRESSED_MAGIC) {
+        return Format::Compressed;
+    }
+    if buffer.starts_with(&FRAGMENT_MAGIC) {
+        return Format::Fragment;
+    }
+
+    // Legacy files are rarely valid text, and empty ones are all zeros either way
+    let Ok(text) = str::from_utf8(buffer) else {
+        return Format::Legacy;
+    };
+    let text = text.trim();
+
+    if text.is_empty() {
+        Format::Legacy
+    } else if text.starts_with(':') {
+        Format::IntelHex
+    } else if text.starts_with(['[', '{']) {
+        Format::JSON
+    } else if text.lines().all(is_number_line) {
+        Format::Numbers
+    } else {
+        Format::Legacy
+    }
+}
+
+#[cfg(feature = "std")]
+/// Load [Memory] from a buffer in any supported format, see [`detect_format`]
+///
+/// # Errors
+/// See [`FromFileError`]
+pub fn load_any_from_buffer(buffer: &[u8]) -> Result<Memory, FromFileError> {
+    let format = detect_format(buffer);
+    // Text formats are only detected in valid UTF-8
+    let text = || str::from_utf8(buffer).unwrap_or_default();
+
+    match format {
+        Format::Legacy | Format::Container | Format::Compressed => {
+            load_from_buffer(buffer).map_err(FromFileError::from)
+        }
+        #[cfg(feature = "json")]
+        Format::JSON => super::load_json(text()).map_err(FromFileError::InvalidJSON),
+        Format::IntelHex => load_intel_hex(text()).map_err(FromFileError::InvalidIntelHex),
+        Format::Numbers => load_numbers(text()).map_err(FromFileError::InvalidNumbers),
+        format => Err(FromFileError::UnsupportedFormat(format)),
+    }
+}
+
+#[cfg(feature = "std")]
+/// Load [Memory] from the given reader in any supported format, see [`detect_format`]
+///
+/// # Errors
+/// See [`FromFileError`]
+pub fn load_any_from_reader(reader: &mut impl Read) -> Result<Memory, FromFileError> {
+    load_any_from_buffer(&read_to_limit(reader)?)
+}
+
+#[cfg(feature = "std")]
+/// Load [Memory] from a file in any supported format given the path str,
+///  see [`detect_format`]
+///
+/// # Errors
+/// See [`FromFileError`]
+pub fn load_any(path: &str) -> Result<Memory, FromFileError> {
+    load_any_from_reader(&mut File::open(path)?)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        assembler::assemble_from_text,
+        file::{
+            save_container_to_buffer, save_intel_hex, save_numbers, save_to_buffer, FromFileError,
+            MAX_CONTAINER_SIZE, MAX_FILE_SIZE,
+        },
+    };
+
+    use super::{detect_format, load_any_from_buffer, Format};
+
+    #[test]
+    fn detect() {
+        let memory = assemble_from_text("IN\nSTO 99\nIN\nADD 99\nOUT\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        let mut legacy = [0; MAX_FILE_SIZE];
+        let mut container = [0; MAX_CONTAINER_SIZE];
+        let saved = [
+            (Format::Legacy, save_to_buffer(&mut legacy, memory).to_vec()),
+            (
+                Format::Container,
+                save_container_to_buffer(&mut container, memory).to_vec(),
+            ),
+            (Format::IntelHex, save_intel_hex(memory).into_bytes()),
+            (Format::Numbers, save_numbers(memory).into_bytes()),
+        ];
+
+        for (format, buffer) in saved {
+            assert_eq!(
+                detect_format(&buffer),
+                format,
+                "Failed to detect the {format} format!"
+            );
+            assert_eq!(
+                load_any_from_buffer(&buffer).expect("failed to load the buffer"),
+                memory,
+                "Failed to load the {format} format!"
+            );
+        }
+
+        assert!(
+            matches!(
+                load_any_from_buffer(b"901\n1000"),
+                Err(FromFileError::InvalidNumbers(_))
+            ),
+            "Failed to reject invalid numbers!"
+        );
+    }
+}
@@ -0,0 +1,361 @@
+use core::{
+    fmt,
+    ops::{Bound, RangeBounds},
+};
+#[cfg(feature = "std")]
+use std::{
+    fs::File,
+    io::{self, Write},
+};
+
+#[cfg(feature = "std")]
+use super::{load::read_to_limit, FromFileError};
+use crate::{computer::Memory, num3::ThreeDigitNumber};
+
+use super::{load::unpack, save_to_buffer_len, BinaryFormat, Error, MAX_FILE_SIZE};
+
+/// The magic bytes at the start of a fragment
+pub const FRAGMENT_MAGIC: [u8; 4] = *b"LMNP";
+/// The size of the fragment header in bytes
+///
+/// The header is the [`FRAGMENT_MAGIC`] bytes, the start address and the number of cells
+pub const FRAGMENT_HEADER_SIZE: usize = FRAGMENT_MAGIC.len() + 1 + 1;
+/// The maximum size of a fragment in bytes
+pub const MAX_FRAGMENT_SIZE: usize = FRAGMENT_HEADER_SIZE + MAX_FILE_SIZE;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Errors for saving, loading and applying fragments
+pub enum FragmentError {
+    /// The range (start, end) is not inside the memory
+    OutOfRange(usize, usize),
+    /// The buffer does not start with the [`FRAGMENT_MAGIC`] bytes
+    NotFragment,
+    /// The buffer is too short for the header
+    TruncatedHeader,
+    /// There are cells after the number of cells in the header
+    TooManyCells,
+    /// The cells could not be loaded, see [Error]
+    LoadError(Error),
+}
+
+impl fmt::Display for FragmentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfRange(start, end) => write!(
+                f,
+                "The range is not inside the memory ({start}..{end} should be inside 0..100)!"
+            ),
+            Self::NotFragment => write!(f, "Not a fragment!"),
+            Self::TruncatedHeader => write!(f, "The fragment header is incomplete!"),
+            Self::TooManyCells => write!(f, "The fragment has more cells than its header!"),
+            Self::LoadError(error) => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FragmentError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::LoadError(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<Error> for FragmentError {
+    fn from(value: Error) -> Self {
+        Self::LoadError(value)
+    }
+}
+
+/// Get the start and end (exclusive) of a range of addresses, checking it is inside the memory
+fn bounds(range: &impl RangeBounds<usize>) -> Result<(usize, usize), FragmentError> {
+    let start = match range.start_bound() {
+        Bound::Included(start) => *start,
+        Bound::Excluded(start) => start.saturating_add(1),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(end) => end.saturating_add(1),
+        Bound::Excluded(end) => *end,
+        Bound::Unbounded => 100,
+    };
+
+    if start > end || end > 100 {
+        Err(FragmentError::OutOfRange(start, end))
+    } else {
+        Ok((start, end))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// The cells in a range of [Memory], such as a data segment,
+///  which can be saved on their own and applied over other memory
+///  as an overlay or a patch.
+///
+/// ```
+/// # use lminc::{assembler::assemble_from_text, file::{self, Fragment, MAX_FRAGMENT_SIZE}};
+/// let program = assemble_from_text("LDA 50\nOUT\nHLT")??;
+/// let data = assemble_from_text("DAT 0\nDAT 0\nDAT 0\nDAT 0\nDAT 0\nDAT 42")??;
+///
+/// // Save only the data segment
+/// let fragment = Fragment::from_memory(&data, 5..=5)?;
+/// let mut buffer = [0; MAX_FRAGMENT_SIZE];
+/// let saved = file::save_fragment_to_buffer(&mut buffer, &fragment);
+///
+/// // Load it and apply it to the program at address 50
+/// let mut memory = program;
+/// file::load_fragment_from_buffer(saved)?.apply_at(&mut memory, 50)?;
+/// assert_eq!(u16::from(memory[50]), 42);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct Fragment {
+    start: usize,
+    len: usize,
+    /// The cells in the fragment, moved to the start
+    cells: Memory,
+}
+
+impl Fragment {
+    /// Create a fragment from the cells in a range of the memory
+    ///
+    /// # Errors
+    /// [`FragmentError::OutOfRange`] - the range is not inside the memory
+    pub fn from_memory(
+        memory: &Memory,
+        range: impl RangeBounds<usize>,
+    ) -> Result<Self, FragmentError> {
+        let (start, end) = bounds(&range)?;
+
+        let mut cells = [ThreeDigitNumber::ZERO; 100];
+        cells[..end - start].copy_from_slice(&memory[start..end]);
+
+        Ok(Self {
+            start,
+            len: end - start,
+            cells,
+        })
+    }
+
+    #[must_use]
+    /// Get the address the fragment was taken from
+    pub const fn start(&self) -> usize {
+        self.start
+    }
+
+    #[must_use]
+    /// Get the number of cells in the fragment
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    #[must_use]
+    /// Check if the fragment has no cells
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[must_use]
+    /// Get the cells in the fragment
+    pub fn cells(&self) -> &[ThreeDigitNumber] {
+        &self.cells[..self.len]
+    }
+
+    /// Apply the fragment to the memory at the address it was taken from
+    pub fn apply(&self, memory: &mut Memory) {
+        memory[self.start..self.start + self.len].copy_from_slice(self.cells());
+    }
+
+    /// Apply the fragment to the memory starting at an address
+    ///
+    /// # Errors
+    /// [`FragmentError::OutOfRange`] - the fragment does not fit in the memory at the address
+    pub fn apply_at(&self, memory: &mut Memory, address: usize) -> Result<(), FragmentError> {
+        let end = address.saturating_add(self.len);
+        memory
+            .get_mut(address..end)
+            .ok_or(FragmentError::OutOfRange(address, end))?
+            .copy_from_slice(self.cells());
+
+        Ok(())
+    }
+}
+
+/// Save a [Fragment] to the buffer and return a trimmed version of it
+pub fn save_fragment_to_buffer<'a>(
+    buffer: &'a mut [u8; MAX_FRAGMENT_SIZE],
+    fragment: &Fragment,
+) -> &'a [u8] {
+    // The cells are at the start, and the rest are zero
+    let mut contents = [0; MAX_FILE_SIZE];
+    let length = save_to_buffer_len(&mut contents, fragment.cells);
+
+    let (header, rest) = buffer.split_at_mut(FRAGMENT_HEADER_SIZE);
+    rest.copy_from_slice(&contents);
+
+    header[..FRAGMENT_MAGIC.len()].copy_from_slice(&FRAGMENT_MAGIC);
+    // Addresses and lengths are at most 100, so they fit in a u8
+    #[allow(clippy::cast_possible_truncation)]
+    {
+        header[4] = fragment.start as u8;
+        header[5] = fragment.len as u8;
+    }
+
+    &buffer[..FRAGMENT_HEADER_SIZE + length]
+}
+
+/// Save the cells in a range of the [Memory] to the buffer and return a trimmed version of it
+///
+/// # Errors
+/// [`FragmentError::OutOfRange`] - the range is not inside the memory
+pub fn save_range_to_buffer(
+    buffer: &mut [u8; MAX_FRAGMENT_SIZE],
+    memory: Memory,
+    range: impl RangeBounds<usize>,
+) -> Result<&[u8], FragmentError> {
+    let fragment = Fragment::from_memory(&memory, range)?;
+    Ok(save_fragment_to_buffer(buffer, &fragment))
+}
+
+/// Load a [Fragment] from a saved buffer
+///
+/// # Errors
+/// See [`FragmentError`]
+pub fn load_fragment_from_buffer(buffer: &[u8]) -> Result<Fragment, FragmentError> {
+    if !buffer.starts_with(&FRAGMENT_MAGIC) {
+        return Err(FragmentError::NotFragment);
+    }
+    let Some((header, contents)) = buffer.split_at_checked(FRAGMENT_HEADER_SIZE) else {
+        return Err(FragmentError::TruncatedHeader);
+    };
+
+    let start = usize::from(header[4]);
+    let len = usize::from(header[5]);
+    let end = start + len;
+    if end > 100 {
+        return Err(FragmentError::OutOfRange(start, end));
+    }
+
+    let cells = unpack(contents, BinaryFormat::Fragment, FRAGMENT_HEADER_SIZE)?;
+    if cells[len..]
+        .iter()
+        .any(|cell| *cell != ThreeDigitNumber::ZERO)
+    {
+        return Err(FragmentError::TooManyCells);
+    }
+
+    Ok(Fragment { start, len, cells })
+}
+
+#[cfg(feature = "std")]
+/// Save a [Fragment] to the given writer
+///
+/// # Errors
+/// [`io::Error`] - writer error
+pub fn save_fragment_to_writer(writer: &mut impl Write, fragment: &Fragment) -> io::Result<()> {
+    let mut buffer = [0; MAX_FRAGMENT_SIZE];
+    writer.write_all(save_fragment_to_buffer(&mut buffer, fragment))
+}
+
+#[cfg(feature = "std")]
+/// Save a [Fragment] to a file given the path str
+///
+/// # Errors
+/// [`io::Error`] - file system error
+pub fn save_fragment(path: &str, fragment: &Fragment) -> io::Result<()> {
+    save_fragment_to_writer(&mut File::create(path)?, fragment)
+}
+
+#[cfg(feature = "std")]
+/// Load a [Fragment] from a file given the path str
+///
+/// # Errors
+/// See [`FromFileError`]
+pub fn load_fragment(path: &str) -> Result<Fragment, FromFileError> {
+    let buffer = read_to_limit(&mut File::open(path)?)?;
+    load_fragment_from_buffer(&buffer).map_err(FromFileError::from)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{assembler::assemble_from_text, file::Error};
+
+    use super::{
+        load_fragment_from_buffer, save_range_to_buffer, Fragment, FragmentError,
+        FRAGMENT_HEADER_SIZE, MAX_FRAGMENT_SIZE,
+    };
+
+    #[test]
+    fn fragment() {
+        let memory = assemble_from_text("IN\nSTO 99\nIN\nADD 99\nOUT\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        let mut buffer = [0; MAX_FRAGMENT_SIZE];
+        let saved = save_range_to_buffer(&mut buffer, memory, 2..5)
+            .expect("failed to save the range")
+            .to_vec();
+        let fragment = load_fragment_from_buffer(&saved).expect("failed to load the fragment");
+        assert_eq!(fragment.start(), 2, "Loaded the wrong start!");
+        assert_eq!(fragment.cells(), &memory[2..5], "Loaded the wrong cells!");
+
+        let mut patched = [memory[0]; 100];
+        fragment.apply(&mut patched);
+        assert_eq!(
+            &patched[2..5],
+            &memory[2..5],
+            "Failed to apply the fragment!"
+        );
+        assert_eq!(patched[5], memory[0], "Applied too many cells!");
+
+        fragment
+            .apply_at(&mut patched, 97)
+            .expect("failed to apply the fragment at the end");
+        assert_eq!(
+            &patched[97..],
+            &memory[2..5],
+            "Failed to apply the fragment at an offset!"
+        );
+        assert_eq!(
+            fragment.apply_at(&mut patched, 98),
+            Err(FragmentError::OutOfRange(98, 101)),
+            "Failed to reject a fragment that did not fit!"
+        );
+
+        assert_eq!(
+            Fragment::from_memory(&memory, 50..=100),
+            Err(FragmentError::OutOfRange(50, 101)),
+            "Failed to reject a range outside the memory!"
+        );
+        assert_eq!(
+            load_fragment_from_buffer(&saved[..FRAGMENT_HEADER_SIZE - 1]),
+            Err(FragmentError::TruncatedHeader),
+            "Failed to reject a truncated header!"
+        );
+        assert_eq!(
+            load_fragment_from_buffer(&[0; 4]),
+            Err(FragmentError::NotFragment),
+            "Failed to reject a buffer without the magic bytes!"
+        );
+
+        let mut too_many = saved.clone();
+        too_many[5] = 1;
+        assert_eq!(
+            load_fragment_from_buffer(&too_many),
+            Err(FragmentError::TooManyCells),
+            "Failed to reject extra cells!"
+        );
+
+        let mut invalid = saved;
+        invalid[FRAGMENT_HEADER_SIZE] = 0xff;
+        assert!(
+            matches!(
+                load_fragment_from_buffer(&invalid),
+                Err(FragmentError::LoadError(Error::InvalidNumber { .. }))
+            ),
+            "Failed to reject an invalid number!"
+        );
+    }
+}
@@ -0,0 +1,286 @@
+#[cfg(feature = "alloc")]
+extern crate alloc;
+use core::fmt::{self, Write};
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+use crate::{
+    computer::Memory,
+    errors::{self, LineNumber},
+};
+
+use super::{load_from_buffer, save_container_to_buffer, Error, MAX_CONTAINER_SIZE};
+
+/// The number of data bytes written in each record
+const RECORD_SIZE: usize = 16;
+/// The data record type
+const DATA: u8 = 0x00;
+/// The end of file record type
+const END_OF_FILE: u8 = 0x01;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Errors for reading Intel HEX
+pub enum IntelHexError {
+    /// The record did not start with a colon
+    MissingStartCode,
+    /// The record had a character that was not a hex digit, or an odd number of digits
+    InvalidHex,
+    /// The record was not as long as its byte count says
+    WrongLength,
+    /// The checksum in the record (first) is not the checksum of the record (second)
+    WrongChecksum(u8, u8),
+    /// The record type is not supported, only data (00) and end of file (01) are
+    UnsupportedRecord(u8),
+    /// The data goes past the end of the largest container
+    AddressTooLarge(usize),
+    /// There was no end of file record
+    MissingEnd,
+    /// The data could not be loaded, see [Error]
+    LoadError(Error),
+}
+
+impl fmt::Display for IntelHexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingStartCode => write!(f, "Intel HEX record does not start with ':'!"),
+            Self::InvalidHex => write!(f, "Intel HEX record has invalid hex digits!"),
+            Self::WrongLength => write!(f, "Intel HEX record has the wrong length!"),
+            Self::WrongChecksum(expected, found) => write!(
+                f,
+                "Intel HEX record is corrupt (checksum {found:02X}, expected {expected:02X})!"
+            ),
+            Self::UnsupportedRecord(record) => {
+                write!(f, "Intel HEX record type is not supported ({record:02X})!")
+            }
+            Self::AddressTooLarge(address) => write!(
+                f,
+                "Intel HEX data is too large ({address} bytes > {MAX_CONTAINER_SIZE} bytes)!"
+            ),
+            Self::MissingEnd => write!(f, "Intel HEX has no end of file record!"),
+            Self::LoadError(error) => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for IntelHexError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::LoadError(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+pub type IntelHexErrorWithLineNumber = errors::ErrorWithLocation<IntelHexError, LineNumber>;
+
+/// Get the checksum of a record, the two's complement of the sum of its bytes
+fn checksum(bytes: impl IntoIterator<Item = u8>) -> u8 {
+    bytes
+        .into_iter()
+        .fold(0_u8, u8::wrapping_add)
+        .wrapping_neg()
+}
+
+/// Write the [Memory] as Intel HEX, with the data being the memory saved in a container
+///
+/// # Errors
+/// If writing to `out` fails
+pub fn write_intel_hex(out: &mut impl Write, memory: Memory) -> fmt::Result {
+    let mut buffer = [0; MAX_CONTAINER_SIZE];
+    let saved = save_container_to_buffer(&mut buffer, memory);
+
+    for (index, record) in saved.chunks(RECORD_SIZE).enumerate() {
+        // The container is much smaller than 64 KiB, so the address and length fit
+        #[allow(clippy::cast_possible_truncation)]
+        let (length, address) = (record.len() as u8, (index * RECORD_SIZE) as u16);
+        let [address_high, address_low] = address.to_be_bytes();
+
+        write!(out, ":{length:02X}{address:04X}{DATA:02X}")?;
+        for byte in record {
+            write!(out, "{byte:02X}")?;
+        }
+        let checksum = checksum(
+            [length, address_high, address_low, DATA]
+                .into_iter()
+                .chain(record.iter().copied()),
+        );
+        writeln!(out, "{checksum:02X}")?;
+    }
+
+    writeln!(out, ":00000001FF")
+}
+
+#[cfg(feature = "alloc")]
+#[must_use]
+/// Save the [Memory] as Intel HEX, see [`write_intel_hex`]
+///
+/// ```
+/// # use lminc::{assembler::assemble_from_text, file};
+/// let memory = assemble_from_text("IN\nOUT\nHLT")??;
+/// let hex = file::save_intel_hex(memory);
+/// assert!(hex.ends_with(":00000001FF\n"));
+/// assert_eq!(file::load_intel_hex(&hex)?, memory);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn save_intel_hex(memory: Memory) -> String {
+    let mut out = String::new();
+    // Writing to a String does not fail
+    let _ = write_intel_hex(&mut out, memory);
+    out
+}
+
+/// Decode a string of hex digits into the buffer, returning the decoded part
+fn decode_hex<'a>(hex: &str, buffer: &'a mut [u8]) -> Result<&'a [u8], IntelHexError> {
+    let hex = hex.as_bytes();
+    if !hex.len().is_multiple_of(2) || hex.len() / 2 > buffer.len() {
+        return Err(IntelHexError::InvalidHex);
+    }
+
+    for (byte, digits) in buffer.iter_mut().zip(hex.chunks(2)) {
+        let digits = core::str::from_utf8(digits).map_err(|_| IntelHexError::InvalidHex)?;
+        *byte = u8::from_str_radix(digits, 16).map_err(|_| IntelHexError::InvalidHex)?;
+    }
+
+    Ok(&buffer[..hex.len() / 2])
+}
+
+/// Load [Memory] from Intel HEX, where the data is saved memory in any binary format.
+///
+/// Only data and end of file records are supported, as saved memory is always small
+///
+/// # Errors
+/// See [`IntelHexError`]
+pub fn load_intel_hex(text: &str) -> Result<Memory, IntelHexErrorWithLineNumber> {
+    let mut buffer = [0; MAX_CONTAINER_SIZE];
+    let mut length = 0;
+
+    for (line_number, line) in text.lines().enumerate() {
+        let line_number = LineNumber(line_number + 1);
+        let error = |error| errors::ErrorWithLocation(line_number, error);
+
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let hex = line
+            .strip_prefix(':')
+            .ok_or_else(|| error(IntelHexError::MissingStartCode))?;
+
+        // A record is at most 5 bytes plus 255 data bytes
+        let mut record = [0; 5 + u8::MAX as usize];
+        let record = decode_hex(hex, &mut record).map_err(error)?;
+
+        let Some((&expected, record)) = record.split_last() else {
+            return Err(error(IntelHexError::WrongLength));
+        };
+        let Some((&[count, address_high, address_low, kind], data)) = record.split_first_chunk()
+        else {
+            return Err(error(IntelHexError::WrongLength));
+        };
+        if data.len() != usize::from(count) {
+            return Err(error(IntelHexError::WrongLength));
+        }
+
+        let found = checksum(record.iter().copied());
+        if expected != found {
+            return Err(error(IntelHexError::WrongChecksum(expected, found)));
+        }
+
+        match kind {
+            DATA => {
+                let address = usize::from(u16::from_be_bytes([address_high, address_low]));
+                let end = address + data.len();
+                buffer
+                    .get_mut(address..end)
+                    .ok_or_else(|| error(IntelHexError::AddressTooLarge(end)))?
+                    .copy_from_slice(data);
+                length = length.max(end);
+            }
+            END_OF_FILE => {
+                return load_from_buffer(&buffer[..length])
+                    .map_err(|load_error| error(IntelHexError::LoadError(load_error)));
+            }
+            kind => return Err(error(IntelHexError::UnsupportedRecord(kind))),
+        }
+    }
+
+    Err(errors::ErrorWithLocation(
+        LineNumber(text.lines().count()),
+        IntelHexError::MissingEnd,
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        assembler::assemble_from_text,
+        errors::{ErrorWithLocation, LineNumber},
+        num3::ThreeDigitNumber,
+    };
+
+    use super::{load_intel_hex, save_intel_hex, IntelHexError};
+
+    #[test]
+    fn intel_hex() {
+        let memory = assemble_from_text("IN\nSTO 99\nIN\nADD 99\nOUT\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        let hex = save_intel_hex(memory);
+        assert!(
+            hex.starts_with(":10000000"),
+            "Saved the wrong first record!"
+        );
+        assert_eq!(
+            load_intel_hex(&hex),
+            Ok(memory),
+            "Failed to load the saved Intel HEX!"
+        );
+
+        // Legacy files in Intel HEX still load
+        assert_eq!(
+            load_intel_hex(":02000000E140DD\n:00000001FF"),
+            Ok(assemble_from_text("IN")
+                .expect("failed to parse the assembly")
+                .expect("failed to assemble the assembly")),
+            "Failed to load a legacy file!"
+        );
+
+        let mut corrupt = hex.clone();
+        corrupt.replace_range(9..10, "F");
+        assert!(
+            matches!(
+                load_intel_hex(&corrupt),
+                Err(ErrorWithLocation(
+                    LineNumber(1),
+                    IntelHexError::WrongChecksum(..)
+                ))
+            ),
+            "Failed to reject a corrupt record!"
+        );
+        assert_eq!(
+            load_intel_hex(":00000001FF\nend"),
+            Ok([ThreeDigitNumber::ZERO; 100]),
+            "Failed to stop at the end of file record!"
+        );
+        assert_eq!(
+            load_intel_hex(&hex[..hex.len() - ":00000001FF\n".len()]),
+            Err(ErrorWithLocation(
+                LineNumber(hex.lines().count() - 1),
+                IntelHexError::MissingEnd
+            )),
+            "Failed to reject Intel HEX without an end of file record!"
+        );
+        assert_eq!(
+            load_intel_hex(":00000002FE\n:00000001FF"),
+            Err(ErrorWithLocation(
+                LineNumber(1),
+                IntelHexError::UnsupportedRecord(2)
+            )),
+            "Failed to reject an unsupported record!"
+        );
+    }
+}
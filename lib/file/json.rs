@@ -0,0 +1,200 @@
+extern crate alloc;
+use core::fmt;
+
+use alloc::{string::String, vec::Vec};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{computer::Memory, num3::ThreeDigitNumber};
+
+use super::Metadata;
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+/// Memory as it is written in JSON, either a bare array of numbers or an object with metadata
+enum JSONImage {
+    Numbers(Vec<u16>),
+    WithMetadata {
+        #[serde(default)]
+        metadata: Metadata,
+        memory: Vec<u16>,
+    },
+}
+
+#[derive(Debug)]
+/// JSON loading errors
+pub enum JSONError {
+    /// The text was not valid JSON, or not an array of numbers or an object with `memory`
+    ParseError(serde_json::Error),
+    /// There were more than 100 numbers
+    TooManyNumbers(usize),
+    /// A number was too large (> 999)
+    InvalidNumber(usize, u16),
+}
+
+impl fmt::Display for JSONError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ParseError(error) => write!(f, "Invalid JSON: {error}"),
+            Self::TooManyNumbers(count) => {
+                write!(f, "Too many numbers ({count} numbers > 100 numbers)!")
+            }
+            Self::InvalidNumber(index, number) => write!(
+                f,
+                "A number in the memory is too large (index {index}, {number} > 999)!"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for JSONError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::ParseError(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for JSONError {
+    fn from(value: serde_json::Error) -> Self {
+        Self::ParseError(value)
+    }
+}
+
+/// Get the numbers in the [Memory], without the trailing zeros
+fn trimmed_numbers(memory: Memory) -> Vec<u16> {
+    let length = memory
+        .iter()
+        .rposition(|number| *number != ThreeDigitNumber::ZERO)
+        .map_or(0, |index| index + 1);
+
+    memory[..length].iter().copied().map(u16::from).collect()
+}
+
+#[must_use]
+/// Save the [Memory] as a JSON array of numbers, without the trailing zeros
+///
+/// ```
+/// # use lminc::{assembler::assemble_from_text, file};
+/// let memory = assemble_from_text("IN\nOUT\nHLT")??;
+/// assert_eq!(file::save_json(memory), "[901,902]");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn save_json(memory: Memory) -> String {
+    // Serialising a list of numbers does not fail
+    serde_json::to_string(&JSONImage::Numbers(trimmed_numbers(memory))).unwrap_or_default()
+}
+
+#[must_use]
+/// Save the [Memory] as a JSON object, with the [Metadata] and the memory
+///  as an array of numbers, without the trailing zeros
+pub fn save_json_with_metadata(memory: Memory, metadata: Metadata) -> String {
+    let image = JSONImage::WithMetadata {
+        metadata,
+        memory: trimmed_numbers(memory),
+    };
+
+    // Serialising strings and numbers does not fail
+    serde_json::to_string(&image).unwrap_or_default()
+}
+
+/// Load [Memory] and any [Metadata] from JSON, either an array of numbers
+///  or an object with the `memory` array and an optional `metadata` object.
+///
+/// Missing numbers at the end of the memory are zero
+///
+/// # Errors
+/// See [`JSONError`]
+pub fn load_json_with_metadata(text: &str) -> Result<(Memory, Option<Metadata>), JSONError> {
+    let (numbers, metadata) = match serde_json::from_str(text)? {
+        JSONImage::Numbers(numbers) => (numbers, None),
+        JSONImage::WithMetadata { metadata, memory } => (memory, Some(metadata)),
+    };
+
+    if numbers.len() > 100 {
+        return Err(JSONError::TooManyNumbers(numbers.len()));
+    }
+
+    let mut memory = [ThreeDigitNumber::ZERO; 100];
+    for (index, (cell, number)) in memory.iter_mut().zip(numbers).enumerate() {
+        *cell = ThreeDigitNumber::try_from(number)
+            .map_err(|_| JSONError::InvalidNumber(index, number))?;
+    }
+
+    Ok((memory, metadata))
+}
+
+/// Load [Memory] from JSON, ignoring any [Metadata]. See [`load_json_with_metadata`]
+///
+/// ```
+/// # use lminc::file;
+/// let memory = file::load_json("[901,902]")?;
+/// assert_eq!(u16::from(memory[1]), 902);
+/// # Ok::<(), file::JSONError>(())
+/// ```
+///
+/// # Errors
+/// See [`JSONError`]
+pub fn load_json(text: &str) -> Result<Memory, JSONError> {
+    load_json_with_metadata(text).map(|(memory, _)| memory)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{assembler::assemble_from_text, num3::ThreeDigitNumber};
+
+    use super::{load_json, load_json_with_metadata, save_json_with_metadata, JSONError, Metadata};
+
+    #[test]
+    fn json() {
+        let memory = assemble_from_text("IN\nSTO 99\nIN\nADD 99\nOUT\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+        let metadata = Metadata {
+            name: Some("add".to_string()),
+            ..Metadata::default()
+        };
+
+        let json = save_json_with_metadata(memory, metadata.clone());
+        assert_eq!(
+            json, r#"{"metadata":{"name":"add"},"memory":[901,399,901,199,902]}"#,
+            "Saved the wrong JSON!"
+        );
+        assert_eq!(
+            load_json_with_metadata(&json).expect("failed to load the JSON"),
+            (memory, Some(metadata)),
+            "Failed to load the saved JSON!"
+        );
+
+        assert!(
+            load_json("[]")
+                .expect("failed to load the JSON")
+                .iter()
+                .all(|number| *number == ThreeDigitNumber::ZERO),
+            "Empty JSON did not load all zeros!"
+        );
+        assert!(
+            matches!(
+                load_json("[1, 1000]"),
+                Err(JSONError::InvalidNumber(1, 1000))
+            ),
+            "Failed to reject a number that was too large!"
+        );
+        assert!(
+            matches!(
+                load_json(&format!("{:?}", [0; 101])),
+                Err(JSONError::TooManyNumbers(101))
+            ),
+            "Failed to reject too many numbers!"
+        );
+        assert!(
+            matches!(
+                load_json(r#"{"name":"add"}"#),
+                Err(JSONError::ParseError(_))
+            ),
+            "Failed to reject JSON without memory!"
+        );
+    }
+}
@@ -0,0 +1,168 @@
+extern crate alloc;
+use core::fmt::{self, Write};
+#[cfg(feature = "std")]
+use std::{fs, io};
+
+use alloc::string::String;
+
+use crate::{computer::Memory, errors::LineNumber};
+
+use super::DebugInfo;
+#[cfg(feature = "std")]
+use super::{save_with_metadata, Metadata};
+
+/// Write a listing of the assembly, each source line with the address and code
+///  of the instruction it assembled to, if it has one:
+/// ```text
+/// 00 901          IN
+/// 01 399          STO 99
+///                 # Lines without an instruction have no address
+/// ```
+///
+/// The `debug_info` should be from the same assembly, see [`DebugInfo::from_assembly`]
+///
+/// # Errors
+/// If writing to `out` fails
+pub fn write_listing(
+    out: &mut impl Write,
+    source: &str,
+    memory: Memory,
+    debug_info: &DebugInfo,
+) -> fmt::Result {
+    for (line_number, line) in source.lines().enumerate() {
+        match debug_info
+            .address(LineNumber(line_number + 1))
+            .and_then(|address| Some((address, memory.get(address)?)))
+        {
+            Some((address, code)) => {
+                writeln!(out, "{address:02} {code:#}          {line}")?;
+            }
+            None if line.trim().is_empty() => writeln!(out)?,
+            None => writeln!(out, "{:16}{line}", "")?,
+        }
+    }
+
+    Ok(())
+}
+
+#[must_use]
+/// Create a listing of the assembly, see [`write_listing`]
+///
+/// ```
+/// # use lminc::{assembler::assemble_from_text, file::{self, DebugInfo}};
+/// let source = "# Echo\nIN\nOUT";
+/// let memory = assemble_from_text(source)??;
+/// let debug_info = DebugInfo::from_assembly(source)?;
+/// assert_eq!(
+///     file::listing(source, memory, &debug_info),
+///     "                # Echo\n00 901          IN\n01 902          OUT\n"
+/// );
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn listing(source: &str, memory: Memory, debug_info: &DebugInfo) -> String {
+    let mut out = String::new();
+    // Writing to a String does not fail
+    let _ = write_listing(&mut out, source, memory, debug_info);
+    out
+}
+
+#[cfg(feature = "std")]
+/// Save the [Memory] to a file in a container with the [Metadata], and a listing
+///  of the assembly it was assembled from to another file, given the path strs
+///
+/// # Errors
+/// [`io::Error`] - file system error, or [`io::ErrorKind::InvalidInput`] if the assembly
+///  could not be parsed, if the paths are the same or with a [`MetadataError`](super::MetadataError)
+pub fn save_with_listing(
+    path: &str,
+    listing_path: &str,
+    memory: Memory,
+    metadata: &Metadata,
+    source: &str,
+) -> io::Result<()> {
+    if path == listing_path {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "cannot save the listing over the memory",
+        ));
+    }
+
+    let debug_info = DebugInfo::from_assembly(source)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error.to_string()))?;
+
+    save_with_metadata(path, memory, metadata)?;
+    fs::write(listing_path, listing(source, memory, &debug_info))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{assembler::assemble_from_text, file::DebugInfo};
+
+    use super::listing;
+
+    #[test]
+    fn listing_lines() {
+        let source = "\
+# Adds two numbers
+        IN
+        STO 99
+
+        IN
+        ADD 99
+        OUT
+        HLT";
+        let memory = assemble_from_text(source)
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+        let debug_info = DebugInfo::from_assembly(source).expect("failed to parse the assembly");
+
+        assert_eq!(
+            listing(source, memory, &debug_info),
+            "                # Adds two numbers\n\
+             00 901                  IN\n\
+             01 399                  STO 99\n\
+             \n\
+             02 901                  IN\n\
+             03 199                  ADD 99\n\
+             04 902                  OUT\n\
+             05 000                  HLT\n",
+            "Created the wrong listing!"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn save_same_path() {
+        use std::{env::temp_dir, fs};
+
+        use uuid::Uuid;
+
+        use crate::file::{save_with_listing, Metadata};
+
+        // Get a new path in the temp directory, with a file to keep
+        let mut path = temp_dir();
+        path.push(format!("lminc-test-{}", Uuid::new_v4()));
+        fs::write(&path, "IN\nOUT").expect("failed to write to file");
+        let path_str = path.to_str().expect("failed to convert path to str");
+
+        let source = "IN\nOUT";
+        let memory = assemble_from_text(source)
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        assert!(
+            save_with_listing(path_str, path_str, memory, &Metadata::default(), source).is_err(),
+            "Saved the listing over the memory!"
+        );
+        assert_eq!(
+            fs::read_to_string(&path).expect("failed to read file"),
+            "IN\nOUT",
+            "Changed the file when saving failed!"
+        );
+
+        // Try to delete the file
+        if let Err(error) = fs::remove_file(&path) {
+            eprintln!("Warning: Failed to remove file ({path_str})!\nError: {error}");
+        }
+    }
+}
@@ -6,28 +6,139 @@ use std::{
     path::PathBuf,
 };
 
-use crate::{computer::Memory, file::MAX_FILE_SIZE};
+#[cfg(feature = "compression")]
+use crate::file::decompress;
+#[cfg(feature = "std")]
+#[cfg(feature = "json")]
+use crate::file::JSONError;
+use crate::{
+    computer::Memory,
+    errors::{Code, ErrorCode},
+    file::{
+        container::{is_compressed, metadata_section_len, split_container},
+        HEADER_SIZE, MAX_FILE_SIZE,
+    },
+};
+#[cfg(feature = "std")]
+use crate::{
+    disassembler::disassemble,
+    file::{
+        BundleError, Format, FragmentError, IntelHexErrorWithLineNumber, MetadataError,
+        MAX_CONTAINER_SIZE, MAX_METADATA_SIZE,
+    },
+    number_assembler,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// The binary formats that memory is saved in
+pub enum BinaryFormat {
+    /// The packed numbers on their own, without a header
+    Legacy,
+    /// The packed numbers in a container, with a header and optional metadata
+    Container,
+    /// The packed numbers in a fragment of memory
+    Fragment,
+}
+
+impl fmt::Display for BinaryFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Legacy => write!(f, "legacy"),
+            Self::Container => write!(f, "container"),
+            Self::Fragment => write!(f, "fragment"),
+        }
+    }
+}
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
-/// Loading Errors
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Loading Errors.
+///
+/// Byte offsets are from the start of the buffer, or the decompressed buffer if it was compressed
 pub enum Error {
-    /// The buffer is more than [`MAX_FILE_SIZE`] bytes long
-    BufferTooLarge(usize),
     /// A number in the decoded buffer is too large (> 999)
-    InvalidNumber(usize, u16),
+    InvalidNumber {
+        /// The format the number was saved in
+        format: BinaryFormat,
+        /// The byte offset of the start of the number
+        offset: usize,
+        /// The address of the number
+        index: usize,
+        /// The decoded number
+        number: u16,
+    },
+    /// There are bytes after the end of the saved memory (and metadata)
+    TrailingData {
+        /// The format the memory was saved in
+        format: BinaryFormat,
+        /// The byte offset of the first unexpected byte
+        offset: usize,
+    },
+    /// The buffer starts with the container magic bytes, but is too short for the header
+    TruncatedHeader,
+    /// The container was saved with an unsupported format version
+    UnsupportedVersion(u8),
+    /// The length in the container header (first) is longer than the contents (second)
+    WrongLength(usize, usize),
+    /// The checksum in the container header (first) is not the checksum of the contents (second)
+    WrongChecksum(u32, u32),
+    /// The buffer is compressed, but the `compression` feature is not enabled
+    Compressed,
+    /// The buffer is compressed, but could not be decompressed
+    InvalidCompression,
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::BufferTooLarge(size) => write!(
+            Self::InvalidNumber {
+                format,
+                offset,
+                index,
+                number,
+            } => write!(
+                f,
+                "A number in the {format} buffer is too large \
+                 (address {index} at byte {offset}, {number} > 999)!"
+            ),
+            Self::TrailingData { format, offset } => write!(
+                f,
+                "The {format} buffer has unexpected data after the memory (from byte {offset})!"
+            ),
+            Self::TruncatedHeader => write!(f, "The container header is incomplete!"),
+            Self::UnsupportedVersion(version) => write!(
                 f,
-                "The buffer is too long bytes long ({size} bytes > {MAX_FILE_SIZE} bytes)"
+                "The container format version is not supported (version {version})!"
             ),
-            Self::InvalidNumber(index, number) => write!(
+            Self::WrongLength(expected, found) => write!(
                 f,
-                "A number in the decoded buffer is too large (index {index}, {number} > 999)"
+                "The container is incomplete ({found} bytes, expected {expected} bytes)!"
             ),
+            Self::WrongChecksum(expected, found) => write!(
+                f,
+                "The container is corrupt (checksum {found:08x}, expected {expected:08x})!"
+            ),
+            Self::Compressed => write!(
+                f,
+                "The buffer is compressed, which needs the compression feature!"
+            ),
+            Self::InvalidCompression => write!(f, "The buffer could not be decompressed!"),
+        }
+    }
+}
+
+impl Code for Error {
+    fn code(&self) -> ErrorCode {
+        match self {
+            Self::InvalidNumber { .. } => ErrorCode(301),
+            Self::TrailingData { .. } => ErrorCode(302),
+            Self::TruncatedHeader => ErrorCode(303),
+            Self::UnsupportedVersion(_) => ErrorCode(304),
+            Self::WrongLength(_, _) => ErrorCode(305),
+            Self::WrongChecksum(_, _) => ErrorCode(306),
+            Self::Compressed => ErrorCode(307),
+            Self::InvalidCompression => ErrorCode(308),
         }
     }
 }
@@ -36,15 +147,78 @@ impl fmt::Display for Error {
 impl std::error::Error for Error {}
 
 #[allow(clippy::module_name_repetitions)]
-/// Load [Memory] from a saved buffer
+/// Load [Memory] from a saved buffer, either in a container or in the legacy format.
+///
+/// Compressed buffers are decompressed first, with the `compression` feature
 ///
 /// # Errors
 /// See [Error]
 pub fn load_from_buffer(buffer: &[u8]) -> Result<Memory, Error> {
+    if is_compressed(buffer) {
+        #[cfg(feature = "compression")]
+        return load_from_uncompressed(&decompress(buffer)?);
+        #[cfg(not(feature = "compression"))]
+        return Err(Error::Compressed);
+    }
+
+    load_from_uncompressed(buffer)
+}
+
+/// Load [Memory] from a buffer in the legacy format, without checking for a container.
+///
+/// This loads legacy buffers that start with the container's [`MAGIC`](super::MAGIC) bytes,
+///  which [`load_from_buffer`] loads as a container
+///
+/// # Errors
+/// See [Error]
+pub fn load_legacy_from_buffer(buffer: &[u8]) -> Result<Memory, Error> {
+    unpack(buffer, BinaryFormat::Legacy, 0)
+}
+
+/// Load [Memory] from a saved buffer that is not compressed
+fn load_from_uncompressed(buffer: &[u8]) -> Result<Memory, Error> {
+    let (contents, rest) = split_container(buffer)?;
+    if contents.len() == buffer.len() {
+        return unpack(contents, BinaryFormat::Legacy, 0);
+    }
+
+    // Any metadata is ignored, but it must be a whole metadata section
+    let offset = buffer.len() - rest.len();
+    if let Some(length) = metadata_section_len(rest) {
+        if rest.len() > length {
+            return Err(Error::TrailingData {
+                format: BinaryFormat::Container,
+                offset: offset + length,
+            });
+        }
+    } else if !rest.is_empty() {
+        return Err(Error::TrailingData {
+            format: BinaryFormat::Container,
+            offset,
+        });
+    }
+
+    unpack(contents, BinaryFormat::Container, HEADER_SIZE)
+}
+
+/// Unpack the 10 bit numbers in saved memory, without a container.
+///
+/// `base` is the byte offset of the saved memory in the buffer, for errors
+pub(super) fn unpack(buffer: &[u8], format: BinaryFormat, base: usize) -> Result<Memory, Error> {
     if buffer.len() > MAX_FILE_SIZE {
-        return Err(Error::BufferTooLarge(buffer.len()));
+        return Err(Error::TrailingData {
+            format,
+            offset: base + MAX_FILE_SIZE,
+        });
     }
 
+    let invalid_number = |index: usize, number| Error::InvalidNumber {
+        format,
+        offset: base + index * 10 / 8,
+        index,
+        number,
+    };
+
     // Initialise the memory, address index and offset
     let mut memory = [0; 100];
     let mut address = 0;
@@ -61,7 +235,7 @@ pub fn load_from_buffer(buffer: &[u8]) -> Result<Memory, Error> {
         if index != 0 {
             // If the latest complete number added is too large, error
             if memory[address] > 999 {
-                return Err(Error::InvalidNumber(address, memory[address]));
+                return Err(invalid_number(address, memory[address]));
             }
 
             // Increment the address
@@ -79,9 +253,14 @@ pub fn load_from_buffer(buffer: &[u8]) -> Result<Memory, Error> {
         }
     }
 
+    // The last number may not have been checked
+    if let Some(number) = memory.get(address).filter(|number| **number > 999) {
+        return Err(invalid_number(address, *number));
+    }
+
     // The numbers have already been checked and are not
     //  over 999, so it is safe to transmute
-    Ok(unsafe { mem::transmute(memory) })
+    Ok(unsafe { mem::transmute::<[u16; 100], Memory>(memory) })
 }
 
 #[cfg(feature = "std")]
@@ -90,10 +269,29 @@ pub fn load_from_buffer(buffer: &[u8]) -> Result<Memory, Error> {
 pub enum FromFileError {
     /// Encountered an Os error while performing a file system operation
     IoError(io::Error),
-    /// The file is more than [MAX_FILE_SIZE] bytes long
+    /// The file is more than [`MAX_FILE_LOAD_SIZE`] bytes long.
+    /// Files are only read up to one byte past the limit, so this is not the full size
     FileTooLarge(u64),
     /// The contents of the file could not be loaded, see [Error]
     LoadError(Error),
+    /// The metadata in the file could not be loaded, see [`MetadataError`]
+    InvalidMetadata(MetadataError),
+    /// The fragment in the file could not be loaded, see [`FragmentError`]
+    InvalidFragment(FragmentError),
+    /// The bundle in the file could not be loaded, see [`BundleError`]
+    InvalidBundle(BundleError),
+    #[cfg(feature = "json")]
+    /// The file was detected as JSON, but could not be loaded, see [`JSONError`]
+    InvalidJSON(JSONError),
+    /// The file was detected as Intel HEX, but could not be loaded,
+    ///  see [`IntelHexError`](crate::file::IntelHexError)
+    InvalidIntelHex(IntelHexErrorWithLineNumber),
+    /// The file was detected as numbers, but could not be loaded,
+    ///  see [`FromNumbersError`](crate::number_assembler::FromNumbersError)
+    InvalidNumbers(number_assembler::ErrorWithLineNumber),
+    /// The file was detected as a format that cannot be loaded as [Memory],
+    ///  or that needs a feature that is not enabled
+    UnsupportedFormat(Format),
 }
 
 #[cfg(feature = "std")]
@@ -106,9 +304,38 @@ impl fmt::Display for FromFileError {
             ),
             Self::FileTooLarge(size) => write!(
                 f,
-                "The file is too large ({size} bytes > {MAX_FILE_SIZE} bytes)"
+                "The file is too large (at least {size} bytes > {MAX_FILE_LOAD_SIZE} bytes)"
             ),
             Self::LoadError(error) => fmt::Display::fmt(error, f),
+            Self::InvalidMetadata(error) => fmt::Display::fmt(error, f),
+            Self::InvalidFragment(error) => fmt::Display::fmt(error, f),
+            Self::InvalidBundle(error) => fmt::Display::fmt(error, f),
+            #[cfg(feature = "json")]
+            Self::InvalidJSON(error) => fmt::Display::fmt(error, f),
+            Self::InvalidIntelHex(error) => fmt::Display::fmt(error, f),
+            Self::InvalidNumbers(error) => fmt::Display::fmt(error, f),
+            Self::UnsupportedFormat(format) => {
+                write!(f, "The file format cannot be loaded ({format})!")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Code for FromFileError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            Self::IoError(_) => ErrorCode(320),
+            Self::FileTooLarge(_) => ErrorCode(321),
+            Self::LoadError(error) => error.code(),
+            Self::InvalidMetadata(_) => ErrorCode(322),
+            Self::InvalidFragment(_) => ErrorCode(323),
+            Self::InvalidBundle(_) => ErrorCode(324),
+            #[cfg(feature = "json")]
+            Self::InvalidJSON(_) => ErrorCode(325),
+            Self::InvalidIntelHex(_) => ErrorCode(326),
+            Self::InvalidNumbers(error) => error.code(),
+            Self::UnsupportedFormat(_) => ErrorCode(327),
         }
     }
 }
@@ -119,7 +346,14 @@ impl std::error::Error for FromFileError {
         match self {
             Self::IoError(error) => Some(error),
             Self::LoadError(error) => Some(error),
-            Self::FileTooLarge(_) => None,
+            Self::InvalidMetadata(error) => Some(error),
+            Self::InvalidFragment(error) => Some(error),
+            Self::InvalidBundle(error) => Some(error),
+            #[cfg(feature = "json")]
+            Self::InvalidJSON(error) => Some(error),
+            Self::InvalidIntelHex(error) => Some(error),
+            Self::InvalidNumbers(error) => Some(error),
+            Self::FileTooLarge(_) | Self::UnsupportedFormat(_) => None,
         }
     }
 }
@@ -138,9 +372,74 @@ impl From<Error> for FromFileError {
     }
 }
 
+#[cfg(feature = "std")]
+impl From<MetadataError> for FromFileError {
+    fn from(value: MetadataError) -> Self {
+        match value {
+            MetadataError::LoadError(error) => Self::LoadError(error),
+            error => Self::InvalidMetadata(error),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<FragmentError> for FromFileError {
+    fn from(value: FragmentError) -> Self {
+        match value {
+            FragmentError::LoadError(error) => Self::LoadError(error),
+            error => Self::InvalidFragment(error),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<BundleError> for FromFileError {
+    fn from(value: BundleError) -> Self {
+        match value {
+            BundleError::InvalidProgram(error) => error.into(),
+            error => Self::InvalidBundle(error),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+/// The maximum size of a file that will be loaded in bytes, a container with metadata
+pub const MAX_FILE_LOAD_SIZE: usize = MAX_CONTAINER_SIZE + MAX_METADATA_SIZE;
+
+#[cfg(feature = "std")]
+/// Read everything from a reader, up to one byte more than [`MAX_FILE_LOAD_SIZE`]
+///  so it does not read forever if it is too large
+pub(super) fn read_to_limit(reader: &mut impl Read) -> Result<Vec<u8>, FromFileError> {
+    let mut buffer = Vec::new();
+    reader
+        .take(MAX_FILE_LOAD_SIZE as u64 + 1)
+        .read_to_end(&mut buffer)?;
+
+    if buffer.len() > MAX_FILE_LOAD_SIZE {
+        return Err(FromFileError::FileTooLarge(buffer.len() as u64));
+    }
+
+    Ok(buffer)
+}
+
+#[cfg(feature = "std")]
+#[allow(clippy::module_name_repetitions)]
+/// Load [Memory] from the given reader, such as a socket or an in-memory buffer
+///
+/// # Errors
+/// See [`FromFileError`]
+pub fn load_from_reader(reader: &mut impl Read) -> Result<Memory, FromFileError> {
+    // Read everything and load it
+    let buffer = read_to_limit(reader)?;
+    load_from_buffer(&buffer).map_err(FromFileError::from)
+}
+
 #[cfg(feature = "std")]
 #[allow(clippy::module_name_repetitions)]
-/// Load [Memory] from the given file
+/// Load [Memory] from the given file.
+///
+/// The file is streamed rather than checking its size first,
+///  so pipes and process substitutions can be loaded
 ///
 /// This function will move the cursor inside file,
 /// unless you know what you are doing, do not use `file` after calling this function
@@ -148,18 +447,7 @@ impl From<Error> for FromFileError {
 /// # Errors
 /// See [`FromFileError`]
 pub fn load_from_file(file: &mut File) -> Result<Memory, FromFileError> {
-    // Make sure the file is not too large
-    let file_size = file.metadata()?.len();
-    if file_size > MAX_FILE_SIZE as u64 {
-        return Err(FromFileError::FileTooLarge(file_size));
-    }
-
-    // Read the file
-    let mut buffer = [0; MAX_FILE_SIZE];
-    let bytes_read = file.read(&mut buffer)?;
-
-    // Load it
-    load_from_buffer(&buffer[..bytes_read]).map_err(FromFileError::from)
+    load_from_reader(file)
 }
 
 #[cfg(feature = "std")]
@@ -171,6 +459,16 @@ pub fn load(path: &str) -> Result<Memory, FromFileError> {
     load_from_file(&mut File::open(path)?)
 }
 
+#[cfg(feature = "std")]
+/// Load [Memory] from a file given the path str and disassemble it to assembly,
+///  see [`disassemble`]
+///
+/// # Errors
+/// See [`FromFileError`]
+pub fn load_to_assembly(path: &str) -> Result<String, FromFileError> {
+    load(path).map(disassemble)
+}
+
 #[cfg(feature = "std")]
 #[allow(clippy::module_name_repetitions)]
 /// Load [Memory] from a file given the path
@@ -191,9 +489,18 @@ mod test {
 
     use uuid::Uuid;
 
-    use crate::file::{load, MAX_FILE_SIZE};
+    use crate::{
+        assembler::assemble_from_text,
+        file::{
+            load, save_container, save_container_to_buffer, save_container_to_writer,
+            FromFileError, MAX_CONTAINER_SIZE, MAX_FILE_SIZE,
+        },
+    };
 
-    use super::load_from_buffer;
+    use super::{
+        load_from_buffer, load_from_reader, load_to_assembly, BinaryFormat, Error,
+        MAX_FILE_LOAD_SIZE,
+    };
 
     #[test]
     fn empty_buffer() {
@@ -228,6 +535,135 @@ mod test {
         );
     }
 
+    #[test]
+    fn error_details() {
+        // The second number is 1023
+        assert_eq!(
+            load_from_buffer(&[0, 0b0011_1111, 0b1111_0000]),
+            Err(Error::InvalidNumber {
+                format: BinaryFormat::Legacy,
+                offset: 1,
+                index: 1,
+                number: 1023
+            }),
+            "Failed to find the invalid number!"
+        );
+        // The last number is incomplete, but still too large
+        assert_eq!(
+            load_from_buffer(&[0, 0b0011_1111]),
+            Err(Error::InvalidNumber {
+                format: BinaryFormat::Legacy,
+                offset: 1,
+                index: 1,
+                number: 1008
+            }),
+            "Failed to find the incomplete invalid number!"
+        );
+        assert_eq!(
+            load_from_buffer(&[0; MAX_FILE_SIZE + 1]),
+            Err(Error::TrailingData {
+                format: BinaryFormat::Legacy,
+                offset: MAX_FILE_SIZE
+            }),
+            "Failed to reject trailing data!"
+        );
+
+        let memory = assemble_from_text("IN\nOUT\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+        let mut buffer = [0; MAX_CONTAINER_SIZE];
+        let mut saved = save_container_to_buffer(&mut buffer, memory).to_vec();
+        let length = saved.len();
+        saved.push(1);
+        assert_eq!(
+            load_from_buffer(&saved),
+            Err(Error::TrailingData {
+                format: BinaryFormat::Container,
+                offset: length
+            }),
+            "Failed to reject trailing data in a container!"
+        );
+
+        // An empty metadata section
+        saved.pop();
+        saved.extend_from_slice(&[0, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(
+            load_from_buffer(&saved),
+            Err(Error::TrailingData {
+                format: BinaryFormat::Container,
+                offset: length + 6
+            }),
+            "Failed to reject trailing data after the metadata!"
+        );
+        assert_eq!(
+            load_from_buffer(&saved[..length + 6]),
+            Ok(memory),
+            "Failed to ignore the metadata!"
+        );
+    }
+
+    #[test]
+    fn reader() {
+        let memory = assemble_from_text("IN\nOUT\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        // Save to and load from an in-memory buffer
+        let mut buffer = Vec::new();
+        save_container_to_writer(&mut buffer, memory).expect("failed to save to the buffer");
+        let loaded = load_from_reader(&mut &buffer[..]).expect("failed to load from the buffer");
+        assert_eq!(loaded, memory, "Failed to load from a reader!");
+
+        // Readers that never end are not read forever
+        assert!(
+            matches!(
+                load_from_reader(&mut std::io::repeat(0)),
+                Err(FromFileError::FileTooLarge(size)) if size == MAX_FILE_LOAD_SIZE as u64 + 1
+            ),
+            "Failed to reject a reader that was too long!"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn endless_file() {
+        // Has no length, and never ends
+        assert!(
+            matches!(
+                load("/dev/zero"),
+                Err(FromFileError::FileTooLarge(size)) if size == MAX_FILE_LOAD_SIZE as u64 + 1
+            ),
+            "Failed to reject a file that never ends!"
+        );
+    }
+
+    #[test]
+    fn to_assembly() {
+        let assembly = "IN\nSTO 99\nIN\nADD 99\nOUT\nHLT\nDAT 5\n";
+        let memory = assemble_from_text(assembly)
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        // Get a new path in the temp directory
+        let mut path = temp_dir();
+        path.push(format!("lminc-test-{}", Uuid::new_v4()));
+        let path_str = path.to_str().expect("failed to convert path to str");
+
+        save_container(path_str, memory).expect("failed to save memory to file");
+        let loaded = load_to_assembly(path_str);
+
+        // Try to delete the file
+        if let Err(error) = fs::remove_file(path.clone()) {
+            eprintln!("Warning: Failed to remove file ({path_str})!\nError: {error}");
+        }
+
+        assert_eq!(
+            loaded.expect("failed to load assembly from file"),
+            assembly,
+            "Loaded the wrong assembly!"
+        );
+    }
+
     #[test]
     fn empty() {
         // Get a new path in the temp directory
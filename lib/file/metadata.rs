@@ -0,0 +1,469 @@
+extern crate alloc;
+use core::{fmt, str};
+#[cfg(feature = "std")]
+use std::{
+    fs::File,
+    io::{self, Read, Write},
+};
+
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+#[cfg(feature = "json")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "compression")]
+use crate::file::{container::is_compressed, decompress};
+#[cfg(feature = "std")]
+use crate::file::{read_to_limit, FromFileError};
+use crate::{
+    computer::Memory,
+    file::{
+        container::{crc32, split_container},
+        load_from_buffer, save_container_to_buffer, Error, MAX_CONTAINER_SIZE,
+        METADATA_HEADER_SIZE,
+    },
+    parser::Parser,
+};
+
+/// The maximum size of the metadata section of a container in bytes
+pub const MAX_METADATA_SIZE: usize = METADATA_HEADER_SIZE + u16::MAX as usize;
+
+// Metadata record tags, each record is a tag, a length byte and the value
+const NAME: u8 = 1;
+const DESCRIPTION: u8 = 2;
+const ENTRY: u8 = 3;
+const FEATURES: u8 = 4;
+const SYMBOL: u8 = 5;
+
+// Feature flags
+const EXTENDED: u8 = 0b1;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+/// Information about a program, saved alongside its memory
+pub struct Metadata {
+    #[cfg_attr(
+        feature = "json",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    /// The name of the program
+    pub name: Option<String>,
+    #[cfg_attr(
+        feature = "json",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    /// A description of what the program does
+    pub description: Option<String>,
+    #[cfg_attr(
+        feature = "json",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    /// The address the program starts at, if it is not 0
+    pub entry: Option<usize>,
+    #[cfg_attr(
+        feature = "json",
+        serde(default, skip_serializing_if = "core::ops::Not::not")
+    )]
+    /// If the program needs extended mode
+    pub extended: bool,
+    #[cfg_attr(
+        feature = "json",
+        serde(default, skip_serializing_if = "BTreeMap::is_empty")
+    )]
+    /// The labels in the program and their addresses
+    pub symbols: BTreeMap<String, usize>,
+}
+
+impl Metadata {
+    #[must_use]
+    /// Create [Metadata] with the symbols of the parsed program,
+    ///  and whether it needs extended mode
+    pub fn from_parser(parser: &Parser) -> Self {
+        let mut metadata = Self::default();
+
+        for (address, instruction) in parser.iter().enumerate() {
+            if let Some(label) = instruction.label {
+                metadata.symbols.insert(label.to_string(), address);
+            }
+
+            #[cfg(feature = "extended")]
+            {
+                use crate::assembly::Instruction;
+
+                metadata.extended |= matches!(
+                    instruction.instruction,
+                    Instruction::EXT | Instruction::INA | Instruction::OUTA
+                );
+            }
+        }
+
+        metadata
+    }
+
+    #[must_use]
+    /// Get the label for an address, if it has one.
+    /// If there are multiple, this is the first alphabetically
+    pub fn label(&self, address: usize) -> Option<&str> {
+        self.symbols.iter().find_map(|(label, label_address)| {
+            (*label_address == address).then_some(label.as_str())
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Errors for saving and loading [Metadata]
+pub enum MetadataError {
+    /// The memory could not be loaded, see [Error]
+    LoadError(Error),
+    /// A name, description or symbol was longer than 255 bytes,
+    ///  or the metadata was longer than [`MAX_METADATA_SIZE`] bytes
+    TooLong,
+    /// An address was too large (> 99)
+    AddressTooLarge(usize),
+    /// The metadata section ended part way through
+    Truncated,
+    /// The checksum in the metadata header (first) is not the checksum of the records (second)
+    WrongChecksum(u32, u32),
+    /// A name, description or symbol was not valid UTF-8
+    InvalidText,
+}
+
+impl fmt::Display for MetadataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LoadError(error) => fmt::Display::fmt(error, f),
+            Self::TooLong => write!(f, "The metadata is too long!"),
+            Self::AddressTooLarge(address) => {
+                write!(f, "Metadata address too large ({address} should be < 100)!")
+            }
+            Self::Truncated => write!(f, "The metadata is incomplete!"),
+            Self::WrongChecksum(expected, found) => write!(
+                f,
+                "The metadata is corrupt (checksum {found:08x}, expected {expected:08x})!"
+            ),
+            Self::InvalidText => write!(f, "The metadata contains invalid text!"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MetadataError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::LoadError(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<Error> for MetadataError {
+    fn from(value: Error) -> Self {
+        Self::LoadError(value)
+    }
+}
+
+/// Write a record, checking that the value fits
+fn push_record(records: &mut Vec<u8>, tag: u8, value: &[&[u8]]) -> Result<(), MetadataError> {
+    let length = value.iter().map(|part| part.len()).sum::<usize>();
+    let length = u8::try_from(length).map_err(|_| MetadataError::TooLong)?;
+
+    records.extend_from_slice(&[tag, length]);
+    for part in value {
+        records.extend_from_slice(part);
+    }
+
+    Ok(())
+}
+
+/// Convert an address to a byte, checking that it is in memory
+fn address_byte(address: usize) -> Result<u8, MetadataError> {
+    u8::try_from(address)
+        .ok()
+        .filter(|address| *address < 100)
+        .ok_or(MetadataError::AddressTooLarge(address))
+}
+
+/// Convert a byte to an address, checking that it is in memory, see [`address_byte`]
+fn byte_address(byte: u8) -> Result<usize, MetadataError> {
+    let address = usize::from(byte);
+    if address < 100 {
+        Ok(address)
+    } else {
+        Err(MetadataError::AddressTooLarge(address))
+    }
+}
+
+/// Read the records of a metadata section
+fn read_records(mut records: &[u8]) -> Result<Metadata, MetadataError> {
+    let text = |value: &[u8]| {
+        str::from_utf8(value)
+            .map(ToString::to_string)
+            .map_err(|_| MetadataError::InvalidText)
+    };
+
+    let mut metadata = Metadata::default();
+
+    while let [tag, length, rest @ ..] = records {
+        let Some((value, rest)) = rest.split_at_checked(usize::from(*length)) else {
+            return Err(MetadataError::Truncated);
+        };
+        records = rest;
+
+        match (*tag, value) {
+            (NAME, name) => metadata.name = Some(text(name)?),
+            (DESCRIPTION, description) => metadata.description = Some(text(description)?),
+            (ENTRY, [entry]) => {
+                metadata.entry = Some(byte_address(*entry)?);
+            }
+            (FEATURES, [features]) => metadata.extended = features & EXTENDED != 0,
+            (SYMBOL, [address, label @ ..]) => {
+                metadata
+                    .symbols
+                    .insert(text(label)?, byte_address(*address)?);
+            }
+            (ENTRY | FEATURES | SYMBOL, _) => return Err(MetadataError::Truncated),
+            // Skip records from newer versions
+            _ => (),
+        }
+    }
+
+    if records.is_empty() {
+        Ok(metadata)
+    } else {
+        Err(MetadataError::Truncated)
+    }
+}
+
+#[allow(clippy::module_name_repetitions)]
+/// Save the [Memory] in a container, followed by the [Metadata].
+///
+/// Containers with metadata still load with [`load_from_buffer`], which ignores the metadata
+///
+/// # Errors
+/// See [`MetadataError`]
+pub fn save_container_with_metadata(
+    memory: Memory,
+    metadata: &Metadata,
+) -> Result<Vec<u8>, MetadataError> {
+    let mut records = Vec::new();
+
+    if let Some(name) = &metadata.name {
+        push_record(&mut records, NAME, &[name.as_bytes()])?;
+    }
+    if let Some(description) = &metadata.description {
+        push_record(&mut records, DESCRIPTION, &[description.as_bytes()])?;
+    }
+    if let Some(entry) = metadata.entry {
+        push_record(&mut records, ENTRY, &[&[address_byte(entry)?]])?;
+    }
+    if metadata.extended {
+        push_record(&mut records, FEATURES, &[&[EXTENDED]])?;
+    }
+    for (label, address) in &metadata.symbols {
+        push_record(
+            &mut records,
+            SYMBOL,
+            &[&[address_byte(*address)?], label.as_bytes()],
+        )?;
+    }
+
+    let length = u16::try_from(records.len()).map_err(|_| MetadataError::TooLong)?;
+
+    let mut buffer = [0; MAX_CONTAINER_SIZE];
+    let mut out = save_container_to_buffer(&mut buffer, memory).to_vec();
+    out.extend_from_slice(&length.to_be_bytes());
+    out.extend_from_slice(&crc32(&records).to_be_bytes());
+    out.extend_from_slice(&records);
+
+    Ok(out)
+}
+
+#[allow(clippy::module_name_repetitions)]
+/// Load [Memory] and any [Metadata] from a saved buffer.
+///
+/// The metadata is `None` for legacy buffers and containers without metadata
+///
+/// # Errors
+/// See [`MetadataError`]
+pub fn load_with_metadata_from_buffer(
+    buffer: &[u8],
+) -> Result<(Memory, Option<Metadata>), MetadataError> {
+    #[cfg(feature = "compression")]
+    let decompressed;
+    #[cfg(feature = "compression")]
+    let buffer = if is_compressed(buffer) {
+        decompressed = decompress(buffer)?;
+        &decompressed
+    } else {
+        buffer
+    };
+
+    let memory = load_from_buffer(buffer)?;
+
+    let (_, section) = split_container(buffer)?;
+    if section.is_empty() {
+        return Ok((memory, None));
+    }
+
+    let Some((header, records)) = section.split_at_checked(METADATA_HEADER_SIZE) else {
+        return Err(MetadataError::Truncated);
+    };
+    let length = usize::from(u16::from_be_bytes([header[0], header[1]]));
+    if records.len() != length {
+        return Err(MetadataError::Truncated);
+    }
+
+    let checksum = u32::from_be_bytes([header[2], header[3], header[4], header[5]]);
+    let actual = crc32(records);
+    if checksum != actual {
+        return Err(MetadataError::WrongChecksum(checksum, actual));
+    }
+
+    Ok((memory, Some(read_records(records)?)))
+}
+
+#[cfg(feature = "std")]
+/// Save the [Memory] to the given writer in a container with the [Metadata]
+///
+/// # Errors
+/// [`io::Error`] - writer error, or [`io::ErrorKind::InvalidInput`] with a [`MetadataError`]
+pub fn save_with_metadata_to_writer(
+    writer: &mut impl Write,
+    memory: Memory,
+    metadata: &Metadata,
+) -> io::Result<()> {
+    let buffer = save_container_with_metadata(memory, metadata)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+
+    writer.write_all(&buffer)
+}
+
+#[cfg(feature = "std")]
+/// Save the [Memory] to a file in a container with the [Metadata] given the path str
+///
+/// # Errors
+/// [`io::Error`] - file system error, or [`io::ErrorKind::InvalidInput`] with a [`MetadataError`]
+pub fn save_with_metadata(path: &str, memory: Memory, metadata: &Metadata) -> io::Result<()> {
+    save_with_metadata_to_writer(&mut File::create(path)?, memory, metadata)
+}
+
+#[cfg(feature = "std")]
+/// Load [Memory] and any [Metadata] from the given reader
+///
+/// # Errors
+/// See [`FromFileError`]
+pub fn load_with_metadata_from_reader(
+    reader: &mut impl Read,
+) -> Result<(Memory, Option<Metadata>), FromFileError> {
+    let buffer = read_to_limit(reader)?;
+    load_with_metadata_from_buffer(&buffer).map_err(FromFileError::from)
+}
+
+#[cfg(feature = "std")]
+/// Load [Memory] and any [Metadata] from a file given the path str
+///
+/// # Errors
+/// See [`FromFileError`]
+pub fn load_with_metadata(path: &str) -> Result<(Memory, Option<Metadata>), FromFileError> {
+    load_with_metadata_from_reader(&mut File::open(path)?)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        assembler::assemble_from_parser,
+        file::{load_from_buffer, Error, HEADER_SIZE},
+        parser::Parser,
+    };
+
+    use super::{
+        load_with_metadata_from_buffer, read_records, save_container_with_metadata, Metadata,
+        MetadataError, ENTRY, SYMBOL,
+    };
+
+    #[test]
+    fn metadata() {
+        let parser = Parser::parse_text("start IN\nSTO 99\nIN\nADD 99\nOUT\nBR start\ncount DAT 0")
+            .expect("failed to parse the assembly");
+        let memory = assemble_from_parser(parser).expect("failed to assemble the assembly");
+
+        let mut metadata = Metadata::from_parser(&parser);
+        assert_eq!(metadata.label(6), Some("count"), "Got the wrong label!");
+        assert!(!metadata.extended, "Program should not need extended mode!");
+        metadata.name = Some("add".to_string());
+
+        let saved =
+            save_container_with_metadata(memory, &metadata).expect("failed to save the metadata");
+        assert_eq!(
+            load_with_metadata_from_buffer(&saved),
+            Ok((memory, Some(metadata.clone()))),
+            "Failed to load the saved metadata!"
+        );
+        assert_eq!(
+            load_from_buffer(&saved),
+            Ok(memory),
+            "Failed to load memory with metadata!"
+        );
+
+        let mut corrupt = saved.clone();
+        *corrupt.last_mut().expect("no metadata") ^= 1;
+        assert!(
+            matches!(
+                load_with_metadata_from_buffer(&corrupt),
+                Err(MetadataError::WrongChecksum(..))
+            ),
+            "Failed to reject corrupt metadata!"
+        );
+
+        let mut corrupt = saved;
+        corrupt[HEADER_SIZE] ^= 1;
+        assert!(
+            matches!(
+                load_with_metadata_from_buffer(&corrupt),
+                Err(MetadataError::LoadError(Error::WrongChecksum(..)))
+            ),
+            "Failed to reject a corrupt container!"
+        );
+
+        metadata.entry = Some(100);
+        assert_eq!(
+            save_container_with_metadata(memory, &metadata),
+            Err(MetadataError::AddressTooLarge(100)),
+            "Failed to reject an entry point outside memory!"
+        );
+    }
+
+    #[test]
+    fn load_addresses() {
+        assert_eq!(
+            read_records(&[ENTRY, 1, 99]).map(|metadata| metadata.entry),
+            Ok(Some(99)),
+            "Failed to load an entry point!"
+        );
+        assert_eq!(
+            read_records(&[ENTRY, 1, 255]),
+            Err(MetadataError::AddressTooLarge(255)),
+            "Loaded an entry point outside memory!"
+        );
+        assert_eq!(
+            read_records(&[SYMBOL, 2, 100, b'a']),
+            Err(MetadataError::AddressTooLarge(100)),
+            "Loaded a symbol outside memory!"
+        );
+    }
+
+    #[cfg(feature = "extended")]
+    #[test]
+    fn extended() {
+        let parser =
+            Parser::parse_text("EXT\nINA\nOTA\nHLT").expect("failed to parse the assembly");
+        assert!(
+            Metadata::from_parser(&parser).extended,
+            "Program should need extended mode!"
+        );
+    }
+}
@@ -1,7 +1,45 @@
+#[cfg(feature = "alloc")]
+mod bundle;
+#[cfg(feature = "alloc")]
+mod cardiac;
+#[cfg(feature = "compression")]
+mod compression;
+mod container;
+#[cfg(feature = "alloc")]
+mod debug_info;
+mod detect;
+mod fragment;
+mod intel_hex;
+#[cfg(feature = "json")]
+mod json;
+#[cfg(feature = "alloc")]
+mod listing;
 mod load;
+#[cfg(feature = "alloc")]
+mod metadata;
+mod numbers;
 mod save;
 
+#[cfg(feature = "alloc")]
+pub use bundle::*;
+#[cfg(feature = "alloc")]
+pub use cardiac::*;
+#[cfg(feature = "compression")]
+pub use compression::*;
+pub use container::*;
+#[cfg(feature = "alloc")]
+pub use debug_info::*;
+pub use detect::*;
+pub use fragment::*;
+pub use intel_hex::*;
+#[cfg(feature = "json")]
+pub use json::*;
+#[cfg(feature = "alloc")]
+pub use listing::*;
 pub use load::*;
+#[cfg(feature = "alloc")]
+pub use metadata::*;
+pub use numbers::*;
 pub use save::*;
 
 // 10 bits needed per number
@@ -0,0 +1,133 @@
+#[cfg(feature = "alloc")]
+extern crate alloc;
+use core::fmt::{self, Write};
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+use crate::{
+    assembly::RawInstruction,
+    computer::Memory,
+    num3::ThreeDigitNumber,
+    number_assembler::{ErrorWithLineNumber, NumberAssembler},
+};
+
+/// Write the [Memory] as one three digit number per line, without the trailing zeros,
+///  in the format read by the [`NumberAssembler`]
+///
+/// # Errors
+/// If writing to `out` fails
+pub fn write_numbers(out: &mut impl Write, memory: Memory) -> fmt::Result {
+    memory[..program_length(&memory)]
+        .iter()
+        .try_for_each(|number| writeln!(out, "{number:#}"))
+}
+
+/// Write the [Memory] like [`write_numbers`], with a comment on each line giving
+///  the address and the instruction that the number runs as
+///
+/// # Errors
+/// If writing to `out` fails
+pub fn write_commented_numbers(out: &mut impl Write, memory: Memory) -> fmt::Result {
+    memory[..program_length(&memory)]
+        .iter()
+        .enumerate()
+        .try_for_each(|(address, number)| {
+            writeln!(
+                out,
+                "{number:#}     # {address:02}: {}",
+                RawInstruction::from(*number)
+            )
+        })
+}
+
+/// Get the length of the program, without the zeros after the last non-zero number
+fn program_length(memory: &Memory) -> usize {
+    memory
+        .iter()
+        .rposition(|number| *number != ThreeDigitNumber::ZERO)
+        .map_or(0, |index| index + 1)
+}
+
+#[cfg(feature = "alloc")]
+#[must_use]
+/// Save the [Memory] as one three digit number per line, see [`write_numbers`]
+///
+/// ```
+/// # use lminc::{assembler::assemble_from_text, file};
+/// let memory = assemble_from_text("IN\nOUT\nHLT")??;
+/// assert_eq!(file::save_numbers(memory), "901\n902\n");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn save_numbers(memory: Memory) -> String {
+    let mut out = String::new();
+    // Writing to a String does not fail
+    let _ = write_numbers(&mut out, memory);
+    out
+}
+
+#[cfg(feature = "alloc")]
+#[must_use]
+/// Save the [Memory] as one three digit number per line with comments,
+///  see [`write_commented_numbers`]
+///
+/// ```
+/// # use lminc::{assembler::assemble_from_text, file};
+/// let memory = assemble_from_text("IN\nOUT\nHLT")??;
+/// assert_eq!(
+///     file::save_commented_numbers(memory),
+///     "901     # 00: IN\n902     # 01: OUT\n"
+/// );
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn save_commented_numbers(memory: Memory) -> String {
+    let mut out = String::new();
+    // Writing to a String does not fail
+    let _ = write_commented_numbers(&mut out, memory);
+    out
+}
+
+/// Load [Memory] from one number per line, with comments, using the [`NumberAssembler`]
+///
+/// # Errors
+/// See [`FromNumbersError`](crate::number_assembler::FromNumbersError)
+pub fn load_numbers(text: &str) -> Result<Memory, ErrorWithLineNumber> {
+    NumberAssembler::assemble_from_text(text)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::number_assembler::NumberAssembler;
+
+    use super::{load_numbers, save_commented_numbers, save_numbers};
+
+    #[test]
+    fn numbers() {
+        let numbers = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/examples/fib_num.txt"));
+        let memory = NumberAssembler::assemble_from_text(numbers).expect("failed to assemble");
+
+        let saved = save_numbers(memory);
+        assert_eq!(
+            saved,
+            "605\n000\n001\n000\n100\n501\n102\n902\n303\n502\n301\n503\n302\n204\n816\n605\n",
+            "Saved the wrong numbers!"
+        );
+        assert_eq!(
+            load_numbers(&saved).expect("failed to load the numbers"),
+            memory,
+            "Failed to load the saved numbers!"
+        );
+
+        let commented = save_commented_numbers(memory);
+        assert_eq!(
+            commented.lines().nth(5),
+            Some("501     # 05: LDA 1"),
+            "Saved the wrong comment!"
+        );
+        assert_eq!(
+            load_numbers(&commented).expect("failed to load the commented numbers"),
+            memory,
+            "Failed to load the saved numbers with comments!"
+        );
+    }
+}
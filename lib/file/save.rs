@@ -1,4 +1,7 @@
-use core::mem;
+use core::{
+    mem::{self, MaybeUninit},
+    ptr,
+};
 #[cfg(feature = "std")]
 use std::{
     fs::File,
@@ -13,8 +16,29 @@ use super::MAX_FILE_SIZE;
 #[allow(clippy::module_name_repetitions)]
 /// Save the [Memory] to the buffer and return a trimmed version of it
 pub fn save_to_buffer(buffer: &mut [u8; MAX_FILE_SIZE], memory: Memory) -> &[u8] {
+    let length = save_to_buffer_len(buffer, memory);
+    &buffer[..length]
+}
+
+#[allow(clippy::module_name_repetitions)]
+/// Save the [Memory] to the buffer and return the number of bytes to keep,
+///  the bytes after them are all zero
+///
+/// ```
+/// # use lminc::{assembler::assemble_from_text, file::{self, MAX_FILE_SIZE}};
+/// let memory = assemble_from_text("IN\nOUT\nHLT")??;
+///
+/// let mut buffer = [0; MAX_FILE_SIZE];
+/// let length = file::save_to_buffer_len(&mut buffer, memory);
+/// assert_eq!(file::load_from_buffer(&buffer[..length])?, memory);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn save_to_buffer_len(buffer: &mut [u8; MAX_FILE_SIZE], memory: Memory) -> usize {
     let memory: [u16; 100] = unsafe { mem::transmute(memory) };
 
+    // The numbers are added to the bytes, so start with zeros
+    buffer.fill(0);
+
     // Initialise the byte index and bit offset
     let mut index = 0;
     let mut offset: u8 = 2;
@@ -39,15 +63,42 @@ pub fn save_to_buffer(buffer: &mut [u8; MAX_FILE_SIZE], memory: Memory) -> &[u8]
         }
     }
 
-    // Get the index of the last non-zero byte
-    let last_index = buffer
+    // Get the length up to the last non-zero byte
+    buffer
         .iter()
-        .enumerate()
-        .rev()
-        .find_map(|(last_index, number)| if *number == 0 { None } else { Some(last_index) });
+        .rposition(|number| *number != 0)
+        .map_or(0, |last_index| last_index + 1)
+}
+
+#[allow(clippy::module_name_repetitions)]
+/// Save the [Memory] to an uninitialised buffer and return the trimmed, initialised part of it
+pub fn save_to_uninit_buffer(
+    buffer: &mut [MaybeUninit<u8>; MAX_FILE_SIZE],
+    memory: Memory,
+) -> &[u8] {
+    for byte in buffer.iter_mut() {
+        byte.write(0);
+    }
+
+    // Every byte has been initialised
+    let buffer = unsafe { &mut *ptr::from_mut(buffer).cast::<[u8; MAX_FILE_SIZE]>() };
+    save_to_buffer(buffer, memory)
+}
+
+#[cfg(feature = "std")]
+#[allow(clippy::module_name_repetitions)]
+/// Save the [Memory] to the given writer, such as a socket or an in-memory buffer
+///
+/// # Errors
+/// [`io::Error`] - writer error
+pub fn save_to_writer(writer: &mut impl Write, memory: Memory) -> io::Result<()> {
+    // Create a buffer
+    let mut buffer = [0; MAX_FILE_SIZE];
+    // Write the memory to the buffer and get the trimmed slice
+    let buffer_trimmed = save_to_buffer(&mut buffer, memory);
 
-    // Return the trimmed buffer
-    last_index.map_or_else(|| &buffer[..0], |last_index| &buffer[..=last_index])
+    // Write the buffer slice to the writer
+    writer.write_all(buffer_trimmed)
 }
 
 #[cfg(feature = "std")]
@@ -60,13 +111,7 @@ pub fn save_to_buffer(buffer: &mut [u8; MAX_FILE_SIZE], memory: Memory) -> &[u8]
 /// # Errors
 /// [`io::Error`] - file system error
 pub fn save_to_file(file: &mut File, memory: Memory) -> io::Result<()> {
-    // Create a buffer
-    let mut buffer = [0; MAX_FILE_SIZE];
-    // Write the memory to the buffer and get the trimmed slice
-    let buffer_trimmed = save_to_buffer(&mut buffer, memory);
-
-    // Write the buffer slice to the memory
-    file.write_all(buffer_trimmed)
+    save_to_writer(file, memory)
 }
 
 #[cfg(feature = "std")]
@@ -90,7 +135,7 @@ pub fn save_to_path(path: PathBuf, memory: Memory) -> io::Result<()> {
 
 #[cfg(test)]
 mod test {
-    use core::assert_eq;
+    use core::{assert_eq, mem::MaybeUninit};
     use std::{
         env::temp_dir,
         fs::{self, File},
@@ -104,7 +149,7 @@ mod test {
         num3::ThreeDigitNumber,
     };
 
-    use super::save_to_buffer;
+    use super::{save_to_buffer, save_to_uninit_buffer};
 
     #[test]
     fn empty_buffer() {
@@ -123,7 +168,7 @@ mod test {
 
     #[test]
     fn full_buffer() {
-        let memory = [unsafe { ThreeDigitNumber::from_unchecked(1) }; 100];
+        let memory = [ThreeDigitNumber::new::<1>(); 100];
         let mut buffer = [0; MAX_FILE_SIZE];
 
         // Write the memory to the buffer
@@ -149,6 +194,19 @@ mod test {
         );
     }
 
+    #[test]
+    fn uninit_buffer() {
+        let memory = [ThreeDigitNumber::new::<1>(); 100];
+        let mut buffer = [0; MAX_FILE_SIZE];
+        let mut uninit = [MaybeUninit::uninit(); MAX_FILE_SIZE];
+
+        assert_eq!(
+            save_to_uninit_buffer(&mut uninit, memory),
+            save_to_buffer(&mut buffer, memory),
+            "Uninitialised buffer did not save the same as a buffer!"
+        );
+    }
+
     #[test]
     fn empty() {
         // Get a new path in the temp directory
@@ -195,7 +253,7 @@ mod test {
         let path_str = path.to_str().expect("failed to convert path to str");
 
         // Save the memory to the file
-        let memory = [unsafe { ThreeDigitNumber::from_unchecked(1) }; 100];
+        let memory = [ThreeDigitNumber::new::<1>(); 100];
         save(path_str, memory).expect("failed to write to file");
 
         // Open the file
@@ -0,0 +1,144 @@
+extern crate alloc;
+use core::fmt::Write;
+
+use alloc::{string::String, vec::Vec};
+
+use crate::{
+    assembly::{InstructionWithLabel, NumberOrLabel},
+    errors::{ErrorWithLocation, LineNumber},
+    helper::try_collect_into_array,
+    parser::Error,
+};
+
+/// The number of spaces between the longest line of code and the comments
+const COMMENT_GAP: usize = 3;
+
+/// Split a line into its instruction (if it has one) and its comment (including the `#` or `;`)
+fn parse_line(
+    line: &str,
+) -> Result<(Option<InstructionWithLabel<'_, NumberOrLabel<'_>>>, &str), Error> {
+    let (code, comment) = line
+        .find(&['#', ';'][..])
+        .map_or((line, ""), |index| line.split_at(index));
+
+    let words: [Option<&str>; 3] =
+        try_collect_into_array(code.split_whitespace()).map_err(|_| Error::TooManyWords)?;
+    let instruction = match words {
+        [Some(first), second, third] => Some(InstructionWithLabel::parse((first, second, third))?),
+        _ => None,
+    };
+
+    Ok((instruction, comment.trim_end()))
+}
+
+/// Format assembly in the canonical style:
+/// - labels are in a column as wide as the longest label
+/// - instructions are upper case, with their main names (e.g. `STA` is written as `STO`)
+/// - comments after code line up, and other comments and blank lines are kept
+///
+/// ```
+/// # use lminc::formatter::format;
+/// let assembly = "start  lda one # load\n  out\nHLT ; stop\n\n; data\none dat 1";
+/// assert_eq!(
+///     format(assembly).expect("failed to format"),
+///     "\
+/// start LDA one   # load
+///       OUT
+///       HLT       ; stop
+///
+/// ; data
+/// one   DAT 1
+/// "
+/// );
+/// ```
+///
+/// # Errors
+/// Returns a [`parser::Error`](Error) with the [`LineNumber`] of the line that could not be parsed
+pub fn format(text: &str) -> Result<String, ErrorWithLocation<Error, LineNumber>> {
+    let lines = text
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            parse_line(line).map_err(|error| ErrorWithLocation(LineNumber(index + 1), error))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let label_width = lines
+        .iter()
+        .filter_map(|(instruction, _)| instruction.as_ref()?.label)
+        .map(|label| label.chars().count() + 1)
+        .max()
+        .unwrap_or_default();
+
+    // Write the code of each line, to find the longest
+    let lines = lines
+        .into_iter()
+        .map(|(instruction, comment)| {
+            let mut code = String::new();
+            if let Some(instruction) = instruction {
+                // Writing to a String does not fail
+                let _ = write!(
+                    code,
+                    "{:label_width$}{}",
+                    instruction.label.unwrap_or_default(),
+                    instruction.instruction
+                );
+            }
+            (code, comment)
+        })
+        .collect::<Vec<_>>();
+    let comment_column = lines
+        .iter()
+        .map(|(code, _)| code.chars().count())
+        .max()
+        .unwrap_or_default()
+        + COMMENT_GAP;
+
+    let mut formatted = String::new();
+    for (code, comment) in lines {
+        if code.is_empty() || comment.is_empty() {
+            formatted.push_str(&code);
+            formatted.push_str(comment);
+        } else {
+            let _ = write!(formatted, "{code:comment_column$}{comment}");
+        }
+        formatted.push('\n');
+    }
+
+    Ok(formatted)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::parser;
+
+    use super::format;
+
+    #[test]
+    fn canonical() {
+        let fib = include_str!("../examples/fib.txt");
+        assert_eq!(
+            format(fib).expect("failed to format fib.txt"),
+            fib,
+            "Changed formatted assembly!"
+        );
+
+        let formatted =
+            format("IN\n\tsta   99 ;; test: in=1 out=1\n    out").expect("failed to format");
+        assert_eq!(
+            formatted, "IN\nSTO 99   ;; test: in=1 out=1\nOUT\n",
+            "Failed to format assembly without labels!"
+        );
+        assert_eq!(
+            format(&formatted).expect("failed to format formatted assembly"),
+            formatted,
+            "Formatting was not idempotent!"
+        );
+
+        assert_eq!(
+            format("IN\nOUT HLT").map_err(|error| (error.location().0, *error.error())),
+            Err((2, parser::Error::MultipleInstructions)),
+            "Failed to find the line that could not be parsed!"
+        );
+    }
+}
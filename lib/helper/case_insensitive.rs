@@ -62,7 +62,7 @@ impl<'a> From<&'a str> for Str<'a> {
     }
 }
 
-impl<'a> PartialEq for Str<'a> {
+impl PartialEq for Str<'_> {
     fn eq(&self, other: &Self) -> bool {
         if self.0.len() == other.0.len() {
             self.0
@@ -76,9 +76,9 @@ impl<'a> PartialEq for Str<'a> {
     }
 }
 
-impl<'a> Eq for Str<'a> {}
+impl Eq for Str<'_> {}
 
-impl<'a> PartialEq<&str> for Str<'a> {
+impl PartialEq<&str> for Str<'_> {
     fn eq(&self, other: &&str) -> bool {
         if self.0.len() == other.len() {
             self.0
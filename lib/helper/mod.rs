@@ -4,6 +4,7 @@ use core::{fmt, mem::MaybeUninit, ptr::addr_of_mut};
 pub mod case_insensitive;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 /// Errors for [`try_collect_into_array`]
 pub enum CollectIntoArrayError {
     /// The array to collect into was not large enough
@@ -38,10 +39,12 @@ pub fn try_collect_into_array<const N: usize, T>(
     let mut index = 0;
     for item in iterator {
         unsafe {
-            ((*array_ptr)
-                .get_mut(index)
-                .ok_or(CollectIntoArrayError::ArrayNotLargeEnough)? as *mut Option<T>)
-                .write(Some(item));
+            core::ptr::from_mut(
+                (*array_ptr)
+                    .get_mut(index)
+                    .ok_or(CollectIntoArrayError::ArrayNotLargeEnough)?,
+            )
+            .write(Some(item));
         };
         index += 1;
     }
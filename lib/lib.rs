@@ -16,12 +16,29 @@ pub mod assembler;
 pub mod assembly;
 /// Run assembled code
 pub mod computer;
+#[cfg(feature = "diagnostics")]
+/// Render errors with the source they came from
+pub mod diagnostics;
+/// Disassemble memory to assembly
+pub mod disassembler;
 /// Generic additions to errors
 pub mod errors;
 /// Save and load memory
 pub mod file;
+#[cfg(feature = "alloc")]
+/// Format assembly in a canonical style
+pub mod formatter;
 #[doc(hidden)]
 pub mod helper;
+/// Find likely mistakes in assembly
+pub mod lint;
+#[cfg(feature = "alloc")]
+/// Assemble and run programs in one go
+pub mod machine;
+/// Numbers with a fixed number of digits
+pub mod num;
+/// Two digit numbers, for addresses
+pub mod num2;
 /// Three digit numbers
 pub mod num3;
 /// Assemble numbers to memory
@@ -30,3 +47,10 @@ pub mod number_assembler;
 pub mod parser;
 /// Run the computer and deal with input and output
 pub mod runner;
+
+pub use errors::Error;
+#[cfg(feature = "alloc")]
+pub use machine::Machine;
+#[cfg(feature = "wasm")]
+/// Bindings for JavaScript, through wasm-bindgen
+pub mod wasm;
@@ -0,0 +1,147 @@
+#[cfg(feature = "alloc")]
+extern crate alloc;
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use alloc::{collections::BTreeSet, vec::Vec};
+
+use crate::errors::{self, Code, ErrorCode, LineNumber};
+#[cfg(feature = "alloc")]
+use crate::{
+    assembly::NumberOrLabel,
+    parser::{self, Parser},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Assembly that is valid, but is probably a mistake
+pub enum Lint<'a> {
+    /// A label is defined but never used
+    UnusedLabel(&'a str),
+    /// A label is defined more than once, so only the first is used
+    DuplicateLabel(&'a str),
+}
+
+impl fmt::Display for Lint<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnusedLabel(label) => write!(f, "Label '{label}' is never used!"),
+            Self::DuplicateLabel(label) => write!(
+                f,
+                "Label '{label}' is defined more than once, only the first is used!"
+            ),
+        }
+    }
+}
+
+impl Code for Lint<'_> {
+    fn code(&self) -> ErrorCode {
+        match self {
+            Self::UnusedLabel(_) => ErrorCode(1001),
+            Self::DuplicateLabel(_) => ErrorCode(1002),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Lint<'_> {}
+
+pub type LintWithLineNumber<'a> = errors::ErrorWithLocation<Lint<'a>, LineNumber>;
+
+#[cfg(feature = "alloc")]
+/// Find the [Lint]s in assembly, in the order of their lines
+///
+/// ```
+/// # use lminc::{errors::{ErrorWithLocation, LineNumber}, lint::{lint, Lint}};
+/// let lints = lint("start IN\nloop OUT\nBR start\nstart HLT")?;
+/// assert_eq!(
+///     lints,
+///     [
+///         ErrorWithLocation(LineNumber(2), Lint::UnusedLabel("loop")),
+///         ErrorWithLocation(LineNumber(4), Lint::DuplicateLabel("start")),
+///     ]
+/// );
+/// # Ok::<(), lminc::parser::ErrorWithLocation<LineNumber>>(())
+/// ```
+///
+/// # Errors
+/// See [`parser::Error`]
+pub fn lint(
+    text: &str,
+) -> Result<Vec<LintWithLineNumber<'_>>, parser::ErrorWithLocation<LineNumber>> {
+    let mut parser = Parser::new();
+    let mut lines = Vec::new();
+
+    for (index, line) in text.lines().enumerate() {
+        let address = parser.len();
+        parser
+            .parse_line(line)
+            .map_err(|error| error.map_location(|_| LineNumber(index + 1)))?;
+
+        // Only lines with an instruction add to the parser
+        if parser.len() != address {
+            lines.push(LineNumber(index + 1));
+        }
+    }
+
+    // The parser is copied, so that the labels borrow from the text instead of the parser
+    let used = parser
+        .into_iter()
+        .filter_map(|instruction| match instruction.instruction.data() {
+            Some(NumberOrLabel::Label(label)) => Some(*label),
+            _ => None,
+        })
+        .collect::<BTreeSet<_>>();
+
+    let mut defined = BTreeSet::new();
+    Ok(parser
+        .into_iter()
+        .zip(lines)
+        .filter_map(|(instruction, line)| {
+            let label = instruction.label?;
+            let lint = if !defined.insert(label) {
+                Lint::DuplicateLabel(label)
+            } else if !used.contains(label) {
+                Lint::UnusedLabel(label)
+            } else {
+                return None;
+            };
+
+            Some(errors::ErrorWithLocation(line, lint))
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::errors::{ErrorWithLocation, LineNumber};
+
+    use super::{lint, Lint};
+
+    #[test]
+    fn lints() {
+        let fib = include_str!("../examples/fib.txt");
+        assert_eq!(
+            lint(fib).expect("failed to lint fib.txt"),
+            [ErrorWithLocation(LineNumber(12), Lint::UnusedLabel("end"))],
+            "Failed to find the unused label in fib.txt!"
+        );
+
+        assert_eq!(
+            lint("# start\n\nstart IN\n  BRZ end\nend HLT\nend HLT\nspare DAT 0")
+                .expect("failed to lint"),
+            [
+                ErrorWithLocation(LineNumber(3), Lint::UnusedLabel("start")),
+                ErrorWithLocation(LineNumber(6), Lint::DuplicateLabel("end")),
+                ErrorWithLocation(LineNumber(7), Lint::UnusedLabel("spare")),
+            ],
+            "Failed to find the lints!"
+        );
+
+        assert_eq!(
+            lint("IN\nOUT HLT").map_err(|error| error.location().0),
+            Err(2),
+            "Failed to find the line that could not be parsed!"
+        );
+    }
+}
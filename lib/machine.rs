@@ -0,0 +1,337 @@
+extern crate alloc;
+
+#[cfg(feature = "extended")]
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{
+    assembler::{self, assemble_from_text},
+    computer::{self, Memory, State},
+    errors::{Code, ErrorCode, LineNumber},
+    num3::ThreeDigitNumber,
+    number_assembler::{self, NumberAssembler},
+    parser,
+    runner::batch::{self, Batch, Stop},
+};
+
+/// The default maximum number of cycles for a [Machine]
+pub const DEFAULT_MAX_CYCLES: u32 = 100_000;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// The error for [Machine]
+pub enum Error {
+    /// The assembly could not be parsed, see [`parser::Error`]
+    ParseError(parser::ErrorWithLocation<LineNumber>),
+    /// The assembly could not be assembled, see [`assembler::Error`]
+    AssemblerError(assembler::ErrorWithInstructionNumber),
+    /// The numbers could not be assembled, see [`number_assembler::FromNumbersError`]
+    NumberAssemblerError(number_assembler::ErrorWithLineNumber),
+    /// An input was too large (> 999)
+    InputTooLarge(u16),
+    #[cfg(feature = "extended")]
+    /// A char input was not a valid input character
+    InvalidCharInput(char),
+    /// The computer requested more inputs than were given
+    RunOutOfInputs,
+    /// The number of cycles reached the maximum
+    RunOutOfCycles,
+    /// The number of outputs went over the maximum
+    TooManyOutputs,
+    /// The computer rejected an input or output that it requested, see [`computer::Error`]
+    ComputerError(computer::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ParseError(error) => fmt::Display::fmt(error, f),
+            Self::AssemblerError(error) => fmt::Display::fmt(error, f),
+            Self::NumberAssemblerError(error) => fmt::Display::fmt(error, f),
+            Self::InputTooLarge(input) => write!(f, "Input is too large (> 999): {input}!"),
+            #[cfg(feature = "extended")]
+            Self::InvalidCharInput(character) => {
+                write!(f, "Invalid char input: {character:?}!")
+            }
+            Self::RunOutOfInputs => write!(f, "Requested more inputs than given!"),
+            Self::RunOutOfCycles => write!(f, "Ran out of cycles!"),
+            Self::TooManyOutputs => write!(f, "Gave too many outputs!"),
+            Self::ComputerError(error) => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+impl Code for Error {
+    fn code(&self) -> ErrorCode {
+        match self {
+            Self::ParseError(error) => error.code(),
+            Self::AssemblerError(error) => error.code(),
+            Self::NumberAssemblerError(error) => error.code(),
+            Self::InputTooLarge(_) => ErrorCode(421),
+            #[cfg(feature = "extended")]
+            Self::InvalidCharInput(_) => ErrorCode(422),
+            Self::RunOutOfInputs => ErrorCode(423),
+            Self::RunOutOfCycles => ErrorCode(424),
+            Self::TooManyOutputs => ErrorCode(425),
+            Self::ComputerError(error) => error.code(),
+        }
+    }
+
+    fn fmt_message(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ParseError(error) => error.fmt_message(f),
+            Self::AssemblerError(error) => error.fmt_message(f),
+            Self::NumberAssemblerError(error) => error.fmt_message(f),
+            _ => fmt::Display::fmt(self, f),
+        }
+    }
+
+    fn location(&self) -> Option<&dyn fmt::Display> {
+        match self {
+            Self::ParseError(error) => Code::location(error),
+            Self::AssemblerError(error) => Code::location(error),
+            Self::NumberAssemblerError(error) => Code::location(error),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::ParseError(error) => Some(error),
+            Self::AssemblerError(error) => Some(error),
+            Self::NumberAssemblerError(error) => Some(error),
+            Self::ComputerError(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<parser::ErrorWithLocation<LineNumber>> for Error {
+    fn from(value: parser::ErrorWithLocation<LineNumber>) -> Self {
+        Self::ParseError(value)
+    }
+}
+
+impl From<assembler::ErrorWithInstructionNumber> for Error {
+    fn from(value: assembler::ErrorWithInstructionNumber) -> Self {
+        Self::AssemblerError(value)
+    }
+}
+
+impl From<number_assembler::ErrorWithLineNumber> for Error {
+    fn from(value: number_assembler::ErrorWithLineNumber) -> Self {
+        Self::NumberAssemblerError(value)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// The result of running a [Machine]
+pub struct Output {
+    pub outputs: Vec<ThreeDigitNumber>,
+    #[cfg(feature = "extended")]
+    pub char_outputs: String,
+    /// The state the computer stopped in
+    pub state: State,
+    pub cycles: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Assemble and run a program in one go
+///
+/// ```
+/// # use lminc::Machine;
+/// let output = Machine::from_assembly("IN\nSTO 99\nIN\nADD 99\nOUT\nHLT")?
+///     .with_inputs([5, 6])
+///     .run()?;
+/// assert_eq!(u16::from(output.outputs[0]), 11);
+/// # Ok::<(), lminc::machine::Error>(())
+/// ```
+pub struct Machine {
+    memory: Memory,
+    inputs: Vec<u16>,
+    #[cfg(feature = "extended")]
+    char_inputs: String,
+    max_cycles: u32,
+    max_outputs: Option<u32>,
+}
+
+impl Machine {
+    #[must_use]
+    /// Create a new [Machine] from [Memory]
+    pub const fn new(memory: Memory) -> Self {
+        Self {
+            memory,
+            inputs: Vec::new(),
+            #[cfg(feature = "extended")]
+            char_inputs: String::new(),
+            max_cycles: DEFAULT_MAX_CYCLES,
+            max_outputs: None,
+        }
+    }
+
+    /// Create a new [Machine] from assembly
+    ///
+    /// # Errors
+    /// See [`parser::Error`] and [`assembler::Error`]
+    pub fn from_assembly(text: &str) -> Result<Self, Error> {
+        Ok(Self::new(assemble_from_text(text)??))
+    }
+
+    /// Create a new [Machine] from numbers, one per line
+    ///
+    /// # Errors
+    /// See [`number_assembler::FromNumbersError`]
+    pub fn from_numbers(text: &str) -> Result<Self, Error> {
+        Ok(Self::new(NumberAssembler::assemble_from_text(text)?))
+    }
+
+    #[must_use]
+    /// Give the computer these inputs, in order
+    pub fn with_inputs(mut self, inputs: impl IntoIterator<Item = u16>) -> Self {
+        self.inputs.extend(inputs);
+        self
+    }
+
+    #[cfg(feature = "extended")]
+    #[must_use]
+    /// Give the computer these char inputs, in order
+    pub fn with_char_inputs(mut self, inputs: &str) -> Self {
+        self.char_inputs.push_str(inputs);
+        self
+    }
+
+    #[must_use]
+    /// Stop the computer after `max_cycles` cycles, by default [`DEFAULT_MAX_CYCLES`]
+    pub const fn with_max_cycles(mut self, max_cycles: u32) -> Self {
+        self.max_cycles = max_cycles;
+        self
+    }
+
+    #[must_use]
+    /// Stop the computer when it gives more than `max_outputs` outputs
+    ///  (including char outputs), by default there is no limit
+    pub const fn with_max_outputs(mut self, max_outputs: u32) -> Self {
+        self.max_outputs = Some(max_outputs);
+        self
+    }
+
+    #[must_use]
+    /// Get the memory that the computer starts with
+    pub const fn memory(&self) -> &Memory {
+        &self.memory
+    }
+
+    /// Run the computer until it stops, returning its outputs
+    ///
+    /// # Errors
+    /// See [Error]
+    pub fn run(&self) -> Result<Output, Error> {
+        let inputs = batch::Inputs {
+            inputs: self
+                .inputs
+                .iter()
+                .map(|input| {
+                    ThreeDigitNumber::try_from(*input).map_err(|_| Error::InputTooLarge(*input))
+                })
+                .collect::<Result<_, _>>()?,
+            #[cfg(feature = "extended")]
+            char_inputs: self
+                .char_inputs
+                .chars()
+                .map(|character| {
+                    ThreeDigitNumber::try_from(character)
+                        .ok()
+                        .ok_or(Error::InvalidCharInput(character))
+                })
+                .collect::<Result<_, _>>()?,
+        };
+
+        let mut batch = Batch::new(self.memory, self.max_cycles);
+        if let Some(max_outputs) = self.max_outputs {
+            batch = batch.with_max_outputs(max_outputs);
+        }
+        let run = batch.run_one(&inputs);
+
+        let state = match run.stop {
+            Stop::State(state) => state,
+            Stop::RunOutOfInputs => return Err(Error::RunOutOfInputs),
+            Stop::RunOutOfCycles => return Err(Error::RunOutOfCycles),
+            Stop::TooManyOutputs => return Err(Error::TooManyOutputs),
+            Stop::InvalidIo(error) => return Err(Error::ComputerError(error)),
+        };
+
+        Ok(Output {
+            outputs: run.outputs,
+            #[cfg(feature = "extended")]
+            char_outputs: run
+                .char_outputs
+                .iter()
+                .map(|number| {
+                    char::from_u32(u32::from(*number)).unwrap_or(char::REPLACEMENT_CHARACTER)
+                })
+                .collect(),
+            state,
+            cycles: run.cycles,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::computer::State;
+
+    use super::{Error, Machine};
+
+    #[test]
+    fn run() {
+        let machine = Machine::from_assembly("IN\nSTO 99\nIN\nADD 99\nOUT\nHLT")
+            .expect("failed to create a machine");
+
+        let output = machine
+            .clone()
+            .with_inputs([5, 6])
+            .run()
+            .expect("failed to run");
+        assert_eq!(output.state, State::Halted, "The computer did not halt!");
+        assert_eq!(
+            output
+                .outputs
+                .iter()
+                .map(|output| u16::from(*output))
+                .collect::<Vec<_>>(),
+            [11],
+            "Gave the wrong outputs!"
+        );
+
+        assert_eq!(
+            machine.clone().with_inputs([5]).run(),
+            Err(Error::RunOutOfInputs),
+            "Did not run out of inputs!"
+        );
+        assert_eq!(
+            machine.with_inputs([5, 1000]).run(),
+            Err(Error::InputTooLarge(1000)),
+            "Accepted an input that was too large!"
+        );
+
+        assert_eq!(
+            Machine::from_numbers("600")
+                .expect("failed to create a machine")
+                .with_max_cycles(10)
+                .run(),
+            Err(Error::RunOutOfCycles),
+            "Did not run out of cycles!"
+        );
+        assert_eq!(
+            Machine::from_numbers("902\n600")
+                .expect("failed to create a machine")
+                .with_max_outputs(5)
+                .run(),
+            Err(Error::TooManyOutputs),
+            "Did not stop after too many outputs!"
+        );
+    }
+}
@@ -0,0 +1,689 @@
+use core::{
+    fmt::{self, Binary, Display, LowerHex, Octal, UpperHex},
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Rem, RemAssign, Sub, SubAssign},
+};
+
+#[cfg(feature = "rand")]
+use rand::{
+    distr::{
+        uniform::{self, SampleBorrow, SampleUniform, UniformInt, UniformSampler},
+        Distribution, StandardUniform,
+    },
+    Rng,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd, Ord, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "u16", into = "u16")
+)]
+// Deserialising goes through `TryFrom<u16>`, so it cannot make an invalid number
+#[cfg_attr(feature = "serde", allow(clippy::unsafe_derive_deserialize))]
+#[repr(transparent)]
+/// A number with a fixed number of digits (`DIGITS`, from 1 to 4), like
+///  [`TwoDigitNumber`](crate::num2::TwoDigitNumber) (0..=99)
+///  and [`ThreeDigitNumber`](crate::num3::ThreeDigitNumber) (0..=999)
+///
+/// With the `serde` feature, it is serialised as an integer,
+///  and deserialising fails if it has too many digits
+pub struct FixedDigitNumber<const DIGITS: u8>(u16);
+
+impl<const DIGITS: u8> FixedDigitNumber<DIGITS> {
+    /// One more than the largest number, where arithmetic wraps around
+    const MODULUS: u16 = {
+        assert!(
+            DIGITS >= 1 && DIGITS <= 4,
+            "fixed digit numbers must have from 1 to 4 digits"
+        );
+        10_u16.pow(DIGITS as u32)
+    };
+
+    pub const ZERO: Self = Self(0);
+    pub const MAX: Self = Self(Self::MODULUS - 1);
+
+    #[must_use]
+    /// Makes a number from a constant, failing to compile if it has too many digits.
+    /// See also [`three_digit!`](crate::three_digit)
+    ///
+    /// ```
+    /// # use lminc::num3::ThreeDigitNumber;
+    /// const INPUT: ThreeDigitNumber = ThreeDigitNumber::new::<605>();
+    /// assert_eq!(u16::from(INPUT), 605);
+    /// ```
+    ///
+    /// ```compile_fail
+    /// # use lminc::num3::ThreeDigitNumber;
+    /// const INPUT: ThreeDigitNumber = ThreeDigitNumber::new::<1000>();
+    /// ```
+    pub const fn new<const VALUE: u16>() -> Self {
+        const {
+            assert!(VALUE < Self::MODULUS, "the value has too many digits");
+        }
+        Self(VALUE)
+    }
+
+    #[must_use]
+    /// Makes a number from a [`u16`] without performing any checks
+    ///
+    /// # Safety
+    /// The caller must make sure that `value` is strictly less than 10 to the power of `DIGITS`
+    ///  (for three digits, within `(0..=999)`)
+    pub const unsafe fn from_unchecked(value: u16) -> Self {
+        Self(value)
+    }
+
+    #[must_use]
+    /// Gets the next number, returning `None` if this is the last ([`Self::MAX`])
+    pub const fn checked_next(self) -> Option<Self> {
+        if self.0 < Self::MAX.0 {
+            Some(Self(self.0 + 1))
+        } else {
+            None
+        }
+    }
+
+    #[must_use]
+    /// Interprets the number as ten's complement, where the top half are negative
+    ///  (for three digits, 500..=999 are -500..=-1)
+    pub const fn as_signed(self) -> i16 {
+        // The number and modulus are at most 10000, so they fit in an i16
+        #[allow(clippy::cast_possible_wrap)]
+        let (value, modulus) = (self.0 as i16, Self::MODULUS as i16);
+        if value >= modulus / 2 {
+            value - modulus
+        } else {
+            value
+        }
+    }
+
+    #[must_use]
+    /// Makes a number from its ten's complement interpretation, returning `None` if `value`
+    ///  is out of range (for three digits, not within `(-500..=499)`)
+    pub const fn from_signed(value: i16) -> Option<Self> {
+        // The modulus is at most 10000, so it fits in an i16
+        #[allow(clippy::cast_possible_wrap)]
+        let modulus = Self::MODULUS as i16;
+
+        // The value is within (0..modulus) after adding the modulus, so it fits in a u16
+        #[allow(clippy::cast_sign_loss)]
+        if value < -modulus / 2 || value >= modulus / 2 {
+            None
+        } else if value < 0 {
+            Some(Self((value + modulus) as u16))
+        } else {
+            Some(Self(value as u16))
+        }
+    }
+
+    #[must_use]
+    /// Gets a wrapper that displays the number as ten's complement, see [`Self::as_signed`]
+    pub const fn signed(self) -> Signed<DIGITS> {
+        Signed(self)
+    }
+
+    #[must_use]
+    /// Adds `rhs`, returning `None` if the result has too many digits
+    pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+        match self.overflowing_add(rhs) {
+            (result, false) => Some(result),
+            (_, true) => None,
+        }
+    }
+
+    #[must_use]
+    /// Adds `rhs`, wrapping around (for three digits, at 1000), and returns if it wrapped
+    pub const fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+        let result = self.0 + rhs.0;
+        (Self(result % Self::MODULUS), result >= Self::MODULUS)
+    }
+
+    #[must_use]
+    /// Adds `rhs`, wrapping around (for three digits, at 1000), and returns the carry.
+    /// This mirrors [`Sub`], which returns the borrow
+    pub const fn add_carry(self, rhs: Self) -> (Self, bool) {
+        self.overflowing_add(rhs)
+    }
+
+    #[must_use]
+    /// Subtracts `rhs`, returning `None` if the result is negative
+    pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
+        match self.overflowing_sub(rhs) {
+            (result, false) => Some(result),
+            (_, true) => None,
+        }
+    }
+
+    #[must_use]
+    /// Subtracts `rhs`, wrapping around at 0, and returns if it wrapped.
+    /// This is the same as [`Sub`]
+    pub const fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+        (
+            Self((self.0 + Self::MODULUS - rhs.0) % Self::MODULUS),
+            self.0 < rhs.0,
+        )
+    }
+
+    #[must_use]
+    /// Multiplies by `rhs`, returning `None` if the result has too many digits
+    pub const fn checked_mul(self, rhs: Self) -> Option<Self> {
+        match self.overflowing_mul(rhs) {
+            (result, false) => Some(result),
+            (_, true) => None,
+        }
+    }
+
+    #[must_use]
+    /// Multiplies by `rhs`, wrapping around (for three digits, at 1000), and returns if it wrapped
+    pub const fn overflowing_mul(self, rhs: Self) -> (Self, bool) {
+        // 9999 * 9999 fits in a u32
+        let result = self.0 as u32 * rhs.0 as u32;
+        // The remainder is less than the modulus, so it fits in a u16
+        #[allow(clippy::cast_possible_truncation)]
+        (
+            Self((result % Self::MODULUS as u32) as u16),
+            result >= Self::MODULUS as u32,
+        )
+    }
+
+    #[must_use]
+    /// Divides by `rhs`, returning `None` if `rhs` is zero
+    pub const fn checked_div(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_div(rhs.0) {
+            Some(result) => Some(Self(result)),
+            None => None,
+        }
+    }
+
+    #[must_use]
+    /// Gets the remainder of dividing by `rhs`, returning `None` if `rhs` is zero
+    pub const fn checked_rem(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_rem(rhs.0) {
+            Some(result) => Some(Self(result)),
+            None => None,
+        }
+    }
+}
+
+impl FixedDigitNumber<3> {
+    #[must_use]
+    /// Checks if the number also a valid two digit number
+    pub const fn is_2_digit(self) -> bool {
+        self.0 < 100
+    }
+
+    #[must_use]
+    /// Gets the hundreds digit (the first), which is the op code of an instruction
+    pub const fn hundreds(self) -> u8 {
+        // The digit is < 10, so it fits in a u8
+        #[allow(clippy::cast_possible_truncation)]
+        let digit = (self.0 / 100) as u8;
+        digit
+    }
+
+    #[must_use]
+    /// Gets the tens digit (the second)
+    pub const fn tens(self) -> u8 {
+        // The digit is < 10, so it fits in a u8
+        #[allow(clippy::cast_possible_truncation)]
+        let digit = (self.0 / 10 % 10) as u8;
+        digit
+    }
+
+    #[must_use]
+    /// Gets the units digit (the last)
+    pub const fn units(self) -> u8 {
+        // The digit is < 10, so it fits in a u8
+        #[allow(clippy::cast_possible_truncation)]
+        let digit = (self.0 % 10) as u8;
+        digit
+    }
+
+    #[must_use]
+    /// Makes a number from its hundreds, tens and units digits,
+    ///  returning `None` if any of them are not digits (> 9)
+    ///
+    /// ```
+    /// # use lminc::num3::ThreeDigitNumber;
+    /// let number = ThreeDigitNumber::from_digits(6, 0, 5).expect("not digits");
+    /// assert_eq!(number, ThreeDigitNumber::new::<605>());
+    /// assert_eq!((number.hundreds(), number.tens(), number.units()), (6, 0, 5));
+    /// ```
+    pub const fn from_digits(hundreds: u8, tens: u8, units: u8) -> Option<Self> {
+        if hundreds > 9 || tens > 9 || units > 9 {
+            return None;
+        }
+
+        Some(Self(
+            hundreds as u16 * 100 + tens as u16 * 10 + units as u16,
+        ))
+    }
+
+    #[must_use]
+    /// Splits the number into its first digit (the op code) and its last two digits
+    ///  (the address), the inverse of [`Self::compose`]
+    pub const fn split_op(self) -> (u8, FixedDigitNumber<2>) {
+        (self.hundreds(), FixedDigitNumber(self.0 % 100))
+    }
+
+    #[must_use]
+    /// Makes a number from an op code (the first digit) and an address (the last two digits),
+    ///  returning `None` if the op code is not a digit (> 9).
+    /// This is the inverse of [`Self::split_op`]
+    ///
+    /// ```
+    /// # use lminc::{num2::TwoDigitNumber, num3::ThreeDigitNumber};
+    /// let number = ThreeDigitNumber::compose(3, TwoDigitNumber::new::<99>()).expect("not a digit");
+    /// assert_eq!(number, ThreeDigitNumber::new::<399>());
+    /// assert_eq!(number.split_op(), (3, TwoDigitNumber::new::<99>()));
+    /// ```
+    pub const fn compose(op_code: u8, address: FixedDigitNumber<2>) -> Option<Self> {
+        if op_code > 9 {
+            return None;
+        }
+
+        Some(Self(op_code as u16 * 100 + address.0))
+    }
+}
+
+// Formatting impls
+
+macro_rules! fmt_impl {
+    ( $trait:path, $fmt:path ) => {
+        impl<const DIGITS: u8> $trait for FixedDigitNumber<DIGITS> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                $fmt(&self.0, f)
+            }
+        }
+    };
+}
+
+impl<const DIGITS: u8> Display for FixedDigitNumber<DIGITS> {
+    /// Formats the number, or with the alternate flag (`{:#}`),
+    ///  formats it padded with zeros to `DIGITS` digits
+    ///
+    /// ```
+    /// # use lminc::num3::ThreeDigitNumber;
+    /// let number = ThreeDigitNumber::new::<5>();
+    /// assert_eq!(format!("{number} {number:#}"), "5 005");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            write!(f, "{:0width$}", self.0, width = usize::from(DIGITS))
+        } else {
+            Display::fmt(&self.0, f)
+        }
+    }
+}
+
+// The alternate flag adds a prefix for these, like for integers
+fmt_impl!(Octal, Octal::fmt);
+fmt_impl!(LowerHex, LowerHex::fmt);
+fmt_impl!(UpperHex, UpperHex::fmt);
+fmt_impl!(Binary, Binary::fmt);
+
+#[cfg(feature = "defmt")]
+impl<const DIGITS: u8> defmt::Format for FixedDigitNumber<DIGITS> {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(f, "{=u16}", self.0);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// Displays a [`FixedDigitNumber`] as ten's complement, so 995 is shown as -5.
+/// See [`FixedDigitNumber::signed`]
+pub struct Signed<const DIGITS: u8>(pub FixedDigitNumber<DIGITS>);
+
+impl<const DIGITS: u8> Display for Signed<DIGITS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0.as_signed(), f)
+    }
+}
+
+// Random impls
+
+#[cfg(feature = "rand")]
+impl<const DIGITS: u8> Distribution<FixedDigitNumber<DIGITS>> for StandardUniform {
+    /// Samples a number uniformly from all the numbers with `DIGITS` digits
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> FixedDigitNumber<DIGITS> {
+        FixedDigitNumber(rng.random_range(0..FixedDigitNumber::<DIGITS>::MODULUS))
+    }
+}
+
+#[cfg(feature = "rand")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Samples [`FixedDigitNumber`]s uniformly from a range, see [`SampleUniform`]
+pub struct UniformFixedDigitNumber<const DIGITS: u8>(UniformInt<u16>);
+
+#[cfg(feature = "rand")]
+impl<const DIGITS: u8> UniformSampler for UniformFixedDigitNumber<DIGITS> {
+    type X = FixedDigitNumber<DIGITS>;
+
+    fn new<B1, B2>(low: B1, high: B2) -> Result<Self, uniform::Error>
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        UniformInt::new(low.borrow().0, high.borrow().0).map(Self)
+    }
+
+    fn new_inclusive<B1, B2>(low: B1, high: B2) -> Result<Self, uniform::Error>
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        UniformInt::new_inclusive(low.borrow().0, high.borrow().0).map(Self)
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X {
+        // The bounds both have at most `DIGITS` digits, so the sample does too
+        FixedDigitNumber(self.0.sample(rng))
+    }
+}
+
+#[cfg(feature = "rand")]
+impl<const DIGITS: u8> SampleUniform for FixedDigitNumber<DIGITS> {
+    type Sampler = UniformFixedDigitNumber<DIGITS>;
+}
+
+// Operation impls
+
+impl<const DIGITS: u8> Add for FixedDigitNumber<DIGITS> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.add_carry(rhs).0
+    }
+}
+
+impl<const DIGITS: u8> AddAssign for FixedDigitNumber<DIGITS> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = self.add_carry(rhs).0;
+    }
+}
+
+macro_rules! primitive_add_impl {
+    ( $( $type:ty ),* ) => {
+        $(
+            impl<const DIGITS: u8> Add<$type> for FixedDigitNumber<DIGITS> {
+                type Output = Self;
+
+                /// Adds a primitive, wrapping around at the largest number
+                fn add(self, rhs: $type) -> Self::Output {
+                    let result = (u32::from(self.0) + u32::from(rhs)) % u32::from(Self::MODULUS);
+                    // The result is less than `MODULUS`, so it fits in a u16
+                    #[allow(clippy::cast_possible_truncation)]
+                    Self(result as u16)
+                }
+            }
+
+            impl<const DIGITS: u8> Add<FixedDigitNumber<DIGITS>> for $type {
+                type Output = FixedDigitNumber<DIGITS>;
+
+                /// Adds to a primitive, wrapping around at the largest number
+                fn add(self, rhs: FixedDigitNumber<DIGITS>) -> Self::Output {
+                    rhs + self
+                }
+            }
+
+            impl<const DIGITS: u8> AddAssign<$type> for FixedDigitNumber<DIGITS> {
+                fn add_assign(&mut self, rhs: $type) {
+                    *self = *self + rhs;
+                }
+            }
+        )*
+    };
+}
+
+primitive_add_impl!(u8, u16);
+
+impl<const DIGITS: u8> Sub for FixedDigitNumber<DIGITS> {
+    type Output = (Self, bool);
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.overflowing_sub(rhs)
+    }
+}
+
+impl<const DIGITS: u8> SubAssign for FixedDigitNumber<DIGITS> {
+    /// Subtracts `rhs`, wrapping around at 0
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = self.overflowing_sub(rhs).0;
+    }
+}
+
+impl<const DIGITS: u8> Mul for FixedDigitNumber<DIGITS> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.overflowing_mul(rhs).0
+    }
+}
+
+impl<const DIGITS: u8> MulAssign for FixedDigitNumber<DIGITS> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<const DIGITS: u8> Div for FixedDigitNumber<DIGITS> {
+    type Output = Self;
+
+    /// # Panics
+    /// If `rhs` is zero
+    fn div(self, rhs: Self) -> Self::Output {
+        Self(self.0 / rhs.0)
+    }
+}
+
+impl<const DIGITS: u8> DivAssign for FixedDigitNumber<DIGITS> {
+    /// # Panics
+    /// If `rhs` is zero
+    fn div_assign(&mut self, rhs: Self) {
+        self.0 /= rhs.0;
+    }
+}
+
+impl<const DIGITS: u8> Rem for FixedDigitNumber<DIGITS> {
+    type Output = Self;
+
+    /// # Panics
+    /// If `rhs` is zero
+    fn rem(self, rhs: Self) -> Self::Output {
+        Self(self.0 % rhs.0)
+    }
+}
+
+impl<const DIGITS: u8> RemAssign for FixedDigitNumber<DIGITS> {
+    /// # Panics
+    /// If `rhs` is zero
+    fn rem_assign(&mut self, rhs: Self) {
+        self.0 %= rhs.0;
+    }
+}
+
+// From impls
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Errors for converting to a [`FixedDigitNumber`] with `DIGITS` digits
+pub enum TryFromError<const DIGITS: u8> {
+    TooLarge,
+    Negative,
+}
+
+impl<const DIGITS: u8> fmt::Display for TryFromError<DIGITS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match DIGITS {
+            1 => "one",
+            2 => "two",
+            3 => "three",
+            _ => "four",
+        };
+
+        match self {
+            Self::TooLarge => write!(
+                f,
+                "Number is too large to be converted to a {name} digit number (> {})!",
+                FixedDigitNumber::<DIGITS>::MAX
+            ),
+            Self::Negative => write!(
+                f,
+                "Number is negative and cannot be converted to a {name} digit number (< 0)!"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<const DIGITS: u8> std::error::Error for TryFromError<DIGITS> {}
+
+impl<const DIGITS: u8> TryFrom<u16> for FixedDigitNumber<DIGITS> {
+    type Error = TryFromError<DIGITS>;
+
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        if value < Self::MODULUS {
+            Ok(Self(value))
+        } else {
+            Err(TryFromError::TooLarge)
+        }
+    }
+}
+
+macro_rules! try_from_impl {
+    ( $type:ty ) => {
+        impl<const DIGITS: u8> TryFrom<$type> for FixedDigitNumber<DIGITS> {
+            type Error = TryFromError<DIGITS>;
+
+            fn try_from(value: $type) -> Result<Self, Self::Error> {
+                #[allow(unused_comparisons)]
+                if value < 0 {
+                    return Err(TryFromError::Negative);
+                }
+
+                u16::try_from(value)
+                    .map_err(|_| TryFromError::TooLarge)
+                    .and_then(Self::try_from)
+            }
+        }
+    };
+}
+
+try_from_impl!(u32);
+try_from_impl!(usize);
+try_from_impl!(i32);
+
+impl<const DIGITS: u8> TryFrom<char> for FixedDigitNumber<DIGITS> {
+    type Error = TryFromError<DIGITS>;
+
+    /// Converts a character to its code point, if it has at most `DIGITS` digits
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        Self::try_from(u32::from(value))
+    }
+}
+
+// Numbers with one or two digits do not fit every u8, and those with more digits do
+macro_rules! u8_impl {
+    ( try $( $digits:literal ),* ; from $( $from_digits:literal ),* ) => {
+        $(
+            impl TryFrom<u8> for FixedDigitNumber<$digits> {
+                type Error = TryFromError<$digits>;
+
+                fn try_from(value: u8) -> Result<Self, Self::Error> {
+                    Self::try_from(u16::from(value))
+                }
+            }
+
+            impl From<FixedDigitNumber<$digits>> for u8 {
+                #[allow(clippy::cast_possible_truncation)]
+                fn from(value: FixedDigitNumber<$digits>) -> Self {
+                    // The number is at most 99, so it fits in a u8
+                    value.0 as Self
+                }
+            }
+        )*
+        $(
+            impl From<u8> for FixedDigitNumber<$from_digits> {
+                fn from(value: u8) -> Self {
+                    Self(value.into())
+                }
+            }
+        )*
+    };
+}
+
+u8_impl!(try 1, 2; from 3, 4);
+
+// Numbers with fewer digits always fit in numbers with more digits
+macro_rules! widen_impl {
+    ( $( $from:literal => $to:literal ),* ) => {
+        $(
+            impl From<FixedDigitNumber<$from>> for FixedDigitNumber<$to> {
+                fn from(value: FixedDigitNumber<$from>) -> Self {
+                    Self(value.0)
+                }
+            }
+
+            impl TryFrom<FixedDigitNumber<$to>> for FixedDigitNumber<$from> {
+                type Error = TryFromError<$from>;
+
+                fn try_from(value: FixedDigitNumber<$to>) -> Result<Self, Self::Error> {
+                    Self::try_from(value.0)
+                }
+            }
+        )*
+    };
+}
+
+widen_impl!(1 => 2, 1 => 3, 1 => 4, 2 => 3, 2 => 4, 3 => 4);
+
+// Into impls
+
+macro_rules! into_impl {
+    ( $type:ty ) => {
+        impl<const DIGITS: u8> From<FixedDigitNumber<DIGITS>> for $type {
+            fn from(value: FixedDigitNumber<DIGITS>) -> Self {
+                value.0.into()
+            }
+        }
+    };
+}
+
+into_impl!(u16);
+into_impl!(u32);
+into_impl!(usize);
+into_impl!(i32);
+
+#[cfg(test)]
+mod test {
+    use super::FixedDigitNumber;
+
+    #[test]
+    fn four_digits() {
+        let number =
+            |value| FixedDigitNumber::<4>::try_from(value).expect("failed to create the number");
+
+        assert_eq!(
+            FixedDigitNumber::<4>::MAX,
+            number(9999_u16),
+            "Got the wrong maximum!"
+        );
+        assert_eq!(
+            number(9999_u16) + number(2_u16),
+            number(1_u16),
+            "Failed to wrap addition!"
+        );
+        assert_eq!(
+            number(5000_u16).as_signed(),
+            -5000,
+            "Failed to read the smallest number!"
+        );
+        assert_eq!(
+            FixedDigitNumber::<4>::from(FixedDigitNumber::<3>::MAX),
+            number(999_u16),
+            "Failed to widen a three digit number!"
+        );
+    }
+}
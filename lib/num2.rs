@@ -0,0 +1,45 @@
+use crate::num::{self, FixedDigitNumber};
+
+/// A two digit number (0..=99), used for memory addresses and the counter
+///
+/// With the `serde` feature, it is serialised as an integer,
+///  and deserialising fails if it is too large (> 99)
+pub type TwoDigitNumber = FixedDigitNumber<2>;
+/// Errors for converting to a [`TwoDigitNumber`]
+pub type TryFromError = num::TryFromError<2>;
+
+#[cfg(test)]
+mod test {
+    use crate::num3::ThreeDigitNumber;
+
+    use super::{TryFromError, TwoDigitNumber};
+
+    #[test]
+    fn conversions() {
+        assert_eq!(
+            TwoDigitNumber::try_from(ThreeDigitNumber::new::<99>()),
+            Ok(TwoDigitNumber::MAX),
+            "Failed to convert from a three digit number!"
+        );
+        assert_eq!(
+            TwoDigitNumber::try_from(ThreeDigitNumber::new::<100>()),
+            Err(TryFromError::TooLarge),
+            "Failed to reject a three digit number that is too large!"
+        );
+        assert_eq!(
+            TwoDigitNumber::try_from(300_usize),
+            Err(TryFromError::TooLarge),
+            "Failed to reject a usize that is too large!"
+        );
+        assert_eq!(
+            ThreeDigitNumber::from(TwoDigitNumber::new::<42>()),
+            ThreeDigitNumber::new::<42>(),
+            "Failed to convert to a three digit number!"
+        );
+        assert_eq!(
+            TwoDigitNumber::MAX.checked_next(),
+            None,
+            "Failed to stop at the last number!"
+        );
+    }
+}
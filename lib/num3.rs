@@ -1,115 +1,233 @@
-use core::{
-    fmt::{self, Binary, Display, LowerHex, Octal, UpperHex},
-    ops::{Add, AddAssign, Sub},
-};
+use crate::num::{self, FixedDigitNumber};
 
-#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd, Ord, Eq, Hash)]
-#[repr(transparent)]
 /// A three digit number (0..=999)
-pub struct ThreeDigitNumber(u16);
-
-impl ThreeDigitNumber {
-    pub const ZERO: Self = Self(0);
-
-    #[must_use]
-    /// Checks if the number also a valid two digit number
-    pub const fn is_2_digit(self) -> bool {
-        self.0 < 100
-    }
-
-    #[must_use]
-    /// Makes a [`ThreeDigitNumber`] from a [`u16`] without performing any checks
-    ///
-    /// # Safety
-    /// The caller must make sure that `value` is strictly less than 1000 (within `(0..=999)`)
-    pub const unsafe fn from_unchecked(value: u16) -> Self {
-        Self(value)
-    }
-}
-
-// Formatting impls
-
-macro_rules! fmt_impl {
-    ( $trait:path, $fmt:path ) => {
-        impl $trait for ThreeDigitNumber {
-            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-                $fmt(&self.0, f)
-            }
-        }
+///
+/// With the `serde` feature, it is serialised as an integer,
+///  and deserialising fails if it is too large (> 999)
+pub type ThreeDigitNumber = FixedDigitNumber<3>;
+/// Errors for converting to a [`ThreeDigitNumber`]
+pub type TryFromError = num::TryFromError<3>;
+/// Displays a [`ThreeDigitNumber`] as ten's complement, so 995 is shown as -5.
+/// See [`ThreeDigitNumber::signed`]
+pub type Signed = num::Signed<3>;
+
+#[macro_export]
+/// Make a [`ThreeDigitNumber`] from a constant, failing to compile if it is too large (> 999).
+/// See [`ThreeDigitNumber::new`]
+///
+/// ```
+/// # use lminc::three_digit;
+/// let numbers = [three_digit!(5), three_digit!(6)];
+/// assert_eq!(numbers[0] + numbers[1], three_digit!(11));
+/// ```
+macro_rules! three_digit {
+    ( $value:expr ) => {
+        $crate::num3::ThreeDigitNumber::new::<{ $value }>()
     };
 }
 
-fmt_impl!(Display, Display::fmt);
-fmt_impl!(Octal, Octal::fmt);
-fmt_impl!(LowerHex, LowerHex::fmt);
-fmt_impl!(UpperHex, UpperHex::fmt);
-fmt_impl!(Binary, Binary::fmt);
-
-// Operation impls
+#[cfg(test)]
+mod test {
+    use crate::num2::TwoDigitNumber;
 
-impl Add for ThreeDigitNumber {
-    type Output = Self;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        Self((self.0 + rhs.0) % 1000)
-    }
-}
+    use super::{ThreeDigitNumber, TryFromError};
 
-impl AddAssign for ThreeDigitNumber {
-    fn add_assign(&mut self, rhs: Self) {
-        self.0 += rhs.0;
-        self.0 %= 1000;
+    fn number(value: u16) -> ThreeDigitNumber {
+        ThreeDigitNumber::try_from(value).expect("failed to create the number")
     }
-}
-
-impl Sub for ThreeDigitNumber {
-    type Output = (Self, bool);
 
-    fn sub(self, rhs: Self) -> Self::Output {
-        (Self((self.0 + 1000 - rhs.0) % 1000), self < rhs)
+    #[test]
+    fn arithmetic() {
+        assert_eq!(
+            number(600).overflowing_add(number(500)),
+            (number(100), true),
+            "Failed to wrap addition!"
+        );
+        assert_eq!(
+            number(600).checked_add(number(399)),
+            Some(number(999)),
+            "Failed to add!"
+        );
+        assert_eq!(
+            number(1).checked_sub(number(2)),
+            None,
+            "Failed to reject negative subtraction!"
+        );
+
+        assert_eq!(
+            number(999).add_carry(number(1)),
+            (ThreeDigitNumber::ZERO, true),
+            "Failed to carry!"
+        );
+        assert_eq!(
+            number(998).add_carry(number(1)),
+            (number(999), false),
+            "Failed to add without a carry!"
+        );
+
+        assert_eq!(number(998) + 3_u16, number(1), "Failed to add a u16!");
+        assert_eq!(
+            u16::MAX + number(999),
+            number(534),
+            "Failed to add to a u16!"
+        );
+        assert_eq!(255_u8 + number(1), number(256), "Failed to add to a u8!");
+
+        let mut value = number(5);
+        value -= number(6);
+        assert_eq!(value, number(999), "Failed to wrap subtraction!");
+
+        assert_eq!(
+            number(999).overflowing_mul(number(999)),
+            (number(1), true),
+            "Failed to wrap multiplication!"
+        );
+        assert_eq!(number(25) * number(4), number(100), "Failed to multiply!");
+        assert_eq!(number(999) / number(10), number(99), "Failed to divide!");
+        assert_eq!(
+            number(999) % number(10),
+            number(9),
+            "Failed to get the remainder!"
+        );
+        assert_eq!(
+            number(1).checked_div(ThreeDigitNumber::ZERO),
+            None,
+            "Failed to reject division by zero!"
+        );
     }
-}
-
-// From impls
 
-impl From<u8> for ThreeDigitNumber {
-    fn from(value: u8) -> Self {
-        Self(value.into())
+    #[test]
+    fn digits() {
+        for value in 0..1000 {
+            let number = number(value);
+            assert_eq!(
+                ThreeDigitNumber::from_digits(number.hundreds(), number.tens(), number.units()),
+                Some(number),
+                "Failed to round trip the digits of {value}!"
+            );
+        }
+        assert_eq!(
+            ThreeDigitNumber::from_digits(0, 10, 0),
+            None,
+            "Failed to reject a digit that is too large!"
+        );
     }
-}
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub enum TryFromError {
-    TooLarge,
-}
-
-impl fmt::Display for TryFromError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::TooLarge => write!(f, "Number is too large to be converted to a three digit number (> 999)!"),
+    #[test]
+    fn split_op() {
+        for value in 0..1000 {
+            let number = number(value);
+            let (op_code, address) = number.split_op();
+            assert_eq!(
+                ThreeDigitNumber::compose(op_code, address),
+                Some(number),
+                "Failed to round trip the op code and address of {value}!"
+            );
         }
+        assert_eq!(
+            number(901).split_op(),
+            (9, TwoDigitNumber::new::<1>()),
+            "Failed to split an instruction!"
+        );
+        assert_eq!(
+            ThreeDigitNumber::compose(10, TwoDigitNumber::ZERO),
+            None,
+            "Failed to reject an op code that is too large!"
+        );
     }
-}
-
-#[cfg(feature = "std")]
-impl std::error::Error for TryFromError {}
 
-impl TryFrom<u16> for ThreeDigitNumber {
-    type Error = TryFromError;
-
-    fn try_from(value: u16) -> Result<Self, Self::Error> {
-        if value < 1000 {
-            Ok(Self(value))
-        } else {
-            Err(TryFromError::TooLarge)
+    #[test]
+    fn signed() {
+        assert_eq!(
+            number(995).as_signed(),
+            -5,
+            "Failed to read a negative number!"
+        );
+        assert_eq!(
+            number(499).as_signed(),
+            499,
+            "Failed to read a positive number!"
+        );
+        assert_eq!(
+            number(500).as_signed(),
+            -500,
+            "Failed to read the smallest number!"
+        );
+        assert_eq!(
+            ThreeDigitNumber::from_signed(-1),
+            Some(number(999)),
+            "Failed to make a negative number!"
+        );
+        assert_eq!(
+            ThreeDigitNumber::from_signed(500),
+            None,
+            "Failed to reject a number that is too large!"
+        );
+        assert_eq!(
+            ThreeDigitNumber::from_signed(-501),
+            None,
+            "Failed to reject a number that is too small!"
+        );
+        for value in -500..500 {
+            assert_eq!(
+                ThreeDigitNumber::from_signed(value).map(ThreeDigitNumber::as_signed),
+                Some(value),
+                "Failed to round trip {value}!"
+            );
         }
+        assert_eq!(
+            number(995).signed().to_string(),
+            "-5",
+            "Failed to display a negative number!"
+        );
     }
-}
 
-// Into impls
+    #[test]
+    fn conversions() {
+        assert_eq!(
+            ThreeDigitNumber::try_from(999_usize),
+            Ok(number(999)),
+            "Failed to convert from a usize!"
+        );
+        assert_eq!(
+            ThreeDigitNumber::try_from(70_000_u32),
+            Err(TryFromError::TooLarge),
+            "Failed to reject a large u32!"
+        );
+        assert_eq!(
+            ThreeDigitNumber::try_from(-1_i32),
+            Err(TryFromError::Negative),
+            "Failed to reject a negative i32!"
+        );
+        assert_eq!(
+            ThreeDigitNumber::try_from('A'),
+            Ok(number(65)),
+            "Failed to convert from a char!"
+        );
+        assert_eq!(
+            ThreeDigitNumber::try_from('€'),
+            Err(TryFromError::TooLarge),
+            "Failed to reject a large char!"
+        );
+        assert_eq!(i32::from(number(999)), 999, "Failed to convert to an i32!");
+    }
 
-impl From<ThreeDigitNumber> for u16 {
-    fn from(value: ThreeDigitNumber) -> Self {
-        value.0
+    #[cfg(feature = "json")]
+    #[test]
+    fn serde() {
+        assert_eq!(
+            serde_json::to_string(&[number(5), number(999)]).expect("failed to serialise"),
+            "[5,999]",
+            "Failed to serialise numbers as integers!"
+        );
+        assert_eq!(
+            serde_json::from_str::<ThreeDigitNumber>("605").ok(),
+            Some(number(605)),
+            "Failed to deserialise a number!"
+        );
+        assert!(
+            serde_json::from_str::<ThreeDigitNumber>("1000").is_err(),
+            "Failed to reject a number that is too large!"
+        );
     }
 }
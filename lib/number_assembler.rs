@@ -2,7 +2,7 @@ use core::{fmt, num::ParseIntError};
 
 use crate::{
     computer::Memory,
-    errors::{ErrorWithLocation, LineNumber},
+    errors::{Code, ErrorCode, ErrorWithLocation, LineNumber},
     num3::{ThreeDigitNumber, TryFromError},
 };
 
@@ -13,10 +13,11 @@ pub struct NumberAssembler {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 /// Errors for assembling from numbers
 pub enum FromNumbersError {
     TooManyNumbers,
-    InvalidNumber(ParseIntError),
+    InvalidNumber(#[cfg_attr(feature = "defmt", defmt(Display2Format))] ParseIntError),
     TooLarge(TryFromError),
 }
 
@@ -30,6 +31,16 @@ impl fmt::Display for FromNumbersError {
     }
 }
 
+impl Code for FromNumbersError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            Self::TooManyNumbers => ErrorCode(201),
+            Self::InvalidNumber(_) => ErrorCode(202),
+            Self::TooLarge(_) => ErrorCode(203),
+        }
+    }
+}
+
 #[cfg(feature = "std")]
 impl std::error::Error for FromNumbersError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
@@ -55,6 +66,12 @@ impl From<TryFromError> for FromNumbersError {
     }
 }
 
+impl Default for NumberAssembler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl NumberAssembler {
     #[must_use]
     /// Create a new [`NumberAssembler`]
@@ -76,8 +93,13 @@ impl NumberAssembler {
         }
 
         // Get the part of the line before any comments
-        let Some(code) = line.split(&['#', ';'][..]).next()
-            .filter(|code| !code.is_empty()) else { return Ok(()) };
+        let Some(code) = line
+            .split(&['#', ';'][..])
+            .next()
+            .filter(|code| !code.is_empty())
+        else {
+            return Ok(());
+        };
 
         // Try to parse as a u16 then try to convert to a three digit number
         let number: u16 = code.trim().parse()?;
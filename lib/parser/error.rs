@@ -1,8 +1,12 @@
 use core::fmt;
 
-use crate::{assembly, errors};
+use crate::{
+    assembly,
+    errors::{self, Code, ErrorCode},
+};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 /// Parsing errors
 pub enum Error {
     /// Too many words on one line (before any comments)
@@ -15,7 +19,7 @@ pub enum Error {
     UnexpectedNumber,
     /// A line did not have an instruction but did have other non-space contents
     NoInstruction,
-    /// See [assembly::Error]
+    /// See [`assembly::Error`]
     DataPresence(assembly::Error),
     /// The label was not found in the parsed assembly
     UnknownLabel,
@@ -35,6 +39,20 @@ impl fmt::Display for Error {
     }
 }
 
+impl Code for Error {
+    fn code(&self) -> ErrorCode {
+        match self {
+            Self::TooManyWords => ErrorCode(1),
+            Self::TooManyInstructions => ErrorCode(2),
+            Self::MultipleInstructions => ErrorCode(3),
+            Self::UnexpectedNumber => ErrorCode(4),
+            Self::NoInstruction => ErrorCode(5),
+            Self::DataPresence(error) => error.code(),
+            Self::UnknownLabel => ErrorCode(8),
+        }
+    }
+}
+
 #[cfg(feature = "std")]
 impl std::error::Error for Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
@@ -26,7 +26,9 @@ impl<'a> InstructionWithLabel<'a, NumberOrLabel<'a>> {
             instruction = Some(inst);
         } else {
             // Make sure the first word is not a number
-            let NumberOrLabel::Label(lab) = first.into() else { return Err(Error::UnexpectedNumber) };
+            let NumberOrLabel::Label(lab) = first.into() else {
+                return Err(Error::UnexpectedNumber);
+            };
 
             label = Some(lab);
         }
@@ -70,6 +72,12 @@ pub struct Parser<'a> {
     instruction_number: usize,
 }
 
+impl Default for Parser<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<'a> Parser<'a> {
     #[must_use]
     /// Create a new [Parser]
@@ -102,8 +110,13 @@ impl<'a> Parser<'a> {
         line: &'a str,
     ) -> Result<(), ErrorWithLocation<InstructionNumber>> {
         // Get the part of the line before any comments
-        let Some(code) = line.split(&['#', ';'][..]).next()
-            .filter(|code| !code.is_empty()) else { return Ok(()) };
+        let Some(code) = line
+            .split(&['#', ';'][..])
+            .next()
+            .filter(|code| !code.is_empty())
+        else {
+            return Ok(());
+        };
 
         // Split the code into words
         let words_iter = code.split_whitespace().filter(|word| !word.is_empty());
@@ -161,9 +174,7 @@ impl<'a> Parser<'a> {
                 parser
                     .parse_line(line)
                     // Add the line number as the error location
-                    .map_err(|error| {
-                        errors::ErrorWithLocation(LineNumber(line_number + 1), error.1)
-                    })
+                    .map_err(|error| error.map_location(|_| LineNumber(line_number + 1)))
             })?;
 
         Ok(parser)
@@ -210,6 +221,15 @@ impl<'a> IntoIterator for Parser<'a> {
     }
 }
 
+impl<'a> IntoIterator for &'a Parser<'a> {
+    type Item = &'a InstructionWithLabel<'a, NumberOrLabel<'a>>;
+    type IntoIter = ParsedIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 #[derive(Clone, Debug)]
 /// An iterator over the parsed instructions in a [Parser]
 pub struct ParsedIter<'a> {
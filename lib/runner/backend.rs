@@ -0,0 +1,243 @@
+use core::fmt;
+
+use crate::{
+    computer::{self, Computer, State},
+    num3::ThreeDigitNumber,
+};
+
+use super::debugger::Debugger;
+
+/// Something that runs a program and exchanges inputs and outputs with it,
+///  so that a [`Test`](super::tester::Test) can be run through it
+pub trait Backend {
+    /// Run one instruction, returning the new [State]
+    fn step(&mut self) -> State;
+
+    /// Get the [Computer] running the program, to check its memory and registers
+    fn computer(&self) -> &Computer;
+
+    /// Mutably get the [Computer] running the program, to set up its memory
+    fn computer_mut(&mut self) -> &mut Computer;
+
+    /// Give an input to the program
+    ///
+    /// # Errors
+    /// [`computer::Error::UnexpectedInput`] - the program was not waiting for an input
+    fn input(&mut self, input: ThreeDigitNumber) -> Result<(), computer::Error>;
+
+    /// Take an output from the program
+    ///
+    /// # Errors
+    /// [`computer::Error::NoOutput`] - the program was not waiting to output
+    fn output(&mut self) -> Result<ThreeDigitNumber, computer::Error>;
+
+    #[cfg(feature = "extended")]
+    /// Give a char input to the program
+    ///
+    /// # Errors
+    /// [`computer::Error::UnexpectedCharInput`] - the program was not waiting for a char input
+    fn input_char(&mut self, input: ThreeDigitNumber) -> Result<(), computer::Error>;
+
+    #[cfg(feature = "extended")]
+    /// Take a char output from the program
+    ///
+    /// # Errors
+    /// [`computer::Error::NoCharOutput`] - the program was not waiting to output a char
+    fn output_char(&mut self) -> Result<ThreeDigitNumber, computer::Error>;
+}
+
+impl Backend for Computer {
+    fn step(&mut self) -> State {
+        Self::step(self)
+    }
+
+    fn computer(&self) -> &Computer {
+        self
+    }
+
+    fn computer_mut(&mut self) -> &mut Computer {
+        self
+    }
+
+    fn input(&mut self, input: ThreeDigitNumber) -> Result<(), computer::Error> {
+        Self::input(self, input)
+    }
+
+    fn output(&mut self) -> Result<ThreeDigitNumber, computer::Error> {
+        Self::output(self)
+    }
+
+    #[cfg(feature = "extended")]
+    fn input_char(&mut self, input: ThreeDigitNumber) -> Result<(), computer::Error> {
+        Self::input_char(self, input)
+    }
+
+    #[cfg(feature = "extended")]
+    fn output_char(&mut self) -> Result<ThreeDigitNumber, computer::Error> {
+        Self::output_char(self)
+    }
+}
+
+/// A [`fmt::Write`] that throws away everything written to it
+struct Discard;
+
+impl fmt::Write for Discard {
+    fn write_str(&mut self, _: &str) -> fmt::Result {
+        Ok(())
+    }
+}
+
+/// Steps the [Debugger] (counting its cycles) without stopping at breakpoints.
+/// Trace lines are not written
+impl Backend for Debugger {
+    fn step(&mut self) -> State {
+        Self::step(self, &mut Discard).expect("failed to write to a discarding writer")
+    }
+
+    fn computer(&self) -> &Computer {
+        Self::computer(self)
+    }
+
+    fn computer_mut(&mut self) -> &mut Computer {
+        Self::computer_mut(self)
+    }
+
+    fn input(&mut self, input: ThreeDigitNumber) -> Result<(), computer::Error> {
+        self.computer_mut().input(input)
+    }
+
+    fn output(&mut self) -> Result<ThreeDigitNumber, computer::Error> {
+        self.computer_mut().output()
+    }
+
+    #[cfg(feature = "extended")]
+    fn input_char(&mut self, input: ThreeDigitNumber) -> Result<(), computer::Error> {
+        self.computer_mut().input_char(input)
+    }
+
+    #[cfg(feature = "extended")]
+    fn output_char(&mut self) -> Result<ThreeDigitNumber, computer::Error> {
+        self.computer_mut().output_char()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        assembler::assemble_from_text,
+        computer::{self, Computer, State},
+        num3::ThreeDigitNumber,
+        runner::{
+            debugger::Debugger,
+            tester::{TestBuilder, TestError},
+        },
+    };
+
+    use super::Backend;
+
+    /// A backend that rejects every input
+    struct Deaf(Computer);
+
+    impl Backend for Deaf {
+        fn step(&mut self) -> State {
+            self.0.step()
+        }
+
+        fn computer(&self) -> &Computer {
+            &self.0
+        }
+
+        fn computer_mut(&mut self) -> &mut Computer {
+            &mut self.0
+        }
+
+        fn input(&mut self, _: ThreeDigitNumber) -> Result<(), computer::Error> {
+            Err(computer::Error::UnexpectedInput)
+        }
+
+        fn output(&mut self) -> Result<ThreeDigitNumber, computer::Error> {
+            self.0.output()
+        }
+
+        #[cfg(feature = "extended")]
+        fn input_char(&mut self, _: ThreeDigitNumber) -> Result<(), computer::Error> {
+            Err(computer::Error::UnexpectedCharInput)
+        }
+
+        #[cfg(feature = "extended")]
+        fn output_char(&mut self) -> Result<ThreeDigitNumber, computer::Error> {
+            self.0.output_char()
+        }
+    }
+
+    #[test]
+    fn rejected_input() {
+        let memory = assemble_from_text("IN\nOUT\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        let test = TestBuilder::new()
+            .with_max_cycles(10)
+            .with_input(0)
+            .build()
+            .expect("failed to build the test");
+        assert_eq!(
+            test.run(&mut Deaf(Computer::new(memory)))
+                .expect_err("passed a test with a rejected input")
+                .1
+                 .1,
+            TestError::InvalidIo(computer::Error::UnexpectedInput),
+            "Got the wrong error!"
+        );
+    }
+
+    #[test]
+    fn debugger_backend() {
+        let memory = assemble_from_text("IN\nADD one\nOUT\nHLT\none DAT 1")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        let test = TestBuilder::new()
+            .with_max_cycles(10)
+            .with_input(4)
+            .with_output(5)
+            .with_memory(4, 1)
+            .with_register(5)
+            .with_negative_flag(false)
+            .with_state(State::Halted)
+            .build()
+            .expect("failed to build the test");
+
+        let mut debugger = Debugger::new(memory);
+        assert_eq!(
+            test.run(&mut debugger)
+                .expect("failed to run the test through a debugger"),
+            4,
+            "The test ran the wrong number of cycles!"
+        );
+        assert_eq!(
+            debugger.cycles(),
+            4,
+            "The debugger counted the wrong number of cycles!"
+        );
+
+        let test = TestBuilder::new()
+            .with_max_cycles(10)
+            .with_input(4)
+            .with_output(5)
+            .with_state(State::ReachedEnd)
+            .build()
+            .expect("failed to build the test");
+        assert_eq!(
+            test.run(&mut Debugger::new(memory))
+                .expect_err("passed a test expecting the wrong state")
+                .1
+                 .1,
+            TestError::DifferentState {
+                expected: State::ReachedEnd,
+                got: State::Halted
+            },
+            "Got the wrong error!"
+        );
+    }
+}
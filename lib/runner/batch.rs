@@ -0,0 +1,234 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::{
+    computer::{self, Computer, Memory, State},
+    num3::ThreeDigitNumber,
+};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+/// The inputs for one run in a [Batch]
+pub struct Inputs {
+    pub inputs: Vec<ThreeDigitNumber>,
+    #[cfg(feature = "extended")]
+    pub char_inputs: Vec<ThreeDigitNumber>,
+}
+
+impl From<Vec<ThreeDigitNumber>> for Inputs {
+    fn from(value: Vec<ThreeDigitNumber>) -> Self {
+        Self {
+            inputs: value,
+            #[cfg(feature = "extended")]
+            char_inputs: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Why a run in a [Batch] stopped
+pub enum Stop {
+    /// The computer stopped running in the given state
+    State(State),
+    /// The computer requested more inputs than were given
+    RunOutOfInputs,
+    /// The number of cycles reached the maximum
+    RunOutOfCycles,
+    /// The number of outputs (including char outputs) went over the maximum
+    TooManyOutputs,
+    /// The computer rejected an input or output that it requested, see [`computer::Error`]
+    InvalidIo(computer::Error),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// The result of one run in a [Batch]
+pub struct Run {
+    pub outputs: Vec<ThreeDigitNumber>,
+    #[cfg(feature = "extended")]
+    pub char_outputs: Vec<ThreeDigitNumber>,
+    pub stop: Stop,
+    pub cycles: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Runs one program against many sets of inputs
+///  (in parallel with the `rayon` feature)
+pub struct Batch {
+    memory: Memory,
+    max_cycles: u32,
+    max_outputs: Option<u32>,
+}
+
+impl Batch {
+    #[must_use]
+    /// Create a new [Batch] that runs the program in `memory`,
+    ///  stopping each run after `max_cycles` cycles
+    pub const fn new(memory: Memory, max_cycles: u32) -> Self {
+        Self {
+            memory,
+            max_cycles,
+            max_outputs: None,
+        }
+    }
+
+    #[must_use]
+    /// Stop each run when it gives more than `max_outputs` outputs (including char outputs)
+    pub const fn with_max_outputs(mut self, max_outputs: u32) -> Self {
+        self.max_outputs = Some(max_outputs);
+        self
+    }
+
+    /// Check if `run` has given more than the maximum number of outputs
+    fn too_many_outputs(&self, run: &Run) -> bool {
+        #[cfg(feature = "extended")]
+        let outputs = run.outputs.len() + run.char_outputs.len();
+        #[cfg(not(feature = "extended"))]
+        let outputs = run.outputs.len();
+
+        self.max_outputs
+            .is_some_and(|max_outputs| outputs > max_outputs as usize)
+    }
+
+    #[must_use]
+    /// Run the program once with the given inputs
+    pub fn run_one(&self, inputs: &Inputs) -> Run {
+        let mut computer = Computer::new(self.memory);
+        let mut numbers = inputs.inputs.iter();
+        #[cfg(feature = "extended")]
+        let mut chars = inputs.char_inputs.iter();
+
+        let mut run = Run {
+            outputs: Vec::new(),
+            #[cfg(feature = "extended")]
+            char_outputs: Vec::new(),
+            stop: Stop::RunOutOfCycles,
+            cycles: 0,
+        };
+
+        while run.cycles < self.max_cycles {
+            run.cycles += 1;
+
+            match computer.step() {
+                State::Running => (),
+                State::AwaitingInput => {
+                    let Some(input) = numbers.next() else {
+                        run.stop = Stop::RunOutOfInputs;
+                        break;
+                    };
+                    if let Err(error) = computer.input(*input) {
+                        run.stop = Stop::InvalidIo(error);
+                        break;
+                    }
+                }
+                State::AwaitingOutput => {
+                    match computer.output() {
+                        Ok(output) => run.outputs.push(output),
+                        Err(error) => {
+                            run.stop = Stop::InvalidIo(error);
+                            break;
+                        }
+                    }
+                    if self.too_many_outputs(&run) {
+                        run.stop = Stop::TooManyOutputs;
+                        break;
+                    }
+                }
+                #[cfg(feature = "extended")]
+                State::AwaitingCharInput => {
+                    let Some(input) = chars.next() else {
+                        run.stop = Stop::RunOutOfInputs;
+                        break;
+                    };
+                    if let Err(error) = computer.input_char(*input) {
+                        run.stop = Stop::InvalidIo(error);
+                        break;
+                    }
+                }
+                #[cfg(feature = "extended")]
+                State::AwaitingCharOutput => {
+                    match computer.output_char() {
+                        Ok(output) => run.char_outputs.push(output),
+                        Err(error) => {
+                            run.stop = Stop::InvalidIo(error);
+                            break;
+                        }
+                    }
+                    if self.too_many_outputs(&run) {
+                        run.stop = Stop::TooManyOutputs;
+                        break;
+                    }
+                }
+                state => {
+                    run.stop = Stop::State(state);
+                    break;
+                }
+            }
+        }
+
+        run
+    }
+
+    #[must_use]
+    /// Run the program once for each set of inputs, returning the runs in the same order
+    pub fn run(&self, inputs: &[Inputs]) -> Vec<Run> {
+        #[cfg(feature = "rayon")]
+        return inputs
+            .par_iter()
+            .map(|inputs| self.run_one(inputs))
+            .collect();
+
+        #[cfg(not(feature = "rayon"))]
+        inputs.iter().map(|inputs| self.run_one(inputs)).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        assembler::assemble_from_text, computer::State, num3::ThreeDigitNumber, three_digit,
+    };
+
+    use super::{Batch, Inputs, Stop};
+
+    #[test]
+    fn run() {
+        let memory = assemble_from_text("IN\nSTO 99\nIN\nADD 99\nOUT\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+        let number = |value: usize| ThreeDigitNumber::try_from(value).expect("value was too large");
+
+        let inputs: Vec<Inputs> = (0..50)
+            .map(|value| Inputs::from(vec![number(value), number(value)]))
+            .chain([Inputs::from(vec![three_digit!(1)])])
+            .collect();
+
+        let runs = Batch::new(memory, 100).run(&inputs);
+
+        assert_eq!(runs.len(), 51, "Returned the wrong number of runs!");
+        for (value, run) in runs.iter().take(50).enumerate() {
+            assert_eq!(run.stop, Stop::State(State::Halted), "A run did not halt!");
+            assert_eq!(
+                run.outputs,
+                [number(value * 2)],
+                "A run gave the wrong output!"
+            );
+        }
+        assert_eq!(
+            runs[50].stop,
+            Stop::RunOutOfInputs,
+            "A run did not run out of inputs!"
+        );
+
+        let looping = assemble_from_text("loop BR loop")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+        assert_eq!(
+            Batch::new(looping, 10).run_one(&Inputs::default()).stop,
+            Stop::RunOutOfCycles,
+            "A run did not run out of cycles!"
+        );
+    }
+}
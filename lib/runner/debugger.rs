@@ -0,0 +1,783 @@
+use core::{fmt, num::ParseIntError};
+#[cfg(feature = "std")]
+use std::io::{self, BufRead, Write};
+
+#[cfg(feature = "std")]
+use crate::file::DebugInfo;
+use crate::{
+    computer::{self, Computer, Memory, State},
+    helper::case_insensitive::Str,
+    num2::TwoDigitNumber,
+    num3::{self, ThreeDigitNumber},
+};
+
+use super::trace::{write_memory, Trace};
+
+const HELP_TEXT: &str = "\
+Commands:
+    step [n]            (s) Run one (or n) instructions
+    continue            (c) Run until a breakpoint, input, output or halt
+                            (or the cycle limit, if there is one)
+    break <address>     (b) Set a breakpoint
+    delete <address>    (d) Remove a breakpoint
+    breakpoints         (l) List the breakpoints
+    memory [start] [end](m) Print the memory (from start to end inclusive)
+    print               (p) Print the register, counter and flags
+    register <value>    (r) Set the register
+    counter <address>       Set the counter
+    input <value>       (i) Give an input to the computer
+    trace <on|off>      (t) Print a trace line before every instruction
+    reset                   Reset the computer without resetting the memory
+    help                (h) Display this message
+    quit                (q) Stop debugging
+";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Commands for the [Debugger]
+pub enum Command {
+    /// Run the given number of instructions
+    Step(u32),
+    /// Run until a breakpoint is reached or the computer stops running
+    Continue,
+    /// Set a breakpoint at the address
+    Break(usize),
+    /// Remove the breakpoint at the address
+    Delete(usize),
+    /// List the breakpoints
+    Breakpoints,
+    /// Print the memory between the addresses (inclusive)
+    Memory(usize, usize),
+    /// Print the register, counter and flags
+    Print,
+    /// Set the register
+    SetRegister(ThreeDigitNumber),
+    /// Set the counter
+    SetCounter(TwoDigitNumber),
+    /// Give an input to the computer
+    Input(ThreeDigitNumber),
+    /// Enable or disable tracing
+    Trace(bool),
+    /// Reset the computer without resetting the memory
+    Reset,
+    /// Print the help text
+    Help,
+    /// Stop debugging
+    Quit,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Debugger errors
+pub enum Error {
+    /// The command was not recognised
+    UnknownCommand,
+    /// The command expected another argument
+    ExpectedArgument,
+    /// The command was given too many arguments
+    UnexpectedArgument,
+    /// An argument was not a valid number
+    InvalidNumber(#[cfg_attr(feature = "defmt", defmt(Display2Format))] ParseIntError),
+    /// A value was too large (> 999)
+    TooLarge(num3::TryFromError),
+    /// An address was too large (> 99)
+    AddressTooLarge(usize),
+    /// The argument to `trace` was not `on` or `off`
+    ExpectedOnOrOff,
+    /// The computer rejected an input, see [`computer::Error`]
+    ComputerError(computer::Error),
+    /// Failed to write to the output
+    FormatError,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownCommand => write!(f, "Unknown command, use 'help' for help!"),
+            Self::ExpectedArgument => write!(f, "Expected another argument!"),
+            Self::UnexpectedArgument => write!(f, "Too many arguments!"),
+            Self::InvalidNumber(_) => write!(f, "Invalid number!"),
+            Self::TooLarge(_) => write!(f, "Number is too large (> 999)!"),
+            Self::AddressTooLarge(address) => write!(f, "Address is too large ({address} > 99)!"),
+            Self::ExpectedOnOrOff => write!(f, "Expected 'on' or 'off'!"),
+            Self::ComputerError(error) => fmt::Display::fmt(error, f),
+            Self::FormatError => write!(f, "Failed to write the output!"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidNumber(error) => Some(error),
+            Self::TooLarge(error) => Some(error),
+            Self::ComputerError(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseIntError> for Error {
+    fn from(value: ParseIntError) -> Self {
+        Self::InvalidNumber(value)
+    }
+}
+
+impl From<num3::TryFromError> for Error {
+    fn from(value: num3::TryFromError) -> Self {
+        Self::TooLarge(value)
+    }
+}
+
+impl From<computer::Error> for Error {
+    fn from(value: computer::Error) -> Self {
+        Self::ComputerError(value)
+    }
+}
+
+impl From<fmt::Error> for Error {
+    fn from(_: fmt::Error) -> Self {
+        Self::FormatError
+    }
+}
+
+fn parse_address(word: Option<&str>) -> Result<usize, Error> {
+    let address = word.ok_or(Error::ExpectedArgument)?.parse()?;
+    if address < 100 {
+        Ok(address)
+    } else {
+        Err(Error::AddressTooLarge(address))
+    }
+}
+
+fn parse_value(word: Option<&str>) -> Result<ThreeDigitNumber, Error> {
+    Ok(word
+        .ok_or(Error::ExpectedArgument)?
+        .parse::<u16>()?
+        .try_into()?)
+}
+
+impl TryFrom<&str> for Command {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let mut words = value.split_whitespace();
+
+        let command = match Str::from(words.next().ok_or(Error::UnknownCommand)?) {
+            c if c == "step" || c == "s" => Self::Step(words.next().map_or(Ok(1), str::parse)?),
+            c if c == "continue" || c == "c" => Self::Continue,
+            c if c == "break" || c == "b" => Self::Break(parse_address(words.next())?),
+            c if c == "delete" || c == "d" => Self::Delete(parse_address(words.next())?),
+            c if c == "breakpoints" || c == "l" => Self::Breakpoints,
+            c if c == "memory" || c == "m" => {
+                let start = words
+                    .next()
+                    .map_or(Ok(0), |word| parse_address(Some(word)))?;
+                let end = words
+                    .next()
+                    .map_or(Ok(99), |word| parse_address(Some(word)))?;
+                Self::Memory(start, end)
+            }
+            c if c == "print" || c == "p" => Self::Print,
+            c if c == "register" || c == "r" => Self::SetRegister(parse_value(words.next())?),
+            c if c == "counter" => {
+                let address = parse_address(words.next())?;
+                Self::SetCounter(
+                    TwoDigitNumber::try_from(address)
+                        .map_err(|_| Error::AddressTooLarge(address))?,
+                )
+            }
+            c if c == "input" || c == "i" => Self::Input(parse_value(words.next())?),
+            c if c == "trace" || c == "t" => {
+                match Str::from(words.next().ok_or(Error::ExpectedArgument)?) {
+                    w if w == "on" => Self::Trace(true),
+                    w if w == "off" => Self::Trace(false),
+                    _ => return Err(Error::ExpectedOnOrOff),
+                }
+            }
+            c if c == "reset" => Self::Reset,
+            c if c == "help" || c == "h" => Self::Help,
+            c if c == "quit" || c == "q" => Self::Quit,
+            _ => return Err(Error::UnknownCommand),
+        };
+
+        if words.next().is_some() {
+            return Err(Error::UnexpectedArgument);
+        }
+
+        Ok(command)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// The reasons that a [Debugger] can stop running
+pub enum Stop {
+    /// All of the requested steps were run
+    Stepped,
+    /// The counter reached a breakpoint
+    Breakpoint(usize),
+    /// The computer stopped running (it is waiting for io, has halted or has errored)
+    State(State),
+    /// Continuing ran the maximum number of cycles without stopping,
+    ///  see [`Debugger::with_max_cycles`]
+    TooManyCycles(u32),
+    /// The user asked to stop debugging
+    Quit,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// A debugger for a [Computer], with breakpoints and tracing
+pub struct Debugger {
+    computer: Computer,
+    breakpoints: [bool; 100],
+    cycles: u32,
+    max_cycles: Option<u32>,
+    trace: bool,
+    stopped_at: Option<usize>,
+}
+
+impl Debugger {
+    #[must_use]
+    /// Create a new [Debugger] from [Memory]
+    pub const fn new(memory: Memory) -> Self {
+        Self::new_from_computer(Computer::new(memory))
+    }
+
+    #[must_use]
+    /// Create a new [Debugger] from a [Computer]
+    pub const fn new_from_computer(computer: Computer) -> Self {
+        Self {
+            computer,
+            breakpoints: [false; 100],
+            cycles: 0,
+            max_cycles: None,
+            trace: false,
+            stopped_at: None,
+        }
+    }
+
+    #[must_use]
+    /// Stop continuing with [`Stop::TooManyCycles`] after running `max_cycles` instructions,
+    ///  so that a program stuck in a loop does not run forever. By default, there is no limit
+    pub const fn with_max_cycles(mut self, max_cycles: u32) -> Self {
+        self.max_cycles = Some(max_cycles);
+        self
+    }
+
+    #[must_use]
+    /// Get the [Computer] being debugged
+    pub const fn computer(&self) -> &Computer {
+        &self.computer
+    }
+
+    /// Mutably get the [Computer] being debugged
+    pub const fn computer_mut(&mut self) -> &mut Computer {
+        &mut self.computer
+    }
+
+    #[must_use]
+    /// Get the number of instructions run, which stops counting at [`u32::MAX`]
+    pub const fn cycles(&self) -> u32 {
+        self.cycles
+    }
+
+    #[must_use]
+    /// Check if there is a breakpoint at the address
+    pub fn is_breakpoint(&self, address: usize) -> bool {
+        self.breakpoints.get(address).copied().unwrap_or(false)
+    }
+
+    /// Set or remove a breakpoint at the address, returning whether there was one before
+    ///
+    /// # Errors
+    /// [`Error::AddressTooLarge`] - the address is not a valid memory address
+    pub fn set_breakpoint(&mut self, address: usize, value: bool) -> Result<bool, Error> {
+        let breakpoint = self
+            .breakpoints
+            .get_mut(address)
+            .ok_or(Error::AddressTooLarge(address))?;
+
+        Ok(core::mem::replace(breakpoint, value))
+    }
+
+    /// Create an iterator over the addresses with breakpoints
+    pub fn breakpoints(&self) -> impl Iterator<Item = usize> + '_ {
+        self.breakpoints
+            .iter()
+            .enumerate()
+            .filter_map(|(address, breakpoint)| breakpoint.then_some(address))
+    }
+
+    #[must_use]
+    /// Take a [Trace] of the current state of the [Computer]
+    pub fn trace(&self) -> Trace {
+        Trace::new(&self.computer, self.cycles)
+    }
+
+    /// Run one instruction, writing a trace line to `out` if tracing is enabled
+    ///
+    /// # Errors
+    /// [`Error::FormatError`] - failed to write to `out`
+    pub fn step(&mut self, out: &mut impl fmt::Write) -> Result<State, Error> {
+        if self.computer.state() != State::Running {
+            return Ok(self.computer.state());
+        }
+
+        if self.trace {
+            writeln!(out, "{}", self.trace())?;
+        }
+
+        self.cycles = self.cycles.saturating_add(1);
+        Ok(self.computer.step())
+    }
+
+    /// Run instructions until the number of steps is reached, a breakpoint is reached
+    ///  or the computer stops running.
+    /// Without a number of steps, it also stops after the maximum number of cycles,
+    ///  see [`Debugger::with_max_cycles`].
+    /// If the debugger last stopped at a breakpoint, that breakpoint is skipped
+    ///
+    /// # Errors
+    /// [`Error::FormatError`] - failed to write to `out`
+    pub fn run(&mut self, steps: Option<u32>, out: &mut impl fmt::Write) -> Result<Stop, Error> {
+        let mut remaining = steps.or(self.max_cycles);
+        let mut skip = self.stopped_at.take();
+
+        let stop = loop {
+            if remaining == Some(0) {
+                break match (steps, self.max_cycles) {
+                    (None, Some(max_cycles)) => Stop::TooManyCycles(max_cycles),
+                    _ => Stop::Stepped,
+                };
+            }
+
+            if let Some(counter) = self.computer.counter().map(usize::from) {
+                if self.is_breakpoint(counter) && skip.take() != Some(counter) {
+                    break Stop::Breakpoint(counter);
+                }
+            }
+            skip = None;
+
+            match self.step(out)? {
+                State::Running => (),
+                state => break Stop::State(state),
+            }
+
+            remaining = remaining.map(|remaining| remaining - 1);
+        };
+
+        // Remember where the debugger stopped, unless it stopped for io or a halt,
+        //  in which case the instruction at the counter has not been run yet
+        if !matches!(stop, Stop::State(_)) {
+            self.stopped_at = self.computer.counter().map(usize::from);
+        }
+
+        Ok(stop)
+    }
+
+    /// Execute a [Command], writing any responses to `out`
+    ///
+    /// Returns [`Some`] if the computer was run and stopped, or if the user quit
+    ///
+    /// # Errors
+    /// See [Error]
+    pub fn execute(
+        &mut self,
+        command: Command,
+        out: &mut impl fmt::Write,
+    ) -> Result<Option<Stop>, Error> {
+        match command {
+            Command::Step(steps) => return self.run(Some(steps), out).map(Some),
+            Command::Continue => return self.run(None, out).map(Some),
+            Command::Break(address) => {
+                self.set_breakpoint(address, true)?;
+            }
+            Command::Delete(address) => {
+                if !self.set_breakpoint(address, false)? {
+                    writeln!(out, "There was no breakpoint at {address:02}")?;
+                }
+            }
+            Command::Breakpoints => {
+                let mut any = false;
+                for address in self.breakpoints() {
+                    write!(out, "{address:02} ")?;
+                    any = true;
+                }
+
+                if any {
+                    writeln!(out)?;
+                } else {
+                    writeln!(out, "No breakpoints")?;
+                }
+            }
+            Command::Memory(start, end) => write_memory(
+                self.computer.get_memory(),
+                start,
+                end,
+                self.computer.counter().map(usize::from),
+                out,
+            )?,
+            Command::Print => {
+                writeln!(
+                    out,
+                    "cycle  address  instruction  register  flags\n{}\nThe computer {}",
+                    self.trace(),
+                    self.computer.state()
+                )?;
+            }
+            Command::SetRegister(value) => Computer::set_register(&mut self.computer, value),
+            Command::SetCounter(address) => {
+                Computer::set_counter(&mut self.computer, address);
+                self.stopped_at = None;
+            }
+            Command::Input(value) => self.computer.input(value)?,
+            Command::Trace(value) => self.trace = value,
+            Command::Reset => {
+                self.computer.reset();
+                self.cycles = 0;
+                self.stopped_at = None;
+            }
+            Command::Help => write!(out, "{HELP_TEXT}")?,
+            Command::Quit => return Ok(Some(Stop::Quit)),
+        }
+
+        Ok(None)
+    }
+
+    #[cfg(feature = "std")]
+    /// Run an interactive debugging session, reading commands from `input`
+    ///  and writing responses to `output`.
+    /// Inputs and outputs requested by the computer are also handled here
+    ///
+    /// The session ends when the user quits or `input` reaches its end
+    ///
+    /// # Errors
+    /// [`io::Error`] - failed to read from `input` or write to `output`,
+    ///  or the computer rejected an input or output that it requested
+    pub fn run_interactive(&mut self, input: impl BufRead, output: impl Write) -> io::Result<()> {
+        self.interact(input, output, None)
+    }
+
+    #[cfg(feature = "std")]
+    /// Run an interactive debugging session like [`Debugger::run_interactive`],
+    ///  also printing the source line of the next instruction whenever the computer stops
+    ///  (and for `print`), using the lines in the [`DebugInfo`]
+    ///
+    /// # Errors
+    /// See [`Debugger::run_interactive`]
+    pub fn run_interactive_with_source(
+        &mut self,
+        input: impl BufRead,
+        output: impl Write,
+        source: &str,
+        debug_info: &DebugInfo,
+    ) -> io::Result<()> {
+        self.interact(input, output, Some((source, debug_info)))
+    }
+
+    #[cfg(feature = "std")]
+    /// Write the source line of the next instruction, if it is known
+    fn write_source_line(
+        &self,
+        output: &mut impl Write,
+        (source, debug_info): (&str, &DebugInfo),
+    ) -> io::Result<()> {
+        let Some(line_number) = self
+            .computer
+            .counter()
+            .and_then(|counter| debug_info.line(usize::from(counter)))
+        else {
+            return Ok(());
+        };
+
+        if let Some(line) = line_number
+            .0
+            .checked_sub(1)
+            .and_then(|index| source.lines().nth(index))
+        {
+            writeln!(output, "--> {} | {}", line_number.0, line.trim_end())?;
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "std")]
+    #[allow(clippy::too_many_lines)]
+    /// Run an interactive debugging session, see [`Debugger::run_interactive`]
+    fn interact(
+        &mut self,
+        mut input: impl BufRead,
+        mut output: impl Write,
+        source: Option<(&str, &DebugInfo)>,
+    ) -> io::Result<()> {
+        let mut buffer = String::new();
+        let mut response = String::new();
+        let mut resume = false;
+
+        loop {
+            match self.computer.state() {
+                State::AwaitingInput => {
+                    write!(output, "(i) > ")?;
+                    output.flush()?;
+
+                    buffer.clear();
+                    if input.read_line(&mut buffer)? == 0 {
+                        return Ok(());
+                    }
+
+                    match parse_value(Some(buffer.trim())) {
+                        Ok(value) => self.computer.input(value).map_err(io::Error::other)?,
+                        Err(error) => {
+                            writeln!(output, "{error}")?;
+                            continue;
+                        }
+                    }
+                }
+                State::AwaitingOutput => {
+                    let value = self.computer.output().map_err(io::Error::other)?;
+                    writeln!(output, "{value}")?;
+                }
+                #[cfg(feature = "extended")]
+                State::AwaitingCharInput => {
+                    write!(output, "(c) > ")?;
+                    output.flush()?;
+
+                    buffer.clear();
+                    if input.read_line(&mut buffer)? == 0 {
+                        return Ok(());
+                    }
+
+                    let character = buffer.chars().next().unwrap_or('\n');
+                    let Some(value) = ThreeDigitNumber::try_from(character).ok() else {
+                        writeln!(output, "Invalid input character!")?;
+                        continue;
+                    };
+
+                    self.computer.input_char(value).map_err(io::Error::other)?;
+                }
+                #[cfg(feature = "extended")]
+                State::AwaitingCharOutput => {
+                    let value = self.computer.output_char().map_err(io::Error::other)?;
+                    match char::from_u32(value.into()) {
+                        Some(character) => writeln!(output, "{character:?}")?,
+                        None => writeln!(output, "Invalid character outputted: {value}!")?,
+                    }
+                }
+                _ => (),
+            }
+
+            let command = if resume {
+                resume = false;
+                Command::Continue
+            } else {
+                write!(output, "(debug) > ")?;
+                output.flush()?;
+
+                buffer.clear();
+                if input.read_line(&mut buffer)? == 0 {
+                    return Ok(());
+                }
+
+                if buffer.trim().is_empty() {
+                    continue;
+                }
+
+                match Command::try_from(buffer.as_str()) {
+                    Ok(command) => command,
+                    Err(error) => {
+                        writeln!(output, "{error}")?;
+                        continue;
+                    }
+                }
+            };
+
+            response.clear();
+            let result = self.execute(command, &mut response);
+            write!(output, "{response}")?;
+            // Show where the computer is after it runs, unless it is about to carry on
+            let show_source = matches!(result, Ok(Some(_))) || command == Command::Print;
+
+            match result {
+                Ok(Some(Stop::Quit)) => return Ok(()),
+                Ok(Some(Stop::Breakpoint(address))) => {
+                    writeln!(output, "Reached breakpoint at {address:02}")?;
+                }
+                Ok(Some(Stop::TooManyCycles(max_cycles))) => writeln!(
+                    output,
+                    "Ran {max_cycles} cycles without stopping, the program may be stuck in a loop!"
+                )?,
+                Ok(Some(Stop::State(state))) => match state {
+                    State::AwaitingInput | State::AwaitingOutput => {
+                        resume = command == Command::Continue;
+                    }
+                    #[cfg(feature = "extended")]
+                    State::AwaitingCharInput | State::AwaitingCharOutput => {
+                        resume = command == Command::Continue;
+                    }
+                    state => writeln!(output, "The computer {state}")?,
+                },
+                Ok(_) => (),
+                Err(error) => writeln!(output, "{error}")?,
+            }
+
+            if let Some(source) = source.filter(|_| show_source && !resume) {
+                self.write_source_line(&mut output, source)?;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{assembler::assemble_from_text, computer::State, file::DebugInfo};
+
+    use super::{Command, Debugger, Stop};
+
+    #[test]
+    fn parse_commands() {
+        assert_eq!(
+            Command::try_from("step").expect("failed to parse step"),
+            Command::Step(1),
+            "Failed to parse 'step'!"
+        );
+        assert_eq!(
+            Command::try_from("S 5").expect("failed to parse step"),
+            Command::Step(5),
+            "Failed to parse 'S 5'!"
+        );
+        assert_eq!(
+            Command::try_from("memory 10").expect("failed to parse memory"),
+            Command::Memory(10, 99),
+            "Failed to parse 'memory 10'!"
+        );
+        assert!(
+            Command::try_from("break 100").is_err(),
+            "Parsed a breakpoint with an invalid address!"
+        );
+        assert!(
+            Command::try_from("print 1").is_err(),
+            "Parsed a command with too many arguments!"
+        );
+    }
+
+    #[test]
+    fn breakpoints() {
+        let assembly = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/examples/fib.txt"));
+        let memory = assemble_from_text(assembly)
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        let mut debugger = Debugger::new(memory);
+        let mut out = String::new();
+
+        debugger
+            .execute(Command::Break(3), &mut out)
+            .expect("failed to set a breakpoint");
+
+        assert_eq!(
+            debugger
+                .execute(Command::Continue, &mut out)
+                .expect("failed to continue"),
+            Some(Stop::State(State::AwaitingOutput)),
+            "Did not stop for the output!"
+        );
+
+        debugger
+            .computer_mut()
+            .output()
+            .expect("failed to get an output");
+
+        assert_eq!(
+            debugger
+                .execute(Command::Continue, &mut out)
+                .expect("failed to continue"),
+            Some(Stop::Breakpoint(3)),
+            "Did not stop at the breakpoint!"
+        );
+        assert_eq!(debugger.cycles(), 3, "Ran the wrong number of cycles!");
+    }
+
+    #[test]
+    fn max_cycles() {
+        let memory = assemble_from_text("loop BR loop")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        let mut debugger = Debugger::new(memory).with_max_cycles(50);
+        let mut out = String::new();
+        assert_eq!(
+            debugger
+                .execute(Command::Continue, &mut out)
+                .expect("failed to continue"),
+            Some(Stop::TooManyCycles(50)),
+            "Did not stop at the cycle limit!"
+        );
+        assert_eq!(debugger.cycles(), 50, "Ran the wrong number of cycles!");
+
+        assert_eq!(
+            debugger
+                .execute(Command::Step(100), &mut out)
+                .expect("failed to step"),
+            Some(Stop::Stepped),
+            "Limited the cycles of a step!"
+        );
+        assert_eq!(debugger.cycles(), 150, "Ran the wrong number of cycles!");
+    }
+
+    #[test]
+    fn interactive() {
+        let assembly = "IN\nOUT\nHLT";
+        let memory = assemble_from_text(assembly)
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        let mut debugger = Debugger::new(memory);
+        let mut output = Vec::new();
+
+        debugger
+            .run_interactive(&b"continue\n5\nquit\n"[..], &mut output)
+            .expect("failed to run the debugger");
+
+        let output = String::from_utf8(output).expect("output was not utf-8");
+        assert!(
+            output.contains("> 5\n"),
+            "The debugger did not output the input!"
+        );
+        assert!(
+            output.contains("halted"),
+            "The debugger did not report the halt!"
+        );
+    }
+
+    #[test]
+    fn interactive_source() {
+        let assembly = "# Echo\nstart IN\n      OUT\n      HLT";
+        let memory = assemble_from_text(assembly)
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+        let debug_info = DebugInfo::from_assembly(assembly).expect("failed to make debug info");
+
+        let mut debugger = Debugger::new(memory);
+        let mut output = Vec::new();
+
+        debugger
+            .run_interactive_with_source(
+                &b"print\nstep\n5\nstep\nquit\n"[..],
+                &mut output,
+                assembly,
+                &debug_info,
+            )
+            .expect("failed to run the debugger");
+
+        let output = String::from_utf8(output).expect("output was not utf-8");
+        assert!(
+            output.contains("--> 2 | start IN\n"),
+            "The debugger did not show the first line!"
+        );
+        assert!(
+            output.contains("--> 3 |       OUT\n"),
+            "The debugger did not show the line after stepping!"
+        );
+    }
+}
@@ -1,5 +1,27 @@
+/// Backends that tests can be run through
+pub mod backend;
+#[cfg(feature = "alloc")]
+/// Run a program against many sets of inputs
+pub mod batch;
+/// An interactive debugger with breakpoints and tracing
+pub mod debugger;
+/// Events that runners can tell observers about
+pub mod observer;
+/// A read-eval-print runner for trying out instructions
+pub mod repl;
+#[cfg(feature = "embedded-io")]
+/// A runner that uses a serial port for inputs and outputs, without std
+pub mod serial;
+#[cfg(feature = "std")]
+/// Record and replay the inputs to a runner
+pub mod session;
 #[cfg(feature = "std")]
 /// A runner that uses stdio for input and outputs
 pub mod stdio;
 /// Runners to run tests on programs
 pub mod tester;
+/// Snapshots of the computer for tracing
+pub mod trace;
+#[cfg(feature = "tui")]
+/// A full-screen terminal front-end
+pub mod tui;
@@ -0,0 +1,33 @@
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+
+use crate::{computer::State, num3::ThreeDigitNumber};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Something that happened while a runner was running the computer
+pub enum Event {
+    /// The computer asked for an input
+    InputRequested,
+    #[cfg(feature = "extended")]
+    /// The computer asked for a char input
+    CharInputRequested,
+    /// The computer gave an output
+    Output(ThreeDigitNumber),
+    #[cfg(feature = "extended")]
+    /// The computer gave a char output
+    CharOutput(ThreeDigitNumber),
+    /// The state of the computer changed to the given state
+    StateChanged(State),
+    /// A number was written to the memory
+    MemoryWritten {
+        address: usize,
+        value: ThreeDigitNumber,
+    },
+}
+
+#[cfg(feature = "alloc")]
+/// A callback that is given every [Event]
+pub type Observer<'a> = Box<dyn FnMut(&Event) + 'a>;
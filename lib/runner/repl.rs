@@ -0,0 +1,379 @@
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io::{self, BufRead, Write};
+
+use crate::{
+    assembler::{self, assemble_instruction},
+    computer::{Computer, Memory, State},
+    errors::ErrorWithLocation,
+    num3::ThreeDigitNumber,
+    parser::{self, Parser},
+};
+
+#[cfg(feature = "std")]
+use super::trace::write_memory;
+
+#[cfg(feature = "std")]
+const HELP_TEXT: &str = "\
+Type an instruction (e.g. 'ADD 10') or a number (e.g. '110') to run it immediately.
+Labels cannot be used, addresses must be numbers.
+
+Commands:
+    :memory [start] [end]   Print the memory (from start to end inclusive)
+    :reset                  Reset the register, counter and flags
+    :clear                  Reset the computer and clear the memory
+    :help                   Display this message
+    :quit                   Stop the REPL
+";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// REPL errors
+pub enum Error {
+    /// The line could not be parsed, see [`parser::Error`]
+    ParseError(parser::Error),
+    /// The instruction could not be assembled, see [`assembler::Error`]
+    AssemblerError(assembler::Error),
+    /// A label was given before the instruction
+    UnexpectedLabel,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ParseError(error) => fmt::Display::fmt(error, f),
+            Self::AssemblerError(error) => fmt::Display::fmt(error, f),
+            Self::UnexpectedLabel => write!(f, "Labels cannot be used in the REPL!"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::ParseError(error) => Some(error),
+            Self::AssemblerError(error) => Some(error),
+            Self::UnexpectedLabel => None,
+        }
+    }
+}
+
+impl From<parser::Error> for Error {
+    fn from(value: parser::Error) -> Self {
+        Self::ParseError(value)
+    }
+}
+
+impl From<assembler::Error> for Error {
+    fn from(value: assembler::Error) -> Self {
+        Self::AssemblerError(value)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// A read-eval-print runner that runs instructions as they are typed
+pub struct Repl {
+    computer: Computer,
+}
+
+impl Repl {
+    #[must_use]
+    /// Create a new [Repl] from [Memory]
+    pub const fn new(memory: Memory) -> Self {
+        Self::new_from_computer(Computer::new(memory))
+    }
+
+    #[must_use]
+    /// Create a new [Repl] from a [Computer]
+    pub const fn new_from_computer(computer: Computer) -> Self {
+        Self { computer }
+    }
+
+    #[must_use]
+    /// Get the [Computer] used by the [Repl]
+    pub const fn computer(&self) -> &Computer {
+        &self.computer
+    }
+
+    /// Mutably get the [Computer] used by the [Repl]
+    pub const fn computer_mut(&mut self) -> &mut Computer {
+        &mut self.computer
+    }
+
+    /// Assemble one line of assembly (or a number) without running it.
+    /// Returns [`None`] if the line was empty or only a comment
+    ///
+    /// # Errors
+    /// See [Error]
+    pub fn assemble_line(line: &str) -> Result<Option<ThreeDigitNumber>, Error> {
+        if let Some(number) = line
+            .trim()
+            .parse::<u16>()
+            .ok()
+            .and_then(|number| ThreeDigitNumber::try_from(number).ok())
+        {
+            return Ok(Some(number));
+        }
+
+        let mut parser = Parser::new();
+        parser
+            .parse_line(line)
+            .map_err(ErrorWithLocation::into_inner)?;
+
+        let Some(instruction) = parser.iter().next() else {
+            return Ok(None);
+        };
+
+        if instruction.label.is_some() {
+            return Err(Error::UnexpectedLabel);
+        }
+
+        // Assemble with an empty parser, so that no labels can be resolved
+        Ok(Some(assemble_instruction(
+            instruction.instruction,
+            &Parser::new(),
+        )?))
+    }
+
+    /// Assemble one line of assembly (or a number) and run it on the computer.
+    /// If the computer had stopped, it is set running again first.
+    /// Returns [`None`] if the line was empty or only a comment
+    ///
+    /// # Errors
+    /// See [Error]
+    pub fn evaluate(&mut self, line: &str) -> Result<Option<State>, Error> {
+        let Some(instruction) = Self::assemble_line(line)? else {
+            return Ok(None);
+        };
+
+        if matches!(
+            self.computer.state(),
+            State::Halted | State::ReachedEnd | State::InvalidInstruction
+        ) {
+            Computer::set_state(&mut self.computer, State::Running);
+        }
+
+        Ok(Some(self.computer.execute(instruction)))
+    }
+
+    #[cfg(feature = "std")]
+    /// Run the REPL, reading lines from `input` and writing responses to `output`.
+    /// Inputs and outputs requested by the computer are also handled here
+    ///
+    /// The REPL ends when the user quits or `input` reaches its end
+    ///
+    /// # Errors
+    /// [`io::Error`] - failed to read from `input` or write to `output`,
+    ///  or the computer rejected an input or output that it requested
+    pub fn run(&mut self, mut input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+        let mut buffer = String::new();
+
+        loop {
+            write!(output, "(lmc) > ")?;
+            output.flush()?;
+
+            buffer.clear();
+            if input.read_line(&mut buffer)? == 0 {
+                return Ok(());
+            }
+
+            let line = buffer.trim();
+
+            if let Some(command) = line.strip_prefix(':') {
+                let mut words = command.split_whitespace();
+                match words.next() {
+                    Some("memory" | "m") => {
+                        let mut address = || {
+                            words
+                                .next()
+                                .and_then(|word| word.parse::<usize>().ok())
+                                .filter(|address| *address < 100)
+                        };
+                        let start = address().unwrap_or(0);
+                        let end = address().unwrap_or(99);
+
+                        let mut response = String::new();
+                        write_memory(
+                            self.computer.get_memory(),
+                            start,
+                            end,
+                            self.computer.counter().map(usize::from),
+                            &mut response,
+                        )
+                        .map_err(io::Error::other)?;
+                        write!(output, "{response}")?;
+                    }
+                    Some("reset") => self.computer.reset(),
+                    Some("clear") => self.computer = Computer::new([ThreeDigitNumber::ZERO; 100]),
+                    Some("help" | "h") => write!(output, "{HELP_TEXT}")?,
+                    Some("quit" | "q") => return Ok(()),
+                    _ => writeln!(output, "Unknown command, use ':help' for help!")?,
+                }
+                continue;
+            }
+
+            match self.evaluate(line) {
+                Ok(Some(_)) => (),
+                Ok(None) => continue,
+                Err(error) => {
+                    writeln!(output, "{error}")?;
+                    continue;
+                }
+            }
+
+            if !self.handle_io(&mut input, &mut output)? {
+                return Ok(());
+            }
+
+            match self.computer.state() {
+                State::Running => writeln!(
+                    output,
+                    "register: {:#}, negative flag: {}",
+                    self.computer.register(),
+                    if self.computer.negative_flag() {
+                        "set"
+                    } else {
+                        "clear"
+                    }
+                )?,
+                state => writeln!(output, "The computer {state}")?,
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    /// Deal with any input or output the computer is waiting for,
+    ///  returning `false` if `input` reached its end
+    fn handle_io(&mut self, input: &mut impl BufRead, output: &mut impl Write) -> io::Result<bool> {
+        let mut buffer = String::new();
+
+        loop {
+            match self.computer.state() {
+                State::AwaitingInput => {
+                    write!(output, "(i) > ")?;
+                    output.flush()?;
+
+                    buffer.clear();
+                    if input.read_line(&mut buffer)? == 0 {
+                        return Ok(false);
+                    }
+
+                    match buffer
+                        .trim()
+                        .parse::<u16>()
+                        .ok()
+                        .and_then(|number| ThreeDigitNumber::try_from(number).ok())
+                    {
+                        Some(number) => self.computer.input(number).map_err(io::Error::other)?,
+                        None => writeln!(output, "Invalid input, enter a number from 0 to 999!")?,
+                    }
+                }
+                State::AwaitingOutput => {
+                    let number = self.computer.output().map_err(io::Error::other)?;
+                    writeln!(output, "{number}")?;
+                }
+                #[cfg(feature = "extended")]
+                State::AwaitingCharInput => {
+                    write!(output, "(c) > ")?;
+                    output.flush()?;
+
+                    buffer.clear();
+                    if input.read_line(&mut buffer)? == 0 {
+                        return Ok(false);
+                    }
+
+                    let character = buffer.chars().next().unwrap_or('\n');
+                    match ThreeDigitNumber::try_from(character).ok() {
+                        Some(number) => {
+                            self.computer.input_char(number).map_err(io::Error::other)?;
+                        }
+                        None => writeln!(output, "Invalid input character!")?,
+                    }
+                }
+                #[cfg(feature = "extended")]
+                State::AwaitingCharOutput => {
+                    let number = self.computer.output_char().map_err(io::Error::other)?;
+                    match char::from_u32(number.into()) {
+                        Some(character) => writeln!(output, "{character:?}")?,
+                        None => writeln!(output, "Invalid character outputted: {number}!")?,
+                    }
+                }
+                _ => return Ok(true),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{computer::State, num3::ThreeDigitNumber};
+
+    use super::{Error, Repl};
+
+    #[test]
+    fn evaluate() {
+        let mut memory = [ThreeDigitNumber::ZERO; 100];
+        memory[90] = ThreeDigitNumber::new::<25>();
+        let mut repl = Repl::new(memory);
+
+        assert_eq!(
+            repl.evaluate("ADD 90")
+                .expect("failed to evaluate 'ADD 90'"),
+            Some(State::Running),
+            "'ADD 90' did not leave the computer running!"
+        );
+        repl.evaluate("add 90")
+            .expect("failed to evaluate 'add 90'");
+        repl.evaluate("STO 91  # comment")
+            .expect("failed to evaluate 'STO 91'");
+
+        assert_eq!(
+            u16::from(repl.computer().get_memory()[91]),
+            50,
+            "Failed to store the register!"
+        );
+
+        assert_eq!(
+            repl.evaluate("# comment")
+                .expect("failed to evaluate a comment"),
+            None,
+            "Evaluated a comment!"
+        );
+
+        assert_eq!(
+            repl.evaluate("590").expect("failed to evaluate '590'"),
+            Some(State::Running),
+            "Failed to evaluate a number!"
+        );
+        assert_eq!(
+            u16::from(repl.computer().register()),
+            25,
+            "Failed to load from memory with a number!"
+        );
+
+        assert_eq!(
+            repl.evaluate("label ADD 90"),
+            Err(Error::UnexpectedLabel),
+            "Evaluated an instruction with a label!"
+        );
+        assert!(
+            repl.evaluate("ADD label").is_err(),
+            "Evaluated an instruction with a label as data!"
+        );
+    }
+
+    #[test]
+    fn run() {
+        let mut repl = Repl::new([ThreeDigitNumber::ZERO; 100]);
+        let mut output = Vec::new();
+
+        repl.run(&b"IN\n7\nOUT\nHLT\n"[..], &mut output)
+            .expect("failed to run the REPL");
+
+        let output = String::from_utf8(output).expect("output was not utf-8");
+        assert!(output.contains("register: 007"), "Failed to take an input!");
+        assert!(output.contains("> 7\n"), "Failed to output!");
+        assert!(output.contains("halted"), "Failed to halt!");
+    }
+}
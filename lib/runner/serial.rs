@@ -0,0 +1,421 @@
+use core::fmt;
+
+use embedded_io::{Read, Write};
+
+use crate::{
+    computer::{self, Computer, Memory, State},
+    num3::ThreeDigitNumber,
+};
+
+/// The longest number input line, in bytes
+const LINE_LENGTH: usize = 8;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// The error for [Runner]
+pub enum Error<E> {
+    /// A serial error occurred
+    SerialError(E),
+    /// The serial input ended
+    EndOfInput,
+    /// The input was not a valid number
+    ParseError,
+    /// The inputted number was too large
+    TooLarge,
+    /// The input line was longer than the longest number input
+    LineTooLong,
+    #[cfg(feature = "extended")]
+    /// The inputted character is not a valid input character
+    InvalidInputCharacter,
+    #[cfg(feature = "extended")]
+    /// The outputted character is not a valid character
+    InvalidOutputCharacter(ThreeDigitNumber),
+    /// The computer rejected an input or output that it requested, see [`computer::Error`]
+    ComputerError(computer::Error),
+}
+
+impl<E: fmt::Display> fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SerialError(error) => write!(f, "Serial error: {error}"),
+            Self::EndOfInput => write!(f, "Ran out of inputs!"),
+            Self::ParseError => write!(f, "Invalid number inputted!"),
+            Self::TooLarge => write!(f, "Inputted number is too large (> 999)!"),
+            Self::LineTooLong => write!(f, "Input line is too long (> {LINE_LENGTH} bytes)!"),
+            #[cfg(feature = "extended")]
+            Self::InvalidInputCharacter => write!(f, "Invalid input character"),
+            #[cfg(feature = "extended")]
+            Self::InvalidOutputCharacter(number) => {
+                write!(f, "Invalid character outputted: {number}!")
+            }
+            Self::ComputerError(error) => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: fmt::Debug + fmt::Display> std::error::Error for Error<E> {}
+
+impl<E: embedded_io::Error> From<E> for Error<E> {
+    fn from(value: E) -> Self {
+        Self::SerialError(value)
+    }
+}
+
+/// A runner that uses a serial port (anything implementing the [`embedded_io`] traits)
+///  for inputs and outputs, without needing std or alloc
+///
+/// Lines can end with `\r`, `\n` or `\r\n`, and outputs end with `\r\n`
+pub struct Runner<S: Read + Write> {
+    computer: Computer,
+    serial: S,
+    echo: bool,
+    last_was_cr: bool,
+    #[cfg(feature = "extended")]
+    mid_char_sequence: bool,
+}
+
+impl<S: Read + Write> Runner<S> {
+    #[must_use]
+    /// Create a new [Runner] from [Memory]
+    pub const fn new(memory: Memory, serial: S) -> Self {
+        Self::new_from_computer(Computer::new(memory), serial)
+    }
+
+    #[must_use]
+    /// Create a new [Runner] from a [Computer]
+    pub const fn new_from_computer(computer: Computer, serial: S) -> Self {
+        Self {
+            computer,
+            serial,
+            echo: true,
+            last_was_cr: false,
+            #[cfg(feature = "extended")]
+            mid_char_sequence: false,
+        }
+    }
+
+    #[must_use]
+    /// Choose whether to write back the characters typed at the prompts, by default `true`.
+    /// Turn this off if the terminal echoes them itself
+    pub const fn with_echo(mut self, echo: bool) -> Self {
+        self.echo = echo;
+        self
+    }
+
+    #[must_use]
+    /// Get the [Computer] used by the [Runner]
+    pub const fn computer(&self) -> &Computer {
+        &self.computer
+    }
+
+    /// Consume the [Runner], returning its serial port
+    pub fn into_serial(self) -> S {
+        self.serial
+    }
+
+    /// Read one byte
+    fn read_byte(&mut self) -> Result<u8, Error<S::Error>> {
+        let mut byte = [0];
+        if self.serial.read(&mut byte)? == 0 {
+            return Err(Error::EndOfInput);
+        }
+
+        Ok(byte[0])
+    }
+
+    /// Read a line of up to [`LINE_LENGTH`] bytes into `buffer`, returning its length.
+    /// Longer lines are read to their end, then [`Error::LineTooLong`] is returned
+    fn read_line(&mut self, buffer: &mut [u8; LINE_LENGTH]) -> Result<usize, Error<S::Error>> {
+        let mut length = 0;
+        let mut too_long = false;
+
+        loop {
+            let byte = self.read_byte()?;
+
+            match byte {
+                b'\n' if self.last_was_cr && length == 0 => self.last_was_cr = false,
+                b'\r' | b'\n' => {
+                    self.last_was_cr = byte == b'\r';
+                    if self.echo {
+                        self.serial.write_all(b"\r\n")?;
+                    }
+                    return if too_long {
+                        Err(Error::LineTooLong)
+                    } else {
+                        Ok(length)
+                    };
+                }
+                // Backspace and delete
+                0x08 | 0x7f => {
+                    if length > 0 {
+                        length -= 1;
+                        if self.echo {
+                            self.serial.write_all(b"\x08 \x08")?;
+                        }
+                    }
+                }
+                _ => {
+                    self.last_was_cr = false;
+                    if length < LINE_LENGTH {
+                        buffer[length] = byte;
+                        length += 1;
+                        if self.echo {
+                            self.serial.write_all(&[byte])?;
+                        }
+                    } else {
+                        too_long = true;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Write a prompt and flush it
+    fn prompt(&mut self, prompt: &str) -> Result<(), Error<S::Error>> {
+        self.serial.write_all(prompt.as_bytes())?;
+        self.serial.flush()?;
+        Ok(())
+    }
+
+    /// Write a number followed by `\r\n`
+    fn write_number(&mut self, number: ThreeDigitNumber) -> Result<(), Error<S::Error>> {
+        let digits = [
+            b'0' + number.hundreds(),
+            b'0' + number.tens(),
+            b'0' + number.units(),
+        ];
+        let start = match u16::from(number) {
+            0..=9 => 2,
+            10..=99 => 1,
+            _ => 0,
+        };
+
+        self.serial.write_all(&digits[start..])?;
+        self.serial.write_all(b"\r\n")?;
+        Ok(())
+    }
+
+    /// End a line of char outputs, if one was started
+    #[cfg_attr(
+        not(feature = "extended"),
+        allow(
+            clippy::unused_self,
+            clippy::unnecessary_wraps,
+            clippy::missing_const_for_fn,
+            clippy::needless_pass_by_ref_mut
+        )
+    )]
+    fn end_char_sequence(&mut self) -> Result<(), Error<S::Error>> {
+        #[cfg(feature = "extended")]
+        if self.mid_char_sequence {
+            self.serial.write_all(b"\r\n")?;
+            self.mid_char_sequence = false;
+        }
+
+        Ok(())
+    }
+
+    /// Step the computer, using the serial port for inputs and outputs
+    ///
+    /// # Errors
+    /// See [Error]
+    pub fn step(&mut self) -> Result<State, Error<S::Error>> {
+        match self.computer.step() {
+            State::AwaitingInput => {
+                self.end_char_sequence()?;
+
+                #[cfg(not(feature = "extended"))]
+                self.prompt("> ")?;
+                #[cfg(feature = "extended")]
+                self.prompt("(i) > ")?;
+
+                let mut buffer = [0; LINE_LENGTH];
+                let length = self.read_line(&mut buffer)?;
+
+                let number = core::str::from_utf8(&buffer[..length])
+                    .map_err(|_| Error::ParseError)?
+                    .trim()
+                    .parse::<u16>()
+                    .map_err(|_| Error::ParseError)?;
+                let number = ThreeDigitNumber::try_from(number).map_err(|_| Error::TooLarge)?;
+
+                self.computer.input(number).map_err(Error::ComputerError)?;
+            }
+            State::AwaitingOutput => {
+                self.end_char_sequence()?;
+
+                let output = self.computer.output().map_err(Error::ComputerError)?;
+                self.write_number(output)?;
+            }
+            #[cfg(feature = "extended")]
+            State::AwaitingCharInput => {
+                self.end_char_sequence()?;
+                self.prompt("(c) > ")?;
+
+                // Skip the \n of a \r\n, like read_line
+                let mut byte = self.read_byte()?;
+                if byte == b'\n' && self.last_was_cr {
+                    byte = self.read_byte()?;
+                }
+                self.last_was_cr = byte == b'\r';
+
+                let byte = match byte {
+                    b'\r' => b'\n',
+                    byte if byte.is_ascii() => byte,
+                    _ => return Err(Error::InvalidInputCharacter),
+                };
+                if self.echo {
+                    if byte == b'\n' {
+                        self.serial.write_all(b"\r\n")?;
+                    } else {
+                        self.serial.write_all(&[byte, b'\r', b'\n'])?;
+                    }
+                }
+
+                self.computer
+                    .input_char(ThreeDigitNumber::from(byte))
+                    .map_err(Error::ComputerError)?;
+            }
+            #[cfg(feature = "extended")]
+            State::AwaitingCharOutput => {
+                let number = self.computer.output_char().map_err(Error::ComputerError)?;
+
+                let character =
+                    char::from_u32(number.into()).ok_or(Error::InvalidOutputCharacter(number))?;
+
+                if character == '\n' {
+                    self.serial.write_all(b"\r\n")?;
+                } else {
+                    let mut buffer = [0; 4];
+                    self.serial
+                        .write_all(character.encode_utf8(&mut buffer).as_bytes())?;
+                }
+                self.mid_char_sequence = character != '\n';
+            }
+            _ => (),
+        }
+
+        Ok(self.computer.state())
+    }
+
+    /// Run the computer until a halt or error state is reached
+    ///
+    /// # Errors
+    /// See [Error]
+    pub fn run(&mut self) -> Result<State, Error<S::Error>> {
+        loop {
+            match self.step()? {
+                State::Running => (),
+                state => {
+                    self.end_char_sequence()?;
+                    self.serial.flush()?;
+                    return Ok(state);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::convert::Infallible;
+
+    use embedded_io::{ErrorType, Read, Write};
+
+    use crate::assembler::assemble_from_text;
+
+    use super::{Error, Runner};
+
+    /// A fake serial port that reads from a slice and writes to a vector
+    struct Serial<'a> {
+        input: &'a [u8],
+        output: Vec<u8>,
+    }
+
+    impl ErrorType for Serial<'_> {
+        type Error = Infallible;
+    }
+
+    impl Read for Serial<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            Ok(self.input.read(buf).expect("failed to read from a slice"))
+        }
+    }
+
+    impl Write for Serial<'_> {
+        fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+            self.output.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn run() {
+        let memory = assemble_from_text("IN\nSTO 99\nIN\nADD 99\nOUT\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        let serial = Serial {
+            input: b"12\r\n3x\x08\r",
+            output: Vec::new(),
+        };
+        let mut runner = Runner::new(memory, serial);
+        runner.run().expect("failed to run");
+
+        let output = String::from_utf8(runner.into_serial().output).expect("output was not utf-8");
+
+        #[cfg(not(feature = "extended"))]
+        assert_eq!(
+            output, "> 12\r\n> 3x\x08 \x08\r\n15\r\n",
+            "Wrote the wrong output!"
+        );
+        #[cfg(feature = "extended")]
+        assert_eq!(
+            output, "(i) > 12\r\n(i) > 3x\x08 \x08\r\n15\r\n",
+            "Wrote the wrong output!"
+        );
+    }
+
+    #[test]
+    fn line_too_long() {
+        let memory = assemble_from_text("IN\nOUT\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        let serial = Serial {
+            input: b"000000005\r",
+            output: Vec::new(),
+        };
+        assert_eq!(
+            Runner::new(memory, serial).run(),
+            Err(Error::LineTooLong),
+            "Did not reject a line that was too long!"
+        );
+    }
+
+    #[cfg(feature = "extended")]
+    #[test]
+    fn char_input_crlf() {
+        let memory = assemble_from_text("EXT\nINA\nOTA\nINA\nOTA\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        let serial = Serial {
+            input: b"\r\na",
+            output: Vec::new(),
+        };
+        let mut runner = Runner::new(memory, serial).with_echo(false);
+        runner.run().expect("failed to run");
+
+        let output = String::from_utf8(runner.into_serial().output).expect("output was not utf-8");
+        assert_eq!(
+            output, "(c) > \r\n(c) > a\r\n",
+            "Did not read \\r\\n as one char input!"
+        );
+    }
+}
@@ -0,0 +1,225 @@
+use core::fmt;
+use std::{
+    io::{self, BufRead, Cursor, Read, Write},
+    time::Instant,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// The kinds of input in a session
+pub enum Kind {
+    /// A number input
+    Number,
+    /// A char input
+    Char,
+}
+
+impl Kind {
+    const fn as_char(self) -> char {
+        match self {
+            Self::Number => 'i',
+            Self::Char => 'c',
+        }
+    }
+}
+
+/// Records inputs to a writer, one per line, in the format
+///  `milliseconds since the start<tab>i or c<tab>input`
+pub struct Recording<'a> {
+    writer: &'a mut dyn Write,
+    start: Instant,
+}
+
+impl<'a> Recording<'a> {
+    #[must_use]
+    /// Start a new [Recording] to `writer`
+    pub fn new(writer: &'a mut dyn Write) -> Self {
+        Self {
+            writer,
+            start: Instant::now(),
+        }
+    }
+
+    /// Record one input
+    ///
+    /// # Errors
+    /// [`io::Error`] - failed to write to the writer
+    pub fn record(&mut self, kind: Kind, input: &str) -> io::Result<()> {
+        writeln!(
+            self.writer,
+            "{}\t{}\t{input}",
+            self.start.elapsed().as_millis(),
+            kind.as_char()
+        )?;
+        self.writer.flush()
+    }
+}
+
+#[derive(Debug)]
+/// Session replay errors
+pub enum Error {
+    /// Failed to read the recorded session
+    IoError(io::Error),
+    /// A line in the recorded session was not in the right format
+    InvalidLine(usize),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IoError(error) => write!(f, "Io error: {error}"),
+            Self::InvalidLine(line) => write!(f, "Invalid session (line {line})!"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::IoError(error) => Some(error),
+            Self::InvalidLine(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(value: io::Error) -> Self {
+        Self::IoError(value)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Inputs from a recorded session that can be given to a runner
+///  (see [`Runner::with_input`](super::stdio::Runner::with_input))
+///
+/// The inputs are replayed in order, without waiting between them
+pub struct Replay {
+    inputs: Cursor<Vec<u8>>,
+    kinds: Vec<Kind>,
+}
+
+impl Replay {
+    /// Read a session recorded by a [Recording]
+    ///
+    /// # Errors
+    /// See [Error]
+    pub fn new(reader: impl BufRead) -> Result<Self, Error> {
+        let mut inputs = Vec::new();
+        let mut kinds = Vec::new();
+
+        for (line_number, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.is_empty() {
+                continue;
+            }
+
+            let mut sections = line.splitn(3, '\t');
+            let (Some(time), Some(kind), Some(input)) =
+                (sections.next(), sections.next(), sections.next())
+            else {
+                return Err(Error::InvalidLine(line_number + 1));
+            };
+
+            if time.parse::<u128>().is_err() {
+                return Err(Error::InvalidLine(line_number + 1));
+            }
+
+            kinds.push(match kind {
+                "i" => Kind::Number,
+                "c" => Kind::Char,
+                _ => return Err(Error::InvalidLine(line_number + 1)),
+            });
+
+            inputs.extend_from_slice(input.as_bytes());
+            inputs.push(b'\n');
+        }
+
+        Ok(Self {
+            inputs: Cursor::new(inputs),
+            kinds,
+        })
+    }
+
+    #[must_use]
+    /// Get the kinds of the recorded inputs, in order
+    pub fn kinds(&self) -> &[Kind] {
+        &self.kinds
+    }
+}
+
+impl Read for Replay {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inputs.read(buf)
+    }
+}
+
+impl BufRead for Replay {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inputs.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inputs.consume(amt);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        assembler::assemble_from_text,
+        computer::{Computer, State},
+        runner::stdio::Runner,
+    };
+
+    use super::{Error, Kind, Replay};
+
+    #[test]
+    fn record_and_replay() {
+        let memory = assemble_from_text("IN\nSTO 99\nIN\nADD 99\nOUT\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        let mut session = Vec::new();
+        let mut runner = Runner::new_with_io(Computer::new(memory), &b"5\n6\n"[..], Vec::new())
+            .with_recording(&mut session);
+        assert_eq!(
+            runner.run().expect("failed to run"),
+            State::Halted,
+            "The computer did not halt!"
+        );
+        let (_, first_output) = runner.into_io();
+
+        let replay = Replay::new(&session[..]).expect("failed to read the session");
+        assert_eq!(
+            replay.kinds(),
+            [Kind::Number, Kind::Number],
+            "Recorded the wrong kinds of inputs!"
+        );
+
+        let mut runner =
+            Runner::new_with_io(Computer::new(memory), &b""[..], Vec::new()).with_input(replay);
+        assert_eq!(
+            runner.run().expect("failed to replay"),
+            State::Halted,
+            "The computer did not halt when replaying!"
+        );
+        let (_, second_output) = runner.into_io();
+
+        let first_output = String::from_utf8(first_output).expect("output was not utf-8");
+        let second_output = String::from_utf8(second_output).expect("output was not utf-8");
+        assert!(
+            first_output.ends_with("11\n") && second_output.ends_with("11\n"),
+            "The replay gave a different output!"
+        );
+    }
+
+    #[test]
+    fn invalid_session() {
+        assert!(
+            matches!(
+                Replay::new(&b"0\ti\t5\nnot a line\n"[..]),
+                Err(Error::InvalidLine(2))
+            ),
+            "Read an invalid session!"
+        );
+    }
+}
@@ -2,13 +2,25 @@ use core::{
     fmt::{self, Display},
     num::ParseIntError,
 };
-use std::io::{self, stdin, stdout, BufRead, Write};
+use std::{
+    fs::File,
+    io::{self, stdin, stdout, BufRead, BufReader, IsTerminal, StdinLock, Stdout, Write},
+    path::Path,
+};
 
 use crate::{
-    computer::{Computer, Memory, State},
+    computer::{self, Computer, Memory, State},
+    errors::{Code, ErrorCode},
     num3::{self, ThreeDigitNumber},
 };
 
+use super::{
+    backend::Backend,
+    observer::{Event, Observer},
+    session::{Kind, Recording},
+    trace::Trace,
+};
+
 #[derive(Debug)]
 /// The error for [Runner]
 pub enum Error {
@@ -18,6 +30,14 @@ pub enum Error {
     ParseError(ParseIntError),
     /// The inputted number was too large
     TooLarge(num3::TryFromError),
+    /// The inputted number was too small (< -500)
+    TooSmall,
+    /// The quoted input was not a single character, or the character is not a valid input character
+    InvalidQuotedCharacter,
+    /// The input ran out before the computer stopped asking for inputs
+    EndOfInput,
+    /// The computer ran the maximum number of cycles without halting, see [`Runner::with_max_cycles`]
+    TooManyCycles(u32),
     #[cfg(feature = "extended")]
     /// Multiple characters were inputted
     MultipleCharacters,
@@ -27,6 +47,8 @@ pub enum Error {
     #[cfg(feature = "extended")]
     /// The outputted character is not a valid character
     InvalidOutputCharacter(ThreeDigitNumber),
+    /// The computer rejected an input or output that it requested, see [`computer::Error`]
+    ComputerError(computer::Error),
 }
 
 impl Display for Error {
@@ -35,6 +57,13 @@ impl Display for Error {
             Self::IoError(error) => write!(f, "Io error: {error}"),
             Self::ParseError(_) => write!(f, "Invalid number inputted!"),
             Self::TooLarge(_) => write!(f, "Inputted number is too large (> 999)!"),
+            Self::TooSmall => write!(f, "Inputted number is too small (< -500)!"),
+            Self::InvalidQuotedCharacter => write!(f, "Invalid quoted character inputted!"),
+            Self::EndOfInput => write!(f, "Ran out of inputs!"),
+            Self::TooManyCycles(max_cycles) => write!(
+                f,
+                "Ran {max_cycles} cycles without halting, the program may be stuck in a loop!"
+            ),
             #[cfg(feature = "extended")]
             Self::MultipleCharacters => write!(f, "Multiple characters inputted!"),
             #[cfg(feature = "extended")]
@@ -43,6 +72,28 @@ impl Display for Error {
             Self::InvalidOutputCharacter(number) => {
                 write!(f, "Invalid character outputted: {number}!")
             }
+            Self::ComputerError(error) => Display::fmt(error, f),
+        }
+    }
+}
+
+impl Code for Error {
+    fn code(&self) -> ErrorCode {
+        match self {
+            Self::IoError(_) => ErrorCode(411),
+            Self::ParseError(_) => ErrorCode(412),
+            Self::TooLarge(_) => ErrorCode(413),
+            Self::TooSmall => ErrorCode(414),
+            Self::InvalidQuotedCharacter => ErrorCode(415),
+            Self::EndOfInput => ErrorCode(416),
+            Self::TooManyCycles(_) => ErrorCode(420),
+            #[cfg(feature = "extended")]
+            Self::MultipleCharacters => ErrorCode(417),
+            #[cfg(feature = "extended")]
+            Self::InvalidInputCharacter => ErrorCode(418),
+            #[cfg(feature = "extended")]
+            Self::InvalidOutputCharacter(_) => ErrorCode(419),
+            Self::ComputerError(error) => error.code(),
         }
     }
 }
@@ -56,6 +107,12 @@ impl From<io::Error> for Error {
     }
 }
 
+impl From<computer::Error> for Error {
+    fn from(value: computer::Error) -> Self {
+        Self::ComputerError(value)
+    }
+}
+
 impl From<ParseIntError> for Error {
     fn from(value: ParseIntError) -> Self {
         Self::ParseError(value)
@@ -68,131 +125,591 @@ impl From<num3::TryFromError> for Error {
     }
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+/// What a [Runner] does when its input runs out (for example when stdin is piped)
+pub enum EndOfInput {
+    #[default]
+    /// Return [`Error::EndOfInput`]
+    Error,
+    /// Give the computer an input of 0
+    Zero,
+    /// Halt the computer
+    Halt,
+}
+
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+/// The formats, other than plain decimal, that a [Runner] accepts at the number input prompt.
+/// By default, only plain decimal numbers are accepted
+pub struct NumberFormats {
+    /// Accept negative numbers (from -500 to -1), converted to ten's complement (-1 becomes 999)
+    pub negative: bool,
+    /// Accept hexadecimal numbers starting with `0x` (e.g. `0x3e7`)
+    pub hex: bool,
+    /// Accept a single quoted character (e.g. `'a'`), converted to its code point
+    pub quoted_char: bool,
+}
+
+impl NumberFormats {
+    /// Accept all of the formats
+    pub const ALL: Self = Self {
+        negative: true,
+        hex: true,
+        quoted_char: true,
+    };
+
+    /// Parse an input in any of the accepted formats
+    ///
+    /// # Errors
+    /// See [Error]
+    pub fn parse(self, input: &str) -> Result<ThreeDigitNumber, Error> {
+        let input = input.trim();
+
+        if self.quoted_char {
+            if let Some(quoted) = input
+                .strip_prefix('\'')
+                .and_then(|input| input.strip_suffix('\''))
+            {
+                let mut chars = quoted.chars();
+                let (Some(character), None) = (chars.next(), chars.next()) else {
+                    return Err(Error::InvalidQuotedCharacter);
+                };
+
+                return ThreeDigitNumber::try_from(character)
+                    .ok()
+                    .ok_or(Error::InvalidQuotedCharacter);
+            }
+        }
+
+        if self.negative {
+            if let Some(magnitude) = input.strip_prefix('-') {
+                let magnitude = self.parse_unsigned(magnitude)?;
+                return i16::try_from(magnitude)
+                    .ok()
+                    .and_then(|magnitude| ThreeDigitNumber::from_signed(-magnitude))
+                    .ok_or(Error::TooSmall);
+            }
+        }
+
+        Ok(self.parse_unsigned(input)?.try_into()?)
+    }
+
+    /// Parse a decimal, or (if accepted) hexadecimal, number
+    fn parse_unsigned(self, input: &str) -> Result<u16, ParseIntError> {
+        match input.strip_prefix("0x") {
+            Some(hex) if self.hex => u16::from_str_radix(hex, 16),
+            _ => input.parse(),
+        }
+    }
+}
+
+#[cfg(feature = "extended")]
+/// Parse a line of char input
+///
+/// # Errors
+/// [`Error::MultipleCharacters`] or [`Error::InvalidInputCharacter`]
+fn parse_char(input: &str) -> Result<ThreeDigitNumber, Error> {
+    let mut chars = input.chars();
+
+    let character = chars.next().unwrap_or('\n');
+
+    let after: String = chars.collect();
+    if !after.trim().is_empty() {
+        return Err(Error::MultipleCharacters);
+    }
+
+    ThreeDigitNumber::try_from(character).map_err(|_| Error::InvalidInputCharacter)
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+/// What a [Runner] does when it is given an invalid input
+pub enum Retry {
+    #[default]
+    /// Return the error
+    FailFast,
+    /// Write the error and ask again, up to the given number of times
+    Times(u32),
+    /// Write the error and ask again until a valid input is given
+    Forever,
+}
+
 /// A runner that uses stdio for inputs and outputs
-pub struct Runner {
+///
+/// The inputs can be read from any [`BufRead`] and outputs written to any [`Write`] instead,
+///  for example to replay inputs from a file
+pub struct Runner<'a, R: BufRead = StdinLock<'static>, W: Write = Stdout> {
     computer: Computer,
+    input: R,
+    output: W,
+    prompts: bool,
+    echo: bool,
     #[cfg(feature = "extended")]
     mid_char_sequence: bool,
+    cycles: u32,
+    max_cycles: Option<u32>,
+    trace: Option<&'a mut dyn Write>,
+    #[cfg(feature = "extended")]
+    char_input: Option<&'a mut dyn BufRead>,
+    recording: Option<Recording<'a>>,
+    end_of_input: EndOfInput,
+    number_formats: NumberFormats,
+    retry: Retry,
+    observers: Vec<Observer<'a>>,
 }
 
-impl Runner {
+impl Runner<'_> {
     #[must_use]
     /// Create a new [Runner] from [Memory]
-    pub const fn new(memory: Memory) -> Self {
-        Self {
-            computer: Computer::new(memory),
-            #[cfg(feature = "extended")]
-            mid_char_sequence: false,
-        }
+    pub fn new(memory: Memory) -> Self {
+        Self::new_from_computer(Computer::new(memory))
     }
 
     #[must_use]
     /// Create a new [Runner] from a [Computer]
-    pub const fn new_from_computer(computer: Computer) -> Self {
+    ///
+    /// If stdin or stdout is not a terminal (for example when they are piped),
+    ///  prompts are not written, so that only the outputs are written.
+    /// See [`Runner::with_prompts`]
+    pub fn new_from_computer(computer: Computer) -> Self {
+        let interactive = stdin().is_terminal() && stdout().is_terminal();
+        Self::new_with_io(computer, stdin().lock(), stdout()).with_prompts(interactive)
+    }
+}
+
+impl<'a, R: BufRead, W: Write> Runner<'a, R, W> {
+    #[must_use]
+    /// Create a new [Runner] from a [Computer], reading inputs from `input`
+    ///  and writing outputs to `output`
+    pub const fn new_with_io(computer: Computer, input: R, output: W) -> Self {
         Self {
             computer,
+            input,
+            output,
+            prompts: true,
+            echo: false,
             #[cfg(feature = "extended")]
             mid_char_sequence: false,
+            cycles: 0,
+            max_cycles: None,
+            trace: None,
+            #[cfg(feature = "extended")]
+            char_input: None,
+            recording: None,
+            end_of_input: EndOfInput::Error,
+            number_formats: NumberFormats {
+                negative: false,
+                hex: false,
+                quoted_char: false,
+            },
+            retry: Retry::FailFast,
+            observers: Vec::new(),
         }
     }
 
-    /// Step the computer, using stdio for inputs and outputs
+    #[must_use]
+    /// Read the inputs from `input` (one per line) instead, echoing each one after its prompt.
+    /// When `input` runs out, [`Error::EndOfInput`] is returned
+    pub fn with_input<I: BufRead>(self, input: I) -> Runner<'a, I, W> {
+        Runner {
+            computer: self.computer,
+            input,
+            output: self.output,
+            prompts: self.prompts,
+            echo: true,
+            #[cfg(feature = "extended")]
+            mid_char_sequence: self.mid_char_sequence,
+            cycles: self.cycles,
+            max_cycles: self.max_cycles,
+            trace: self.trace,
+            #[cfg(feature = "extended")]
+            char_input: self.char_input,
+            recording: self.recording,
+            end_of_input: self.end_of_input,
+            number_formats: self.number_formats,
+            retry: self.retry,
+            observers: self.observers,
+        }
+    }
+
+    /// Read the inputs from the file at `path` (one per line) instead.
+    /// See [`Runner::with_input`]
+    ///
+    /// # Errors
+    /// [`io::Error`] - failed to open the file
+    pub fn with_input_file(
+        self,
+        path: impl AsRef<Path>,
+    ) -> io::Result<Runner<'a, BufReader<File>, W>> {
+        Ok(self.with_input(BufReader::new(File::open(path)?)))
+    }
+
+    #[cfg(feature = "extended")]
+    #[must_use]
+    /// Read the char inputs from `input` (one per line, where an empty line is a new line)
+    ///  instead of the same input as the numbers, echoing each one after its prompt.
+    /// When `input` runs out, the [`EndOfInput`] policy is used
+    pub fn with_char_input(mut self, input: &'a mut dyn BufRead) -> Self {
+        self.char_input = Some(input);
+        self.echo = true;
+        self
+    }
+
+    #[must_use]
+    /// Write the outputs (and prompts) to `output` instead
+    pub fn with_output<O: Write>(self, output: O) -> Runner<'a, R, O> {
+        Runner {
+            computer: self.computer,
+            input: self.input,
+            output,
+            prompts: self.prompts,
+            echo: self.echo,
+            #[cfg(feature = "extended")]
+            mid_char_sequence: self.mid_char_sequence,
+            cycles: self.cycles,
+            max_cycles: self.max_cycles,
+            trace: self.trace,
+            #[cfg(feature = "extended")]
+            char_input: self.char_input,
+            recording: self.recording,
+            end_of_input: self.end_of_input,
+            number_formats: self.number_formats,
+            retry: self.retry,
+            observers: self.observers,
+        }
+    }
+
+    #[must_use]
+    /// Choose whether to write prompts before reading inputs, by default `true`
+    ///  (except with [`Runner::new`], see [`Runner::new_from_computer`]).
+    /// Without prompts, inputs are not echoed either
+    pub const fn with_prompts(mut self, prompts: bool) -> Self {
+        self.prompts = prompts;
+        self
+    }
+
+    #[must_use]
+    /// Write a trace line (cycle, address, instruction, register and flags)
+    ///  to `writer` before every instruction is run.
+    /// See [`Trace`] for the format
+    pub fn with_trace(mut self, writer: &'a mut dyn Write) -> Self {
+        self.trace = Some(writer);
+        self
+    }
+
+    #[must_use]
+    /// Choose what happens when the input runs out (see [`EndOfInput`]),
+    ///  by default [`EndOfInput::Error`]
+    pub const fn with_end_of_input(mut self, policy: EndOfInput) -> Self {
+        self.end_of_input = policy;
+        self
+    }
+
+    #[must_use]
+    /// Stop with [`Error::TooManyCycles`] instead of running more than `max_cycles` instructions,
+    ///  so that a program stuck in a loop does not run forever.
+    /// By default, there is no limit
+    pub const fn with_max_cycles(mut self, max_cycles: u32) -> Self {
+        self.max_cycles = Some(max_cycles);
+        self
+    }
+
+    #[must_use]
+    /// Choose what happens when an invalid input is given (see [`Retry`]),
+    ///  by default [`Retry::FailFast`]
+    pub const fn with_retry(mut self, policy: Retry) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    #[must_use]
+    /// Choose which formats are accepted at the number input prompt (see [`NumberFormats`]),
+    ///  by default only plain decimal numbers
+    pub const fn with_number_formats(mut self, formats: NumberFormats) -> Self {
+        self.number_formats = formats;
+        self
+    }
+
+    #[must_use]
+    /// Record every input (with the time since this was called) to `writer`,
+    ///  so that the session can be replayed with [`Replay`](super::session::Replay)
+    pub fn with_recording(mut self, writer: &'a mut dyn Write) -> Self {
+        self.recording = Some(Recording::new(writer));
+        self
+    }
+
+    #[must_use]
+    /// Call `observer` with every [`Event`] (inputs requested, outputs given, state changes
+    ///  and memory writes), so that a front-end can update without checking the whole computer.
+    /// See [`Runner::add_observer`]
+    pub fn with_observer(mut self, observer: impl FnMut(&Event) + 'a) -> Self {
+        self.add_observer(observer);
+        self
+    }
+
+    /// Call `observer` with every [`Event`], as well as any observers already added
+    pub fn add_observer(&mut self, observer: impl FnMut(&Event) + 'a) {
+        self.observers.push(Box::new(observer));
+    }
+
+    #[must_use]
+    /// Get the [Computer] used by the [Runner]
+    pub const fn computer(&self) -> &Computer {
+        &self.computer
+    }
+
+    #[must_use]
+    /// Get the number of instructions run
+    pub const fn cycles(&self) -> u32 {
+        self.cycles
+    }
+
+    /// Consume the [Runner], returning its input and output
+    pub fn into_io(self) -> (R, W) {
+        (self.input, self.output)
+    }
+
+    /// End a line of char outputs, if one was started
+    #[cfg_attr(
+        not(feature = "extended"),
+        allow(
+            clippy::unused_self,
+            clippy::unnecessary_wraps,
+            clippy::missing_const_for_fn,
+            clippy::needless_pass_by_ref_mut
+        )
+    )]
+    fn end_char_sequence(&mut self) -> io::Result<()> {
+        #[cfg(feature = "extended")]
+        if self.mid_char_sequence {
+            writeln!(self.output)?;
+            self.mid_char_sequence = false;
+        }
+
+        Ok(())
+    }
+
+    /// Read a line of input of the kind into `buffer`, from the char input if there is one
+    #[cfg_attr(not(feature = "extended"), allow(unused_variables))]
+    fn read_line(&mut self, kind: Kind, buffer: &mut String) -> io::Result<usize> {
+        #[cfg(feature = "extended")]
+        if let (Kind::Char, Some(input)) = (kind, &mut self.char_input) {
+            return input.read_line(buffer);
+        }
+
+        self.input.read_line(buffer)
+    }
+
+    /// Write a prompt (if enabled) and read a line of input into `buffer`,
+    ///  returning `false` if the input has no more lines
+    fn read_input(&mut self, prompt: &str, kind: Kind, buffer: &mut String) -> io::Result<bool> {
+        let echo = self.prompts && self.echo;
+
+        if self.prompts {
+            write!(self.output, "{prompt}")?;
+            self.output.flush()?;
+        }
+
+        if self.read_line(kind, buffer)? == 0 {
+            if echo {
+                writeln!(self.output)?;
+            }
+            return Ok(false);
+        }
+
+        let line = buffer.trim_end_matches(['\r', '\n']);
+
+        if echo {
+            writeln!(self.output, "{line}")?;
+        }
+
+        if let Some(recording) = &mut self.recording {
+            recording.record(kind, line)?;
+        }
+
+        Ok(true)
+    }
+
+    /// Deal with the input running out, according to the [`EndOfInput`] policy
+    ///
+    /// # Errors
+    /// [`Error::EndOfInput`] - the policy is [`EndOfInput::Error`]
+    fn handle_end_of_input(
+        &mut self,
+        give: fn(&mut Computer, ThreeDigitNumber) -> Result<(), computer::Error>,
+    ) -> Result<(), Error> {
+        match self.end_of_input {
+            EndOfInput::Error => return Err(Error::EndOfInput),
+            EndOfInput::Zero => {
+                give(&mut self.computer, ThreeDigitNumber::ZERO)?;
+            }
+            EndOfInput::Halt => Computer::set_state(&mut self.computer, State::Halted),
+        }
+
+        Ok(())
+    }
+
+    /// Deal with an invalid input, according to the [`Retry`] policy.
+    /// If the input can be retried, `retries` is incremented and the error is written
+    ///  to the output, if prompts are on (otherwise only the program's outputs are written)
+    ///
+    /// # Errors
+    /// `error` - the input cannot be retried
+    fn retry(&mut self, error: Error, retries: &mut u32) -> Result<(), Error> {
+        let retry = match self.retry {
+            Retry::FailFast => false,
+            Retry::Times(times) => *retries < times,
+            Retry::Forever => true,
+        };
+        if !retry {
+            return Err(error);
+        }
+
+        if self.prompts {
+            writeln!(self.output, "{error}")?;
+        }
+        *retries += 1;
+        Ok(())
+    }
+
+    /// Tell the observers about an event
+    fn notify(&mut self, event: &Event) {
+        for observer in &mut self.observers {
+            observer(event);
+        }
+    }
+
+    /// Deal with any input or output the computer is waiting for
     ///
     /// # Errors
     /// See [Error]
-    pub fn step(&mut self) -> Result<State, Error> {
-        match self.computer.step() {
+    fn handle_io(&mut self, state: State) -> Result<(), Error> {
+        match state {
             State::AwaitingInput => {
-                #[cfg(feature = "extended")]
-                if self.mid_char_sequence {
-                    println!();
-                    self.mid_char_sequence = false;
-                }
-
-                #[cfg(not(feature = "extended"))]
-                print!("> ");
-                #[cfg(feature = "extended")]
-                print!("(i) > ");
-                stdout().flush()?;
+                self.end_char_sequence()?;
+                self.notify(&Event::InputRequested);
 
                 let mut buffer = String::with_capacity(4);
-                stdin().lock().read_line(&mut buffer)?;
+                let mut retries = 0;
+                let num = loop {
+                    buffer.clear();
+                    #[cfg(not(feature = "extended"))]
+                    let read = self.read_input("> ", Kind::Number, &mut buffer)?;
+                    #[cfg(feature = "extended")]
+                    let read = self.read_input("(i) > ", Kind::Number, &mut buffer)?;
 
-                let num: ThreeDigitNumber = buffer.trim().parse::<u16>()?.try_into()?;
+                    if !read {
+                        self.handle_end_of_input(Computer::input)?;
+                        return Ok(());
+                    }
 
-                self.computer
-                    .input(num)
-                    .expect("failed to give an input to a computer");
+                    match self.number_formats.parse(&buffer) {
+                        Ok(num) => break num,
+                        Err(error) => self.retry(error, &mut retries)?,
+                    }
+                };
+
+                self.computer.input(num)?;
             }
             State::AwaitingOutput => {
-                #[cfg(feature = "extended")]
-                if self.mid_char_sequence {
-                    println!();
-                    self.mid_char_sequence = false;
-                }
+                self.end_char_sequence()?;
 
-                let output: u16 = self
-                    .computer
-                    .output()
-                    .expect("failed to get an output from a computer")
-                    .into();
-                println!("{output}");
+                let output = self.computer.output()?;
+                self.notify(&Event::Output(output));
+                writeln!(self.output, "{}", u16::from(output))?;
             }
             #[cfg(feature = "extended")]
             State::AwaitingCharInput => {
-                #[cfg(feature = "extended")]
-                if self.mid_char_sequence {
-                    println!();
-                    self.mid_char_sequence = false;
-                }
-
-                print!("(c) > ");
-                stdout().flush()?;
+                self.end_char_sequence()?;
+                self.notify(&Event::CharInputRequested);
 
                 let mut buffer = String::with_capacity(2);
-                stdin().lock().read_line(&mut buffer)?;
-
-                let mut chars = buffer.chars();
-
-                let character = chars.next().unwrap_or('\n');
-
-                let after: String = chars.collect();
-                if !after.trim().is_empty() {
-                    return Err(Error::MultipleCharacters);
-                }
-
-                let num = character as u32;
-                if num >= 1000 {
-                    return Err(Error::InvalidInputCharacter);
-                }
+                let mut retries = 0;
+                let num = loop {
+                    buffer.clear();
+                    if !self.read_input("(c) > ", Kind::Char, &mut buffer)? {
+                        self.handle_end_of_input(Computer::input_char)?;
+                        return Ok(());
+                    }
 
-                #[allow(clippy::cast_possible_truncation)]
-                let num = unsafe { ThreeDigitNumber::from_unchecked(num as u16) };
+                    match parse_char(&buffer) {
+                        Ok(num) => break num,
+                        Err(error) => self.retry(error, &mut retries)?,
+                    }
+                };
 
-                self.computer
-                    .input_char(num)
-                    .expect("failed to give a char input to a computer");
+                self.computer.input_char(num)?;
             }
             #[cfg(feature = "extended")]
             State::AwaitingCharOutput => {
-                let num = self
-                    .computer
-                    .output_char()
-                    .expect("failed to get a char output from a computer");
-
-                let char = char::from_u32(u32::from(u16::from(num)))
-                    .ok_or(Error::InvalidOutputCharacter(num))?;
-                print!("{char}");
-
-                #[cfg(feature = "extended")]
-                if char == '\n' {
-                    self.mid_char_sequence = false;
-                }
+                let num = self.computer.output_char()?;
+                self.notify(&Event::CharOutput(num));
+
+                let char = char::from_u32(num.into()).ok_or(Error::InvalidOutputCharacter(num))?;
+                write!(self.output, "{char}")?;
+                self.mid_char_sequence = char != '\n';
             }
             _ => (),
         }
 
-        Ok(self.computer.state())
+        Ok(())
+    }
+
+    /// Step the computer without dealing with its inputs or outputs,
+    ///  writing the trace and telling the observers
+    ///
+    /// # Errors
+    /// If the trace cannot be written
+    fn step_computer(&mut self) -> io::Result<State> {
+        let before = self.computer.state();
+        let written = if before == State::Running {
+            if let Some(trace) = &mut self.trace {
+                writeln!(trace, "{}", Trace::new(&self.computer, self.cycles))?;
+            }
+            self.cycles += 1;
+
+            // Only STO writes to memory
+            self.computer
+                .counter()
+                .map(|counter| self.computer.get_memory()[counter].split_op())
+                .filter(|(op_code, _)| *op_code == 3)
+                .map(|(_, address)| usize::from(address))
+        } else {
+            None
+        };
+
+        let state = self.computer.step();
+
+        if let Some(address) = written {
+            let value = self.computer.get_memory()[address];
+            self.notify(&Event::MemoryWritten { address, value });
+        }
+        if state != before {
+            self.notify(&Event::StateChanged(state));
+        }
+
+        Ok(state)
+    }
+
+    /// Step the computer, using stdio for inputs and outputs
+    ///
+    /// # Errors
+    /// See [Error]
+    pub fn step(&mut self) -> Result<State, Error> {
+        if let Some(max_cycles) = self.max_cycles {
+            if self.computer.state() == State::Running && self.cycles >= max_cycles {
+                return Err(Error::TooManyCycles(max_cycles));
+            }
+        }
+
+        let state = self.step_computer()?;
+        self.handle_io(state)?;
+
+        let after = self.computer.state();
+        if after != state {
+            self.notify(&Event::StateChanged(after));
+        }
+
+        Ok(after)
     }
 
     /// Run the computer until a halt or error state is reached
@@ -204,14 +721,440 @@ impl Runner {
             match self.step()? {
                 State::Running => (),
                 state => {
-                    #[cfg(feature = "extended")]
-                    if self.mid_char_sequence {
-                        println!();
-                        self.mid_char_sequence = false;
-                    }
+                    self.end_char_sequence()?;
                     return Ok(state);
                 }
             }
         }
     }
 }
+
+/// Steps the [Runner], which reads the inputs from its input with its [`Retry`],
+///  [`EndOfInput`] and [`NumberFormats`] policies, and leaves the outputs for the test.
+/// If an input cannot be read (for example when the input runs out with [`EndOfInput::Error`]),
+///  the computer is left waiting for the test to give it.
+/// The maximum number of cycles of the test is used instead of the runner's
+impl<R: BufRead, W: Write> Backend for Runner<'_, R, W> {
+    fn step(&mut self) -> State {
+        let Ok(state) = self.step_computer() else {
+            return self.computer.state();
+        };
+
+        #[cfg(not(feature = "extended"))]
+        let input = state == State::AwaitingInput;
+        #[cfg(feature = "extended")]
+        let input = matches!(state, State::AwaitingInput | State::AwaitingCharInput);
+
+        // An input that cannot be read is left for the test
+        if input && self.handle_io(state).is_ok() {
+            let after = self.computer.state();
+            if after != state {
+                self.notify(&Event::StateChanged(after));
+            }
+            return after;
+        }
+
+        state
+    }
+
+    fn computer(&self) -> &Computer {
+        &self.computer
+    }
+
+    fn computer_mut(&mut self) -> &mut Computer {
+        &mut self.computer
+    }
+
+    fn input(&mut self, input: ThreeDigitNumber) -> Result<(), computer::Error> {
+        self.computer.input(input)
+    }
+
+    fn output(&mut self) -> Result<ThreeDigitNumber, computer::Error> {
+        let output = self.computer.output()?;
+        self.notify(&Event::Output(output));
+        Ok(output)
+    }
+
+    #[cfg(feature = "extended")]
+    fn input_char(&mut self, input: ThreeDigitNumber) -> Result<(), computer::Error> {
+        self.computer.input_char(input)
+    }
+
+    #[cfg(feature = "extended")]
+    fn output_char(&mut self) -> Result<ThreeDigitNumber, computer::Error> {
+        let output = self.computer.output_char()?;
+        self.notify(&Event::CharOutput(output));
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        assembler::assemble_from_text,
+        computer::{Computer, State},
+        num2::TwoDigitNumber,
+        num3::ThreeDigitNumber,
+        runner::{observer::Event, tester::TestBuilder},
+    };
+
+    use super::{EndOfInput, Error, NumberFormats, Retry, Runner};
+
+    #[test]
+    fn trace() {
+        let memory = assemble_from_text("LDA 3\nADD 3\nHLT\nDAT 2")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        let mut trace = Vec::new();
+        let mut runner =
+            Runner::new_with_io(Computer::new(memory), &b""[..], Vec::new()).with_trace(&mut trace);
+
+        assert_eq!(
+            runner.run().expect("failed to run"),
+            State::Halted,
+            "The computer did not halt!"
+        );
+        assert_eq!(runner.cycles(), 3, "Ran the wrong number of cycles!");
+        drop(runner);
+
+        let trace = String::from_utf8(trace).expect("trace was not utf-8");
+        let lines: Vec<&str> = trace.lines().collect();
+
+        assert_eq!(lines.len(), 3, "Wrote the wrong number of trace lines!");
+        assert!(
+            lines[1].starts_with("    1  01  103  002"),
+            "Wrote the wrong second trace line!"
+        );
+    }
+
+    #[test]
+    fn replay_inputs() {
+        let memory = assemble_from_text("loop IN\nOUT\nBR loop")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        let mut runner = Runner::new_with_io(Computer::new(memory), &b""[..], Vec::new())
+            .with_input(&b"5\n6\n"[..]);
+
+        assert!(
+            matches!(runner.run(), Err(Error::EndOfInput)),
+            "Did not error when the inputs ran out!"
+        );
+
+        let (_, output) = runner.into_io();
+        let output = String::from_utf8(output).expect("output was not utf-8");
+
+        #[cfg(not(feature = "extended"))]
+        assert_eq!(output, "> 5\n5\n> 6\n6\n> \n", "Wrote the wrong output!");
+        #[cfg(feature = "extended")]
+        assert_eq!(
+            output, "(i) > 5\n5\n(i) > 6\n6\n(i) > \n",
+            "Wrote the wrong output!"
+        );
+    }
+
+    #[cfg(feature = "extended")]
+    #[test]
+    fn char_input() {
+        let memory = assemble_from_text("EXT\nINA\nOTA\nIN\nOUT\nINA\nOTA\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        let mut chars = &b"a\nb\n"[..];
+        let mut runner = Runner::new_with_io(Computer::new(memory), &b"5\n"[..], Vec::new())
+            .with_prompts(false)
+            .with_char_input(&mut chars);
+        assert_eq!(
+            runner.run().expect("failed to run"),
+            State::Halted,
+            "The computer did not halt!"
+        );
+
+        let (_, output) = runner.into_io();
+        assert_eq!(
+            String::from_utf8(output).expect("output was not utf-8"),
+            "a\n5\nb\n",
+            "Did not read the chars from the char input!"
+        );
+    }
+
+    #[test]
+    fn max_cycles() {
+        let memory = assemble_from_text("loop BR loop")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        let mut runner =
+            Runner::new_with_io(Computer::new(memory), &b""[..], Vec::new()).with_max_cycles(50);
+        assert!(
+            matches!(runner.run(), Err(Error::TooManyCycles(50))),
+            "Did not stop the infinite loop!"
+        );
+        assert_eq!(runner.cycles(), 50, "Ran the wrong number of cycles!");
+
+        let memory = assemble_from_text("LDA 3\nOUT\nHLT\nDAT 5")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+        let mut runner =
+            Runner::new_with_io(Computer::new(memory), &b""[..], Vec::new()).with_max_cycles(3);
+        assert_eq!(
+            runner
+                .run()
+                .expect("failed to run within the maximum cycles"),
+            State::Halted,
+            "The computer did not halt!"
+        );
+    }
+
+    #[test]
+    fn end_of_input() {
+        let memory = assemble_from_text("IN\nOUT\nIN\nOUT\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        let mut runner = Runner::new_with_io(Computer::new(memory), &b"5\n"[..], Vec::new())
+            .with_end_of_input(EndOfInput::Zero);
+        assert_eq!(
+            runner.run().expect("failed to run"),
+            State::Halted,
+            "The computer did not halt!"
+        );
+        let (_, output) = runner.into_io();
+        let output = String::from_utf8(output).expect("output was not utf-8");
+        assert!(
+            output.ends_with("0\n"),
+            "Did not input 0 at the end of input!"
+        );
+
+        let mut runner = Runner::new_with_io(Computer::new(memory), &b"5\n"[..], Vec::new())
+            .with_end_of_input(EndOfInput::Halt);
+        assert_eq!(
+            runner.run().expect("failed to run"),
+            State::Halted,
+            "The computer did not halt at the end of input!"
+        );
+        assert_eq!(
+            runner.computer().counter(),
+            Some(TwoDigitNumber::new::<3>()),
+            "The computer did not halt at the second input!"
+        );
+    }
+
+    #[test]
+    fn number_formats() {
+        let parse = |input| NumberFormats::ALL.parse(input).map(u16::from);
+
+        assert_eq!(
+            parse("42").expect("failed to parse '42'"),
+            42,
+            "Parsed '42' wrong!"
+        );
+        assert_eq!(
+            parse("-1").expect("failed to parse '-1'"),
+            999,
+            "Parsed '-1' wrong!"
+        );
+        assert_eq!(
+            parse("-500").expect("failed to parse '-500'"),
+            500,
+            "Parsed '-500' wrong!"
+        );
+        assert_eq!(
+            parse("-0").expect("failed to parse '-0'"),
+            0,
+            "Parsed '-0' wrong!"
+        );
+        assert_eq!(
+            parse("0x3e7").expect("failed to parse '0x3e7'"),
+            999,
+            "Parsed '0x3e7' wrong!"
+        );
+        assert_eq!(
+            parse("-0x10").expect("failed to parse '-0x10'"),
+            984,
+            "Parsed '-0x10' wrong!"
+        );
+        assert_eq!(
+            parse("'a'").expect("failed to parse \"'a'\""),
+            97,
+            "Parsed \"'a'\" wrong!"
+        );
+
+        assert!(
+            matches!(parse("-501"), Err(Error::TooSmall)),
+            "Parsed '-501'!"
+        );
+        assert!(
+            matches!(parse("0x3e8"), Err(Error::TooLarge(_))),
+            "Parsed '0x3e8'!"
+        );
+        assert!(
+            matches!(parse("'ab'"), Err(Error::InvalidQuotedCharacter)),
+            "Parsed \"'ab'\"!"
+        );
+
+        let plain = NumberFormats::default();
+        assert!(
+            plain.parse("-1").is_err(),
+            "Parsed '-1' without negative numbers on!"
+        );
+        assert!(
+            plain.parse("0x10").is_err(),
+            "Parsed '0x10' without hex on!"
+        );
+        assert!(
+            plain.parse("'a'").is_err(),
+            "Parsed \"'a'\" without quoted chars on!"
+        );
+
+        let memory = assemble_from_text("IN\nOUT\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+        let mut runner = Runner::new_with_io(Computer::new(memory), &b"-2\n"[..], Vec::new())
+            .with_number_formats(NumberFormats::ALL);
+        assert_eq!(
+            runner.run().expect("failed to run"),
+            State::Halted,
+            "The computer did not halt!"
+        );
+        let (_, output) = runner.into_io();
+        let output = String::from_utf8(output).expect("output was not utf-8");
+        assert!(output.ends_with("998\n"), "Did not input -2 as 998!");
+    }
+
+    #[test]
+    fn retry() {
+        let memory = assemble_from_text("IN\nOUT\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        let mut runner = Runner::new_with_io(Computer::new(memory), &b"a\n5\n"[..], Vec::new());
+        assert!(
+            matches!(runner.run(), Err(Error::ParseError(_))),
+            "Did not fail fast on an invalid input!"
+        );
+
+        let mut runner =
+            Runner::new_with_io(Computer::new(memory), &b"a\n1000\n5\n"[..], Vec::new())
+                .with_retry(Retry::Times(2));
+        assert_eq!(
+            runner.run().expect("failed to run"),
+            State::Halted,
+            "The computer did not halt!"
+        );
+        let (_, output) = runner.into_io();
+        let output = String::from_utf8(output).expect("output was not utf-8");
+        assert!(
+            output.contains("Invalid number inputted!") && output.ends_with("5\n"),
+            "Did not retry the invalid inputs!"
+        );
+
+        let mut runner = Runner::new_with_io(Computer::new(memory), &b"a\n5\n"[..], Vec::new())
+            .with_retry(Retry::Times(1))
+            .with_prompts(false);
+        runner.run().expect("failed to run");
+        let (_, output) = runner.into_io();
+        assert_eq!(
+            String::from_utf8(output).expect("output was not utf-8"),
+            "5\n",
+            "Wrote the error without prompts!"
+        );
+
+        let mut runner = Runner::new_with_io(Computer::new(memory), &b"a\nb\n5\n"[..], Vec::new())
+            .with_retry(Retry::Times(1));
+        assert!(
+            matches!(runner.run(), Err(Error::ParseError(_))),
+            "Retried too many times!"
+        );
+    }
+
+    #[test]
+    fn backend() {
+        let memory = assemble_from_text("IN\nOUT\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+        let test = || {
+            TestBuilder::new()
+                .with_max_cycles(10)
+                .with_output(5)
+                .with_state(State::Halted)
+        };
+
+        let mut runner = Runner::new_with_io(Computer::new(memory), &b"a\n5\n"[..], Vec::new())
+            .with_retry(Retry::Times(1));
+        assert_eq!(
+            test()
+                .build()
+                .expect("failed to build the test")
+                .run(&mut runner)
+                .expect("failed to run the test through a runner"),
+            3,
+            "The test ran the wrong number of cycles!"
+        );
+        let (_, output) = runner.into_io();
+        assert!(
+            String::from_utf8(output)
+                .expect("output was not utf-8")
+                .contains("Invalid number inputted!"),
+            "Did not retry the invalid input!"
+        );
+
+        // The runner has no inputs, so the test gives it
+        let mut runner = Runner::new_with_io(Computer::new(memory), &b""[..], Vec::new());
+        test()
+            .with_input(5)
+            .build()
+            .expect("failed to build the test")
+            .run(&mut runner)
+            .expect("failed to give the input from the test");
+    }
+
+    #[test]
+    fn observers() {
+        let memory = assemble_from_text("IN\nSTO 99\nOUT\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        let mut events = Vec::new();
+        let mut runner = Runner::new_with_io(Computer::new(memory), &b"7\n"[..], Vec::new())
+            .with_observer(|event| events.push(*event));
+        runner.run().expect("failed to run");
+        drop(runner);
+
+        let seven = ThreeDigitNumber::new::<7>();
+        assert_eq!(
+            events,
+            [
+                Event::StateChanged(State::AwaitingInput),
+                Event::InputRequested,
+                Event::StateChanged(State::Running),
+                Event::MemoryWritten {
+                    address: 99,
+                    value: seven
+                },
+                Event::StateChanged(State::AwaitingOutput),
+                Event::Output(seven),
+                Event::StateChanged(State::Running),
+                Event::StateChanged(State::Halted),
+            ],
+            "Observed the wrong events!"
+        );
+    }
+
+    #[test]
+    fn without_prompts() {
+        let memory = assemble_from_text("IN\nOUT\nIN\nOUT\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        let mut runner = Runner::new_with_io(Computer::new(memory), &b""[..], Vec::new())
+            .with_input(&b"5\n6\n"[..])
+            .with_prompts(false);
+        runner.run().expect("failed to run");
+
+        let (_, output) = runner.into_io();
+        let output = String::from_utf8(output).expect("output was not utf-8");
+        assert_eq!(output, "5\n6\n", "Wrote prompts or echoed inputs!");
+    }
+}
@@ -1,35 +1,55 @@
 extern crate alloc;
 use core::{fmt, num::ParseIntError};
 
-use alloc::collections::linked_list::{IntoIter, LinkedList};
+#[cfg(feature = "extended")]
+use alloc::borrow::Cow;
+use alloc::{
+    collections::linked_list::{IntoIter, LinkedList},
+    vec::Vec,
+};
+#[cfg(any(feature = "json", feature = "toml"))]
+use alloc::{collections::BTreeMap, string::String};
+
+#[cfg(any(feature = "json", feature = "toml"))]
+use serde::Deserialize;
 
+#[cfg(any(feature = "json", feature = "toml"))]
+use crate::computer::State;
 use crate::{
-    errors::{self, LineNumber},
+    errors::{self, Code, ErrorCode, LineNumber},
     num3::ThreeDigitNumber,
 };
 
-use super::Test;
+use super::{MatcherError, OutputMatcher, Test};
 
 #[cfg(feature = "extended")]
-/// A test for programs using [`LinkedList`]s for the inputs and outputs
+/// A test for programs using [`LinkedList`]s for the inputs, outputs and memory cells
 pub type StdTest<'a> = Test<
     'a,
     IntoIter<ThreeDigitNumber>,
-    IntoIter<ThreeDigitNumber>,
+    IntoIter<OutputMatcher>,
+    IntoIter<(usize, ThreeDigitNumber)>,
+    IntoIter<(usize, ThreeDigitNumber)>,
     IntoIter<ThreeDigitNumber>,
     IntoIter<ThreeDigitNumber>,
 >;
 
 #[cfg(not(feature = "extended"))]
-/// A test for programs using [`LinkedList`]s for the inputs and outputs
-pub type StdTest<'a> = Test<'a, IntoIter<ThreeDigitNumber>, IntoIter<ThreeDigitNumber>>;
+/// A test for programs using [`LinkedList`]s for the inputs, outputs and memory cells
+pub type StdTest<'a> = Test<
+    'a,
+    IntoIter<ThreeDigitNumber>,
+    IntoIter<OutputMatcher>,
+    IntoIter<(usize, ThreeDigitNumber)>,
+    IntoIter<(usize, ThreeDigitNumber)>,
+>;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 /// CSV parsing errors
 pub enum CSVError {
-    /// A line did not have exactly 4 sections (or 6 with extended mode)
+    /// A line did not have 4 or 5 sections (or 6 or 7 with extended mode)
     NumberOfSections(usize),
-    /// The max_cycles entry was not a valid number
+    /// The `max_cycles` entry was not a valid number
     InvalidMaxCycles(ParseIntError),
     /// An input number was not a valid number
     InvalidInputNumber(ParseIntError),
@@ -39,12 +59,32 @@ pub enum CSVError {
     InvalidOutputNumber(ParseIntError),
     /// An output number was too large
     OutputTooLarge(u16),
+    /// An output range did not contain any numbers
+    EmptyOutputRange,
     #[cfg(feature = "extended")]
     /// An input character was not a valid input character
     InvalidCharInput(char),
     #[cfg(feature = "extended")]
     /// An output character was not a valid output character
     InvalidCharOutput(char),
+    /// A memory cell was not in the format `address=value`
+    InvalidMemoryCell,
+    /// A memory address was not a valid number
+    InvalidAddress(ParseIntError),
+    /// A memory address was too large
+    AddressTooLarge(usize),
+    /// A memory value was not a valid number
+    InvalidMemoryValue(ParseIntError),
+    /// A memory value was too large
+    MemoryValueTooLarge(u16),
+    /// A quoted section was not closed
+    UnterminatedQuote,
+    /// A quoted section did not end at a separator
+    UnexpectedQuote,
+    /// A quoted name contained an escaped quote (`""`)
+    EscapedQuoteInName,
+    /// The separator cannot be used, as it is a quote
+    InvalidSeparator(char),
 }
 
 impl fmt::Display for CSVError {
@@ -52,12 +92,15 @@ impl fmt::Display for CSVError {
         match self {
             #[cfg(not(feature = "extended"))]
             Self::NumberOfSections(sections) => {
-                write!(f, "Wrong number of sections ({sections}, should be 4)!")
+                write!(
+                    f,
+                    "Wrong number of sections ({sections}, should be 4 or 5)!"
+                )
             }
             #[cfg(feature = "extended")]
             Self::NumberOfSections(sections) => write!(
                 f,
-                "Wrong number of sections ({sections}, should be 4 or 6)!"
+                "Wrong number of sections ({sections}, should be 4 to 7)!"
             ),
             Self::InvalidMaxCycles(_) => write!(f, "Invalid maximum number of cycles!"),
             Self::InvalidInputNumber(_) => write!(f, "Invalid input number!"),
@@ -68,6 +111,7 @@ impl fmt::Display for CSVError {
             Self::OutputTooLarge(number) => {
                 write!(f, "Output number too large ({number} should be < 1000)!")
             }
+            Self::EmptyOutputRange => write!(f, "Empty output range!"),
             #[cfg(feature = "extended")]
             Self::InvalidCharInput(character) => {
                 write!(f, "Invalid input character ({character:?})!")
@@ -76,6 +120,53 @@ impl fmt::Display for CSVError {
             Self::InvalidCharOutput(character) => {
                 write!(f, "Invalid output character ({character:?})!")
             }
+            Self::InvalidMemoryCell => {
+                write!(f, "Invalid memory cell (should be address=value)!")
+            }
+            Self::InvalidAddress(_) => write!(f, "Invalid memory address!"),
+            Self::AddressTooLarge(address) => {
+                write!(f, "Memory address too large ({address} should be < 100)!")
+            }
+            Self::InvalidMemoryValue(_) => write!(f, "Invalid memory value!"),
+            Self::MemoryValueTooLarge(number) => {
+                write!(f, "Memory value too large ({number} should be < 1000)!")
+            }
+            Self::UnterminatedQuote => write!(f, "Quoted section was not closed!"),
+            Self::UnexpectedQuote => write!(
+                f,
+                "Unexpected quote (quoted sections must end at a separator)!"
+            ),
+            Self::EscapedQuoteInName => write!(f, "Names cannot contain escaped quotes!"),
+            Self::InvalidSeparator(separator) => {
+                write!(f, "Invalid separator ({separator:?})!")
+            }
+        }
+    }
+}
+
+impl Code for CSVError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            Self::NumberOfSections(_) => ErrorCode(601),
+            Self::InvalidMaxCycles(_) => ErrorCode(602),
+            Self::InvalidInputNumber(_) => ErrorCode(603),
+            Self::InputTooLarge(_) => ErrorCode(604),
+            Self::InvalidOutputNumber(_) => ErrorCode(605),
+            Self::OutputTooLarge(_) => ErrorCode(606),
+            Self::EmptyOutputRange => ErrorCode(607),
+            #[cfg(feature = "extended")]
+            Self::InvalidCharInput(_) => ErrorCode(608),
+            #[cfg(feature = "extended")]
+            Self::InvalidCharOutput(_) => ErrorCode(609),
+            Self::InvalidMemoryCell => ErrorCode(610),
+            Self::InvalidAddress(_) => ErrorCode(611),
+            Self::AddressTooLarge(_) => ErrorCode(612),
+            Self::InvalidMemoryValue(_) => ErrorCode(613),
+            Self::MemoryValueTooLarge(_) => ErrorCode(614),
+            Self::UnterminatedQuote => ErrorCode(615),
+            Self::UnexpectedQuote => ErrorCode(616),
+            Self::EscapedQuoteInName => ErrorCode(617),
+            Self::InvalidSeparator(_) => ErrorCode(618),
         }
     }
 }
@@ -86,7 +177,9 @@ impl std::error::Error for CSVError {
         match self {
             Self::InvalidMaxCycles(error)
             | Self::InvalidInputNumber(error)
-            | Self::InvalidOutputNumber(error) => Some(error),
+            | Self::InvalidOutputNumber(error)
+            | Self::InvalidAddress(error)
+            | Self::InvalidMemoryValue(error) => Some(error),
             _ => None,
         }
     }
@@ -94,125 +187,412 @@ impl std::error::Error for CSVError {
 
 pub type CSVErrorWithLineNumber = errors::ErrorWithLocation<CSVError, LineNumber>;
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Errors for creating tests from numbers
+pub enum NumberError {
+    /// An input number was too large
+    InputTooLarge(u16),
+    /// An output number was too large
+    OutputTooLarge(u16),
+    /// A memory address was too large
+    AddressTooLarge(usize),
+    /// A memory value was too large
+    MemoryValueTooLarge(u16),
+    /// The register value was too large
+    RegisterTooLarge(u16),
+    #[cfg(feature = "extended")]
+    /// An input character was not a valid input character
+    InvalidCharInput(char),
+    #[cfg(feature = "extended")]
+    /// An output character was not a valid output character
+    InvalidCharOutput(char),
+}
+
+impl fmt::Display for NumberError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InputTooLarge(number) => {
+                write!(f, "Input number too large ({number} should be < 1000)!")
+            }
+            Self::OutputTooLarge(number) => {
+                write!(f, "Output number too large ({number} should be < 1000)!")
+            }
+            Self::AddressTooLarge(address) => {
+                write!(f, "Memory address too large ({address} should be < 100)!")
+            }
+            Self::MemoryValueTooLarge(number) => {
+                write!(f, "Memory value too large ({number} should be < 1000)!")
+            }
+            Self::RegisterTooLarge(number) => {
+                write!(f, "Register value too large ({number} should be < 1000)!")
+            }
+            #[cfg(feature = "extended")]
+            Self::InvalidCharInput(character) => {
+                write!(f, "Invalid input character ({character:?})!")
+            }
+            #[cfg(feature = "extended")]
+            Self::InvalidCharOutput(character) => {
+                write!(f, "Invalid output character ({character:?})!")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NumberError {}
+
+#[cfg(any(feature = "json", feature = "toml"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+/// The state that a test expects the computer to stop in,
+///  written as `"halted"` or `"reached_end"`
+pub enum FinalState {
+    Halted,
+    ReachedEnd,
+}
+
+#[cfg(any(feature = "json", feature = "toml"))]
+impl From<FinalState> for State {
+    fn from(value: FinalState) -> Self {
+        match value {
+            FinalState::Halted => Self::Halted,
+            FinalState::ReachedEnd => Self::ReachedEnd,
+        }
+    }
+}
+
+#[cfg(any(feature = "json", feature = "toml"))]
+/// Convert numbers to an iterator, using `error` for numbers that are too large
+pub(super) fn to_numbers<E>(
+    numbers: &[u16],
+    error: fn(u16) -> E,
+) -> Result<IntoIter<ThreeDigitNumber>, E> {
+    numbers
+        .iter()
+        .map(|number| ThreeDigitNumber::try_from(*number).map_err(|_| error(*number)))
+        .collect::<Result<LinkedList<_>, _>>()
+        .map(LinkedList::into_iter)
+}
+
+#[cfg(any(feature = "json", feature = "toml"))]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+/// An expected output as it is written in JSON or TOML,
+///  either a number or an [`OutputMatcher`] such as `"*"` or `"10..20"`
+pub enum RawOutput {
+    Number(u16),
+    Matcher(String),
+}
+
+#[cfg(any(feature = "json", feature = "toml"))]
+/// Convert expected outputs to an iterator, using `too_large` for numbers that are too large
+///  and `invalid` for matchers that could not be parsed
+pub(super) fn to_outputs<E>(
+    outputs: &[RawOutput],
+    too_large: fn(u16) -> E,
+    invalid: fn(MatcherError) -> E,
+) -> Result<IntoIter<OutputMatcher>, E> {
+    outputs
+        .iter()
+        .map(|output| match output {
+            RawOutput::Number(number) => ThreeDigitNumber::try_from(*number)
+                .map(OutputMatcher::Exact)
+                .map_err(|_| too_large(*number)),
+            RawOutput::Matcher(matcher) => matcher.parse().map_err(|error| match error {
+                MatcherError::TooLarge(number) => too_large(number),
+                error => invalid(error),
+            }),
+        })
+        .collect::<Result<LinkedList<_>, _>>()
+        .map(LinkedList::into_iter)
+}
+
+#[cfg(any(feature = "json", feature = "toml"))]
+/// Convert memory cells to an iterator, using `address_error` for addresses that are too large
+///  and `value_error` for values that are too large
+pub(super) fn to_cells<E>(
+    cells: &BTreeMap<usize, u16>,
+    address_error: fn(usize) -> E,
+    value_error: fn(u16) -> E,
+) -> Result<IntoIter<(usize, ThreeDigitNumber)>, E> {
+    cells
+        .iter()
+        .map(|(address, value)| {
+            if *address >= 100 {
+                return Err(address_error(*address));
+            }
+
+            Ok((
+                *address,
+                ThreeDigitNumber::try_from(*value).map_err(|_| value_error(*value))?,
+            ))
+        })
+        .collect::<Result<LinkedList<_>, _>>()
+        .map(LinkedList::into_iter)
+}
+
+#[cfg(feature = "extended")]
+/// Convert a character to its number, if it is < 1000
+pub(super) fn char_number(character: char) -> Option<ThreeDigitNumber> {
+    ThreeDigitNumber::try_from(character).ok()
+}
+
+#[cfg(feature = "extended")]
+/// Convert characters to an iterator, using `error` for invalid characters
+pub(super) fn to_chars<E>(
+    chars: &str,
+    error: fn(char) -> E,
+) -> Result<IntoIter<ThreeDigitNumber>, E> {
+    chars
+        .chars()
+        .map(|character| char_number(character).ok_or_else(|| error(character)))
+        .collect::<Result<LinkedList<_>, _>>()
+        .map(LinkedList::into_iter)
+}
+
+/// Parse comma separated memory cells in the format `address=value`
+fn parse_memory_cells(text: &str) -> Result<IntoIter<(usize, ThreeDigitNumber)>, CSVError> {
+    text.split(',')
+        .filter(|cell| !cell.is_empty())
+        .map(|cell| {
+            let (address, value) = cell.split_once('=').ok_or(CSVError::InvalidMemoryCell)?;
+
+            let address = address.parse::<usize>().map_err(CSVError::InvalidAddress)?;
+            if address >= 100 {
+                return Err(CSVError::AddressTooLarge(address));
+            }
+
+            let value = value.parse::<u16>().map_err(CSVError::InvalidMemoryValue)?;
+            Ok((
+                address,
+                ThreeDigitNumber::try_from(value)
+                    .map_err(|_| CSVError::MemoryValueTooLarge(value))?,
+            ))
+        })
+        .collect::<Result<LinkedList<_>, _>>()
+        .map(LinkedList::into_iter)
+}
+
+/// The default separator between the sections of a line of CSV
+pub const DEFAULT_SEPARATOR: char = ';';
+
+/// A section of a line of CSV, without its surrounding quotes
+struct Field<'a> {
+    text: &'a str,
+    quoted: bool,
+}
+
+#[cfg(feature = "extended")]
+impl<'a> Field<'a> {
+    /// Get the text with escaped quotes (`""`) replaced with quotes
+    fn unescape(&self) -> Cow<'a, str> {
+        if self.quoted && self.text.contains("\"\"") {
+            Cow::Owned(self.text.replace("\"\"", "\""))
+        } else {
+            Cow::Borrowed(self.text)
+        }
+    }
+}
+
+/// Split a line of CSV into its sections.
+/// Sections can be wrapped in quotes (`"`) to include the separator,
+///  and quotes in quoted sections are escaped by doubling them (`""`).
+/// A quote is only special at the start of a section, so unquoted sections can contain quotes
+fn split_fields(text: &str, separator: char) -> Result<Vec<Field<'_>>, CSVError> {
+    if separator == '"' {
+        return Err(CSVError::InvalidSeparator(separator));
+    }
+
+    let mut fields = Vec::new();
+    let mut rest = text;
+
+    loop {
+        if let Some(quoted) = rest.strip_prefix('"') {
+            // Find the closing quote, skipping escaped quotes
+            let mut chars = quoted.char_indices().peekable();
+            let end = loop {
+                match chars.next() {
+                    Some((index, '"')) => {
+                        if chars.next_if(|(_, character)| *character == '"').is_none() {
+                            break index;
+                        }
+                    }
+                    Some(_) => (),
+                    None => return Err(CSVError::UnterminatedQuote),
+                }
+            };
+
+            fields.push(Field {
+                text: &quoted[..end],
+                quoted: true,
+            });
+
+            rest = &quoted[end + 1..];
+            if rest.is_empty() {
+                break;
+            }
+            rest = rest
+                .strip_prefix(separator)
+                .ok_or(CSVError::UnexpectedQuote)?;
+        } else {
+            let (field, next) = rest
+                .split_once(separator)
+                .map_or((rest, None), |(field, next)| (field, Some(next)));
+
+            fields.push(Field {
+                text: field,
+                quoted: false,
+            });
+
+            let Some(next) = next else {
+                break;
+            };
+            rest = next;
+        }
+    }
+
+    Ok(fields)
+}
+
+/// Parse comma separated numbers, using `invalid` and `too_large` for errors
+fn parse_numbers(
+    text: &str,
+    invalid: fn(ParseIntError) -> CSVError,
+    too_large: fn(u16) -> CSVError,
+) -> Result<IntoIter<ThreeDigitNumber>, CSVError> {
+    text.split(',')
+        .filter(|number| !number.is_empty())
+        .map(|number| {
+            let number = number.parse::<u16>().map_err(invalid)?;
+            ThreeDigitNumber::try_from(number).map_err(|_| too_large(number))
+        })
+        .collect::<Result<LinkedList<_>, _>>()
+        .map(LinkedList::into_iter)
+}
+
+/// Parse comma separated expected outputs (see [`OutputMatcher`])
+fn parse_outputs(text: &str) -> Result<IntoIter<OutputMatcher>, CSVError> {
+    text.split(',')
+        .filter(|output| !output.is_empty())
+        .map(|output| {
+            output.parse().map_err(|error| match error {
+                MatcherError::InvalidNumber(error) => CSVError::InvalidOutputNumber(error),
+                MatcherError::TooLarge(number) => CSVError::OutputTooLarge(number),
+                MatcherError::EmptyRange => CSVError::EmptyOutputRange,
+            })
+        })
+        .collect::<Result<LinkedList<_>, _>>()
+        .map(LinkedList::into_iter)
+}
+
 impl<'a> StdTest<'a> {
     #[cfg_attr(
         not(feature = "extended"),
-        doc = "Creates a new test from a line of csv in the format \n `name;comma separated inputs;comma separated outputs;maximum cycles`"
+        doc = "Creates a new test from a line of csv in the format \n `name;comma separated inputs;comma separated outputs;maximum cycles[;comma separated memory cells]`, where the contents of the `[..]` is optional, memory cells are `address=value` and outputs can also be `*` or ranges (`10..20` or `10..=20`)"
     )]
     #[cfg_attr(
         feature = "extended",
-        doc = "Creates a new test from a line of csv in the format \n `name;comma separated inputs;comma separated outputs;[non-separated char inputs; non-separated char outputs;]maximum cycles`, where the contents of the `[..]` is optional"
+        doc = "Creates a new test from a line of csv in the format \n `name;comma separated inputs;comma separated outputs;[non-separated char inputs; non-separated char outputs;]maximum cycles[;comma separated memory cells]`, where the contents of the `[..]`s are optional, memory cells are `address=value` and outputs can also be `*` or ranges (`10..20` or `10..=20`)"
     )]
+    ///
+    /// Sections can be wrapped in quotes (`"`) to include `;`, and quotes in
+    ///  quoted sections are escaped by doubling them (`""`).
+    /// Quotes in the middle of unquoted sections are kept as they are.
+    /// Names cannot contain escaped quotes, as they are borrowed from `text`
+    ///
+    /// # Errors
+    /// See [`CSVError`]
     pub fn from_csv_line(text: &'a str) -> Result<Self, CSVError> {
-        let mut sections = text.split(';');
+        Self::from_csv_line_with_separator(text, DEFAULT_SEPARATOR)
+    }
 
-        let number_of_sections = sections.clone().count();
+    /// Creates a new test from a line of csv, like [`StdTest::from_csv_line`],
+    ///  but with `separator` between the sections instead of `;`.
+    /// If `separator` is `,`, lists of numbers must be quoted
+    ///
+    /// # Errors
+    /// See [`CSVError`]
+    pub fn from_csv_line_with_separator(text: &'a str, separator: char) -> Result<Self, CSVError> {
+        let fields = split_fields(text, separator)?;
+        let number_of_sections = fields.len();
 
         #[cfg(not(feature = "extended"))]
-        if number_of_sections != 4 {
-            return Err(CSVError::NumberOfSections(number_of_sections));
-        }
-
-        #[cfg(feature = "extended")]
-        let char_io = match number_of_sections {
-            4 => false,
-            6 => true,
+        let (name, inputs, outputs, max_cycles, memory) = match fields.as_slice() {
+            [name, inputs, outputs, max_cycles] => (name, inputs, outputs, max_cycles, None),
+            [name, inputs, outputs, max_cycles, memory] => {
+                (name, inputs, outputs, max_cycles, Some(memory))
+            }
             _ => return Err(CSVError::NumberOfSections(number_of_sections)),
         };
 
-        let name = sections
-            .next()
-            .ok_or(CSVError::NumberOfSections(number_of_sections))?;
-
-        let inputs_str = sections
-            .next()
-            .ok_or(CSVError::NumberOfSections(number_of_sections))?;
-
-        let outputs_str = sections
-            .next()
-            .ok_or(CSVError::NumberOfSections(number_of_sections))?;
-
         #[cfg(feature = "extended")]
-        let (char_inputs_str, char_outputs_str) = if char_io {
-            (
-                Some(
-                    sections
-                        .next()
-                        .ok_or(CSVError::NumberOfSections(number_of_sections))?,
-                ),
-                Some(
-                    sections
-                        .next()
-                        .ok_or(CSVError::NumberOfSections(number_of_sections))?,
-                ),
-            )
-        } else {
-            (None, None)
+        let (name, inputs, outputs, char_io, max_cycles, memory) = match fields.as_slice() {
+            [name, inputs, outputs, max_cycles] => (name, inputs, outputs, None, max_cycles, None),
+            [name, inputs, outputs, max_cycles, memory] => {
+                (name, inputs, outputs, None, max_cycles, Some(memory))
+            }
+            [name, inputs, outputs, char_inputs, char_outputs, max_cycles] => (
+                name,
+                inputs,
+                outputs,
+                Some((char_inputs, char_outputs)),
+                max_cycles,
+                None,
+            ),
+            [name, inputs, outputs, char_inputs, char_outputs, max_cycles, memory] => (
+                name,
+                inputs,
+                outputs,
+                Some((char_inputs, char_outputs)),
+                max_cycles,
+                Some(memory),
+            ),
+            _ => return Err(CSVError::NumberOfSections(number_of_sections)),
         };
 
-        let max_cycles = sections
-            .next()
-            .ok_or(CSVError::NumberOfSections(number_of_sections))?;
-
-        let mut inputs = LinkedList::new();
-        let mut outputs = LinkedList::new();
-        #[cfg(feature = "extended")]
-        let mut char_inputs = LinkedList::new();
-        #[cfg(feature = "extended")]
-        let mut char_outputs = LinkedList::new();
-
-        for input in inputs_str.split(',').filter(|number| !number.is_empty()) {
-            let number = input.parse::<u16>().map_err(CSVError::InvalidInputNumber)?;
-            inputs.push_back(
-                ThreeDigitNumber::try_from(number).map_err(|_| CSVError::InputTooLarge(number))?,
-            );
-        }
-
-        for output in outputs_str.split(',').filter(|number| !number.is_empty()) {
-            let number = output
-                .parse::<u16>()
-                .map_err(CSVError::InvalidOutputNumber)?;
-            outputs.push_back(
-                ThreeDigitNumber::try_from(number).map_err(|_| CSVError::OutputTooLarge(number))?,
-            );
+        if name.quoted && name.text.contains('"') {
+            return Err(CSVError::EscapedQuoteInName);
         }
 
         #[cfg(feature = "extended")]
-        {
-            if let Some(char_inputs_str) = char_inputs_str {
-                for char_input in char_inputs_str.chars() {
-                    char_inputs.push_back(
-                        ThreeDigitNumber::try_from(
-                            u16::try_from(char_input as u32)
-                                .map_err(|_| CSVError::InvalidCharInput(char_input))?,
-                        )
-                        .map_err(|_| CSVError::InvalidCharInput(char_input))?,
-                    );
-                }
-            }
-
-            if let Some(char_outputs_str) = char_outputs_str {
-                for char_output in char_outputs_str.chars() {
-                    char_outputs.push_back(
-                        ThreeDigitNumber::try_from(
-                            u16::try_from(char_output as u32)
-                                .map_err(|_| CSVError::InvalidCharInput(char_output))?,
-                        )
-                        .map_err(|_| CSVError::InvalidCharInput(char_output))?,
-                    );
-                }
-            }
-        }
+        let (char_inputs, char_outputs) = match char_io {
+            Some((char_inputs, char_outputs)) => (
+                to_chars(&char_inputs.unescape(), CSVError::InvalidCharInput)?,
+                to_chars(&char_outputs.unescape(), CSVError::InvalidCharOutput)?,
+            ),
+            None => (LinkedList::new().into_iter(), LinkedList::new().into_iter()),
+        };
 
         Ok(Self {
-            name: if name.is_empty() { None } else { Some(name) },
-            max_cycles: max_cycles.parse().map_err(CSVError::InvalidMaxCycles)?,
-            inputs: inputs.into_iter(),
-            outputs: outputs.into_iter(),
+            name: if name.text.is_empty() {
+                None
+            } else {
+                Some(name.text)
+            },
+            max_cycles: max_cycles
+                .text
+                .parse()
+                .map_err(CSVError::InvalidMaxCycles)?,
+            max_outputs: None,
+            seed: None,
+            #[cfg(feature = "std")]
+            timeout: None,
+            inputs: parse_numbers(
+                inputs.text,
+                CSVError::InvalidInputNumber,
+                CSVError::InputTooLarge,
+            )?,
+            outputs: parse_outputs(outputs.text)?,
+            unordered_outputs: false,
+            memory: parse_memory_cells(memory.map_or("", |memory| memory.text))?,
+            setup: LinkedList::new().into_iter(),
+            register: None,
+            negative_flag: None,
+            state: None,
             #[cfg(feature = "extended")]
-            char_inputs: char_inputs.into_iter(),
+            char_inputs,
             #[cfg(feature = "extended")]
-            char_outputs: char_outputs.into_iter(),
+            char_outputs,
         })
     }
 
@@ -223,21 +603,109 @@ impl<'a> StdTest<'a> {
     /// Iterator can return a [`CSVError`] with a [`LineNumber`]
     pub fn from_csv(
         text: &'a str,
-    ) -> impl Iterator<Item = Result<StdTest, CSVErrorWithLineNumber>> {
-        text.lines().enumerate().map(|(line_number, line)| {
-            Self::from_csv_line(line)
+    ) -> impl Iterator<Item = Result<StdTest<'a>, CSVErrorWithLineNumber>> {
+        Self::from_csv_with_separator(text, DEFAULT_SEPARATOR)
+    }
+
+    /// Creates an iterator over tests from CSV text, with `separator` between the sections.
+    /// See `from_csv_line_with_separator` for format and errors
+    ///
+    /// # Errors
+    /// Iterator can return a [`CSVError`] with a [`LineNumber`]
+    pub fn from_csv_with_separator(
+        text: &'a str,
+        separator: char,
+    ) -> impl Iterator<Item = Result<StdTest<'a>, CSVErrorWithLineNumber>> {
+        text.lines().enumerate().map(move |(line_number, line)| {
+            Self::from_csv_line_with_separator(line, separator)
                 .map_err(|error| errors::ErrorWithLocation(LineNumber(line_number + 1), error))
         })
     }
+
+    /// Creates a new test from slices of inputs and expected outputs
+    ///
+    /// ```
+    /// # use lminc::runner::tester::StdTest;
+    /// let test = StdTest::from_slices(Some("add"), &[5, 6], &[11], 100)?;
+    /// # Ok::<(), lminc::runner::tester::NumberError>(())
+    /// ```
+    ///
+    /// # Errors
+    /// See [`NumberError`]
+    pub fn from_slices(
+        name: Option<&'a str>,
+        inputs: &[u16],
+        outputs: &[u16],
+        max_cycles: u32,
+    ) -> Result<Self, NumberError> {
+        Self::from_iters(
+            name,
+            inputs.iter().copied(),
+            outputs.iter().copied(),
+            max_cycles,
+        )
+    }
+
+    /// Creates a new test from iterators of inputs and expected outputs
+    ///
+    /// # Errors
+    /// See [`NumberError`]
+    pub fn from_iters(
+        name: Option<&'a str>,
+        inputs: impl IntoIterator<Item = u16>,
+        outputs: impl IntoIterator<Item = u16>,
+        max_cycles: u32,
+    ) -> Result<Self, NumberError> {
+        Ok(Self {
+            name,
+            max_cycles,
+            max_outputs: None,
+            seed: None,
+            #[cfg(feature = "std")]
+            timeout: None,
+            inputs: inputs
+                .into_iter()
+                .map(|number| {
+                    ThreeDigitNumber::try_from(number)
+                        .map_err(|_| NumberError::InputTooLarge(number))
+                })
+                .collect::<Result<LinkedList<_>, _>>()?
+                .into_iter(),
+            outputs: outputs
+                .into_iter()
+                .map(|number| {
+                    ThreeDigitNumber::try_from(number)
+                        .map(OutputMatcher::Exact)
+                        .map_err(|_| NumberError::OutputTooLarge(number))
+                })
+                .collect::<Result<LinkedList<_>, _>>()?
+                .into_iter(),
+            unordered_outputs: false,
+            memory: LinkedList::new().into_iter(),
+            setup: LinkedList::new().into_iter(),
+            register: None,
+            negative_flag: None,
+            state: None,
+            #[cfg(feature = "extended")]
+            char_inputs: LinkedList::new().into_iter(),
+            #[cfg(feature = "extended")]
+            char_outputs: LinkedList::new().into_iter(),
+        })
+    }
 }
 
 #[cfg(test)]
 mod test {
     use core::assert_eq;
 
-    use crate::{assembler::assemble_from_text, computer::Computer, num3::ThreeDigitNumber};
+    use crate::{
+        assembler::assemble_from_text, computer::Computer, num3::ThreeDigitNumber, three_digit,
+    };
 
-    use super::StdTest;
+    use super::{
+        super::{OutputMatcher, TestError},
+        CSVError, NumberError, StdTest,
+    };
 
     #[test]
     fn csv_line_empty() {
@@ -283,13 +751,13 @@ mod test {
 
         assert_eq!(
             inputs.next(),
-            Some(unsafe { ThreeDigitNumber::from_unchecked(1) }),
+            Some(ThreeDigitNumber::new::<1>()),
             "Failed to get first input from CSV line!",
         );
 
         assert_eq!(
             inputs.next(),
-            Some(unsafe { ThreeDigitNumber::from_unchecked(2) }),
+            Some(ThreeDigitNumber::new::<2>()),
             "Failed to get second input from CSV line!",
         );
 
@@ -299,13 +767,13 @@ mod test {
 
         assert_eq!(
             outputs.next(),
-            Some(unsafe { ThreeDigitNumber::from_unchecked(3) }),
+            Some(OutputMatcher::Exact(ThreeDigitNumber::new::<3>())),
             "Failed to get first output from CSV line!",
         );
 
         assert_eq!(
             outputs.next(),
-            Some(unsafe { ThreeDigitNumber::from_unchecked(4) }),
+            Some(OutputMatcher::Exact(ThreeDigitNumber::new::<4>())),
             "Failed to get second output from CSV line!",
         );
 
@@ -331,6 +799,123 @@ mod test {
         );
     }
 
+    #[test]
+    fn csv_line_memory() {
+        let test =
+            StdTest::from_csv_line("name;1;;5;99=1,98=20").expect("failed to parse csv line");
+
+        assert_eq!(
+            test.memory.collect::<Vec<_>>(),
+            [
+                (99, ThreeDigitNumber::new::<1>()),
+                (98, ThreeDigitNumber::new::<20>())
+            ],
+            "Failed to get the memory cells from CSV line!"
+        );
+
+        assert!(
+            matches!(
+                StdTest::from_csv_line(";;;5;100=1"),
+                Err(CSVError::AddressTooLarge(100))
+            ),
+            "Read a memory address that was too large!"
+        );
+        assert!(
+            matches!(
+                StdTest::from_csv_line(";;;5;99"),
+                Err(CSVError::InvalidMemoryCell)
+            ),
+            "Read a memory cell without a value!"
+        );
+    }
+
+    #[test]
+    fn csv_line_quoted() {
+        let test = StdTest::from_csv_line(r#""a;b";"1,2";3;5"#).expect("failed to parse csv line");
+
+        assert_eq!(
+            test.name,
+            Some("a;b"),
+            "Failed to get a quoted name from CSV line!"
+        );
+        assert_eq!(
+            test.inputs.len(),
+            2,
+            "Failed to get quoted inputs from CSV line!"
+        );
+
+        assert!(
+            matches!(
+                StdTest::from_csv_line(r#""a;;;5"#),
+                Err(CSVError::UnterminatedQuote)
+            ),
+            "Read an unterminated quote!"
+        );
+        assert_eq!(
+            StdTest::from_csv_line(r#"a"b;;;5"#)
+                .expect("failed to parse a quote in an unquoted section")
+                .name,
+            Some(r#"a"b"#),
+            "Failed to keep a quote in an unquoted section!"
+        );
+        assert!(
+            matches!(
+                StdTest::from_csv_line(r#""a"b;;;5"#),
+                Err(CSVError::UnexpectedQuote)
+            ),
+            "Read text after a quoted section!"
+        );
+        assert!(
+            matches!(
+                StdTest::from_csv_line(r#""a""b";;;5"#),
+                Err(CSVError::EscapedQuoteInName)
+            ),
+            "Read an escaped quote in a name!"
+        );
+    }
+
+    #[test]
+    fn csv_line_separator() {
+        let test = StdTest::from_csv_line_with_separator(r#"name,"1,2",3,5"#, ',')
+            .expect("failed to parse csv line");
+
+        assert_eq!(test.name, Some("name"), "Failed to get name from CSV line!");
+        assert_eq!(
+            test.inputs.collect::<Vec<_>>(),
+            [three_digit!(1), three_digit!(2)],
+            "Failed to get inputs from CSV line!"
+        );
+        assert_eq!(
+            test.max_cycles, 5,
+            "Failed to get the max cycles from CSV line!"
+        );
+
+        assert!(
+            matches!(
+                StdTest::from_csv_line_with_separator("", '"'),
+                Err(CSVError::InvalidSeparator('"'))
+            ),
+            "Used a quote as a separator!"
+        );
+    }
+
+    #[cfg(feature = "extended")]
+    #[test]
+    fn csv_line_extended_quoted() {
+        let test = StdTest::from_csv_line(r#";;;"a;""";"";1"#).expect("failed to parse csv line");
+
+        assert_eq!(
+            test.char_inputs.collect::<Vec<_>>(),
+            [b'a', b';', b'"'].map(ThreeDigitNumber::from),
+            "Failed to get quoted char inputs from CSV line!"
+        );
+        assert_eq!(
+            test.char_outputs.len(),
+            0,
+            "Got too many char outputs from CSV line!"
+        );
+    }
+
     #[cfg(feature = "extended")]
     #[test]
     fn csv_line_extended() {
@@ -344,13 +929,13 @@ mod test {
 
         assert_eq!(
             inputs.next(),
-            Some(unsafe { ThreeDigitNumber::from_unchecked(1) }),
+            Some(ThreeDigitNumber::new::<1>()),
             "Failed to get first input from CSV line!",
         );
 
         assert_eq!(
             inputs.next(),
-            Some(unsafe { ThreeDigitNumber::from_unchecked(2) }),
+            Some(ThreeDigitNumber::new::<2>()),
             "Failed to get second input from CSV line!",
         );
 
@@ -360,13 +945,13 @@ mod test {
 
         assert_eq!(
             outputs.next(),
-            Some(unsafe { ThreeDigitNumber::from_unchecked(3) }),
+            Some(OutputMatcher::Exact(ThreeDigitNumber::new::<3>())),
             "Failed to get first output from CSV line!",
         );
 
         assert_eq!(
             outputs.next(),
-            Some(unsafe { ThreeDigitNumber::from_unchecked(4) }),
+            Some(OutputMatcher::Exact(ThreeDigitNumber::new::<4>())),
             "Failed to get second output from CSV line!",
         );
 
@@ -376,13 +961,13 @@ mod test {
 
         assert_eq!(
             char_inputs.next(),
-            Some(unsafe { ThreeDigitNumber::from_unchecked(b'a'.into()) }),
+            Some(ThreeDigitNumber::from(b'a')),
             "Failed to get first char input from CSV line!",
         );
 
         assert_eq!(
             char_inputs.next(),
-            Some(unsafe { ThreeDigitNumber::from_unchecked(b'b'.into()) }),
+            Some(ThreeDigitNumber::from(b'b')),
             "Failed to get second char input from CSV line!",
         );
 
@@ -396,13 +981,13 @@ mod test {
 
         assert_eq!(
             char_outputs.next(),
-            Some(unsafe { ThreeDigitNumber::from_unchecked(b'c'.into()) }),
+            Some(ThreeDigitNumber::from(b'c')),
             "Failed to get first char output from CSV line!",
         );
 
         assert_eq!(
             char_outputs.next(),
-            Some(unsafe { ThreeDigitNumber::from_unchecked(b'd'.into()) }),
+            Some(ThreeDigitNumber::from(b'd')),
             "Failed to get second char output from CSV line!",
         );
 
@@ -418,6 +1003,175 @@ mod test {
         );
     }
 
+    #[test]
+    fn run_memory() {
+        let memory = assemble_from_text("IN\nSTO 99\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        let mut computer = Computer::new(memory);
+        StdTest::from_csv_line(";7;;10;99=7")
+            .expect("failed to parse csv line")
+            .run(&mut computer)
+            .expect("test failed");
+
+        let mut computer = Computer::new(memory);
+        let error = StdTest::from_csv_line(";7;;10;99=8")
+            .expect("failed to parse csv line")
+            .run(&mut computer)
+            .expect_err("test passed with the wrong memory");
+        assert_eq!(
+            error.1 .1,
+            TestError::DifferentMemory {
+                address: 99,
+                expected: ThreeDigitNumber::new::<8>(),
+                got: ThreeDigitNumber::new::<7>(),
+            },
+            "Got the wrong error!"
+        );
+    }
+
+    #[test]
+    fn run_unordered() {
+        let memory = assemble_from_text("IN\nSTO 99\nIN\nOUT\nLDA 99\nOUT\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        let run = |line| {
+            let mut test = StdTest::from_csv_line(line).expect("failed to parse csv line");
+            test.unordered_outputs = true;
+            test.run(&mut Computer::new(memory))
+                .map_err(|error| error.into_inner().into_inner())
+        };
+
+        assert!(
+            run(";1,2;1,2;20").is_ok(),
+            "Failed to match unordered outputs!"
+        );
+        assert!(
+            run(";1,1;1,1;20").is_ok(),
+            "Failed to match unordered outputs!"
+        );
+        assert_eq!(
+            run(";1,2;1,3;20"),
+            Err(TestError::MissingOutput(OutputMatcher::Exact(
+                three_digit!(3)
+            ))),
+            "Got the wrong error!"
+        );
+        assert_eq!(
+            run(";1,2;1;20"),
+            Err(TestError::RunOutOfOutputs(three_digit!(2))),
+            "Got the wrong error!"
+        );
+        assert!(
+            run(";5,2;2,0..=5;20").is_ok(),
+            "Failed to match unordered ranges!"
+        );
+        assert!(
+            run(";5,2;*,2;20").is_ok(),
+            "Failed to match a wildcard before an exact output!"
+        );
+        assert!(
+            run(";2,8;1..=10,1..=3;20").is_ok(),
+            "Failed to match overlapping ranges!"
+        );
+        assert!(
+            run(";7,5;0..=6,5..=8;20").is_ok(),
+            "Failed to match overlapping ranges!"
+        );
+        assert_eq!(
+            run(";5,2;*,3..5;20"),
+            Err(TestError::MissingOutput(OutputMatcher::Range {
+                min: three_digit!(3),
+                max: three_digit!(4)
+            })),
+            "Got the wrong error!"
+        );
+    }
+
+    #[test]
+    fn slices() {
+        let memory = assemble_from_text("IN\nSTO 99\nIN\nADD 99\nOUT\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        StdTest::from_slices(Some("add"), &[5, 6], &[11], 100)
+            .expect("failed to create the test")
+            .run(&mut Computer::new(memory))
+            .expect("test failed");
+        StdTest::from_iters(None, [1, 2], 3..4, 100)
+            .expect("failed to create the test")
+            .run(&mut Computer::new(memory))
+            .expect("test failed");
+
+        assert_eq!(
+            StdTest::from_slices(None, &[1000], &[], 100).map(|_| ()),
+            Err(NumberError::InputTooLarge(1000)),
+            "Created a test with an input that was too large!"
+        );
+        assert_eq!(
+            StdTest::from_slices(None, &[], &[1000], 100).map(|_| ()),
+            Err(NumberError::OutputTooLarge(1000)),
+            "Created a test with an output that was too large!"
+        );
+    }
+
+    #[test]
+    fn run_max_outputs() {
+        let memory = assemble_from_text("loop OUT\nBRA loop")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        let mut test = StdTest::from_csv_line(";;0,0,0;100").expect("failed to parse csv line");
+        test.unordered_outputs = true;
+        test.max_outputs = Some(3);
+        assert_eq!(
+            test.run(&mut Computer::new(memory))
+                .expect_err("test passed with too many outputs")
+                .1
+                 .1,
+            TestError::TooManyOutputs(3),
+            "Got the wrong error!"
+        );
+    }
+
+    #[test]
+    fn run_matchers() {
+        let memory = assemble_from_text("IN\nOUT\nIN\nOUT\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        let run = |line| {
+            StdTest::from_csv_line(line)
+                .expect("failed to parse csv line")
+                .run(&mut Computer::new(memory))
+                .map_err(|error| error.into_inner().into_inner())
+        };
+
+        assert!(run(";7,15;*,10..20;20").is_ok(), "Failed to match outputs!");
+        assert!(
+            run(";7,20;*,10..=20;20").is_ok(),
+            "Failed to match outputs!"
+        );
+        assert_eq!(
+            run(";7,20;*,10..20;20"),
+            Err(TestError::DifferentOutput {
+                expected: OutputMatcher::Range {
+                    min: ThreeDigitNumber::new::<10>(),
+                    max: ThreeDigitNumber::new::<19>(),
+                },
+                got: ThreeDigitNumber::new::<20>(),
+            }),
+            "Got the wrong error!"
+        );
+        assert_eq!(
+            StdTest::from_csv_line(";;5..5;20").map(|_| ()),
+            Err(CSVError::EmptyOutputRange),
+            "Parsed an empty range!"
+        );
+    }
+
     #[test]
     fn run() {
         let assembly = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/examples/fib.txt"));
@@ -0,0 +1,187 @@
+extern crate alloc;
+use core::fmt;
+
+use alloc::vec::Vec;
+
+use super::{Report, TestReport};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// The cycle counts of one or more test runs
+pub struct CycleStats {
+    /// The number of runs
+    pub runs: u32,
+    /// The fewest cycles of any run
+    pub min: u32,
+    /// The most cycles of any run
+    pub max: u32,
+    /// The cycles of all the runs added together
+    pub total: u64,
+}
+
+impl CycleStats {
+    #[must_use]
+    /// Create [`CycleStats`] for a single run
+    pub const fn new(cycles: u32) -> Self {
+        Self {
+            runs: 1,
+            min: cycles,
+            max: cycles,
+            total: cycles as u64,
+        }
+    }
+
+    /// Add a run
+    pub fn add(&mut self, cycles: u32) {
+        self.runs += 1;
+        self.min = self.min.min(cycles);
+        self.max = self.max.max(cycles);
+        self.total += u64::from(cycles);
+    }
+
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    /// Get the average number of cycles, rounded down
+    pub const fn average(&self) -> u32 {
+        // The average is never larger than max, so it fits in a u32
+        (self.total / self.runs as u64) as u32
+    }
+}
+
+impl fmt::Display for CycleStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "min {}, avg {}, max {} cycles",
+            self.min,
+            self.average(),
+            self.max
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// The cycle counts of the passing tests with a name (or one unnamed test) in a [Benchmark]
+pub struct TestBenchmark<'a> {
+    pub name: Option<&'a str>,
+    pub cycles: CycleStats,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+/// The cycle counts of the passing tests in a [Report], so changes to a program can be compared.
+///
+/// Tests with the same name are counted together, and failing tests are not counted
+pub struct Benchmark<'a> {
+    pub tests: Vec<TestBenchmark<'a>>,
+    /// The cycle counts of all the passing tests, or `None` if no tests passed
+    pub total: Option<CycleStats>,
+}
+
+impl<'a> Benchmark<'a> {
+    /// Add a test to the benchmark, if it passed
+    fn push(&mut self, test: &TestReport<'a>) {
+        if !test.passed() {
+            return;
+        }
+
+        match &mut self.total {
+            Some(total) => total.add(test.cycles),
+            None => self.total = Some(CycleStats::new(test.cycles)),
+        }
+
+        let existing = test.name.and_then(|name| {
+            self.tests
+                .iter_mut()
+                .find(|benchmark| benchmark.name == Some(name))
+        });
+        match existing {
+            Some(benchmark) => benchmark.cycles.add(test.cycles),
+            None => self.tests.push(TestBenchmark {
+                name: test.name,
+                cycles: CycleStats::new(test.cycles),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for Benchmark<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, test) in self.tests.iter().enumerate() {
+            match test.name {
+                Some(name) => write!(f, "{name}")?,
+                None => write!(f, "test {}", index + 1)?,
+            }
+            writeln!(f, ": {} ({} runs)", test.cycles, test.cycles.runs)?;
+        }
+
+        match self.total {
+            Some(total) => write!(
+                f,
+                "total: {} cycles over {} runs ({total})",
+                total.total, total.runs
+            ),
+            None => write!(f, "total: no tests passed"),
+        }
+    }
+}
+
+impl<'a> Report<'a> {
+    #[must_use]
+    /// Get the cycle counts of the passing tests, see [Benchmark]
+    pub fn benchmark(&self) -> Benchmark<'a> {
+        let mut benchmark = Benchmark::default();
+        for test in &self.tests {
+            benchmark.push(test);
+        }
+        benchmark
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{assembler::assemble_from_text, runner::tester::StdTest};
+
+    use super::{CycleStats, Report};
+
+    #[test]
+    fn benchmark() {
+        // Counts down from the input
+        let memory = assemble_from_text("IN\nloop OUT\nSUB one\nBRP loop\nHLT\none DAT 1")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        let report = Report::run(
+            memory,
+            StdTest::from_csv("count;0;0;10\ncount;2;2,1,0;20\n;1;1,0;20\nwrong;1;5;20"),
+        )
+        .expect("failed to read the tests");
+        let benchmark = report.benchmark();
+
+        assert_eq!(
+            benchmark.tests.len(),
+            2,
+            "Got the wrong number of benchmarked tests!"
+        );
+        assert_eq!(
+            benchmark.tests[0].cycles,
+            CycleStats {
+                runs: 2,
+                min: 5,
+                max: 11,
+                total: 16
+            },
+            "Got the wrong cycles for the named tests!"
+        );
+        assert_eq!(
+            benchmark.total.map(|total| (total.runs, total.total)),
+            Some((3, 24)),
+            "Got the wrong total cycles!"
+        );
+        assert_eq!(
+            benchmark.to_string(),
+            "count: min 5, avg 8, max 11 cycles (2 runs)\n\
+             test 2: min 8, avg 8, max 8 cycles (1 runs)\n\
+             total: 24 cycles over 3 runs (min 5, avg 8, max 11 cycles)",
+            "Wrote the wrong benchmark!"
+        );
+    }
+}
@@ -0,0 +1,395 @@
+extern crate alloc;
+use alloc::{
+    collections::{linked_list::IntoIter, LinkedList},
+    vec::Vec,
+};
+
+#[cfg(feature = "std")]
+use std::time::Duration;
+
+use crate::{computer::State, machine::DEFAULT_MAX_CYCLES, num3::ThreeDigitNumber};
+
+#[cfg(feature = "extended")]
+use super::char_number;
+use super::{NumberError, OutputMatcher, StdTest};
+
+/// Convert a [`Vec`] to the iterator used by [`StdTest`]
+fn into_list<T>(items: Vec<T>) -> IntoIter<T> {
+    items.into_iter().collect::<LinkedList<_>>().into_iter()
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+/// Builds a [`StdTest`] one part at a time, without naming its generic parameters
+///
+/// ```
+/// # use lminc::runner::tester::TestBuilder;
+/// let test = TestBuilder::new()
+///     .with_input(5)
+///     .with_input(6)
+///     .with_output(30)
+///     .with_max_cycles(1000)
+///     .build()?;
+/// # Ok::<(), lminc::runner::tester::NumberError>(())
+/// ```
+pub struct TestBuilder<'a> {
+    name: Option<&'a str>,
+    max_cycles: Option<u32>,
+    max_outputs: Option<u32>,
+    seed: Option<u64>,
+    #[cfg(feature = "std")]
+    timeout: Option<Duration>,
+    inputs: Vec<ThreeDigitNumber>,
+    outputs: Vec<OutputMatcher>,
+    unordered_outputs: bool,
+    memory: Vec<(usize, ThreeDigitNumber)>,
+    setup: Vec<(usize, ThreeDigitNumber)>,
+    register: Option<ThreeDigitNumber>,
+    negative_flag: Option<bool>,
+    state: Option<State>,
+    #[cfg(feature = "extended")]
+    char_inputs: Vec<ThreeDigitNumber>,
+    #[cfg(feature = "extended")]
+    char_outputs: Vec<ThreeDigitNumber>,
+    /// The first invalid number given to the builder
+    error: Option<NumberError>,
+}
+
+impl<'a> TestBuilder<'a> {
+    #[must_use]
+    /// Create a new [`TestBuilder`] for a test with no inputs or outputs
+    pub const fn new() -> Self {
+        Self {
+            name: None,
+            max_cycles: None,
+            max_outputs: None,
+            seed: None,
+            #[cfg(feature = "std")]
+            timeout: None,
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            unordered_outputs: false,
+            memory: Vec::new(),
+            setup: Vec::new(),
+            register: None,
+            negative_flag: None,
+            state: None,
+            #[cfg(feature = "extended")]
+            char_inputs: Vec::new(),
+            #[cfg(feature = "extended")]
+            char_outputs: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// Convert a number, keeping the first error for `build`
+    fn number(&mut self, number: u16, error: fn(u16) -> NumberError) -> Option<ThreeDigitNumber> {
+        let result = ThreeDigitNumber::try_from(number).ok();
+        if result.is_none() {
+            self.error.get_or_insert_with(|| error(number));
+        }
+        result
+    }
+
+    /// Convert a memory cell, keeping the first error for `build`
+    fn cell(&mut self, address: usize, value: u16) -> Option<(usize, ThreeDigitNumber)> {
+        if address >= 100 {
+            self.error
+                .get_or_insert(NumberError::AddressTooLarge(address));
+            return None;
+        }
+
+        self.number(value, NumberError::MemoryValueTooLarge)
+            .map(|value| (address, value))
+    }
+
+    #[cfg(feature = "extended")]
+    /// Convert characters, keeping the first error for `build`
+    fn chars(&mut self, text: &str, error: fn(char) -> NumberError) -> Vec<ThreeDigitNumber> {
+        text.chars()
+            .filter_map(|character| {
+                let number = char_number(character);
+                if number.is_none() {
+                    self.error.get_or_insert_with(|| error(character));
+                }
+                number
+            })
+            .collect()
+    }
+
+    #[must_use]
+    /// Name the test
+    pub const fn with_name(mut self, name: &'a str) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    #[must_use]
+    /// Stop the test after `max_cycles` cycles, by default [`DEFAULT_MAX_CYCLES`]
+    pub const fn with_max_cycles(mut self, max_cycles: u32) -> Self {
+        self.max_cycles = Some(max_cycles);
+        self
+    }
+
+    #[must_use]
+    /// Fail the test if the program gives more than `max_outputs` outputs
+    pub const fn with_max_outputs(mut self, max_outputs: u32) -> Self {
+        self.max_outputs = Some(max_outputs);
+        self
+    }
+
+    #[must_use]
+    /// Record the seed that created the test, see [`StdTest::seed`]
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    #[cfg(feature = "std")]
+    #[must_use]
+    /// Fail the test if it runs for longer than `timeout` in real time
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    #[must_use]
+    /// Give the program an input
+    pub fn with_input(mut self, input: u16) -> Self {
+        if let Some(input) = self.number(input, NumberError::InputTooLarge) {
+            self.inputs.push(input);
+        }
+        self
+    }
+
+    #[must_use]
+    /// Give the program these inputs, in order
+    pub fn with_inputs(self, inputs: impl IntoIterator<Item = u16>) -> Self {
+        inputs.into_iter().fold(self, Self::with_input)
+    }
+
+    #[must_use]
+    /// Expect the program to give an output
+    pub fn with_output(mut self, output: u16) -> Self {
+        if let Some(output) = self.number(output, NumberError::OutputTooLarge) {
+            self.outputs.push(OutputMatcher::Exact(output));
+        }
+        self
+    }
+
+    #[must_use]
+    /// Expect the program to give these outputs, in order
+    pub fn with_outputs(self, outputs: impl IntoIterator<Item = u16>) -> Self {
+        outputs.into_iter().fold(self, Self::with_output)
+    }
+
+    #[must_use]
+    /// Expect the program to give an output matching `matcher`
+    pub fn with_output_matcher(mut self, matcher: OutputMatcher) -> Self {
+        self.outputs.push(matcher);
+        self
+    }
+
+    #[must_use]
+    /// Match the outputs in any order
+    pub const fn with_unordered_outputs(mut self) -> Self {
+        self.unordered_outputs = true;
+        self
+    }
+
+    #[must_use]
+    /// Expect the memory cell at `address` to have `value` after the program stops
+    pub fn with_memory(mut self, address: usize, value: u16) -> Self {
+        if let Some(cell) = self.cell(address, value) {
+            self.memory.push(cell);
+        }
+        self
+    }
+
+    #[must_use]
+    /// Write `value` to the memory cell at `address` before the program starts
+    pub fn with_setup(mut self, address: usize, value: u16) -> Self {
+        if let Some(cell) = self.cell(address, value) {
+            self.setup.push(cell);
+        }
+        self
+    }
+
+    #[must_use]
+    /// Expect the register to have `value` after the program stops
+    pub fn with_register(mut self, value: u16) -> Self {
+        self.register = self.number(value, NumberError::RegisterTooLarge);
+        self
+    }
+
+    #[must_use]
+    /// Expect the negative flag to be `value` after the program stops
+    pub const fn with_negative_flag(mut self, value: bool) -> Self {
+        self.negative_flag = Some(value);
+        self
+    }
+
+    #[must_use]
+    /// Expect the program to stop in `state` ([`State::Halted`] or [`State::ReachedEnd`])
+    pub const fn with_state(mut self, state: State) -> Self {
+        self.state = Some(state);
+        self
+    }
+
+    #[cfg(feature = "extended")]
+    #[must_use]
+    /// Give the program these char inputs, in order
+    pub fn with_char_inputs(mut self, inputs: impl IntoIterator<Item = ThreeDigitNumber>) -> Self {
+        self.char_inputs.extend(inputs);
+        self
+    }
+
+    #[cfg(feature = "extended")]
+    #[must_use]
+    /// Expect the program to give these char outputs, in order
+    pub fn with_char_outputs(
+        mut self,
+        outputs: impl IntoIterator<Item = ThreeDigitNumber>,
+    ) -> Self {
+        self.char_outputs.extend(outputs);
+        self
+    }
+
+    #[cfg(feature = "extended")]
+    #[must_use]
+    /// Give the program the characters in `text` as char inputs, in order
+    pub fn with_char_input_str(mut self, text: &str) -> Self {
+        let inputs = self.chars(text, NumberError::InvalidCharInput);
+        self.char_inputs.extend(inputs);
+        self
+    }
+
+    #[cfg(feature = "extended")]
+    #[must_use]
+    /// Expect the program to give the characters in `text` as char outputs, in order
+    pub fn with_char_output_str(mut self, text: &str) -> Self {
+        let outputs = self.chars(text, NumberError::InvalidCharOutput);
+        self.char_outputs.extend(outputs);
+        self
+    }
+
+    /// Build the test
+    ///
+    /// # Errors
+    /// The first [`NumberError`] from the numbers given to the builder
+    pub fn build(self) -> Result<StdTest<'a>, NumberError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+
+        Ok(StdTest {
+            name: self.name,
+            max_cycles: self.max_cycles.unwrap_or(DEFAULT_MAX_CYCLES),
+            max_outputs: self.max_outputs,
+            seed: self.seed,
+            #[cfg(feature = "std")]
+            timeout: self.timeout,
+            inputs: into_list(self.inputs),
+            outputs: into_list(self.outputs),
+            unordered_outputs: self.unordered_outputs,
+            memory: into_list(self.memory),
+            setup: into_list(self.setup),
+            register: self.register,
+            negative_flag: self.negative_flag,
+            state: self.state,
+            #[cfg(feature = "extended")]
+            char_inputs: into_list(self.char_inputs),
+            #[cfg(feature = "extended")]
+            char_outputs: into_list(self.char_outputs),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        assembler::assemble_from_text,
+        computer::{Computer, State},
+    };
+
+    use super::{NumberError, OutputMatcher, TestBuilder};
+
+    #[test]
+    fn builder() {
+        let memory = assemble_from_text("IN\nSTO 99\nIN\nADD 99\nOUT\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        TestBuilder::new()
+            .with_name("add")
+            .with_inputs([5, 6])
+            .with_output_matcher(OutputMatcher::Any)
+            .with_memory(99, 5)
+            .with_register(11)
+            .with_state(State::Halted)
+            .with_max_cycles(100)
+            .build()
+            .expect("failed to build the test")
+            .run(&mut Computer::new(memory))
+            .expect("test failed");
+
+        assert_eq!(
+            TestBuilder::new()
+                .with_input(1000)
+                .with_memory(100, 0)
+                .build()
+                .map(|_| ()),
+            Err(NumberError::InputTooLarge(1000)),
+            "Failed to keep the first error!"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn timeout() {
+        use std::time::Duration;
+
+        use super::super::TestError;
+
+        let memory = assemble_from_text("loop BRA loop")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        let error = TestBuilder::new()
+            .with_max_cycles(u32::MAX)
+            .with_timeout(Duration::from_millis(10))
+            .build()
+            .expect("failed to build the test")
+            .run(&mut Computer::new(memory))
+            .expect_err("the test did not time out");
+        assert_eq!(
+            error.1 .1,
+            TestError::TimedOut(Duration::from_millis(10)),
+            "Failed with the wrong error!"
+        );
+    }
+
+    #[cfg(feature = "extended")]
+    #[test]
+    fn char_str() {
+        let memory = assemble_from_text("EXT\nINA\nOTA\nINA\nOTA\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        TestBuilder::new()
+            .with_char_input_str("hi")
+            .with_char_output_str("hi")
+            .build()
+            .expect("failed to build the test")
+            .run(&mut Computer::new(memory))
+            .expect("test failed");
+
+        assert_eq!(
+            TestBuilder::new()
+                .with_char_output_str("h\u{3e8}")
+                .build()
+                .map(|_| ()),
+            Err(NumberError::InvalidCharOutput('\u{3e8}')),
+            "Failed to reject an invalid character!"
+        );
+    }
+}
@@ -0,0 +1,204 @@
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+/// Chooses which tests to run, by name and by tag, and whether to stop after the first failure.
+/// The default filter matches every test
+pub struct Filter<'a> {
+    name: Option<&'a str>,
+    skip: Option<&'a str>,
+    tags: &'a [&'a str],
+    fail_fast: bool,
+}
+
+impl<'a> Filter<'a> {
+    #[must_use]
+    /// Create a new [Filter] that matches every test
+    pub const fn new() -> Self {
+        Self {
+            name: None,
+            skip: None,
+            tags: &[],
+            fail_fast: false,
+        }
+    }
+
+    #[must_use]
+    /// Only match tests with names matching `pattern`.
+    /// If the pattern contains `*` (any characters) or `?` (one character),
+    ///  it must match the whole name, otherwise it only needs to be in the name.
+    /// Unnamed tests do not match
+    pub const fn with_name(mut self, pattern: &'a str) -> Self {
+        self.name = Some(pattern);
+        self
+    }
+
+    #[must_use]
+    /// Do not match tests with names matching `pattern`, in the same way as [`Filter::with_name`].
+    /// Unnamed tests are not skipped
+    pub const fn with_skip(mut self, pattern: &'a str) -> Self {
+        self.skip = Some(pattern);
+        self
+    }
+
+    #[must_use]
+    /// Only match tests that have at least one of `tags`
+    pub const fn with_tags(mut self, tags: &'a [&'a str]) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    #[must_use]
+    /// Stop running tests after the first one that fails
+    pub const fn with_fail_fast(mut self) -> Self {
+        self.fail_fast = true;
+        self
+    }
+
+    #[must_use]
+    /// Check if the tests should stop after the first one that fails
+    pub const fn fail_fast(&self) -> bool {
+        self.fail_fast
+    }
+
+    #[must_use]
+    /// Check if a test with `name` and `tags` matches the filter
+    pub fn matches<T: AsRef<str>>(&self, name: Option<&str>, tags: &[T]) -> bool {
+        let name_matches = match (self.name, name) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(pattern), Some(name)) => matches_name(pattern, name),
+        };
+        let skipped = match (self.skip, name) {
+            (Some(pattern), Some(name)) => matches_name(pattern, name),
+            _ => false,
+        };
+
+        name_matches
+            && !skipped
+            && (self.tags.is_empty() || tags.iter().any(|tag| self.tags.contains(&tag.as_ref())))
+    }
+}
+
+/// Check if `name` matches `pattern`, see [`Filter::with_name`]
+fn matches_name(pattern: &str, name: &str) -> bool {
+    if pattern.contains(['*', '?']) {
+        glob_matches(pattern, name)
+    } else {
+        name.contains(pattern)
+    }
+}
+
+/// Check if `text` matches the glob `pattern`, where `*` matches any characters
+///  and `?` matches one character
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    let (mut pattern_index, mut text_index) = (0, 0);
+    // The position of the last `*` in the pattern, and where it started matching in the text
+    let mut star = None;
+
+    while let Some(character) = text[text_index..].chars().next() {
+        match pattern[pattern_index..].chars().next() {
+            Some('*') => {
+                star = Some((pattern_index, text_index));
+                pattern_index += 1;
+            }
+            Some(pattern_character)
+                if pattern_character == '?' || pattern_character == character =>
+            {
+                pattern_index += pattern_character.len_utf8();
+                text_index += character.len_utf8();
+            }
+            _ => {
+                // Backtrack, making the last `*` match one more character
+                let Some((star_index, star_text_index)) = star else {
+                    return false;
+                };
+
+                let next = star_text_index
+                    + text[star_text_index..]
+                        .chars()
+                        .next()
+                        .map_or(0, char::len_utf8);
+                star = Some((star_index, next));
+                pattern_index = star_index + 1;
+                text_index = next;
+            }
+        }
+    }
+
+    pattern[pattern_index..]
+        .chars()
+        .all(|character| character == '*')
+}
+
+#[cfg(test)]
+mod test {
+    use super::Filter;
+
+    #[test]
+    fn filter() {
+        let no_tags: &[&str] = &[];
+
+        assert!(
+            Filter::new().matches(None, no_tags),
+            "The default filter did not match!"
+        );
+
+        let substring = Filter::new().with_name("add");
+        assert!(
+            substring.matches(Some("can add"), no_tags),
+            "Failed to match a substring!"
+        );
+        assert!(
+            !substring.matches(Some("subtract"), no_tags),
+            "Matched a name without the substring!"
+        );
+        assert!(
+            !substring.matches(None, no_tags),
+            "Matched an unnamed test!"
+        );
+
+        let glob = Filter::new().with_name("a*s");
+        assert!(
+            glob.matches(Some("abacus"), no_tags),
+            "Failed to match a glob!"
+        );
+        assert!(
+            !glob.matches(Some("add"), no_tags),
+            "Matched a name that did not match the glob!"
+        );
+        assert!(
+            !glob.matches(Some("bass"), no_tags),
+            "Matched a name that did not match the glob!"
+        );
+        assert!(
+            Filter::new().with_name("a?d").matches(Some("add"), no_tags),
+            "Failed to match a glob!"
+        );
+        assert!(
+            !Filter::new().with_name("a?d").matches(Some("ad"), no_tags),
+            "Matched a name that did not match the glob!"
+        );
+
+        let skip = Filter::new().with_name("a").with_skip("sub*");
+        assert!(
+            skip.matches(Some("add"), no_tags),
+            "Skipped a name that did not match!"
+        );
+        assert!(
+            !skip.matches(Some("subtract"), no_tags),
+            "Failed to skip a name!"
+        );
+        assert!(
+            Filter::new().with_skip("add").matches(None, no_tags),
+            "Skipped an unnamed test!"
+        );
+
+        let tags = Filter::new().with_tags(&["slow", "io"]);
+        assert!(
+            tags.matches(None, &["fast", "io"]),
+            "Failed to match a tag!"
+        );
+        assert!(
+            !tags.matches(None, &["fast"]),
+            "Matched a test without the tags!"
+        );
+    }
+}
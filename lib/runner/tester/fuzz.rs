@@ -0,0 +1,264 @@
+extern crate alloc;
+use core::{fmt, ops::Range};
+
+use alloc::vec::Vec;
+
+use crate::{
+    computer::{Memory, State},
+    machine::DEFAULT_MAX_CYCLES,
+    num3::ThreeDigitNumber,
+    runner::batch::{Batch, Inputs, Stop},
+};
+
+use super::{Rng, DEFAULT_SEED};
+
+/// The default number of runs for a [Fuzzer]
+pub const DEFAULT_RUNS: u32 = 1000;
+/// The default maximum number of inputs for each run of a [Fuzzer]
+pub const DEFAULT_MAX_INPUTS: usize = 10;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Problems that a [Fuzzer] looks for
+pub enum FuzzProblem {
+    /// The program ran an invalid instruction
+    InvalidInstruction,
+    /// The program did not stop within the cycle budget
+    RunOutOfCycles,
+}
+
+impl fmt::Display for FuzzProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidInstruction => write!(f, "Ran an invalid instruction"),
+            Self::RunOutOfCycles => write!(f, "Did not stop"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// A run that a [Fuzzer] found a problem in
+pub struct FuzzFailure {
+    /// The seed the fuzzer was run with
+    pub seed: u64,
+    /// The number of the run that failed (starting at 1)
+    pub run: u32,
+    /// The inputs that were given to the program
+    pub inputs: Vec<ThreeDigitNumber>,
+    /// The memory cells that were changed before the run, as `(address, value)`
+    pub perturbations: Vec<(usize, ThreeDigitNumber)>,
+    /// The number of cycles the program ran for
+    pub cycles: u32,
+    /// The problem that was found
+    pub problem: FuzzProblem,
+}
+
+impl fmt::Display for FuzzFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} after {} cycles for inputs [",
+            self.problem, self.cycles
+        )?;
+        for (index, input) in self.inputs.iter().enumerate() {
+            if index != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{input}")?;
+        }
+        write!(f, "]")?;
+
+        if !self.perturbations.is_empty() {
+            write!(f, " and memory [")?;
+            for (index, (address, value)) in self.perturbations.iter().enumerate() {
+                if index != 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{address}={value}")?;
+            }
+            write!(f, "]")?;
+        }
+
+        write!(f, " (run {}, seed {})!", self.run, self.seed)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FuzzFailure {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Runs a program on random inputs (and optionally random memory perturbations),
+///  looking for invalid instructions and programs that do not stop.
+///
+/// Running out of inputs is not a problem, and char inputs are not given
+///
+/// ```
+/// # use lminc::{assembler::assemble_from_text, runner::tester::Fuzzer};
+/// let memory = assemble_from_text("IN\nSTO 99\nIN\nADD 99\nOUT\nHLT")??;
+///
+/// Fuzzer::new(memory).with_runs(100).run()?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct Fuzzer {
+    memory: Memory,
+    runs: u32,
+    max_cycles: u32,
+    max_inputs: usize,
+    seed: u64,
+    perturbed: Range<usize>,
+    perturbations: usize,
+}
+
+impl Fuzzer {
+    #[must_use]
+    /// Create a new [Fuzzer] for the program in `memory`
+    pub const fn new(memory: Memory) -> Self {
+        Self {
+            memory,
+            runs: DEFAULT_RUNS,
+            max_cycles: DEFAULT_MAX_CYCLES,
+            max_inputs: DEFAULT_MAX_INPUTS,
+            seed: DEFAULT_SEED,
+            perturbed: 0..0,
+            perturbations: 0,
+        }
+    }
+
+    #[must_use]
+    /// Run the program `runs` times, by default [`DEFAULT_RUNS`]
+    pub const fn with_runs(mut self, runs: u32) -> Self {
+        self.runs = runs;
+        self
+    }
+
+    #[must_use]
+    /// Stop each run after `max_cycles` cycles, reporting [`FuzzProblem::RunOutOfCycles`]
+    pub const fn with_max_cycles(mut self, max_cycles: u32) -> Self {
+        self.max_cycles = max_cycles;
+        self
+    }
+
+    #[must_use]
+    /// Give each run up to `max_inputs` inputs, by default [`DEFAULT_MAX_INPUTS`]
+    pub const fn with_max_inputs(mut self, max_inputs: usize) -> Self {
+        self.max_inputs = max_inputs;
+        self
+    }
+
+    #[must_use]
+    /// Generate the runs with `seed`, by default [`DEFAULT_SEED`]
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    #[must_use]
+    /// Before each run, set `count` random memory cells in `addresses` to random values.
+    /// Addresses past the end of memory are ignored
+    pub fn with_perturbations(mut self, addresses: Range<usize>, count: usize) -> Self {
+        self.perturbed = addresses.start..addresses.end.min(self.memory.len());
+        self.perturbations = count;
+        self
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    /// Get a random number in `0..bound`
+    fn below(rng: &mut Rng, bound: usize) -> usize {
+        // below(bound) is always < bound
+        rng.below(bound as u64) as usize
+    }
+
+    /// Fuzz the program, returning the number of runs
+    ///
+    /// # Errors
+    /// If a run found a problem, see [`FuzzFailure`]
+    pub fn run(&self) -> Result<u32, FuzzFailure> {
+        let mut rng = Rng::new(self.seed);
+
+        for run_number in 1..=self.runs {
+            let inputs = (0..Self::below(&mut rng, self.max_inputs + 1))
+                .map(|_| rng.number())
+                .collect::<Vec<_>>();
+
+            let mut memory = self.memory;
+            let mut perturbations = Vec::new();
+            if !self.perturbed.is_empty() {
+                for _ in 0..self.perturbations {
+                    let address =
+                        self.perturbed.start + Self::below(&mut rng, self.perturbed.len());
+                    let value = rng.number();
+                    memory[address] = value;
+                    perturbations.push((address, value));
+                }
+            }
+
+            let run = Batch::new(memory, self.max_cycles).run_one(&Inputs::from(inputs.clone()));
+
+            let problem = match run.stop {
+                Stop::State(State::InvalidInstruction) => FuzzProblem::InvalidInstruction,
+                Stop::RunOutOfCycles => FuzzProblem::RunOutOfCycles,
+                Stop::State(_)
+                | Stop::RunOutOfInputs
+                | Stop::TooManyOutputs
+                | Stop::InvalidIo(_) => continue,
+            };
+
+            return Err(FuzzFailure {
+                seed: self.seed,
+                run: run_number,
+                inputs,
+                perturbations,
+                cycles: run.cycles,
+                problem,
+            });
+        }
+
+        Ok(self.runs)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::assembler::assemble_from_text;
+
+    use super::{FuzzProblem, Fuzzer};
+
+    #[test]
+    fn fuzz() {
+        // Counts down from the input
+        let memory = assemble_from_text("IN\nloop OUT\nSUB one\nBRP loop\nHLT\none DAT 1")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        assert_eq!(
+            Fuzzer::new(memory).with_runs(50).run(),
+            Ok(50),
+            "Found a problem in a robust program!"
+        );
+
+        let failure = Fuzzer::new(memory)
+            .with_max_cycles(100)
+            .run()
+            .expect_err("failed to find a program that does not stop");
+        assert_eq!(
+            failure.problem,
+            FuzzProblem::RunOutOfCycles,
+            "Found the wrong problem!"
+        );
+
+        // Replacing the `HLT` with an invalid instruction
+        let failure = Fuzzer::new(memory)
+            .with_perturbations(4..5, 1)
+            .run()
+            .expect_err("failed to find an invalid instruction");
+        assert_eq!(
+            failure.problem,
+            FuzzProblem::InvalidInstruction,
+            "Found the wrong problem!"
+        );
+        assert_eq!(
+            failure.perturbations.len(),
+            1,
+            "Failed to record the perturbations!"
+        );
+    }
+}
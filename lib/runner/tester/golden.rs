@@ -0,0 +1,350 @@
+extern crate alloc;
+use core::fmt::{self, Write};
+
+use alloc::{string::String, vec::Vec};
+
+use crate::{
+    computer::{Memory, State},
+    errors,
+    machine::DEFAULT_MAX_CYCLES,
+    num3::ThreeDigitNumber,
+    runner::batch::{Batch, Inputs, Stop},
+};
+
+use super::{CSVError, CSVErrorWithLineNumber, Report, StdTest, TestNumber, DEFAULT_SEPARATOR};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Errors for recording a golden file
+pub enum GoldenError {
+    /// The program did not halt or reach the end, so its outputs were not recorded
+    DidNotStop(Stop),
+    /// A name contained a quote or a line break, which cannot be written to the golden file
+    InvalidName,
+    #[cfg(feature = "extended")]
+    /// A character contained a line break, which cannot be written to the golden file
+    InvalidChar(char),
+    /// The golden file could not be read
+    InvalidCSV(CSVError),
+}
+
+impl fmt::Display for GoldenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DidNotStop(Stop::State(state)) => {
+                write!(f, "Program did not stop (program {state})!")
+            }
+            Self::DidNotStop(Stop::RunOutOfInputs) => {
+                write!(f, "Program did not stop (ran out of inputs)!")
+            }
+            Self::DidNotStop(Stop::RunOutOfCycles) => {
+                write!(f, "Program did not stop (ran out of cycles)!")
+            }
+            Self::DidNotStop(Stop::TooManyOutputs) => {
+                write!(f, "Program did not stop (gave too many outputs)!")
+            }
+            Self::DidNotStop(Stop::InvalidIo(error)) => {
+                write!(f, "Program did not stop ({error})")
+            }
+            Self::InvalidName => write!(f, "Names cannot contain quotes or line breaks!"),
+            #[cfg(feature = "extended")]
+            Self::InvalidChar(character) => {
+                write!(f, "Cannot record a line break character ({character:?})!")
+            }
+            Self::InvalidCSV(error) => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for GoldenError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidCSV(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+pub type GoldenErrorWithTestNumber = errors::ErrorWithLocation<GoldenError, TestNumber>;
+
+/// Write comma separated numbers
+fn write_numbers(out: &mut String, numbers: &[ThreeDigitNumber]) {
+    for (index, number) in numbers.iter().enumerate() {
+        if index != 0 {
+            out.push(',');
+        }
+        // Writing to a String does not fail
+        let _ = write!(out, "{number}");
+    }
+}
+
+#[cfg(feature = "extended")]
+/// Write characters as a quoted section, escaping quotes
+fn write_chars(out: &mut String, chars: &[ThreeDigitNumber]) -> Result<(), GoldenError> {
+    out.push('"');
+    for number in chars {
+        // Numbers below 1000 are never surrogates, so they are all valid characters
+        let character = char::from_u32(u32::from(*number)).unwrap_or_default();
+        match character {
+            '"' => out.push_str("\"\""),
+            '\n' | '\r' => return Err(GoldenError::InvalidChar(character)),
+            character => out.push(character),
+        }
+    }
+    out.push('"');
+    Ok(())
+}
+
+/// Records the outputs of a program into a golden file and verifies the program against it later.
+///
+/// Golden files are CSV test files (see [`StdTest::from_csv`]), so they can also be run with `test`
+///
+/// ```
+/// # use lminc::{assembler::assemble_from_text, num3::ThreeDigitNumber, runner::{batch::Inputs, tester::Golden}};
+/// let memory = assemble_from_text("IN\nSTO 99\nIN\nADD 99\nOUT\nHLT")??;
+/// let golden = Golden::new(memory);
+///
+/// let inputs = vec![ThreeDigitNumber::try_from(5_u16)?, ThreeDigitNumber::try_from(6_u16)?];
+/// let file = golden.record([(Some("add"), Inputs::from(inputs))])?;
+/// assert_eq!(file, "add;5,6;11;100000\n");
+///
+/// assert!(golden.verify(&file)?.all_passed());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Golden {
+    memory: Memory,
+    max_cycles: u32,
+}
+
+impl Golden {
+    #[must_use]
+    /// Create a new [Golden] for the program in `memory`
+    pub const fn new(memory: Memory) -> Self {
+        Self {
+            memory,
+            max_cycles: DEFAULT_MAX_CYCLES,
+        }
+    }
+
+    #[must_use]
+    /// Stop each recorded run after `max_cycles` cycles, by default [`DEFAULT_MAX_CYCLES`].
+    /// This is also written to the golden file
+    pub const fn with_max_cycles(mut self, max_cycles: u32) -> Self {
+        self.max_cycles = max_cycles;
+        self
+    }
+
+    /// Run the program for one case and write it as a line of the golden file
+    fn record_case(
+        &self,
+        out: &mut String,
+        name: Option<&str>,
+        inputs: &Inputs,
+        max_cycles: u32,
+    ) -> Result<(), GoldenError> {
+        let run = Batch::new(self.memory, max_cycles).run_one(inputs);
+        if !matches!(run.stop, Stop::State(State::Halted | State::ReachedEnd)) {
+            return Err(GoldenError::DidNotStop(run.stop));
+        }
+
+        if let Some(name) = name {
+            if name.contains(['"', '\n', '\r']) {
+                return Err(GoldenError::InvalidName);
+            }
+
+            if name.contains(DEFAULT_SEPARATOR) {
+                out.push('"');
+                out.push_str(name);
+                out.push('"');
+            } else {
+                out.push_str(name);
+            }
+        }
+
+        out.push(DEFAULT_SEPARATOR);
+        write_numbers(out, &inputs.inputs);
+        out.push(DEFAULT_SEPARATOR);
+        write_numbers(out, &run.outputs);
+
+        #[cfg(feature = "extended")]
+        if !inputs.char_inputs.is_empty() || !run.char_outputs.is_empty() {
+            out.push(DEFAULT_SEPARATOR);
+            write_chars(out, &inputs.char_inputs)?;
+            out.push(DEFAULT_SEPARATOR);
+            write_chars(out, &run.char_outputs)?;
+        }
+
+        // Writing to a String does not fail
+        let _ = writeln!(out, "{DEFAULT_SEPARATOR}{max_cycles}");
+
+        Ok(())
+    }
+
+    /// Record the outputs of the program for each named set of inputs, giving the golden file
+    ///
+    /// # Errors
+    /// See [`GoldenError`]
+    pub fn record<'a>(
+        &self,
+        cases: impl IntoIterator<Item = (Option<&'a str>, Inputs)>,
+    ) -> Result<String, GoldenErrorWithTestNumber> {
+        let mut out = String::new();
+
+        for (index, (name, inputs)) in cases.into_iter().enumerate() {
+            self.record_case(&mut out, name, &inputs, self.max_cycles)
+                .map_err(|error| errors::ErrorWithLocation(TestNumber(index + 1), error))?;
+        }
+
+        Ok(out)
+    }
+
+    /// Run the program against a golden file, where any difference from the recorded outputs fails
+    ///
+    /// # Errors
+    /// If the golden file could not be read
+    pub fn verify<'a>(&self, golden: &'a str) -> Result<Report<'a>, CSVErrorWithLineNumber> {
+        Report::run(self.memory, StdTest::from_csv(golden))
+    }
+
+    /// Record the outputs of the program again, for the names, inputs and maximum cycles
+    ///  in an existing golden file, giving the updated golden file.
+    /// Each line of the golden file is one test, so the test numbers in errors are also line numbers
+    ///
+    /// # Errors
+    /// See [`GoldenError`]
+    pub fn update(&self, golden: &str) -> Result<String, GoldenErrorWithTestNumber> {
+        let mut out = String::new();
+
+        for (index, line) in golden.lines().enumerate() {
+            let location = TestNumber(index + 1);
+            let test = StdTest::from_csv_line(line).map_err(|error| {
+                errors::ErrorWithLocation(location, GoldenError::InvalidCSV(error))
+            })?;
+
+            let inputs = Inputs {
+                inputs: test.inputs.collect::<Vec<_>>(),
+                #[cfg(feature = "extended")]
+                char_inputs: test.char_inputs.collect::<Vec<_>>(),
+            };
+
+            self.record_case(&mut out, test.name, &inputs, test.max_cycles)
+                .map_err(|error| errors::ErrorWithLocation(location, error))?;
+        }
+
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        assembler::assemble_from_text,
+        errors::ErrorWithLocation,
+        num3::ThreeDigitNumber,
+        runner::batch::{Inputs, Stop},
+    };
+
+    use super::{Golden, GoldenError, TestNumber};
+
+    fn inputs(numbers: &[u16]) -> Inputs {
+        Inputs::from(
+            numbers
+                .iter()
+                .map(|number| ThreeDigitNumber::try_from(*number).expect("number was too large"))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    #[test]
+    fn golden() {
+        let memory = assemble_from_text("IN\nSTO 99\nIN\nADD 99\nOUT\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+        let golden = Golden::new(memory).with_max_cycles(100);
+
+        let file = golden
+            .record([
+                (Some("add"), inputs(&[5, 6])),
+                (Some("a;b"), inputs(&[1, 2])),
+                (None, inputs(&[0, 0])),
+            ])
+            .expect("failed to record the golden file");
+        assert_eq!(
+            file, "add;5,6;11;100\n\"a;b\";1,2;3;100\n;0,0;0;100\n",
+            "Recorded the wrong golden file!"
+        );
+        assert!(
+            golden
+                .verify(&file)
+                .expect("failed to read the golden file")
+                .all_passed(),
+            "Failed to verify the golden file!"
+        );
+
+        // Subtracts instead of adding
+        let changed = assemble_from_text("IN\nSTO 99\nIN\nSUB 99\nOUT\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+        let changed = Golden::new(changed);
+        assert_eq!(
+            changed
+                .verify(&file)
+                .expect("failed to read the golden file")
+                .failed(),
+            2,
+            "Verified a changed program!"
+        );
+
+        let updated = changed
+            .update(&file)
+            .expect("failed to update the golden file");
+        assert_eq!(
+            updated, "add;5,6;1;100\n\"a;b\";1,2;1;100\n;0,0;0;100\n",
+            "Updated the golden file wrongly!"
+        );
+        assert!(
+            changed
+                .verify(&updated)
+                .expect("failed to read the golden file")
+                .all_passed(),
+            "Failed to verify the updated golden file!"
+        );
+
+        assert_eq!(
+            golden.record([(None, inputs(&[5]))]),
+            Err(ErrorWithLocation(
+                TestNumber(1),
+                GoldenError::DidNotStop(Stop::RunOutOfInputs)
+            )),
+            "Recorded a program that did not stop!"
+        );
+    }
+
+    #[cfg(feature = "extended")]
+    #[test]
+    fn golden_chars() {
+        let memory = assemble_from_text("EXT\nINA\nOTA\nOTA\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+        let golden = Golden::new(memory).with_max_cycles(10);
+
+        let mut quote = inputs(&[]);
+        quote.char_inputs.push(ThreeDigitNumber::from(b'"'));
+
+        let file = golden
+            .record([(None, quote)])
+            .expect("failed to record the golden file");
+        assert_eq!(
+            file, ";;;\"\"\"\";\"\"\"\"\"\";10\n",
+            "Recorded the wrong golden file!"
+        );
+        assert!(
+            golden
+                .verify(&file)
+                .expect("failed to read the golden file")
+                .all_passed(),
+            "Failed to verify the golden file!"
+        );
+    }
+}
@@ -0,0 +1,249 @@
+extern crate alloc;
+use core::{fmt, num::ParseIntError};
+
+use alloc::string::{String, ToString};
+
+use crate::errors::{self, Code, ErrorCode, LineNumber};
+
+use super::{MatcherError, NumberError, StdTest, TestBuilder};
+
+/// The comments that mark an inline test, after the line is trimmed
+const INLINE_TEST_PREFIXES: [&str; 2] = [";; test:", "## test:"];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Errors for reading inline tests from assembly comments
+pub enum InlineTestError {
+    /// A part of the test was not `key=value` or a known flag
+    UnknownKey(String),
+    /// An input was not a valid number
+    InvalidInputNumber(ParseIntError),
+    /// An expected output was not valid
+    InvalidOutput(MatcherError),
+    /// The maximum number of cycles was not a valid number
+    InvalidMaxCycles(ParseIntError),
+    /// The maximum number of outputs was not a valid number
+    InvalidMaxOutputs(ParseIntError),
+    /// A number was out of range
+    Number(NumberError),
+}
+
+impl fmt::Display for InlineTestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownKey(key) => write!(f, "Unknown key ({key:?})!"),
+            Self::InvalidInputNumber(_) => write!(f, "Invalid input number!"),
+            Self::InvalidOutput(error) => write!(f, "Invalid output: {error}"),
+            Self::InvalidMaxCycles(_) => write!(f, "Invalid maximum number of cycles!"),
+            Self::InvalidMaxOutputs(_) => write!(f, "Invalid maximum number of outputs!"),
+            Self::Number(error) => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+impl Code for InlineTestError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            Self::UnknownKey(_) => ErrorCode(701),
+            Self::InvalidInputNumber(_) => ErrorCode(702),
+            Self::InvalidOutput(_) => ErrorCode(703),
+            Self::InvalidMaxCycles(_) => ErrorCode(704),
+            Self::InvalidMaxOutputs(_) => ErrorCode(705),
+            Self::Number(_) => ErrorCode(706),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InlineTestError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidInputNumber(error)
+            | Self::InvalidMaxCycles(error)
+            | Self::InvalidMaxOutputs(error) => Some(error),
+            Self::InvalidOutput(error) => Some(error),
+            Self::Number(error) => Some(error),
+            Self::UnknownKey(_) => None,
+        }
+    }
+}
+
+pub type InlineTestErrorWithLineNumber = errors::ErrorWithLocation<InlineTestError, LineNumber>;
+
+/// Split a comma separated list, ignoring empty items
+fn list(text: &str) -> impl Iterator<Item = &str> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+}
+
+impl<'a> StdTest<'a> {
+    /// Creates a new test from the text of an inline test, after the `test:`.
+    ///
+    /// The test is made of whitespace separated parts:
+    /// - `name=<name>`
+    /// - `in=<inputs>`, comma separated numbers
+    /// - `out=<outputs>`, comma separated expected outputs (see [`OutputMatcher`](super::OutputMatcher))
+    /// - `cycles=<max cycles>`
+    /// - `max_outputs=<max outputs>`
+    /// - `char_in=<text>` and `char_out=<text>`, char inputs and outputs (with the `extended` feature)
+    /// - `unordered`, to match the outputs in any order
+    ///
+    /// ```
+    /// # use lminc::runner::tester::StdTest;
+    /// let test = StdTest::from_inline_test("name=add in=5,6 out=11 cycles=100")?;
+    /// # Ok::<(), lminc::runner::tester::InlineTestError>(())
+    /// ```
+    ///
+    /// # Errors
+    /// See [`InlineTestError`]
+    pub fn from_inline_test(text: &'a str) -> Result<Self, InlineTestError> {
+        let mut builder = TestBuilder::new();
+
+        for part in text.split_whitespace() {
+            if part == "unordered" {
+                builder = builder.with_unordered_outputs();
+                continue;
+            }
+
+            let Some((key, value)) = part.split_once('=') else {
+                return Err(InlineTestError::UnknownKey(part.to_string()));
+            };
+
+            builder = match key {
+                "name" => builder.with_name(value),
+                "in" => list(value).try_fold(builder, |builder, input| {
+                    input
+                        .parse()
+                        .map(|input| builder.with_input(input))
+                        .map_err(InlineTestError::InvalidInputNumber)
+                })?,
+                "out" => list(value).try_fold(builder, |builder, output| {
+                    output
+                        .parse()
+                        .map(|output| builder.with_output_matcher(output))
+                        .map_err(InlineTestError::InvalidOutput)
+                })?,
+                "cycles" => builder
+                    .with_max_cycles(value.parse().map_err(InlineTestError::InvalidMaxCycles)?),
+                "max_outputs" => builder
+                    .with_max_outputs(value.parse().map_err(InlineTestError::InvalidMaxOutputs)?),
+                #[cfg(feature = "extended")]
+                "char_in" => builder.with_char_input_str(value),
+                #[cfg(feature = "extended")]
+                "char_out" => builder.with_char_output_str(value),
+                _ => return Err(InlineTestError::UnknownKey(key.to_string())),
+            };
+        }
+
+        builder.build().map_err(InlineTestError::Number)
+    }
+
+    /// Creates an iterator over the inline tests in the comments of assembly text.
+    ///
+    /// An inline test is a line starting with `;; test:` or `## test:`
+    ///  (see `from_inline_test` for the format), so a program can carry its own tests.
+    ///
+    /// ```
+    /// # use lminc::runner::tester::StdTest;
+    /// let assembly = "\
+    /// ;; test: in=5,6 out=11
+    /// ;; test: in=1,2 out=3
+    /// IN
+    /// STO 99
+    /// IN
+    /// ADD 99
+    /// OUT
+    /// HLT";
+    /// assert_eq!(StdTest::from_assembly_comments(assembly).count(), 2);
+    /// ```
+    ///
+    /// # Errors
+    /// Iterator can return an [`InlineTestError`] with a [`LineNumber`]
+    pub fn from_assembly_comments(
+        text: &'a str,
+    ) -> impl Iterator<Item = Result<StdTest<'a>, InlineTestErrorWithLineNumber>> {
+        text.lines()
+            .enumerate()
+            .filter_map(|(line_number, line)| {
+                let line = line.trim_start();
+                INLINE_TEST_PREFIXES
+                    .iter()
+                    .find_map(|prefix| line.strip_prefix(prefix))
+                    .map(|test| (line_number, test))
+            })
+            .map(|(line_number, test)| {
+                Self::from_inline_test(test)
+                    .map_err(|error| errors::ErrorWithLocation(LineNumber(line_number + 1), error))
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        assembler::assemble_from_text,
+        computer::Computer,
+        errors::{ErrorWithLocation, LineNumber},
+    };
+
+    use super::{InlineTestError, NumberError, StdTest};
+
+    #[test]
+    fn inline_tests() {
+        let assembly = "\
+# Multiplies two numbers
+;; test: name=multiply in=5,6 out=30
+## test: in=0,7 out=0 cycles=100
+;; test: in=3,3 out=5..10
+        IN
+        STO a
+        IN
+        STO b
+loop    LDA b
+        BRZ end
+        SUB one
+        STO b
+        LDA total
+        ADD a
+        STO total
+        BRA loop
+end     LDA total
+        OUT
+        HLT
+a       DAT 0
+b       DAT 0
+total   DAT 0
+one     DAT 1";
+
+        let memory = assemble_from_text(assembly)
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        let tests = StdTest::from_assembly_comments(assembly)
+            .collect::<Result<Vec<_>, _>>()
+            .expect("failed to read the inline tests");
+        assert_eq!(tests.len(), 3, "Read the wrong number of tests!");
+        assert_eq!(tests[0].name, Some("multiply"), "Read the wrong name!");
+        assert_eq!(tests[1].max_cycles, 100, "Read the wrong maximum cycles!");
+
+        for test in tests {
+            test.run(&mut Computer::new(memory)).expect("test failed");
+        }
+
+        assert_eq!(
+            StdTest::from_assembly_comments("IN\n;; test: in=5 output=5")
+                .map(|test| test.map(|_| ()))
+                .collect::<Vec<_>>(),
+            [Err(ErrorWithLocation(
+                LineNumber(2),
+                InlineTestError::UnknownKey("output".to_string())
+            ))],
+            "Failed to reject an unknown key!"
+        );
+        assert_eq!(
+            StdTest::from_inline_test("in=1000").map(|_| ()),
+            Err(InlineTestError::Number(NumberError::InputTooLarge(1000))),
+            "Failed to reject an input that was too large!"
+        );
+    }
+}
@@ -0,0 +1,356 @@
+extern crate alloc;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::time::Duration;
+
+#[cfg(feature = "extended")]
+use alloc::string::String;
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use serde::Deserialize;
+
+use crate::{
+    computer::State,
+    errors::{self, Code, ErrorCode},
+    num3::ThreeDigitNumber,
+};
+
+#[cfg(feature = "extended")]
+use super::to_chars;
+use super::{
+    to_cells, to_numbers, to_outputs, FinalState, MatcherError, RawOutput, StdTest, TestNumber,
+};
+
+#[derive(Debug)]
+/// JSON test parsing errors
+pub enum JSONError {
+    /// The text was not valid JSON, or not an array of tests
+    ParseError(serde_json::Error),
+    /// An input number was too large
+    InputTooLarge(u16),
+    /// An output number was too large
+    OutputTooLarge(u16),
+    /// An output was not a number, `*` or a range
+    InvalidOutput(MatcherError),
+    /// A memory address was too large
+    AddressTooLarge(usize),
+    /// A memory value was too large
+    MemoryValueTooLarge(u16),
+    /// The register value was too large
+    RegisterTooLarge(u16),
+    #[cfg(feature = "extended")]
+    /// An input character was not a valid input character
+    InvalidCharInput(char),
+    #[cfg(feature = "extended")]
+    /// An output character was not a valid output character
+    InvalidCharOutput(char),
+}
+
+impl fmt::Display for JSONError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ParseError(error) => write!(f, "Invalid JSON: {error}"),
+            Self::InputTooLarge(number) => {
+                write!(f, "Input number too large ({number} should be < 1000)!")
+            }
+            Self::OutputTooLarge(number) => {
+                write!(f, "Output number too large ({number} should be < 1000)!")
+            }
+            Self::InvalidOutput(error) => write!(f, "Invalid output: {error}"),
+            Self::AddressTooLarge(address) => {
+                write!(f, "Memory address too large ({address} should be < 100)!")
+            }
+            Self::MemoryValueTooLarge(number) => {
+                write!(f, "Memory value too large ({number} should be < 1000)!")
+            }
+            Self::RegisterTooLarge(number) => {
+                write!(f, "Register value too large ({number} should be < 1000)!")
+            }
+            #[cfg(feature = "extended")]
+            Self::InvalidCharInput(character) => {
+                write!(f, "Invalid input character ({character:?})!")
+            }
+            #[cfg(feature = "extended")]
+            Self::InvalidCharOutput(character) => {
+                write!(f, "Invalid output character ({character:?})!")
+            }
+        }
+    }
+}
+
+impl Code for JSONError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            Self::ParseError(_) => ErrorCode(801),
+            Self::InputTooLarge(_) => ErrorCode(802),
+            Self::OutputTooLarge(_) => ErrorCode(803),
+            Self::InvalidOutput(_) => ErrorCode(804),
+            Self::AddressTooLarge(_) => ErrorCode(805),
+            Self::MemoryValueTooLarge(_) => ErrorCode(806),
+            Self::RegisterTooLarge(_) => ErrorCode(807),
+            #[cfg(feature = "extended")]
+            Self::InvalidCharInput(_) => ErrorCode(808),
+            #[cfg(feature = "extended")]
+            Self::InvalidCharOutput(_) => ErrorCode(809),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for JSONError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::ParseError(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for JSONError {
+    fn from(value: serde_json::Error) -> Self {
+        Self::ParseError(value)
+    }
+}
+
+pub type JSONErrorWithTestNumber = errors::ErrorWithLocation<JSONError, TestNumber>;
+
+#[derive(Deserialize)]
+/// A test as it is written in JSON
+struct JSONTest<'a> {
+    #[serde(borrow, default)]
+    name: Option<&'a str>,
+    #[serde(default)]
+    inputs: Vec<u16>,
+    #[serde(default)]
+    outputs: Vec<RawOutput>,
+    #[serde(default)]
+    unordered_outputs: bool,
+    #[serde(default)]
+    memory: BTreeMap<usize, u16>,
+    #[serde(default)]
+    setup: BTreeMap<usize, u16>,
+    #[serde(default)]
+    register: Option<u16>,
+    #[serde(default)]
+    negative_flag: Option<bool>,
+    #[serde(default)]
+    state: Option<FinalState>,
+    #[cfg(feature = "extended")]
+    #[serde(default)]
+    char_inputs: String,
+    #[cfg(feature = "extended")]
+    #[serde(default)]
+    char_outputs: String,
+    max_cycles: u32,
+    #[serde(default)]
+    max_outputs: Option<u32>,
+    #[serde(default)]
+    seed: Option<u64>,
+    #[cfg(feature = "std")]
+    #[serde(default)]
+    timeout_ms: Option<u64>,
+}
+
+impl<'a> TryFrom<JSONTest<'a>> for StdTest<'a> {
+    type Error = JSONError;
+
+    fn try_from(value: JSONTest<'a>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            name: value.name.filter(|name| !name.is_empty()),
+            max_cycles: value.max_cycles,
+            max_outputs: value.max_outputs,
+            seed: value.seed,
+            #[cfg(feature = "std")]
+            timeout: value.timeout_ms.map(Duration::from_millis),
+            inputs: to_numbers(&value.inputs, JSONError::InputTooLarge)?,
+            outputs: to_outputs(
+                &value.outputs,
+                JSONError::OutputTooLarge,
+                JSONError::InvalidOutput,
+            )?,
+            unordered_outputs: value.unordered_outputs,
+            memory: to_cells(
+                &value.memory,
+                JSONError::AddressTooLarge,
+                JSONError::MemoryValueTooLarge,
+            )?,
+            setup: to_cells(
+                &value.setup,
+                JSONError::AddressTooLarge,
+                JSONError::MemoryValueTooLarge,
+            )?,
+            register: value
+                .register
+                .map(|number| {
+                    ThreeDigitNumber::try_from(number)
+                        .map_err(|_| JSONError::RegisterTooLarge(number))
+                })
+                .transpose()?,
+            negative_flag: value.negative_flag,
+            state: value.state.map(State::from),
+            #[cfg(feature = "extended")]
+            char_inputs: to_chars(&value.char_inputs, JSONError::InvalidCharInput)?,
+            #[cfg(feature = "extended")]
+            char_outputs: to_chars(&value.char_outputs, JSONError::InvalidCharOutput)?,
+        })
+    }
+}
+
+impl<'a> StdTest<'a> {
+    #[cfg_attr(
+        not(feature = "extended"),
+        doc = "Creates an iterator over tests from JSON text, an array of tests in the format \n `{\"name\": \"add\", \"inputs\": [1, 2], \"outputs\": [3], \"unordered_outputs\": false, \"memory\": {\"99\": 3}, \"setup\": {\"90\": 25}, \"register\": 3, \"negative_flag\": false, \"state\": \"halted\", \"max_cycles\": 100, \"max_outputs\": 10, \"seed\": 42, \"timeout_ms\": 500}`, where only `max_cycles` is required"
+    )]
+    #[cfg_attr(
+        feature = "extended",
+        doc = "Creates an iterator over tests from JSON text, an array of tests in the format \n `{\"name\": \"add\", \"inputs\": [1, 2], \"outputs\": [3], \"unordered_outputs\": false, \"memory\": {\"99\": 3}, \"setup\": {\"90\": 25}, \"register\": 3, \"negative_flag\": false, \"state\": \"halted\", \"char_inputs\": \"ab\", \"char_outputs\": \"c\", \"max_cycles\": 100, \"max_outputs\": 10, \"seed\": 42, \"timeout_ms\": 500}`, where only `max_cycles` is required"
+    )]
+    ///
+    /// Names cannot contain escape sequences, as they are borrowed from `text`
+    ///
+    /// # Errors
+    /// Returns a [`JSONError::ParseError`] if the text is not an array of tests,
+    ///  and the iterator can return a [`JSONError`] with a [`TestNumber`]
+    pub fn from_json(
+        text: &'a str,
+    ) -> Result<impl Iterator<Item = Result<StdTest<'a>, JSONErrorWithTestNumber>>, JSONError> {
+        let tests: Vec<JSONTest<'a>> = serde_json::from_str(text)?;
+
+        Ok(tests.into_iter().enumerate().map(|(index, test)| {
+            Self::try_from(test)
+                .map_err(|error| errors::ErrorWithLocation(TestNumber(index + 1), error))
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        assembler::assemble_from_text,
+        computer::{Computer, State},
+        num3::ThreeDigitNumber,
+        three_digit,
+    };
+
+    use super::{super::OutputMatcher, JSONError, StdTest, TestNumber};
+
+    #[test]
+    fn json() {
+        let json = r#"[
+            {"name": "first", "inputs": [1, 2], "outputs": [3], "memory": {"99": 4}, "register": 3, "state": "reached_end", "max_cycles": 5},
+            {"max_cycles": 1}
+        ]"#;
+
+        let mut tests = StdTest::from_json(json).expect("failed to parse the JSON");
+
+        let test = tests
+            .next()
+            .expect("failed to get the first test")
+            .expect("failed to read the first test");
+        assert_eq!(
+            test.name,
+            Some("first"),
+            "Failed to get the name from JSON!"
+        );
+        assert_eq!(
+            test.inputs.collect::<Vec<_>>(),
+            [three_digit!(1), three_digit!(2)],
+            "Failed to get the inputs from JSON!"
+        );
+        assert_eq!(
+            test.outputs.collect::<Vec<_>>(),
+            [OutputMatcher::Exact(ThreeDigitNumber::new::<3>())],
+            "Failed to get the outputs from JSON!"
+        );
+        assert_eq!(
+            test.memory.collect::<Vec<_>>(),
+            [(99, ThreeDigitNumber::new::<4>())],
+            "Failed to get the memory cells from JSON!"
+        );
+        assert_eq!(
+            (test.register, test.negative_flag, test.state),
+            (
+                Some(ThreeDigitNumber::new::<3>()),
+                None,
+                Some(State::ReachedEnd)
+            ),
+            "Failed to get the register, flag and state from JSON!"
+        );
+        assert_eq!(
+            test.max_cycles, 5,
+            "Failed to get the max cycles from JSON!"
+        );
+
+        let test = tests
+            .next()
+            .expect("failed to get the second test")
+            .expect("failed to read the second test");
+        assert_eq!(test.name, None, "Got a name from JSON!");
+        assert_eq!(test.inputs.len(), 0, "Got too many inputs from JSON!");
+
+        assert!(tests.next().is_none(), "Got too many tests!");
+
+        let error =
+            StdTest::from_json(r#"[{"max_cycles": 1}, {"outputs": [1000], "max_cycles": 1}]"#)
+                .expect("failed to parse the JSON")
+                .nth(1)
+                .expect("failed to get the second test")
+                .expect_err("read an output that was too large");
+        assert!(
+            matches!(error.1, JSONError::OutputTooLarge(1000)) && error.0 == TestNumber(2),
+            "Got the wrong error!"
+        );
+
+        assert!(
+            matches!(
+                StdTest::from_json(r#"{"max_cycles": 1}"#),
+                Err(JSONError::ParseError(_))
+            ),
+            "Read a test that was not in an array!"
+        );
+    }
+
+    #[cfg(feature = "extended")]
+    #[test]
+    fn json_extended() {
+        let mut tests =
+            StdTest::from_json(r#"[{"char_inputs": "ab", "char_outputs": "c", "max_cycles": 1}]"#)
+                .expect("failed to parse the JSON");
+
+        let test = tests
+            .next()
+            .expect("failed to get the test")
+            .expect("failed to read the test");
+        assert_eq!(
+            test.char_inputs.collect::<Vec<_>>(),
+            [b'a', b'b'].map(ThreeDigitNumber::from),
+            "Failed to get the char inputs from JSON!"
+        );
+        assert_eq!(
+            test.char_outputs.collect::<Vec<_>>(),
+            [ThreeDigitNumber::from(b'c')],
+            "Failed to get the char outputs from JSON!"
+        );
+    }
+
+    #[test]
+    fn run() {
+        let assembly = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/examples/fib.txt"));
+        let tests_json = include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/examples/fib_test.json"
+        ));
+
+        let memory = assemble_from_text(assembly)
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        let mut computer = Computer::new(memory);
+
+        for test in StdTest::from_json(tests_json).expect("failed to parse the JSON") {
+            test.expect("failed to read the test")
+                .run(&mut computer)
+                .expect("test failed");
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use core::{fmt, num::ParseIntError, str::FromStr};
+
+use crate::num3::ThreeDigitNumber;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// An expected output in a test, written as a number, `*` (any number),
+///  `min..max` (excluding `max`) or `min..=max` (including `max`)
+pub enum OutputMatcher {
+    /// Match exactly this number
+    Exact(ThreeDigitNumber),
+    /// Match any number
+    Any,
+    /// Match any number from `min` to `max` (inclusive)
+    Range {
+        min: ThreeDigitNumber,
+        max: ThreeDigitNumber,
+    },
+}
+
+impl OutputMatcher {
+    #[must_use]
+    /// Check if `output` matches
+    pub fn matches(self, output: ThreeDigitNumber) -> bool {
+        match self {
+            Self::Exact(expected) => output == expected,
+            Self::Any => true,
+            Self::Range { min, max } => (min..=max).contains(&output),
+        }
+    }
+}
+
+impl From<ThreeDigitNumber> for OutputMatcher {
+    fn from(value: ThreeDigitNumber) -> Self {
+        Self::Exact(value)
+    }
+}
+
+impl fmt::Display for OutputMatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Exact(number) => write!(f, "{number}"),
+            Self::Any => write!(f, "*"),
+            Self::Range { min, max } => write!(f, "{min}..={max}"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Errors for parsing an [`OutputMatcher`]
+pub enum MatcherError {
+    /// A number was not a valid number
+    InvalidNumber(ParseIntError),
+    /// A number was too large
+    TooLarge(u16),
+    /// A range did not contain any numbers
+    EmptyRange,
+}
+
+impl fmt::Display for MatcherError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidNumber(_) => write!(f, "Invalid number!"),
+            Self::TooLarge(number) => write!(f, "Number too large ({number} should be < 1000)!"),
+            Self::EmptyRange => write!(f, "Empty range!"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MatcherError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidNumber(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Parse a number, which must be less than `limit`
+fn parse_number(text: &str, limit: u16) -> Result<u16, MatcherError> {
+    let number = text
+        .trim()
+        .parse::<u16>()
+        .map_err(MatcherError::InvalidNumber)?;
+
+    if number < limit {
+        Ok(number)
+    } else {
+        Err(MatcherError::TooLarge(number))
+    }
+}
+
+/// Convert a number from [`parse_number`] to a [`ThreeDigitNumber`]
+fn three_digit_number(number: u16) -> Result<ThreeDigitNumber, MatcherError> {
+    ThreeDigitNumber::try_from(number).map_err(|_| MatcherError::TooLarge(number))
+}
+
+impl FromStr for OutputMatcher {
+    type Err = MatcherError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s == "*" {
+            return Ok(Self::Any);
+        }
+
+        let (min, max) = if let Some((min, max)) = s.split_once("..=") {
+            (parse_number(min, 1000)?, parse_number(max, 1000)?)
+        } else if let Some((min, max)) = s.split_once("..") {
+            // The end is excluded, so it can be 1000
+            let max = parse_number(max, 1001)?;
+            (
+                parse_number(min, 1000)?,
+                max.checked_sub(1).ok_or(MatcherError::EmptyRange)?,
+            )
+        } else {
+            return Ok(Self::Exact(three_digit_number(parse_number(s, 1000)?)?));
+        };
+
+        if min > max {
+            return Err(MatcherError::EmptyRange);
+        }
+
+        Ok(Self::Range {
+            min: three_digit_number(min)?,
+            max: three_digit_number(max)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::three_digit;
+
+    use super::{MatcherError, OutputMatcher};
+
+    #[test]
+    fn matcher() {
+        assert_eq!(
+            "5".parse(),
+            Ok(OutputMatcher::Exact(three_digit!(5))),
+            "Failed to parse a number!"
+        );
+        assert_eq!(
+            "*".parse(),
+            Ok(OutputMatcher::Any),
+            "Failed to parse a wildcard!"
+        );
+        assert_eq!(
+            "10..20".parse(),
+            Ok(OutputMatcher::Range {
+                min: three_digit!(10),
+                max: three_digit!(19)
+            }),
+            "Failed to parse a range!"
+        );
+        assert_eq!(
+            "10..=20".parse(),
+            Ok(OutputMatcher::Range {
+                min: three_digit!(10),
+                max: three_digit!(20)
+            }),
+            "Failed to parse an inclusive range!"
+        );
+        assert_eq!(
+            "900..1000".parse(),
+            Ok(OutputMatcher::Range {
+                min: three_digit!(900),
+                max: three_digit!(999)
+            }),
+            "Failed to parse a range to the end!"
+        );
+
+        assert_eq!(
+            "10..10".parse::<OutputMatcher>(),
+            Err(MatcherError::EmptyRange),
+            "Parsed an empty range!"
+        );
+        assert_eq!(
+            "1000".parse::<OutputMatcher>(),
+            Err(MatcherError::TooLarge(1000)),
+            "Parsed a number that was too large!"
+        );
+        assert!(
+            matches!(
+                "a".parse::<OutputMatcher>(),
+                Err(MatcherError::InvalidNumber(_))
+            ),
+            "Parsed an invalid number!"
+        );
+
+        let range = OutputMatcher::Range {
+            min: three_digit!(10),
+            max: three_digit!(19),
+        };
+        assert!(range.matches(three_digit!(10)), "The range did not match!");
+        assert!(!range.matches(three_digit!(20)), "The range matched!");
+        assert!(
+            OutputMatcher::Any.matches(three_digit!(0)),
+            "The wildcard did not match!"
+        );
+    }
+}
@@ -1,29 +1,99 @@
 use core::fmt;
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
 
 use crate::{
-    computer::{Computer, State},
-    errors::ErrorWithLocation,
+    computer::{self, Computer, State},
+    errors::{Code, ErrorCode, ErrorWithLocation},
     num3::ThreeDigitNumber,
 };
 
+use super::backend::Backend;
+
 #[cfg(feature = "alloc")]
 mod alloc_tester;
 #[cfg(feature = "alloc")]
 pub use alloc_tester::*;
+#[cfg(feature = "alloc")]
+mod benchmark;
+#[cfg(feature = "alloc")]
+pub use benchmark::*;
+#[cfg(feature = "alloc")]
+mod builder;
+#[cfg(feature = "alloc")]
+pub use builder::*;
+mod filter;
+pub use filter::*;
+#[cfg(feature = "alloc")]
+mod fuzz;
+#[cfg(feature = "alloc")]
+pub use fuzz::*;
+#[cfg(feature = "alloc")]
+mod golden;
+#[cfg(feature = "alloc")]
+pub use golden::*;
+#[cfg(feature = "alloc")]
+mod inline;
+#[cfg(feature = "alloc")]
+pub use inline::*;
+mod matcher;
+pub use matcher::*;
+#[cfg(feature = "alloc")]
+mod property;
+#[cfg(feature = "alloc")]
+pub use property::*;
+mod random;
+pub use random::*;
+#[cfg(feature = "alloc")]
+mod report;
+#[cfg(feature = "alloc")]
+pub use report::*;
+mod slice_tester;
+pub use slice_tester::*;
+#[cfg(feature = "json")]
+mod json_tester;
+#[cfg(feature = "json")]
+pub use json_tester::*;
+#[cfg(feature = "toml")]
+mod suite;
+#[cfg(feature = "toml")]
+pub use suite::*;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 /// Tests for programs
 pub struct Test<
     'a,
     Inputs: Iterator<Item = ThreeDigitNumber>,
-    Outputs: Iterator<Item = ThreeDigitNumber>,
+    Outputs: Iterator<Item = OutputMatcher>,
+    Cells: Iterator<Item = (usize, ThreeDigitNumber)>,
+    Setup: Iterator<Item = (usize, ThreeDigitNumber)>,
     #[cfg(feature = "extended")] AInputs: Iterator<Item = ThreeDigitNumber>,
     #[cfg(feature = "extended")] AOutputs: Iterator<Item = ThreeDigitNumber>,
 > {
     pub name: Option<&'a str>,
     pub max_cycles: u32,
+    /// The maximum number of outputs (including char outputs) the program can give
+    pub max_outputs: Option<u32>,
+    /// The seed of the random generator that created the test (for example a `Property`),
+    ///  recorded in reports so a failure can be replayed
+    pub seed: Option<u64>,
+    #[cfg(feature = "std")]
+    /// The maximum real time the test can run for, checked between cycles
+    pub timeout: Option<Duration>,
     pub inputs: Inputs,
     pub outputs: Outputs,
+    /// Match `outputs` as a multiset, in any order, instead of as a sequence
+    pub unordered_outputs: bool,
+    /// The expected values of memory cells, as `(address, value)`, after the program stops
+    pub memory: Cells,
+    /// Values to write to memory cells, as `(address, value)`, before the program starts
+    pub setup: Setup,
+    /// The expected value of the register (accumulator) after the program stops
+    pub register: Option<ThreeDigitNumber>,
+    /// The expected value of the negative flag after the program stops
+    pub negative_flag: Option<bool>,
+    /// The expected state ([`State::Halted`] or [`State::ReachedEnd`]) after the program stops
+    pub state: Option<State>,
     #[cfg(feature = "extended")]
     pub char_inputs: AInputs,
     #[cfg(feature = "extended")]
@@ -31,10 +101,16 @@ pub struct Test<
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 /// Errors for tests
 pub enum TestError {
     /// The number of cycles exceeded `max_cycles`
     RunOutOfCycles,
+    /// The number of outputs exceeded `max_outputs`
+    TooManyOutputs(u32),
+    #[cfg(feature = "std")]
+    /// The test ran for longer than `timeout`
+    TimedOut(#[cfg_attr(feature = "defmt", defmt(Debug2Format))] Duration),
 
     /// The computer requested more inputs than expected
     RunOutOfInputs,
@@ -49,7 +125,7 @@ pub enum TestError {
 
     /// An output from the computer did not match the expected output
     DifferentOutput {
-        expected: ThreeDigitNumber,
+        expected: OutputMatcher,
         got: ThreeDigitNumber,
     },
     #[cfg(feature = "extended")]
@@ -65,6 +141,8 @@ pub enum TestError {
     ExpectedMoreInputs,
     /// The computer gave less outputs than expected
     ExpectedMoreOutputs,
+    /// The computer did not give an output that was expected (with unordered outputs)
+    MissingOutput(OutputMatcher),
     #[cfg(feature = "extended")]
     /// The computer requested less char inputs than expected
     ExpectedMoreCharInputs,
@@ -72,14 +150,39 @@ pub enum TestError {
     /// The computer gave less char outputs than expected
     ExpectedMoreCharOutputs,
 
+    /// A memory cell did not have the expected value after the program stopped
+    DifferentMemory {
+        address: usize,
+        expected: ThreeDigitNumber,
+        got: ThreeDigitNumber,
+    },
+    /// A memory address to check or set up was not valid (> 99)
+    InvalidAddress(usize),
+    /// The register did not have the expected value after the program stopped
+    DifferentRegister {
+        expected: ThreeDigitNumber,
+        got: ThreeDigitNumber,
+    },
+    /// The negative flag did not have the expected value after the program stopped
+    DifferentNegativeFlag { expected: bool, got: bool },
+    /// The program stopped in a different state than expected
+    DifferentState { expected: State, got: State },
+
     /// The computer errored
     ComputerError(State),
+    /// The computer rejected an input or output that it requested, see [`computer::Error`]
+    InvalidIo(computer::Error),
 }
 
 impl fmt::Display for TestError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::RunOutOfCycles => write!(f, "Ran out of cycles!"),
+            Self::TooManyOutputs(max_outputs) => {
+                write!(f, "Gave more than the maximum of {max_outputs} outputs!")
+            }
+            #[cfg(feature = "std")]
+            Self::TimedOut(timeout) => write!(f, "Ran out of time (limit: {timeout:?})!"),
 
             Self::RunOutOfInputs => write!(f, "Requested more inputs than expected!"),
             Self::RunOutOfOutputs(output) => {
@@ -112,7 +215,7 @@ impl fmt::Display for TestError {
                 got,
                 got_char,
             } => {
-                write!(f, "Different output than expected (expected {expected}",)?;
+                write!(f, "Different output than expected (expected {expected}")?;
                 if let Some(character) = expected_char {
                     write!(f, " = {character:?}")?;
                 }
@@ -127,12 +230,72 @@ impl fmt::Display for TestError {
 
             Self::ExpectedMoreInputs => write!(f, "Expected more inputs!"),
             Self::ExpectedMoreOutputs => write!(f, "Expected more outputs!"),
+            Self::MissingOutput(output) => write!(f, "Expected an output of {output}!"),
             #[cfg(feature = "extended")]
             Self::ExpectedMoreCharInputs => write!(f, "Expected more char inputs!"),
             #[cfg(feature = "extended")]
             Self::ExpectedMoreCharOutputs => write!(f, "Expected more char outputs!"),
 
+            Self::DifferentMemory {
+                address,
+                expected,
+                got,
+            } => write!(
+                f,
+                "Different value in memory than expected (address {address}, expected {expected}, got {got})!"
+            ),
+            Self::InvalidAddress(address) => {
+                write!(f, "Invalid memory address ({address} should be < 100)!")
+            }
+            Self::DifferentRegister { expected, got } => write!(
+                f,
+                "Different register value than expected (expected {expected}, got {got})!"
+            ),
+            Self::DifferentNegativeFlag { expected, got } => write!(
+                f,
+                "Different negative flag than expected (expected {expected}, got {got})!"
+            ),
+            Self::DifferentState { expected, got } => write!(
+                f,
+                "Stopped in a different state than expected (expected: program {expected}, got: program {got})!"
+            ),
+
             Self::ComputerError(state) => write!(f, "Computer error: {state:?}!"),
+            Self::InvalidIo(error) => write!(f, "Invalid input / output: {error}"),
+        }
+    }
+}
+
+impl Code for TestError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            Self::RunOutOfCycles => ErrorCode(501),
+            Self::TooManyOutputs(_) => ErrorCode(502),
+            #[cfg(feature = "std")]
+            Self::TimedOut(_) => ErrorCode(503),
+            Self::RunOutOfInputs => ErrorCode(504),
+            Self::RunOutOfOutputs(_) => ErrorCode(505),
+            #[cfg(feature = "extended")]
+            Self::RunOutOfCharInputs => ErrorCode(506),
+            #[cfg(feature = "extended")]
+            Self::RunOutOfCharOutputs(_, _) => ErrorCode(507),
+            Self::DifferentOutput { .. } => ErrorCode(508),
+            #[cfg(feature = "extended")]
+            Self::DifferentCharOutput { .. } => ErrorCode(509),
+            Self::ExpectedMoreInputs => ErrorCode(510),
+            Self::ExpectedMoreOutputs => ErrorCode(511),
+            Self::MissingOutput(_) => ErrorCode(512),
+            #[cfg(feature = "extended")]
+            Self::ExpectedMoreCharInputs => ErrorCode(513),
+            #[cfg(feature = "extended")]
+            Self::ExpectedMoreCharOutputs => ErrorCode(514),
+            Self::DifferentMemory { .. } => ErrorCode(515),
+            Self::InvalidAddress(_) => ErrorCode(516),
+            Self::DifferentRegister { .. } => ErrorCode(517),
+            Self::DifferentNegativeFlag { .. } => ErrorCode(518),
+            Self::DifferentState { .. } => ErrorCode(519),
+            Self::ComputerError(_) => ErrorCode(520),
+            Self::InvalidIo(_) => ErrorCode(521),
         }
     }
 }
@@ -141,32 +304,126 @@ impl fmt::Display for TestError {
 impl std::error::Error for TestError {}
 
 crate::create_location_type!(
-    "A number of cycles for use with [ErrorWithLocation]":
+    "A number of cycles for use with [`ErrorWithLocation`]":
     AfterCycles(pub u32): number => "after {} cycles", number.0
 );
 
 pub type ErrorWithCycles = ErrorWithLocation<TestError, AfterCycles>;
 
 crate::create_location_type!(
-    "A test name for use with [ErrorWithLocation]":
+    "A test name for use with [`ErrorWithLocation`]":
     TestName<'a,>(pub &'a str): name => "test {}", name.0
 );
 
+crate::create_location_type!(
+    "The number of a test in a file (starting at 1) for use with [`ErrorWithLocation`]":
+    TestNumber(pub usize): number => "test {}", number.0
+);
+
 pub type ErrorWithOptionalTestName<'a> = ErrorWithLocation<ErrorWithCycles, Option<TestName<'a>>>;
 
+/// The outputs that a test has been given so far
+struct OutputProgress {
+    /// The number of outputs, including char outputs
+    total: u32,
+    /// The number of times each output value was given, for unordered outputs
+    counts: Option<[u16; 1000]>,
+}
+
+/// Match the expected outputs to the counts of each output value that was given,
+///  in any order, taking the matched outputs from the counts.
+/// Exact outputs are matched first, then the wildcards and ranges in order of their
+///  maximum, each taking the smallest output left that it matches,
+///  which matches all of them whenever that is possible
+///
+/// # Errors
+/// The first expected output that could not be matched
+fn match_unordered(
+    counts: &mut [u16; 1000],
+    outputs: &(impl Iterator<Item = OutputMatcher> + Clone),
+) -> Result<(), OutputMatcher> {
+    /// The bounds of a wildcard or range
+    const fn bounds(matcher: OutputMatcher) -> Option<(ThreeDigitNumber, ThreeDigitNumber)> {
+        match matcher {
+            OutputMatcher::Exact(_) => None,
+            OutputMatcher::Any => Some((ThreeDigitNumber::ZERO, ThreeDigitNumber::MAX)),
+            OutputMatcher::Range { min, max } => Some((min, max)),
+        }
+    }
+
+    for expected in outputs.clone() {
+        if let OutputMatcher::Exact(number) = expected {
+            let count = &mut counts[usize::from(number)];
+            *count = count.checked_sub(1).ok_or(expected)?;
+        }
+    }
+
+    // Which maximums there are, so that only those are searched for
+    let mut maximums = [false; 1000];
+    for (_, max) in outputs.clone().filter_map(bounds) {
+        maximums[usize::from(max)] = true;
+    }
+
+    for maximum in (0..1000).filter(|maximum| maximums[*maximum]) {
+        for expected in outputs.clone() {
+            let Some((min, max)) = bounds(expected).filter(|(_, max)| usize::from(*max) == maximum)
+            else {
+                continue;
+            };
+
+            let count = counts[usize::from(min)..=usize::from(max)]
+                .iter_mut()
+                .find(|count| **count != 0)
+                .ok_or(expected)?;
+            *count -= 1;
+        }
+    }
+
+    Ok(())
+}
+
 macro_rules! test_methods {
     () => {
-        #[allow(clippy::too_many_lines)]
         /// Run one step of a test.
         /// Only use this if you know what you are doing!
-        /// You probably want `run` instead
+        /// You probably want `run` instead.
+        /// This always matches outputs in order, ignoring `unordered_outputs` and `max_outputs`
         ///
         /// # Errors
         /// See [`TestError`]
-        pub fn step(
-            computer: &mut Computer,
+        pub fn step<B: Backend + ?Sized>(
+            computer: &mut B,
+            test: &mut Self,
+            cycles: &mut u32,
+        ) -> Result<bool, ErrorWithOptionalTestName<'a>> {
+            Self::step_with_progress(computer, test, cycles, None)
+        }
+
+        /// Count an output in `progress`, checking `max_outputs`
+        fn count_output(
+            test: &Self,
+            progress: &mut OutputProgress,
+            cycles: u32,
+        ) -> Result<(), ErrorWithOptionalTestName<'a>> {
+            progress.total = progress.total.saturating_add(1);
+
+            match test.max_outputs {
+                Some(max_outputs) if progress.total > max_outputs => Err(ErrorWithLocation(
+                    test.name.map(TestName),
+                    ErrorWithLocation(AfterCycles(cycles), TestError::TooManyOutputs(max_outputs)),
+                )),
+                _ => Ok(()),
+            }
+        }
+
+        #[allow(clippy::too_many_lines)]
+        /// Run one step of a test, tracking the outputs in `progress` if it is given.
+        /// With unordered outputs, the outputs are counted instead of matched
+        fn step_with_progress<B: Backend + ?Sized>(
+            computer: &mut B,
             test: &mut Self,
             cycles: &mut u32,
+            progress: Option<&mut OutputProgress>,
         ) -> Result<bool, ErrorWithOptionalTestName<'a>> {
             if *cycles == test.max_cycles {
                 return Err(ErrorWithLocation(
@@ -186,17 +443,34 @@ macro_rules! test_methods {
                         )
                     })?;
 
-                    computer
-                        .input(input)
-                        .expect("failed to give an input to a computer");
+                    computer.input(input).map_err(|error| {
+                        ErrorWithLocation(
+                            test.name.map(TestName),
+                            ErrorWithLocation(AfterCycles(*cycles), TestError::InvalidIo(error)),
+                        )
+                    })?;
 
                     false
                 }
 
                 State::AwaitingOutput => {
-                    let output = computer
-                        .output()
-                        .expect("failed to get an output from a computer");
+                    let output = computer.output().map_err(|error| {
+                        ErrorWithLocation(
+                            test.name.map(TestName),
+                            ErrorWithLocation(AfterCycles(*cycles), TestError::InvalidIo(error)),
+                        )
+                    })?;
+
+                    if let Some(progress) = progress {
+                        Self::count_output(test, progress, *cycles)?;
+
+                        if let Some(counts) = progress.counts.as_mut() {
+                            let count = &mut counts[usize::from(output)];
+                            *count = count.saturating_add(1);
+                            *cycles += 1;
+                            return Ok(false);
+                        }
+                    }
 
                     let expected = test.outputs.next().ok_or_else(|| {
                         ErrorWithLocation(
@@ -208,7 +482,7 @@ macro_rules! test_methods {
                         )
                     })?;
 
-                    if output != expected {
+                    if !expected.matches(output) {
                         return Err(ErrorWithLocation(
                             test.name.map(TestName),
                             ErrorWithLocation(
@@ -233,18 +507,28 @@ macro_rules! test_methods {
                         )
                     })?;
 
-                    computer
-                        .input_char(input)
-                        .expect("failed to give a char input to a computer");
+                    computer.input_char(input).map_err(|error| {
+                        ErrorWithLocation(
+                            test.name.map(TestName),
+                            ErrorWithLocation(AfterCycles(*cycles), TestError::InvalidIo(error)),
+                        )
+                    })?;
 
                     false
                 }
 
                 #[cfg(feature = "extended")]
                 State::AwaitingCharOutput => {
-                    let output = computer
-                        .output_char()
-                        .expect("failed to get a char output from a computer");
+                    let output = computer.output_char().map_err(|error| {
+                        ErrorWithLocation(
+                            test.name.map(TestName),
+                            ErrorWithLocation(AfterCycles(*cycles), TestError::InvalidIo(error)),
+                        )
+                    })?;
+
+                    if let Some(progress) = progress {
+                        Self::count_output(test, progress, *cycles)?;
+                    }
 
                     let expected = test.char_outputs.next().ok_or_else(|| {
                         ErrorWithLocation(
@@ -253,7 +537,7 @@ macro_rules! test_methods {
                                 AfterCycles(*cycles),
                                 TestError::RunOutOfCharOutputs(
                                     output,
-                                    char::from_u32(u16::from(output).into()),
+                                    char::from_u32(output.into()),
                                 ),
                             ),
                         )
@@ -266,9 +550,9 @@ macro_rules! test_methods {
                                 AfterCycles(*cycles),
                                 TestError::DifferentCharOutput {
                                     expected,
-                                    expected_char: char::from_u32(u16::from(expected).into()),
+                                    expected_char: char::from_u32(expected.into()),
                                     got: output,
-                                    got_char: char::from_u32(u16::from(output).into()),
+                                    got_char: char::from_u32(output.into()),
                                 },
                             ),
                         ));
@@ -292,17 +576,50 @@ macro_rules! test_methods {
             Ok(done)
         }
 
-        /// Run the test with the given memory
+        /// Run the test on the given [`Backend`]
+        ///  (for example a [`Computer`](crate::computer::Computer))
         ///
         /// # Errors
         /// See [`TestError`]
-        pub fn run(
+        #[allow(clippy::cast_possible_truncation, clippy::too_many_lines)]
+        pub fn run<B: Backend + ?Sized>(
             mut self,
-            computer: &mut Computer,
-        ) -> Result<u32, ErrorWithOptionalTestName<'a>> {
+            computer: &mut B,
+        ) -> Result<u32, ErrorWithOptionalTestName<'a>>
+        where
+            Outputs: Clone,
+        {
             let mut cycles = 0;
 
-            while !Self::step(computer, &mut self, &mut cycles)? {}
+            // Set up the memory
+
+            let memory = Computer::get_memory_mut(computer.computer_mut());
+            for (address, value) in self.setup.by_ref() {
+                let Some(cell) = memory.get_mut(address) else {
+                    return Err(ErrorWithLocation(
+                        self.name.map(TestName),
+                        ErrorWithLocation(AfterCycles(cycles), TestError::InvalidAddress(address)),
+                    ));
+                };
+                *cell = value;
+            }
+
+            let mut progress = OutputProgress {
+                total: 0,
+                counts: self.unordered_outputs.then_some([0; 1000]),
+            };
+            #[cfg(feature = "std")]
+            let start = Instant::now();
+            while !Self::step_with_progress(computer, &mut self, &mut cycles, Some(&mut progress))?
+            {
+                #[cfg(feature = "std")]
+                if let Some(timeout) = self.timeout.filter(|timeout| start.elapsed() > *timeout) {
+                    return Err(ErrorWithLocation(
+                        self.name.map(TestName),
+                        ErrorWithLocation(AfterCycles(cycles), TestError::TimedOut(timeout)),
+                    ));
+                }
+            }
 
             // Make sure all the inputs and outputs were used
 
@@ -313,7 +630,25 @@ macro_rules! test_methods {
                 ));
             }
 
-            if self.outputs.next().is_some() {
+            if let Some(mut output_counts) = progress.counts {
+                if let Err(expected) = match_unordered(&mut output_counts, &self.outputs) {
+                    return Err(ErrorWithLocation(
+                        self.name.map(TestName),
+                        ErrorWithLocation(AfterCycles(cycles), TestError::MissingOutput(expected)),
+                    ));
+                }
+
+                if let Some(output) = output_counts
+                    .iter()
+                    .position(|count| *count != 0)
+                    .and_then(|output| ThreeDigitNumber::try_from(output).ok())
+                {
+                    return Err(ErrorWithLocation(
+                        self.name.map(TestName),
+                        ErrorWithLocation(AfterCycles(cycles), TestError::RunOutOfOutputs(output)),
+                    ));
+                }
+            } else if self.outputs.next().is_some() {
                 return Err(ErrorWithLocation(
                     self.name.map(TestName),
                     ErrorWithLocation(AfterCycles(cycles), TestError::ExpectedMoreOutputs),
@@ -336,14 +671,67 @@ macro_rules! test_methods {
                 ));
             }
 
-            Ok(cycles)
+            // Check the memory
+
+            let computer = computer.computer();
+            let memory = computer.get_memory();
+            for (address, expected) in self.memory {
+                let error = match memory.get(address) {
+                    Some(got) if *got == expected => continue,
+                    Some(got) => TestError::DifferentMemory {
+                        address,
+                        expected,
+                        got: *got,
+                    },
+                    None => TestError::InvalidAddress(address),
+                };
+
+                return Err(ErrorWithLocation(
+                    self.name.map(TestName),
+                    ErrorWithLocation(AfterCycles(cycles), error),
+                ));
+            }
+
+            // Check the state, register and flag
+
+            let error = match (self.state, self.register, self.negative_flag) {
+                (Some(expected), _, _) if computer.state() != expected => {
+                    TestError::DifferentState {
+                        expected,
+                        got: computer.state(),
+                    }
+                }
+                (_, Some(expected), _) if computer.register() != expected => {
+                    TestError::DifferentRegister {
+                        expected,
+                        got: computer.register(),
+                    }
+                }
+                (_, _, Some(expected)) if computer.negative_flag() != expected => {
+                    TestError::DifferentNegativeFlag {
+                        expected,
+                        got: computer.negative_flag(),
+                    }
+                }
+                _ => return Ok(cycles),
+            };
+
+            Err(ErrorWithLocation(
+                self.name.map(TestName),
+                ErrorWithLocation(AfterCycles(cycles), error),
+            ))
         }
     };
 }
 
 #[cfg(not(feature = "extended"))]
-impl<'a, Inputs: Iterator<Item = ThreeDigitNumber>, Outputs: Iterator<Item = ThreeDigitNumber>>
-    Test<'a, Inputs, Outputs>
+impl<
+        'a,
+        Inputs: Iterator<Item = ThreeDigitNumber>,
+        Outputs: Iterator<Item = OutputMatcher>,
+        Cells: Iterator<Item = (usize, ThreeDigitNumber)>,
+        Setup: Iterator<Item = (usize, ThreeDigitNumber)>,
+    > Test<'a, Inputs, Outputs, Cells, Setup>
 {
     test_methods!();
 }
@@ -352,10 +740,12 @@ impl<'a, Inputs: Iterator<Item = ThreeDigitNumber>, Outputs: Iterator<Item = Thr
 impl<
         'a,
         Inputs: Iterator<Item = ThreeDigitNumber>,
-        Outputs: Iterator<Item = ThreeDigitNumber>,
+        Outputs: Iterator<Item = OutputMatcher>,
+        Cells: Iterator<Item = (usize, ThreeDigitNumber)>,
+        Setup: Iterator<Item = (usize, ThreeDigitNumber)>,
         AInputs: Iterator<Item = ThreeDigitNumber>,
         AOutputs: Iterator<Item = ThreeDigitNumber>,
-    > Test<'a, Inputs, Outputs, AInputs, AOutputs>
+    > Test<'a, Inputs, Outputs, Cells, Setup, AInputs, AOutputs>
 {
     test_methods!();
 }
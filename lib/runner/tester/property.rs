@@ -0,0 +1,332 @@
+extern crate alloc;
+use core::fmt;
+
+use alloc::vec::Vec;
+
+use crate::{
+    computer::{Memory, State},
+    machine::DEFAULT_MAX_CYCLES,
+    num3::ThreeDigitNumber,
+    runner::batch::{Batch, Inputs, Stop},
+};
+
+use super::{NumberError, Rng, StdTest, TestBuilder};
+
+/// The default number of random cases for a [Property]
+pub const DEFAULT_CASES: u32 = 100;
+/// The default seed for a [Property]
+pub const DEFAULT_SEED: u64 = 0x004C_4D49_4E43;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// A case that a [Property] failed on
+pub struct Counterexample {
+    /// The seed the property was checked with
+    pub seed: u64,
+    /// The number of the case that failed (starting at 1)
+    pub case: u32,
+    /// The inputs that were generated for the case
+    pub original_inputs: Vec<u16>,
+    /// The smallest inputs found that still fail
+    pub inputs: Vec<u16>,
+    /// The outputs that the oracle gave for `inputs`
+    pub expected: Vec<u16>,
+    /// The outputs that the program gave for `inputs`
+    pub got: Vec<u16>,
+    /// Why the program stopped
+    pub stop: Stop,
+}
+
+impl fmt::Display for Counterexample {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Failed for inputs {:?} (expected {:?}, got {:?}",
+            self.inputs, self.expected, self.got
+        )?;
+
+        match self.stop {
+            Stop::State(State::Halted | State::ReachedEnd) => (),
+            Stop::State(state) => write!(f, ", program {state}")?,
+            Stop::RunOutOfInputs => write!(f, ", ran out of inputs")?,
+            Stop::RunOutOfCycles => write!(f, ", ran out of cycles")?,
+            Stop::TooManyOutputs => write!(f, ", gave too many outputs")?,
+            Stop::InvalidIo(error) => write!(f, ", {error}")?,
+        }
+
+        write!(f, ") (case {}, seed {})!", self.case, self.seed)
+    }
+}
+
+impl Counterexample {
+    /// Create a test that replays the counterexample, recording its seed.
+    /// The test expects the oracle's outputs, so it fails until the program is fixed
+    ///
+    /// # Errors
+    /// If the oracle gave an output that was too large, see [`NumberError`]
+    pub fn to_test(&self, max_cycles: u32) -> Result<StdTest<'static>, NumberError> {
+        TestBuilder::new()
+            .with_inputs(self.inputs.iter().copied())
+            .with_outputs(self.expected.iter().copied())
+            .with_max_cycles(max_cycles)
+            .with_seed(self.seed)
+            .build()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Errors for [Property]
+pub enum PropertyError {
+    /// The generator gave an input that was too large (> 999)
+    InputTooLarge(u16),
+    /// The program did not match the oracle
+    Failed(Counterexample),
+}
+
+impl fmt::Display for PropertyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InputTooLarge(input) => {
+                write!(f, "Generated an input that was too large ({input} > 999)!")
+            }
+            Self::Failed(counterexample) => fmt::Display::fmt(counterexample, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PropertyError {}
+
+/// The result of running the program for one set of inputs
+struct Case {
+    expected: Vec<u16>,
+    got: Vec<u16>,
+    stop: Stop,
+}
+
+impl Case {
+    fn passed(&self) -> bool {
+        matches!(self.stop, Stop::State(State::Halted | State::ReachedEnd))
+            && self.got == self.expected
+    }
+}
+
+/// Checks a program against an oracle on randomly generated inputs,
+///  shrinking the inputs of any failing case
+///
+/// ```
+/// # use lminc::{assembler::assemble_from_text, runner::tester::Property};
+/// let memory = assemble_from_text("IN\nSTO 99\nIN\nADD 99\nOUT\nHLT")??;
+///
+/// Property::new(
+///     memory,
+///     |rng| vec![rng.below(500) as u16, rng.below(500) as u16],
+///     |inputs| vec![inputs[0] + inputs[1]],
+/// )
+/// .check()?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct Property<G, O>
+where
+    G: FnMut(&mut Rng) -> Vec<u16>,
+    O: Fn(&[u16]) -> Vec<u16>,
+{
+    memory: Memory,
+    generator: G,
+    oracle: O,
+    cases: u32,
+    max_cycles: u32,
+    seed: u64,
+}
+
+impl<G, O> Property<G, O>
+where
+    G: FnMut(&mut Rng) -> Vec<u16>,
+    O: Fn(&[u16]) -> Vec<u16>,
+{
+    #[must_use]
+    /// Create a new [Property] for the program in `memory`, where `generator` creates the inputs
+    ///  for each case, and `oracle` gives the expected outputs for the inputs.
+    /// While shrinking, `oracle` may be given fewer or smaller inputs than `generator` creates
+    pub const fn new(memory: Memory, generator: G, oracle: O) -> Self {
+        Self {
+            memory,
+            generator,
+            oracle,
+            cases: DEFAULT_CASES,
+            max_cycles: DEFAULT_MAX_CYCLES,
+            seed: DEFAULT_SEED,
+        }
+    }
+
+    #[must_use]
+    /// Check `cases` random cases, by default [`DEFAULT_CASES`]
+    pub const fn with_cases(mut self, cases: u32) -> Self {
+        self.cases = cases;
+        self
+    }
+
+    #[must_use]
+    /// Stop each case after `max_cycles` cycles
+    pub const fn with_max_cycles(mut self, max_cycles: u32) -> Self {
+        self.max_cycles = max_cycles;
+        self
+    }
+
+    #[must_use]
+    /// Generate the cases with `seed`, by default [`DEFAULT_SEED`]
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Run the program for one set of inputs
+    fn run(&self, inputs: &[u16]) -> Result<Case, PropertyError> {
+        let numbers = inputs
+            .iter()
+            .map(|input| {
+                ThreeDigitNumber::try_from(*input).map_err(|_| PropertyError::InputTooLarge(*input))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let run = Batch::new(self.memory, self.max_cycles).run_one(&Inputs::from(numbers));
+
+        Ok(Case {
+            expected: (self.oracle)(inputs),
+            got: run.outputs.into_iter().map(u16::from).collect(),
+            stop: run.stop,
+        })
+    }
+
+    /// Get smaller versions of `inputs`
+    fn shrink_candidates(inputs: &[u16]) -> Vec<Vec<u16>> {
+        let mut candidates = Vec::new();
+
+        for index in 0..inputs.len() {
+            let mut candidate = inputs.to_vec();
+            candidate.remove(index);
+            candidates.push(candidate);
+        }
+
+        for (index, input) in inputs.iter().enumerate() {
+            for smaller in [0, input / 2, input.saturating_sub(1)] {
+                if smaller < *input {
+                    let mut candidate = inputs.to_vec();
+                    candidate[index] = smaller;
+                    candidates.push(candidate);
+                }
+            }
+        }
+
+        candidates
+    }
+
+    /// Check the property on random cases, returning the number of cases checked
+    ///
+    /// # Errors
+    /// See [`PropertyError`]
+    pub fn check(&mut self) -> Result<u32, PropertyError> {
+        let mut rng = Rng::new(self.seed);
+
+        for case_number in 1..=self.cases {
+            let original_inputs = (self.generator)(&mut rng);
+            let case = self.run(&original_inputs)?;
+            if case.passed() {
+                continue;
+            }
+
+            // Shrink the inputs, only accepting inputs that fail in the same way
+            //  if the original inputs did not run out
+            let ran_out = case.stop == Stop::RunOutOfInputs;
+            let (mut inputs, mut case) = (original_inputs.clone(), case);
+            'shrink: loop {
+                for candidate in Self::shrink_candidates(&inputs) {
+                    let candidate_case = self.run(&candidate)?;
+                    if !candidate_case.passed()
+                        && (ran_out || candidate_case.stop != Stop::RunOutOfInputs)
+                    {
+                        inputs = candidate;
+                        case = candidate_case;
+                        continue 'shrink;
+                    }
+                }
+
+                break;
+            }
+
+            return Err(PropertyError::Failed(Counterexample {
+                seed: self.seed,
+                case: case_number,
+                original_inputs,
+                inputs,
+                expected: case.expected,
+                got: case.got,
+                stop: case.stop,
+            }));
+        }
+
+        Ok(self.cases)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        assembler::assemble_from_text,
+        computer::{Computer, State},
+        runner::batch::Stop,
+    };
+
+    use super::{Property, PropertyError, DEFAULT_SEED};
+
+    #[test]
+    fn property() {
+        let memory = assemble_from_text("IN\nSTO 99\nIN\nADD 99\nOUT\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        let generator = |rng: &mut super::Rng| {
+            vec![
+                u16::try_from(rng.below(500)).expect("number was too large"),
+                u16::try_from(rng.below(500)).expect("number was too large"),
+            ]
+        };
+
+        assert_eq!(
+            Property::new(memory, generator, |inputs| vec![inputs[0] + inputs[1]])
+                .with_cases(50)
+                .check(),
+            Ok(50),
+            "The property failed!"
+        );
+
+        // Fails when the sum is at least 100, and shrinking removes inputs
+        let error = Property::new(memory, generator, |inputs| {
+            vec![inputs.iter().sum::<u16>() % 100]
+        })
+        .check()
+        .expect_err("the property passed");
+        let PropertyError::Failed(counterexample) = error else {
+            panic!("Got the wrong error!");
+        };
+
+        assert_eq!(
+            counterexample.stop,
+            Stop::State(State::Halted),
+            "Stopped in the wrong state!"
+        );
+        assert_eq!(
+            counterexample.inputs.iter().sum::<u16>(),
+            100,
+            "Failed to shrink the inputs!"
+        );
+
+        let test = counterexample
+            .to_test(100)
+            .expect("failed to create the test");
+        assert_eq!(test.seed, Some(DEFAULT_SEED), "Failed to record the seed!");
+        assert!(
+            test.run(&mut Computer::new(memory)).is_err(),
+            "The replayed counterexample passed!"
+        );
+    }
+}
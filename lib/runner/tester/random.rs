@@ -0,0 +1,118 @@
+use crate::num3::ThreeDigitNumber;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// A small, seeded pseudo-random number generator (`SplitMix64`) for generating test cases.
+/// The same seed always gives the same numbers
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    #[must_use]
+    /// Create a new [Rng] from a seed
+    pub const fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Get the next random [u64]
+    pub const fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    /// Get a random number in `0..bound`, or 0 if `bound` is 0
+    pub fn below(&mut self, bound: u64) -> u64 {
+        // The top 64 bits of the product are always < bound
+        ((u128::from(self.next_u64()) * u128::from(bound)) >> 64) as u64
+    }
+
+    /// Get a random bool
+    pub const fn bool(&mut self) -> bool {
+        self.next_u64() >> 63 == 1
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    /// Get a random [`ThreeDigitNumber`]
+    pub fn number(&mut self) -> ThreeDigitNumber {
+        // below(1000) is always < 1000, so this never falls back to 0
+        ThreeDigitNumber::try_from(self.below(1000) as u16).unwrap_or(ThreeDigitNumber::ZERO)
+    }
+}
+
+#[cfg(feature = "rand")]
+/// Lets [Rng] be used with `rand`, for example to sample [`ThreeDigitNumber`]s
+///  with `rng.random()` or `rng.random_range(..)`
+impl rand::RngCore for Rng {
+    #[allow(clippy::cast_possible_truncation)]
+    fn next_u32(&mut self) -> u32 {
+        // Use the top bits, which are the most random
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        Self::next_u64(self)
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        for chunk in dst.chunks_mut(8) {
+            let bytes = Self::next_u64(self).to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Rng;
+
+    #[test]
+    fn rng() {
+        let mut first = Rng::new(42);
+        let mut second = Rng::new(42);
+
+        for _ in 0..100 {
+            assert_eq!(
+                first.next_u64(),
+                second.next_u64(),
+                "Gave different numbers for the same seed!"
+            );
+            assert!(first.below(10) < 10, "Gave a number out of range!");
+            second.below(10);
+        }
+
+        assert_ne!(
+            Rng::new(1).next_u64(),
+            Rng::new(2).next_u64(),
+            "Gave the same number for different seeds!"
+        );
+        assert_eq!(Rng::new(3).below(0), 0, "Gave a number out of range!");
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn rand() {
+        use crate::num3::ThreeDigitNumber;
+        use rand::Rng as _;
+
+        let mut rng = Rng::new(42);
+        let (low, high) = (
+            ThreeDigitNumber::new::<100>(),
+            ThreeDigitNumber::new::<199>(),
+        );
+
+        for _ in 0..1000 {
+            let _: ThreeDigitNumber = rng.random();
+
+            let number = rng.random_range(low..=high);
+            assert!(
+                (low..=high).contains(&number),
+                "Sampled a number out of range!"
+            );
+        }
+    }
+}
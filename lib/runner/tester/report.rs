@@ -0,0 +1,645 @@
+extern crate alloc;
+use core::fmt::{self, Write};
+
+use alloc::vec::Vec;
+
+use crate::{
+    computer::{Computer, Memory, State},
+    errors::ErrorWithLocation,
+};
+
+use super::{AfterCycles, Filter, StdTest, TestError};
+
+/// A [`fmt::Write`] that escapes characters before writing them to the inner writer
+struct Escaper<'a, W: Write> {
+    out: &'a mut W,
+    /// Get the escaped form of a character, or `None` to write it unchanged
+    escape: fn(char) -> Option<&'static str>,
+}
+
+impl<W: Write> Write for Escaper<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for character in s.chars() {
+            match (self.escape)(character) {
+                Some(escaped) => self.out.write_str(escaped)?,
+                None => self.out.write_char(character)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Escape XML special characters
+const fn escape_xml(character: char) -> Option<&'static str> {
+    match character {
+        '&' => Some("&amp;"),
+        '<' => Some("&lt;"),
+        '>' => Some("&gt;"),
+        '"' => Some("&quot;"),
+        '\'' => Some("&apos;"),
+        _ => None,
+    }
+}
+
+/// Escape characters that have a meaning in a TAP test description
+const fn escape_tap(character: char) -> Option<&'static str> {
+    match character {
+        '#' => Some("\\#"),
+        '\\' => Some("\\\\"),
+        '\n' => Some(" "),
+        _ => None,
+    }
+}
+
+/// Escape characters in a single quoted YAML string
+const fn escape_yaml(character: char) -> Option<&'static str> {
+    match character {
+        '\'' => Some("''"),
+        _ => None,
+    }
+}
+
+/// A [`fmt::Write`] that escapes characters for a JSON string before writing them to the inner writer
+struct JSONEscaper<'a, W: Write>(&'a mut W);
+
+impl<W: Write> Write for JSONEscaper<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for character in s.chars() {
+            match character {
+                '"' => self.0.write_str("\\\"")?,
+                '\\' => self.0.write_str("\\\\")?,
+                '\n' => self.0.write_str("\\n")?,
+                '\t' => self.0.write_str("\\t")?,
+                character if character.is_control() => {
+                    write!(self.0, "\\u{:04x}", u32::from(character))?;
+                }
+                character => self.0.write_char(character)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Get the name of a state for machine-readable reports
+const fn state_name(state: State) -> &'static str {
+    match state {
+        State::Running => "running",
+        State::AwaitingInput => "awaiting_input",
+        State::AwaitingOutput => "awaiting_output",
+        #[cfg(feature = "extended")]
+        State::AwaitingCharInput => "awaiting_char_input",
+        #[cfg(feature = "extended")]
+        State::AwaitingCharOutput => "awaiting_char_output",
+        State::Halted => "halted",
+        State::ReachedEnd => "reached_end",
+        State::InvalidInstruction => "invalid_instruction",
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Whether a test in a [Report] passed or failed
+pub enum Outcome {
+    /// The test passed, and the computer stopped in the given state
+    Passed(State),
+    /// The test failed with the given error
+    Failed(TestError),
+    /// The test was not run, as a stage it needs did not pass
+    Skipped,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// The result of one test in a [Report]
+pub struct TestReport<'a> {
+    pub name: Option<&'a str>,
+    pub outcome: Outcome,
+    /// The number of cycles the test ran for
+    pub cycles: u32,
+    /// The seed that created the test, see [`StdTest::seed`]
+    pub seed: Option<u64>,
+}
+
+impl TestReport<'_> {
+    #[must_use]
+    /// Check if the test passed
+    pub const fn passed(&self) -> bool {
+        matches!(self.outcome, Outcome::Passed(_))
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+/// The results of running many tests against a program
+pub struct Report<'a> {
+    pub tests: Vec<TestReport<'a>>,
+}
+
+impl<'a> Report<'a> {
+    /// Run the tests against the program in `memory`, each on a new [Computer].
+    /// Failing tests are recorded in the report
+    ///
+    /// # Errors
+    /// Returns the first error from `tests`, for example from reading a test
+    pub fn run<E>(
+        memory: Memory,
+        tests: impl IntoIterator<Item = Result<StdTest<'a>, E>>,
+    ) -> Result<Self, E> {
+        let mut report = Self::default();
+
+        for test in tests {
+            report.push(memory, test?);
+        }
+
+        Ok(report)
+    }
+
+    /// Run the tests with names matching `filter` against the program in `memory`, like [`Report::run`].
+    /// These tests do not have tags, so they do not match a filter with tags.
+    /// With [`Filter::with_fail_fast`], the tests after the first failure are left out
+    ///
+    /// # Errors
+    /// Returns the first error from `tests`, for example from reading a test
+    pub fn run_matching<E>(
+        memory: Memory,
+        tests: impl IntoIterator<Item = Result<StdTest<'a>, E>>,
+        filter: Filter<'_>,
+    ) -> Result<Self, E> {
+        let no_tags: &[&str] = &[];
+        let mut report = Self::default();
+
+        for test in tests {
+            let test = test?;
+            if !filter.matches(test.name, no_tags) {
+                continue;
+            }
+
+            report.push(memory, test);
+            if filter.fail_fast() && report.last_failed() {
+                break;
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Check if the last test added to the report failed
+    pub(super) fn last_failed(&self) -> bool {
+        self.tests
+            .last()
+            .is_some_and(|test| matches!(test.outcome, Outcome::Failed(_)))
+    }
+
+    /// Add a test to the report without running it, see [`Outcome::Skipped`]
+    pub fn skip(&mut self, test: &StdTest<'a>) {
+        self.tests.push(TestReport {
+            name: test.name,
+            outcome: Outcome::Skipped,
+            cycles: 0,
+            seed: test.seed,
+        });
+    }
+
+    /// Run a test against the program in `memory` on a new [Computer], and add the result to the report
+    pub fn push(&mut self, memory: Memory, test: StdTest<'a>) {
+        let (name, seed) = (test.name, test.seed);
+        let mut computer = Computer::new(memory);
+
+        let (outcome, cycles) = match test.run(&mut computer) {
+            Ok(cycles) => (Outcome::Passed(computer.state()), cycles),
+            Err(error) => (Outcome::Failed(error.1 .1), error.1 .0 .0),
+        };
+
+        self.tests.push(TestReport {
+            name,
+            outcome,
+            cycles,
+            seed,
+        });
+    }
+
+    #[must_use]
+    /// Get the number of tests that passed
+    pub fn passed(&self) -> usize {
+        self.tests.iter().filter(|test| test.passed()).count()
+    }
+
+    #[must_use]
+    /// Get the number of tests that failed
+    pub fn failed(&self) -> usize {
+        self.tests
+            .iter()
+            .filter(|test| matches!(test.outcome, Outcome::Failed(_)))
+            .count()
+    }
+
+    #[must_use]
+    /// Get the number of tests that were skipped
+    pub fn skipped(&self) -> usize {
+        self.tests
+            .iter()
+            .filter(|test| test.outcome == Outcome::Skipped)
+            .count()
+    }
+
+    #[must_use]
+    /// Check if all the tests passed
+    pub fn all_passed(&self) -> bool {
+        self.tests.iter().all(TestReport::passed)
+    }
+
+    /// Write the report as `JUnit` XML, with the tests in a suite called `suite_name`.
+    /// Unnamed tests are named by their number (starting at 1)
+    ///
+    /// # Errors
+    /// If writing to `out` fails
+    pub fn write_junit<W: Write>(&self, out: &mut W, suite_name: &str) -> fmt::Result {
+        let tests = self.tests.len();
+        let failures = self.failed();
+        let skipped = self.skipped();
+        // Only written if there are skipped tests
+        let write_skipped = |out: &mut W| match skipped {
+            0 => Ok(()),
+            skipped => write!(out, r#" skipped="{skipped}""#),
+        };
+
+        writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        write!(out, r#"<testsuites tests="{tests}" failures="{failures}""#)?;
+        write_skipped(out)?;
+        writeln!(out, ">")?;
+
+        write!(out, r#"  <testsuite name=""#)?;
+        Escaper {
+            out,
+            escape: escape_xml,
+        }
+        .write_str(suite_name)?;
+        write!(out, r#"" tests="{tests}" failures="{failures}""#)?;
+        write_skipped(out)?;
+        writeln!(out, ">")?;
+
+        for (index, test) in self.tests.iter().enumerate() {
+            write!(out, r#"    <testcase name=""#)?;
+            match test.name {
+                Some(name) => Escaper {
+                    out,
+                    escape: escape_xml,
+                }
+                .write_str(name)?,
+                None => write!(out, "test {}", index + 1)?,
+            }
+            write!(out, r#"" classname=""#)?;
+            Escaper {
+                out,
+                escape: escape_xml,
+            }
+            .write_str(suite_name)?;
+            writeln!(out, r#"">"#)?;
+
+            write!(
+                out,
+                r#"      <properties><property name="cycles" value="{}"/>"#,
+                test.cycles
+            )?;
+            if let Some(seed) = test.seed {
+                write!(out, r#"<property name="seed" value="{seed}"/>"#)?;
+            }
+            writeln!(out, "</properties>")?;
+
+            if let Outcome::Failed(error) = test.outcome {
+                write!(out, r#"      <failure message=""#)?;
+                write!(
+                    Escaper {
+                        out,
+                        escape: escape_xml
+                    },
+                    "{}",
+                    ErrorWithLocation(AfterCycles(test.cycles), error)
+                )?;
+                writeln!(out, r#""/>"#)?;
+            }
+            if test.outcome == Outcome::Skipped {
+                writeln!(
+                    out,
+                    r#"      <skipped message="A stage it needs did not pass"/>"#
+                )?;
+            }
+
+            writeln!(out, "    </testcase>")?;
+        }
+
+        writeln!(out, "  </testsuite>")?;
+        writeln!(out, "</testsuites>")
+    }
+
+    /// Write the report in the Test Anything Protocol (version 13),
+    ///  with the errors and cycles of failed tests in YAML blocks
+    ///
+    /// # Errors
+    /// If writing to `out` fails
+    pub fn write_tap(&self, out: &mut impl Write) -> fmt::Result {
+        writeln!(out, "TAP version 13")?;
+        writeln!(out, "1..{}", self.tests.len())?;
+
+        for (index, test) in self.tests.iter().enumerate() {
+            if matches!(test.outcome, Outcome::Failed(_)) {
+                write!(out, "not ")?;
+            }
+            write!(out, "ok {}", index + 1)?;
+
+            if let Some(name) = test.name {
+                write!(out, " - ")?;
+                Escaper {
+                    out,
+                    escape: escape_tap,
+                }
+                .write_str(name)?;
+            }
+            if test.outcome == Outcome::Skipped {
+                write!(out, " # SKIP a stage it needs did not pass")?;
+            }
+            writeln!(out)?;
+
+            if let Outcome::Failed(error) = test.outcome {
+                writeln!(out, "  ---")?;
+                write!(out, "  message: '")?;
+                write!(
+                    Escaper {
+                        out,
+                        escape: escape_yaml
+                    },
+                    "{error}"
+                )?;
+                writeln!(out, "'")?;
+                writeln!(out, "  cycles: {}", test.cycles)?;
+                if let Some(seed) = test.seed {
+                    writeln!(out, "  seed: {seed}")?;
+                }
+                writeln!(out, "  ...")?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write the report as JSON, in the format
+    ///  `{"passed": 1, "failed": 1, "tests": [{"name": "add", "passed": true, "state": "halted", "cycles": 6},
+    ///  {"name": null, "passed": false, "error": "Ran out of cycles!", "cycles": 10, "seed": 42}]}`,
+    ///  where `seed` is only written for tests with a seed, and `"skipped": true` (and the
+    ///  number of skipped tests) is only written for skipped tests
+    ///
+    /// # Errors
+    /// If writing to `out` fails
+    pub fn write_json(&self, out: &mut impl Write) -> fmt::Result {
+        write!(
+            out,
+            r#"{{"passed": {}, "failed": {}, "#,
+            self.passed(),
+            self.failed()
+        )?;
+        match self.skipped() {
+            0 => (),
+            skipped => write!(out, r#""skipped": {skipped}, "#)?,
+        }
+        write!(out, r#""tests": ["#)?;
+
+        for (index, test) in self.tests.iter().enumerate() {
+            if index != 0 {
+                write!(out, ", ")?;
+            }
+
+            write!(out, r#"{{"name": "#)?;
+            match test.name {
+                Some(name) => {
+                    write!(out, "\"")?;
+                    JSONEscaper(out).write_str(name)?;
+                    write!(out, "\"")?;
+                }
+                None => write!(out, "null")?,
+            }
+
+            match test.outcome {
+                Outcome::Passed(state) => {
+                    write!(out, r#", "passed": true, "state": "{}""#, state_name(state))?;
+                }
+                Outcome::Failed(error) => {
+                    write!(out, r#", "passed": false, "error": ""#)?;
+                    write!(JSONEscaper(out), "{error}")?;
+                    write!(out, "\"")?;
+                }
+                Outcome::Skipped => write!(out, r#", "passed": false, "skipped": true"#)?,
+            }
+
+            write!(out, r#", "cycles": {}"#, test.cycles)?;
+            if let Some(seed) = test.seed {
+                write!(out, r#", "seed": {seed}"#)?;
+            }
+            write!(out, "}}")?;
+        }
+
+        write!(out, "]}}")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{assembler::assemble_from_text, computer::State, runner::tester::TestError};
+
+    use super::{super::TestBuilder, Filter, Outcome, Report, StdTest};
+
+    #[test]
+    fn report() {
+        let memory = assemble_from_text("IN\nSTO 99\nIN\nADD 99\nOUT\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        let report = Report::run(memory, StdTest::from_csv("add;1,2;3;10\nwrong;1,2;4;10"))
+            .expect("failed to read the tests");
+
+        assert_eq!(report.tests.len(), 2, "Got the wrong number of tests!");
+        assert_eq!(report.tests[0].name, Some("add"), "Got the wrong name!");
+        assert_eq!(
+            report.tests[0].outcome,
+            Outcome::Passed(State::Halted),
+            "The first test did not pass!"
+        );
+        assert_eq!(report.tests[0].cycles, 6, "Got the wrong number of cycles!");
+        assert!(
+            matches!(
+                report.tests[1].outcome,
+                Outcome::Failed(TestError::DifferentOutput { .. })
+            ),
+            "The second test did not fail!"
+        );
+        assert_eq!(
+            (report.passed(), report.failed(), report.all_passed()),
+            (1, 1, false),
+            "Counted the wrong number of passes and fails!"
+        );
+
+        assert!(
+            Report::run(memory, StdTest::from_csv("add;1,2;3;10\n;;")).is_err(),
+            "Did not return an error from reading the tests!"
+        );
+
+        let report = Report::run_matching(
+            memory,
+            StdTest::from_csv("add;1,2;3;10\nwrong;1,2;4;10"),
+            Filter::new().with_name("wr*"),
+        )
+        .expect("failed to read the tests");
+        assert_eq!(
+            report.tests.len(),
+            1,
+            "Ran the wrong number of filtered tests!"
+        );
+        assert_eq!(
+            report.tests[0].name,
+            Some("wrong"),
+            "Ran the wrong filtered test!"
+        );
+
+        let report = Report::run_matching(
+            memory,
+            StdTest::from_csv(
+                "wrong;1,2;4;10
+add;1,2;3;10",
+            ),
+            Filter::new().with_fail_fast(),
+        )
+        .expect("failed to read the tests");
+        assert_eq!(report.tests.len(), 1, "Ran tests after the first failure!");
+    }
+
+    #[test]
+    fn junit() {
+        let memory = assemble_from_text("IN\nSTO 99\nIN\nADD 99\nOUT\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        let report = Report::run(memory, StdTest::from_csv("a<b;1,2;3;10\n;1,2;4;10"))
+            .expect("failed to read the tests");
+
+        let mut xml = String::new();
+        report
+            .write_junit(&mut xml, "add & check")
+            .expect("failed to write the report");
+
+        assert_eq!(
+            xml,
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites tests="2" failures="1">
+  <testsuite name="add &amp; check" tests="2" failures="1">
+    <testcase name="a&lt;b" classname="add &amp; check">
+      <properties><property name="cycles" value="6"/></properties>
+    </testcase>
+    <testcase name="test 2" classname="add &amp; check">
+      <properties><property name="cycles" value="4"/></properties>
+      <failure message="Different output than expected (expected 4, got 3) (after 4 cycles)"/>
+    </testcase>
+  </testsuite>
+</testsuites>
+"#,
+            "Wrote the wrong XML!"
+        );
+    }
+
+    #[test]
+    fn tap() {
+        let memory = assemble_from_text("IN\nSTO 99\nIN\nADD 99\nOUT\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        let report = Report::run(memory, StdTest::from_csv("add #1;1,2;3;10\n;1,2;4;10"))
+            .expect("failed to read the tests");
+
+        let mut tap = String::new();
+        report
+            .write_tap(&mut tap)
+            .expect("failed to write the report");
+
+        assert_eq!(
+            tap,
+            r"TAP version 13
+1..2
+ok 1 - add \#1
+not ok 2
+  ---
+  message: 'Different output than expected (expected 4, got 3)'
+  cycles: 4
+  ...
+",
+            "Wrote the wrong TAP!"
+        );
+    }
+
+    #[test]
+    fn skipped() {
+        let mut report = Report::default();
+        report.skip(
+            &TestBuilder::new()
+                .with_name("later")
+                .build()
+                .expect("failed to build the test"),
+        );
+
+        let mut tap = String::new();
+        report
+            .write_tap(&mut tap)
+            .expect("failed to write the report");
+        assert_eq!(
+            tap, "TAP version 13\n1..1\nok 1 - later # SKIP a stage it needs did not pass\n",
+            "Wrote the wrong TAP!"
+        );
+
+        let mut json = String::new();
+        report
+            .write_json(&mut json)
+            .expect("failed to write the report");
+        assert_eq!(
+            json,
+            r#"{"passed": 0, "failed": 0, "skipped": 1, "tests": [{"name": "later", "passed": false, "skipped": true, "cycles": 0}]}"#,
+            "Wrote the wrong JSON!"
+        );
+    }
+
+    #[test]
+    fn json() {
+        let memory = assemble_from_text("IN\nSTO 99\nIN\nADD 99\nOUT\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        let report = Report::run(memory, StdTest::from_csv("a\\b;1,2;3;10\n;1,2;4;10"))
+            .expect("failed to read the tests");
+
+        let mut json = String::new();
+        report
+            .write_json(&mut json)
+            .expect("failed to write the report");
+
+        assert_eq!(
+            json,
+            r#"{"passed": 1, "failed": 1, "tests": [{"name": "a\\b", "passed": true, "state": "halted", "cycles": 6}, {"name": null, "passed": false, "error": "Different output than expected (expected 4, got 3)", "cycles": 4}]}"#,
+            "Wrote the wrong JSON!"
+        );
+
+        let mut report = Report::default();
+        report.push(
+            memory,
+            TestBuilder::new()
+                .with_inputs([1, 2])
+                .with_output(4)
+                .with_seed(42)
+                .build()
+                .expect("failed to build the test"),
+        );
+
+        let mut json = String::new();
+        report
+            .write_json(&mut json)
+            .expect("failed to write the report");
+        assert!(
+            json.ends_with(r#""cycles": 4, "seed": 42}]}"#),
+            "Failed to write the seed!"
+        );
+    }
+}
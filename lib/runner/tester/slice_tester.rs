@@ -0,0 +1,132 @@
+use core::{iter::Copied, slice::Iter};
+
+use crate::num3::ThreeDigitNumber;
+
+use super::{OutputMatcher, Test};
+
+#[cfg(feature = "extended")]
+/// A test for programs using slices for the inputs, outputs and memory cells, so it does not need `alloc`
+pub type SliceTest<'a> = Test<
+    'a,
+    Copied<Iter<'a, ThreeDigitNumber>>,
+    Copied<Iter<'a, OutputMatcher>>,
+    Copied<Iter<'a, (usize, ThreeDigitNumber)>>,
+    Copied<Iter<'a, (usize, ThreeDigitNumber)>>,
+    Copied<Iter<'a, ThreeDigitNumber>>,
+    Copied<Iter<'a, ThreeDigitNumber>>,
+>;
+
+#[cfg(not(feature = "extended"))]
+/// A test for programs using slices for the inputs, outputs and memory cells, so it does not need `alloc`
+pub type SliceTest<'a> = Test<
+    'a,
+    Copied<Iter<'a, ThreeDigitNumber>>,
+    Copied<Iter<'a, OutputMatcher>>,
+    Copied<Iter<'a, (usize, ThreeDigitNumber)>>,
+    Copied<Iter<'a, (usize, ThreeDigitNumber)>>,
+>;
+
+impl<'a> SliceTest<'a> {
+    #[must_use]
+    /// Creates a new test from slices of inputs and expected outputs, without memory cells
+    ///  or char inputs and outputs
+    ///
+    /// ```
+    /// # use lminc::{num3::ThreeDigitNumber, runner::tester::{OutputMatcher, SliceTest}};
+    /// const INPUTS: [ThreeDigitNumber; 2] = [ThreeDigitNumber::new::<5>(), ThreeDigitNumber::new::<6>()];
+    /// const OUTPUTS: [OutputMatcher; 1] = [OutputMatcher::Any];
+    ///
+    /// let test = SliceTest::new(Some("add"), &INPUTS, &OUTPUTS, 100);
+    /// ```
+    pub fn new(
+        name: Option<&'a str>,
+        inputs: &'a [ThreeDigitNumber],
+        outputs: &'a [OutputMatcher],
+        max_cycles: u32,
+    ) -> Self {
+        Self {
+            name,
+            max_cycles,
+            max_outputs: None,
+            seed: None,
+            #[cfg(feature = "std")]
+            timeout: None,
+            inputs: inputs.iter().copied(),
+            outputs: outputs.iter().copied(),
+            unordered_outputs: false,
+            memory: Iter::default().copied(),
+            setup: Iter::default().copied(),
+            register: None,
+            negative_flag: None,
+            state: None,
+            #[cfg(feature = "extended")]
+            char_inputs: Iter::default().copied(),
+            #[cfg(feature = "extended")]
+            char_outputs: Iter::default().copied(),
+        }
+    }
+
+    #[must_use]
+    /// Expect the memory cells, as `(address, value)`, after the program stops
+    pub fn with_memory(mut self, memory: &'a [(usize, ThreeDigitNumber)]) -> Self {
+        self.memory = memory.iter().copied();
+        self
+    }
+
+    #[must_use]
+    /// Write the memory cells, as `(address, value)`, before the program starts
+    pub fn with_setup(mut self, setup: &'a [(usize, ThreeDigitNumber)]) -> Self {
+        self.setup = setup.iter().copied();
+        self
+    }
+
+    #[cfg(feature = "extended")]
+    #[must_use]
+    /// Give the program these char inputs, in order
+    pub fn with_char_inputs(mut self, char_inputs: &'a [ThreeDigitNumber]) -> Self {
+        self.char_inputs = char_inputs.iter().copied();
+        self
+    }
+
+    #[cfg(feature = "extended")]
+    #[must_use]
+    /// Expect the program to give these char outputs, in order
+    pub fn with_char_outputs(mut self, char_outputs: &'a [ThreeDigitNumber]) -> Self {
+        self.char_outputs = char_outputs.iter().copied();
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{assembler::assemble_from_text, computer::Computer, three_digit};
+
+    use super::{super::TestError, OutputMatcher, SliceTest};
+
+    #[test]
+    fn slices() {
+        let memory = assemble_from_text("IN\nSTO 99\nIN\nADD 99\nOUT\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        let inputs = [three_digit!(5), three_digit!(6)];
+        let outputs = [OutputMatcher::Exact(three_digit!(11))];
+        let cells = [(99, three_digit!(5))];
+
+        SliceTest::new(Some("add"), &inputs, &outputs, 100)
+            .with_memory(&cells)
+            .run(&mut Computer::new(memory))
+            .expect("test failed");
+
+        let wrong = [(99, three_digit!(6))];
+        assert!(
+            matches!(
+                SliceTest::new(None, &inputs, &outputs, 100)
+                    .with_memory(&wrong)
+                    .run(&mut Computer::new(memory)),
+                Err(error) if matches!(error.error().error(), TestError::DifferentMemory { .. })
+            ),
+            "Failed to check the memory!"
+        );
+    }
+}
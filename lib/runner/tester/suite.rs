@@ -0,0 +1,628 @@
+extern crate alloc;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::time::Duration;
+
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+use serde::Deserialize;
+
+use crate::{
+    computer::{Memory, State},
+    errors::{self, Code, ErrorCode},
+    num3::ThreeDigitNumber,
+};
+
+#[cfg(feature = "extended")]
+use super::to_chars;
+use super::{
+    to_cells, to_numbers, to_outputs, Filter, FinalState, MatcherError, RawOutput, Report, StdTest,
+    TestNumber, TestReport,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Errors for tests in a [`TestSuite`]
+pub enum SuiteError {
+    /// An input number was too large
+    InputTooLarge(u16),
+    /// An output number was too large
+    OutputTooLarge(u16),
+    /// An output was not a number, `*` or a range
+    InvalidOutput(MatcherError),
+    /// A memory address was too large
+    AddressTooLarge(usize),
+    /// A memory value was too large
+    MemoryValueTooLarge(u16),
+    /// The register value was too large
+    RegisterTooLarge(u16),
+    #[cfg(feature = "extended")]
+    /// An input character was not a valid input character
+    InvalidCharInput(char),
+    #[cfg(feature = "extended")]
+    /// An output character was not a valid output character
+    InvalidCharOutput(char),
+    /// Neither the test nor the suite set `max_cycles`
+    MissingMaxCycles,
+    /// A test or stage named a stage that was not declared (before it, for a stage's `needs`)
+    UnknownStage(String),
+}
+
+impl fmt::Display for SuiteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InputTooLarge(number) => {
+                write!(f, "Input number too large ({number} should be < 1000)!")
+            }
+            Self::OutputTooLarge(number) => {
+                write!(f, "Output number too large ({number} should be < 1000)!")
+            }
+            Self::InvalidOutput(error) => write!(f, "Invalid output: {error}"),
+            Self::AddressTooLarge(address) => {
+                write!(f, "Memory address too large ({address} should be < 100)!")
+            }
+            Self::MemoryValueTooLarge(number) => {
+                write!(f, "Memory value too large ({number} should be < 1000)!")
+            }
+            Self::RegisterTooLarge(number) => {
+                write!(f, "Register value too large ({number} should be < 1000)!")
+            }
+            #[cfg(feature = "extended")]
+            Self::InvalidCharInput(character) => {
+                write!(f, "Invalid input character ({character:?})!")
+            }
+            #[cfg(feature = "extended")]
+            Self::InvalidCharOutput(character) => {
+                write!(f, "Invalid output character ({character:?})!")
+            }
+            Self::MissingMaxCycles => write!(f, "No maximum number of cycles!"),
+            Self::UnknownStage(stage) => write!(f, "Unknown stage ({stage:?})!"),
+        }
+    }
+}
+
+impl Code for SuiteError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            Self::InputTooLarge(_) => ErrorCode(901),
+            Self::OutputTooLarge(_) => ErrorCode(902),
+            Self::InvalidOutput(_) => ErrorCode(903),
+            Self::AddressTooLarge(_) => ErrorCode(904),
+            Self::MemoryValueTooLarge(_) => ErrorCode(905),
+            Self::RegisterTooLarge(_) => ErrorCode(906),
+            #[cfg(feature = "extended")]
+            Self::InvalidCharInput(_) => ErrorCode(907),
+            #[cfg(feature = "extended")]
+            Self::InvalidCharOutput(_) => ErrorCode(908),
+            Self::MissingMaxCycles => ErrorCode(909),
+            Self::UnknownStage(_) => ErrorCode(910),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SuiteError {}
+
+pub type SuiteErrorWithTestNumber = errors::ErrorWithLocation<SuiteError, TestNumber>;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+/// A test in a [`TestSuite`]
+pub struct SuiteTest {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// The name of the stage the test is in, see [`Stage`]
+    #[serde(default)]
+    pub stage: Option<String>,
+    #[serde(default)]
+    pub inputs: Vec<u16>,
+    /// Numbers, or matchers such as `"*"` and `"10..20"`
+    #[serde(default)]
+    pub outputs: Vec<RawOutput>,
+    /// Match the outputs in any order
+    #[serde(default)]
+    pub unordered_outputs: bool,
+    /// The expected values of memory cells after the program stops
+    #[serde(default)]
+    pub memory: BTreeMap<usize, u16>,
+    /// Values to write to memory cells before the program starts
+    #[serde(default)]
+    pub setup: BTreeMap<usize, u16>,
+    /// The expected value of the register (accumulator) after the program stops
+    #[serde(default)]
+    pub register: Option<u16>,
+    /// The expected value of the negative flag after the program stops
+    #[serde(default)]
+    pub negative_flag: Option<bool>,
+    /// The expected state after the program stops
+    #[serde(default)]
+    pub state: Option<FinalState>,
+    #[cfg(feature = "extended")]
+    #[serde(default)]
+    pub char_inputs: String,
+    #[cfg(feature = "extended")]
+    #[serde(default)]
+    pub char_outputs: String,
+    /// Overrides the suite's `max_cycles`
+    #[serde(default)]
+    pub max_cycles: Option<u32>,
+    /// Overrides the suite's `max_outputs`
+    #[serde(default)]
+    pub max_outputs: Option<u32>,
+    /// The seed of the random generator that created the test, recorded in reports
+    #[serde(default)]
+    pub seed: Option<u64>,
+    #[cfg(feature = "std")]
+    /// Overrides the suite's `timeout_ms`
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+}
+
+impl SuiteTest {
+    #[must_use]
+    /// Check if the test has the given tag
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|test_tag| test_tag == tag)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+/// A stage of a [`TestSuite`]. Stages run in the order they are declared, after the tests without a stage,
+///  and the tests in a stage are skipped unless all the tests in the stages it `needs` passed
+pub struct Stage {
+    pub name: String,
+    /// The names of earlier stages that must pass first
+    #[serde(default)]
+    pub needs: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+/// A suite of tests with shared defaults
+///
+/// This can be deserialised from any format supported by serde,
+///  or from TOML with [`TestSuite::from_toml`]
+pub struct TestSuite {
+    /// The default maximum number of cycles for the tests
+    #[serde(default)]
+    pub max_cycles: Option<u32>,
+    /// The default maximum number of outputs for the tests
+    #[serde(default)]
+    pub max_outputs: Option<u32>,
+    #[cfg(feature = "std")]
+    /// The default maximum real time for the tests, in milliseconds
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+    #[serde(default, rename = "stage")]
+    pub stages: Vec<Stage>,
+    #[serde(default, rename = "test")]
+    pub tests: Vec<SuiteTest>,
+}
+
+impl TestSuite {
+    /// Read a test suite from TOML, for example
+    ///
+    /// ```toml
+    /// max_cycles = 1000
+    /// max_outputs = 100
+    /// timeout_ms = 500
+    ///
+    /// [[stage]]
+    /// name = "basic"
+    ///
+    /// [[stage]]
+    /// name = "edge cases"
+    /// needs = ["basic"]
+    ///
+    /// [[test]]
+    /// name = "add"
+    /// description = "Adds two numbers"
+    /// tags = ["arithmetic"]
+    /// stage = "basic"
+    /// inputs = [1, 2]
+    /// outputs = [3]
+    /// unordered_outputs = false
+    /// memory = { 99 = 1 }
+    /// setup = { 90 = 25 }
+    /// register = 3
+    /// negative_flag = false
+    /// state = "halted"
+    /// seed = 42
+    /// ```
+    ///
+    /// # Errors
+    /// [`toml::de::Error`] - the text is not valid TOML, or not in the format above
+    pub fn from_toml(text: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(text)
+    }
+
+    /// Creates an iterator over the tests in the suite, with the suite's defaults applied
+    ///
+    /// # Errors
+    /// Iterator can return a [`SuiteError`] with a [`TestNumber`]
+    pub fn tests(&self) -> impl Iterator<Item = Result<StdTest<'_>, SuiteErrorWithTestNumber>> {
+        self.tests.iter().enumerate().map(|(index, test)| {
+            self.to_test(test)
+                .map_err(|error| errors::ErrorWithLocation(TestNumber(index + 1), error))
+        })
+    }
+
+    /// Creates an iterator over the tests in the suite that match `filter`,
+    ///  with the suite's defaults applied
+    ///
+    /// # Errors
+    /// Iterator can return a [`SuiteError`] with a [`TestNumber`] (counting all the tests)
+    pub fn tests_matching<'a>(
+        &'a self,
+        filter: Filter<'a>,
+    ) -> impl Iterator<Item = Result<StdTest<'a>, SuiteErrorWithTestNumber>> {
+        self.tests
+            .iter()
+            .enumerate()
+            .filter(move |(_, test)| filter.matches(test.name.as_deref(), &test.tags))
+            .map(|(index, test)| {
+                self.to_test(test)
+                    .map_err(|error| errors::ErrorWithLocation(TestNumber(index + 1), error))
+            })
+    }
+
+    /// Run the tests in the suite against the program in `memory`, by stage (see [`Stage`])
+    ///
+    /// # Errors
+    /// Returns the first [`SuiteError`] from the tests, with its [`TestNumber`]
+    pub fn run(&self, memory: Memory) -> Result<Report<'_>, SuiteErrorWithTestNumber> {
+        self.run_matching(memory, Filter::new())
+    }
+
+    /// Get the index of a stage
+    fn stage_index(&self, name: &str) -> Result<usize, SuiteError> {
+        self.stages
+            .iter()
+            .position(|stage| stage.name == name)
+            .ok_or_else(|| SuiteError::UnknownStage(name.into()))
+    }
+
+    /// Run the tests in the suite that match `filter` against the program in `memory`,
+    ///  by stage (see [`Stage`]).
+    /// A stage passes if all its tests that match `filter` pass.
+    /// With [`Filter::with_fail_fast`], the tests after the first failure are left out
+    ///
+    /// # Errors
+    /// Returns the first [`SuiteError`] from the tests, with its [`TestNumber`].
+    /// Errors in a stage's `needs` are given with the number of the first test in the stage
+    pub fn run_matching<'a>(
+        &'a self,
+        memory: Memory,
+        filter: Filter<'a>,
+    ) -> Result<Report<'a>, SuiteErrorWithTestNumber> {
+        // The stage of each test, where 0 is no stage and the rest are offset by 1
+        let stages = self
+            .tests
+            .iter()
+            .enumerate()
+            .map(|(index, test)| {
+                test.stage
+                    .as_deref()
+                    .map_or(Ok(0), |stage| {
+                        self.stage_index(stage).map(|index| index + 1)
+                    })
+                    .map_err(|error| errors::ErrorWithLocation(TestNumber(index + 1), error))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut report = Report::default();
+        let mut passed = Vec::with_capacity(self.stages.len() + 1);
+
+        for stage in 0..=self.stages.len() {
+            // Whether the stages this stage needs passed, which is only an error if the stage has tests
+            let needs_passed = stage.checked_sub(1).map_or(Ok(true), |stage_index| {
+                self.stages[stage_index]
+                    .needs
+                    .iter()
+                    .try_fold(true, |run, need| match self.stage_index(need) {
+                        Ok(need) if need < stage_index => Ok(run && passed[need + 1]),
+                        _ => Err(SuiteError::UnknownStage(need.clone())),
+                    })
+            });
+            let mut stage_passed = needs_passed == Ok(true);
+
+            for (index, test) in self.tests.iter().enumerate() {
+                if stages[index] != stage || !filter.matches(test.name.as_deref(), &test.tags) {
+                    continue;
+                }
+                let location = TestNumber(index + 1);
+
+                let run = needs_passed
+                    .clone()
+                    .map_err(|error| errors::ErrorWithLocation(location, error))?;
+                let test = self
+                    .to_test(test)
+                    .map_err(|error| errors::ErrorWithLocation(location, error))?;
+
+                if run {
+                    report.push(memory, test);
+                    if filter.fail_fast() && report.last_failed() {
+                        return Ok(report);
+                    }
+                    stage_passed &= report.tests.last().is_some_and(TestReport::passed);
+                } else {
+                    report.skip(&test);
+                }
+            }
+
+            passed.push(stage_passed);
+        }
+
+        Ok(report)
+    }
+
+    /// Convert one of the tests, with the suite's defaults applied
+    fn to_test<'a>(&self, test: &'a SuiteTest) -> Result<StdTest<'a>, SuiteError> {
+        Ok(StdTest {
+            name: test.name.as_deref().filter(|name| !name.is_empty()),
+            max_cycles: test
+                .max_cycles
+                .or(self.max_cycles)
+                .ok_or(SuiteError::MissingMaxCycles)?,
+            max_outputs: test.max_outputs.or(self.max_outputs),
+            seed: test.seed,
+            #[cfg(feature = "std")]
+            timeout: test
+                .timeout_ms
+                .or(self.timeout_ms)
+                .map(Duration::from_millis),
+            inputs: to_numbers(&test.inputs, SuiteError::InputTooLarge)?,
+            outputs: to_outputs(
+                &test.outputs,
+                SuiteError::OutputTooLarge,
+                SuiteError::InvalidOutput,
+            )?,
+            unordered_outputs: test.unordered_outputs,
+            memory: to_cells(
+                &test.memory,
+                SuiteError::AddressTooLarge,
+                SuiteError::MemoryValueTooLarge,
+            )?,
+            setup: to_cells(
+                &test.setup,
+                SuiteError::AddressTooLarge,
+                SuiteError::MemoryValueTooLarge,
+            )?,
+            register: test
+                .register
+                .map(|number| {
+                    ThreeDigitNumber::try_from(number)
+                        .map_err(|_| SuiteError::RegisterTooLarge(number))
+                })
+                .transpose()?,
+            negative_flag: test.negative_flag,
+            state: test.state.map(State::from),
+            #[cfg(feature = "extended")]
+            char_inputs: to_chars(&test.char_inputs, SuiteError::InvalidCharInput)?,
+            #[cfg(feature = "extended")]
+            char_outputs: to_chars(&test.char_outputs, SuiteError::InvalidCharOutput)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{assembler::assemble_from_text, computer::Computer, errors::ErrorWithLocation};
+
+    use super::{super::Outcome, Filter, SuiteError, TestNumber, TestSuite};
+
+    #[test]
+    fn toml() {
+        let suite = TestSuite::from_toml(
+            r#"
+                max_cycles = 100
+
+                [[test]]
+                name = "add"
+                description = "Adds two numbers"
+                tags = ["arithmetic"]
+                inputs = [1, 2]
+                outputs = [3]
+                memory = { 99 = 1 }
+                register = 3
+                negative_flag = false
+                state = "halted"
+
+                [[test]]
+                inputs = [5, 6]
+                outputs = ["10..20"]
+                max_cycles = 7
+            "#,
+        )
+        .expect("failed to parse the TOML");
+
+        assert_eq!(suite.tests.len(), 2, "Got the wrong number of tests!");
+        assert!(
+            suite.tests[0].has_tag("arithmetic"),
+            "Failed to get the tags from TOML!"
+        );
+
+        let tests: Vec<_> = suite
+            .tests()
+            .collect::<Result<_, _>>()
+            .expect("failed to read the tests");
+        assert_eq!(tests[0].name, Some("add"), "Failed to get the name!");
+        assert_eq!(
+            tests[0].max_cycles, 100,
+            "Failed to use the suite's max cycles!"
+        );
+        assert_eq!(tests[1].name, None, "Got a name for an unnamed test!");
+        assert_eq!(
+            tests[1].max_cycles, 7,
+            "Failed to use the test's max cycles!"
+        );
+
+        let memory = assemble_from_text("IN\nSTO 99\nIN\nADD 99\nOUT\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+        let mut computer = Computer::new(memory);
+        for test in tests {
+            computer.reset();
+            test.run(&mut computer).expect("test failed");
+        }
+
+        let report = suite
+            .run_matching(memory, Filter::new().with_tags(&["arithmetic"]))
+            .expect("failed to read the tests");
+        assert_eq!(
+            report.tests.len(),
+            1,
+            "Ran the wrong number of filtered tests!"
+        );
+        assert!(report.all_passed(), "A filtered test failed!");
+
+        let suite =
+            TestSuite::from_toml("[[test]]\noutputs = [1]").expect("failed to parse the TOML");
+        let error = suite
+            .tests()
+            .next()
+            .expect("failed to get the test")
+            .expect_err("read a test without max cycles");
+        assert_eq!(
+            (error.0, error.1),
+            (TestNumber(1), SuiteError::MissingMaxCycles),
+            "Got the wrong error!"
+        );
+    }
+
+    #[test]
+    fn stages() {
+        let suite = TestSuite::from_toml(
+            r#"
+                max_cycles = 10
+
+                [[stage]]
+                name = "basic"
+
+                [[stage]]
+                name = "edge cases"
+                needs = ["basic"]
+
+                [[stage]]
+                name = "extra"
+                needs = ["edge cases"]
+
+                [[test]]
+                name = "extra"
+                stage = "extra"
+                inputs = [0, 0]
+                outputs = [0]
+
+                [[test]]
+                name = "wrong"
+                stage = "edge cases"
+                inputs = [1, 1]
+                outputs = [5]
+
+                [[test]]
+                name = "add"
+                stage = "basic"
+                inputs = [1, 2]
+                outputs = [3]
+
+                [[test]]
+                name = "unstaged"
+                inputs = [2, 2]
+                outputs = [4]
+            "#,
+        )
+        .expect("failed to parse the TOML");
+
+        let memory = assemble_from_text("IN\nSTO 99\nIN\nADD 99\nOUT\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+        let report = suite.run(memory).expect("failed to read the tests");
+
+        assert_eq!(
+            report
+                .tests
+                .iter()
+                .map(|test| (test.name, test.outcome == Outcome::Skipped))
+                .collect::<Vec<_>>(),
+            [
+                (Some("unstaged"), false),
+                (Some("add"), false),
+                (Some("wrong"), false),
+                (Some("extra"), true)
+            ],
+            "Ran the stages in the wrong order!"
+        );
+        assert_eq!(
+            (report.passed(), report.failed(), report.skipped()),
+            (2, 1, 1),
+            "Counted the wrong number of passes, fails and skips!"
+        );
+
+        let suite = TestSuite::from_toml(
+            r#"
+                [[stage]]
+                name = "first"
+                needs = ["second"]
+
+                [[stage]]
+                name = "second"
+
+                [[test]]
+                stage = "first"
+                max_cycles = 10
+            "#,
+        )
+        .expect("failed to parse the TOML");
+        assert_eq!(
+            suite.run(memory).map(|_| ()),
+            Err(ErrorWithLocation(
+                TestNumber(1),
+                SuiteError::UnknownStage("second".into())
+            )),
+            "Failed to reject a stage that needs a later stage!"
+        );
+    }
+
+    #[test]
+    fn setup() {
+        let suite = TestSuite::from_toml(
+            "
+                [[test]]
+                setup = { 90 = 25 }
+                outputs = [25]
+                memory = { 90 = 25 }
+                max_cycles = 10
+            ",
+        )
+        .expect("failed to parse the TOML");
+
+        let memory = assemble_from_text("LDA 90\nOUT\nHLT")
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+        let report = suite.run(memory).expect("failed to read the tests");
+        assert!(report.all_passed(), "Failed to set up the memory!");
+    }
+
+    #[test]
+    fn run() {
+        let assembly = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/examples/fib.txt"));
+        let suite_toml = include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/examples/fib_test.toml"
+        ));
+
+        let memory = assemble_from_text(assembly)
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+
+        let mut computer = Computer::new(memory);
+
+        let suite = TestSuite::from_toml(suite_toml).expect("failed to parse the TOML");
+        for test in suite.tests() {
+            test.expect("failed to read the test")
+                .run(&mut computer)
+                .expect("test failed");
+        }
+    }
+}
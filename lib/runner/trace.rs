@@ -0,0 +1,125 @@
+use core::fmt;
+
+use crate::{
+    computer::{Computer, Memory, State},
+    num2::TwoDigitNumber,
+    num3::ThreeDigitNumber,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// A snapshot of a [Computer] before it runs an instruction
+pub struct Trace {
+    /// The number of cycles run before this one
+    pub cycle: u32,
+    /// The address of the instruction (the counter),
+    ///  or [`None`] if the counter is past the end of memory
+    pub address: Option<TwoDigitNumber>,
+    /// The instruction at `address`, or [`None`] if the counter is past the end of memory
+    pub instruction: Option<ThreeDigitNumber>,
+    pub register: ThreeDigitNumber,
+    pub negative_flag: bool,
+    #[cfg(feature = "extended")]
+    pub extended_mode_flag: bool,
+    pub state: State,
+}
+
+impl Trace {
+    #[must_use]
+    /// Take a [Trace] of the [Computer] at the given cycle
+    pub fn new(computer: &Computer, cycle: u32) -> Self {
+        let address = computer.counter();
+
+        Self {
+            cycle,
+            address,
+            instruction: address.map(|address| computer.get_memory()[address]),
+            register: computer.register(),
+            negative_flag: computer.negative_flag(),
+            #[cfg(feature = "extended")]
+            extended_mode_flag: computer.extended_mode_flag(),
+            state: computer.state(),
+        }
+    }
+}
+
+impl fmt::Display for Trace {
+    /// Formats the trace as one line with the cycle, address, instruction, register and flags
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:>5}  ", self.cycle)?;
+
+        match (self.address, self.instruction) {
+            (Some(address), Some(instruction)) => {
+                write!(f, "{address:#}  {instruction:#}")?;
+            }
+            _ => write!(f, "--  ---")?,
+        }
+
+        write!(
+            f,
+            "  {:#}  {}",
+            self.register,
+            if self.negative_flag { 'N' } else { '-' }
+        )?;
+
+        #[cfg(feature = "extended")]
+        write!(f, "{}", if self.extended_mode_flag { 'E' } else { '-' })?;
+
+        Ok(())
+    }
+}
+
+/// Write the memory between the addresses (inclusive) to `out`, ten cells per line,
+///  marking the cell at `counter` with a `>`
+///
+/// # Errors
+/// [`fmt::Error`] - failed to write to `out`
+pub fn write_memory(
+    memory: &Memory,
+    start: usize,
+    end: usize,
+    counter: Option<usize>,
+    out: &mut impl fmt::Write,
+) -> fmt::Result {
+    for (address, number) in memory.iter().enumerate().take(end.min(99) + 1).skip(start) {
+        if address == start || address % 10 == 0 {
+            if address != start {
+                writeln!(out)?;
+            }
+            write!(out, "{address:02}:")?;
+        }
+
+        let marker = if Some(address) == counter { '>' } else { ' ' };
+        write!(out, " {marker}{number:#}")?;
+    }
+
+    writeln!(out)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{computer::Computer, num3::ThreeDigitNumber};
+
+    use super::Trace;
+
+    #[test]
+    fn format() {
+        let mut memory = [ThreeDigitNumber::ZERO; 100];
+        memory[0] = ThreeDigitNumber::new::<902>();
+        let computer = Computer::new(memory);
+
+        let trace = Trace::new(&computer, 7);
+
+        #[cfg(not(feature = "extended"))]
+        assert_eq!(
+            trace.to_string(),
+            "    7  00  902  000  -",
+            "Trace was formatted incorrectly!"
+        );
+        #[cfg(feature = "extended")]
+        assert_eq!(
+            trace.to_string(),
+            "    7  00  902  000  --",
+            "Trace was formatted incorrectly!"
+        );
+    }
+}
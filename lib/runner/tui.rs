@@ -0,0 +1,461 @@
+use std::{io, time::Duration};
+
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
+    layout::{Constraint, Layout, Rect},
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Block, Cell, Paragraph, Row, Table},
+    DefaultTerminal, Frame,
+};
+
+use crate::{
+    computer::{Computer, Memory, State},
+    num3::ThreeDigitNumber,
+};
+
+/// How long to wait for a key press before stepping when running
+const TICK: Duration = Duration::from_millis(100);
+
+/// How many instructions to run each tick when running
+const STEPS_PER_TICK: u32 = 100;
+
+/// A full-screen terminal front-end that shows the memory, register, counter, flags and io
+pub struct Tui {
+    computer: Computer,
+    cycles: u32,
+    running: bool,
+    console: Vec<String>,
+    mid_char_sequence: bool,
+    input: String,
+    last_written: Option<usize>,
+    message: Option<&'static str>,
+}
+
+impl Tui {
+    #[must_use]
+    /// Create a new [Tui] from [Memory]
+    pub const fn new(memory: Memory) -> Self {
+        Self::new_from_computer(Computer::new(memory))
+    }
+
+    #[must_use]
+    /// Create a new [Tui] from a [Computer]
+    pub const fn new_from_computer(computer: Computer) -> Self {
+        Self {
+            computer,
+            cycles: 0,
+            running: false,
+            console: Vec::new(),
+            mid_char_sequence: false,
+            input: String::new(),
+            last_written: None,
+            message: None,
+        }
+    }
+
+    /// Take over the terminal and run until the user quits,
+    ///  returning the final [State] of the computer
+    ///
+    /// # Errors
+    /// [`io::Error`] - failed to draw to the terminal or read a key press
+    pub fn run(mut self) -> io::Result<State> {
+        let mut terminal = ratatui::init();
+        let result = self.event_loop(&mut terminal);
+        ratatui::restore();
+
+        result.map(|()| self.computer.state())
+    }
+
+    fn event_loop(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if event::poll(TICK)? {
+                if let Event::Key(key) = event::read()? {
+                    if key.kind == KeyEventKind::Press && self.handle_key(key) {
+                        return Ok(());
+                    }
+                }
+            } else if self.running {
+                self.run_batch();
+            }
+        }
+    }
+
+    /// Handle a key press, returning `true` if the user quit.
+    /// While the computer is waiting for a char input, every key but escape is the input,
+    ///  so escape is the only way to quit
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        self.message = None;
+
+        match (self.computer.state(), key.code) {
+            (_, KeyCode::Esc) => return true,
+
+            (State::AwaitingInput, KeyCode::Char(character)) if character.is_ascii_digit() => {
+                self.input.push(character);
+            }
+            (State::AwaitingInput, KeyCode::Backspace) => {
+                self.input.pop();
+            }
+            (State::AwaitingInput, KeyCode::Enter) => {
+                match self
+                    .input
+                    .parse::<u16>()
+                    .ok()
+                    .and_then(|number| ThreeDigitNumber::try_from(number).ok())
+                {
+                    Some(number) => {
+                        if self.computer.input(number).is_err() {
+                            self.message = Some("The computer rejected the input!");
+                            return false;
+                        }
+                        self.console.push(format!("> {number}"));
+                        self.input.clear();
+                    }
+                    None => self.message = Some("Invalid input, enter a number from 0 to 999!"),
+                }
+            }
+
+            #[cfg(feature = "extended")]
+            (State::AwaitingCharInput, KeyCode::Char(character)) => {
+                match ThreeDigitNumber::try_from(character).ok() {
+                    Some(number) => {
+                        if self.computer.input_char(number).is_err() {
+                            self.message = Some("The computer rejected the input!");
+                            return false;
+                        }
+                        self.console.push(format!("> {character:?}"));
+                    }
+                    None => self.message = Some("Invalid input character!"),
+                }
+            }
+
+            (_, KeyCode::Char('q')) => return true,
+            (_, KeyCode::Char('s' | ' ')) => {
+                self.running = false;
+                self.step();
+            }
+            (_, KeyCode::Char('r')) => self.running = !self.running,
+            (_, KeyCode::Char('x')) => {
+                self.computer.reset();
+                self.cycles = 0;
+                self.running = false;
+                self.last_written = None;
+                self.console.clear();
+                self.mid_char_sequence = false;
+            }
+            _ => (),
+        }
+
+        false
+    }
+
+    /// Run up to [`STEPS_PER_TICK`] instructions, stopping early if the computer stops
+    ///  running (for example to wait for an input)
+    fn run_batch(&mut self) {
+        for _ in 0..STEPS_PER_TICK {
+            if !self.running || self.computer.state() != State::Running {
+                break;
+            }
+            self.step();
+        }
+    }
+
+    /// Run one instruction and deal with any output
+    fn step(&mut self) {
+        if self.computer.state() != State::Running {
+            return;
+        }
+
+        let before = *self.computer.get_memory();
+        let state = self.computer.step();
+        self.cycles += 1;
+
+        self.last_written = before
+            .iter()
+            .zip(self.computer.get_memory())
+            .position(|(before, after)| before != after);
+
+        match state {
+            State::AwaitingOutput => {
+                let Ok(output) = self.computer.output() else {
+                    self.message = Some("The computer did not give an output!");
+                    self.running = false;
+                    return;
+                };
+                self.console.push(output.to_string());
+                self.mid_char_sequence = false;
+            }
+            #[cfg(feature = "extended")]
+            State::AwaitingCharOutput => {
+                let Ok(output) = self.computer.output_char() else {
+                    self.message = Some("The computer did not give an output!");
+                    self.running = false;
+                    return;
+                };
+
+                match char::from_u32(output.into()) {
+                    Some('\n') => self.mid_char_sequence = false,
+                    Some(character) => {
+                        if self.mid_char_sequence {
+                            if let Some(line) = self.console.last_mut() {
+                                line.push(character);
+                            }
+                        } else {
+                            self.console.push(character.to_string());
+                            self.mid_char_sequence = true;
+                        }
+                    }
+                    None => self.message = Some("Invalid character outputted!"),
+                }
+            }
+            State::Halted | State::ReachedEnd | State::InvalidInstruction => self.running = false,
+            _ => (),
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let [main, help] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+        let [memory, side] =
+            Layout::horizontal([Constraint::Length(53), Constraint::Min(24)]).areas(main);
+        let [registers, console] =
+            Layout::vertical([Constraint::Length(9), Constraint::Min(3)]).areas(side);
+
+        self.draw_memory(frame, memory);
+        self.draw_registers(frame, registers);
+        self.draw_console(frame, console);
+
+        // Every key but escape is a char input
+        #[cfg(feature = "extended")]
+        let char_input = self.computer.state() == State::AwaitingCharInput;
+        #[cfg(not(feature = "extended"))]
+        let char_input = false;
+
+        let help_text = self.message.unwrap_or(if char_input {
+            "type a char to input it  esc: quit"
+        } else {
+            "s/space: step  r: run/pause  x: reset  q/esc: quit  (type inputs and press enter)"
+        });
+        frame.render_widget(Line::from(help_text).dim(), help);
+    }
+
+    fn draw_memory(&self, frame: &mut Frame, area: Rect) {
+        let memory = self.computer.get_memory();
+        let counter = self.computer.counter().map(usize::from);
+
+        let header = Row::new(
+            core::iter::once(Cell::from(""))
+                .chain((0..10).map(|column| Cell::from(format!("  {column}")))),
+        )
+        .bold();
+
+        let rows = (0..10).map(|row| {
+            Row::new(
+                core::iter::once(Cell::from(format!("{row}0")).bold()).chain((0..10).map(
+                    |column| {
+                        let address = row * 10 + column;
+                        let cell = Cell::from(format!("{:#}", memory[address]));
+
+                        if Some(address) == counter {
+                            cell.reversed()
+                        } else if Some(address) == self.last_written {
+                            cell.underlined()
+                        } else {
+                            cell
+                        }
+                    },
+                )),
+            )
+        });
+
+        let table = Table::new(rows, [Constraint::Length(3); 11])
+            .header(header)
+            .column_spacing(1)
+            .block(Block::bordered().title(" Memory "));
+
+        frame.render_widget(table, area);
+    }
+
+    fn draw_registers(&self, frame: &mut Frame, area: Rect) {
+        let flag = |value: bool| if value { "set" } else { "clear" };
+
+        #[allow(unused_mut)]
+        let mut lines = vec![
+            Line::from(format!("Register:      {:#}", self.computer.register())),
+            Line::from(self.computer.counter().map_or_else(
+                || "Counter:       --".to_string(),
+                |counter| format!("Counter:       {counter:#}"),
+            )),
+            Line::from(format!(
+                "Negative flag: {}",
+                flag(self.computer.negative_flag())
+            )),
+        ];
+
+        #[cfg(feature = "extended")]
+        lines.push(Line::from(format!(
+            "Extended mode: {}",
+            flag(self.computer.extended_mode_flag())
+        )));
+
+        lines.push(Line::from(format!("Cycles:        {}", self.cycles)));
+        lines.push(Line::from(format!(
+            "The computer {}{}",
+            self.computer.state(),
+            if self.running { " (running)" } else { "" }
+        )));
+
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::bordered().title(" Registers ")),
+            area,
+        );
+    }
+
+    fn draw_console(&self, frame: &mut Frame, area: Rect) {
+        let height = usize::from(area.height.saturating_sub(2));
+
+        let mut lines: Vec<Line> = self.console.iter().map(Line::raw).collect();
+
+        match self.computer.state() {
+            State::AwaitingInput => {
+                lines.push(Line::styled(
+                    format!("(i) > {}_", self.input),
+                    Style::new().bold(),
+                ));
+            }
+            #[cfg(feature = "extended")]
+            State::AwaitingCharInput => {
+                lines.push(Line::styled("(c) > _", Style::new().bold()));
+            }
+            _ => (),
+        }
+
+        let skip = lines.len().saturating_sub(height);
+        let lines: Vec<Line> = lines.into_iter().skip(skip).collect();
+
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::bordered().title(" Console ")),
+            area,
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ratatui::crossterm::event::{KeyCode, KeyEvent};
+
+    use crate::{assembler::assemble_from_text, computer::State};
+
+    use super::Tui;
+
+    /// Create a [Tui] running the assembly
+    fn tui(assembly: &str) -> Tui {
+        let memory = assemble_from_text(assembly)
+            .expect("failed to parse the assembly")
+            .expect("failed to assemble the assembly");
+        Tui::new(memory)
+    }
+
+    /// Press the keys, returning `true` if any of them quit
+    fn press(tui: &mut Tui, keys: impl IntoIterator<Item = KeyCode>) -> bool {
+        keys.into_iter()
+            .any(|key| tui.handle_key(KeyEvent::from(key)))
+    }
+
+    #[test]
+    fn number_input() {
+        let mut tui = tui("IN\nOUT\nHLT");
+        tui.step();
+        assert_eq!(
+            tui.computer.state(),
+            State::AwaitingInput,
+            "The computer is not waiting for an input!"
+        );
+
+        assert!(
+            !press(
+                &mut tui,
+                [
+                    KeyCode::Char('1'),
+                    KeyCode::Char('0'),
+                    KeyCode::Char('0'),
+                    KeyCode::Char('0'),
+                    KeyCode::Enter
+                ]
+            ),
+            "Quit while typing an input!"
+        );
+        assert!(tui.message.is_some(), "Did not reject an input over 999!");
+        assert_eq!(
+            tui.computer.state(),
+            State::AwaitingInput,
+            "Gave an input over 999!"
+        );
+
+        press(
+            &mut tui,
+            [KeyCode::Backspace, KeyCode::Backspace, KeyCode::Enter],
+        );
+        assert_eq!(tui.console, ["> 10"], "Gave the wrong input!");
+
+        tui.run_batch();
+        tui.running = true;
+        tui.run_batch();
+        assert_eq!(tui.console, ["> 10", "10"], "Did not output the input!");
+        assert_eq!(
+            tui.computer.state(),
+            State::Halted,
+            "The computer did not halt!"
+        );
+        assert!(!tui.running, "Kept running after halting!");
+    }
+
+    #[cfg(feature = "extended")]
+    #[test]
+    fn char_io() {
+        let mut tui =
+            tui("EXT\nINA\nOTA\nLDA i\nOTA\nLDA nl\nOTA\nLDA i\nOTA\nHLT\ni DAT 105\nnl DAT 10");
+        tui.running = true;
+        tui.run_batch();
+        assert_eq!(
+            tui.computer.state(),
+            State::AwaitingCharInput,
+            "The computer is not waiting for a char input!"
+        );
+
+        assert!(
+            !press(&mut tui, [KeyCode::Char('q')]),
+            "Quit instead of inputting 'q'!"
+        );
+        assert_eq!(tui.console, ["> 'q'"], "Gave the wrong char input!");
+
+        tui.run_batch();
+        assert_eq!(
+            tui.console,
+            ["> 'q'", "qi", "i"],
+            "Did not join the char outputs into lines!"
+        );
+    }
+
+    #[test]
+    fn reset_and_quit() {
+        let mut tui = tui("OUT\nHLT");
+        tui.running = true;
+        tui.run_batch();
+        assert_eq!(tui.cycles, 2, "Ran the wrong number of cycles!");
+
+        assert!(!press(&mut tui, [KeyCode::Char('x')]), "Quit on reset!");
+        assert_eq!(tui.cycles, 0, "Did not reset the cycles!");
+        assert!(tui.console.is_empty(), "Did not clear the console!");
+        assert_eq!(
+            tui.computer.state(),
+            State::Running,
+            "Did not reset the computer!"
+        );
+
+        assert!(press(&mut tui, [KeyCode::Char('q')]), "Did not quit on q!");
+        assert!(press(&mut tui, [KeyCode::Esc]), "Did not quit on escape!");
+    }
+}
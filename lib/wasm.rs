@@ -0,0 +1,178 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    assembler::assemble_from_text,
+    computer::{Computer, Memory, State},
+    num3::ThreeDigitNumber,
+};
+
+/// Get the name of a [State] for JavaScript
+const fn state_name(state: State) -> &'static str {
+    match state {
+        State::Running => "running",
+        State::AwaitingInput => "awaitingInput",
+        State::AwaitingOutput => "awaitingOutput",
+        #[cfg(feature = "extended")]
+        State::AwaitingCharInput => "awaitingCharInput",
+        #[cfg(feature = "extended")]
+        State::AwaitingCharOutput => "awaitingCharOutput",
+        State::Halted => "halted",
+        State::ReachedEnd => "reachedEnd",
+        State::InvalidInstruction => "invalidInstruction",
+    }
+}
+
+fn to_number(value: u16) -> Result<ThreeDigitNumber, JsError> {
+    ThreeDigitNumber::try_from(value).map_err(|error| JsError::new(&error.to_string()))
+}
+
+#[wasm_bindgen]
+/// Assemble assembly text to memory (an array of 100 numbers)
+///
+/// # Errors
+/// If the text could not be parsed or assembled, with the error message
+pub fn assemble(text: &str) -> Result<Vec<u16>, JsError> {
+    let memory = assemble_from_text(text)
+        .map_err(|error| JsError::new(&error.to_string()))?
+        .map_err(|error| JsError::new(&error.to_string()))?;
+
+    Ok(memory.iter().map(|number| u16::from(*number)).collect())
+}
+
+#[wasm_bindgen]
+/// A computer that can be run from JavaScript
+pub struct Machine {
+    computer: Computer,
+}
+
+// wasm-bindgen cannot export const functions
+#[allow(clippy::missing_const_for_fn)]
+#[wasm_bindgen]
+impl Machine {
+    #[wasm_bindgen(constructor)]
+    /// Create a new [Machine] from memory (an array of up to 100 numbers, each up to 999)
+    ///
+    /// # Errors
+    /// If there are more than 100 numbers or a number is too large
+    pub fn new(memory: &[u16]) -> Result<Self, JsError> {
+        if memory.len() > 100 {
+            return Err(JsError::new("Memory is too large (> 100 numbers)!"));
+        }
+
+        let mut full_memory: Memory = [ThreeDigitNumber::ZERO; 100];
+        for (cell, value) in full_memory.iter_mut().zip(memory) {
+            *cell = to_number(*value)?;
+        }
+
+        Ok(Self {
+            computer: Computer::new(full_memory),
+        })
+    }
+
+    /// Run one instruction, returning the new state
+    pub fn step(&mut self) -> String {
+        state_name(self.computer.step()).to_owned()
+    }
+
+    /// Run until the computer stops running or `max_cycles` instructions have been run,
+    ///  returning the new state
+    pub fn run(&mut self, max_cycles: u32) -> String {
+        for _ in 0..max_cycles {
+            if self.computer.step() != State::Running {
+                break;
+            }
+        }
+
+        self.state()
+    }
+
+    #[wasm_bindgen(getter)]
+    #[must_use]
+    /// Get the state of the computer (e.g. `"running"` or `"awaitingInput"`)
+    pub fn state(&self) -> String {
+        state_name(self.computer.state()).to_owned()
+    }
+
+    /// Give an input to the computer
+    ///
+    /// # Errors
+    /// If the input is too large or the computer was not waiting for an input
+    pub fn input(&mut self, value: u16) -> Result<(), JsError> {
+        self.computer
+            .input(to_number(value)?)
+            .map_err(|error| JsError::new(&error.to_string()))
+    }
+
+    /// Take an output from the computer
+    ///
+    /// # Errors
+    /// If the computer was not waiting to output
+    pub fn output(&mut self) -> Result<u16, JsError> {
+        self.computer
+            .output()
+            .map(u16::from)
+            .map_err(|error| JsError::new(&error.to_string()))
+    }
+
+    #[cfg(feature = "extended")]
+    #[wasm_bindgen(js_name = inputChar)]
+    /// Give a char input (a character code) to the computer
+    ///
+    /// # Errors
+    /// If the input is too large or the computer was not waiting for a char input
+    pub fn input_char(&mut self, value: u16) -> Result<(), JsError> {
+        self.computer
+            .input_char(to_number(value)?)
+            .map_err(|error| JsError::new(&error.to_string()))
+    }
+
+    #[cfg(feature = "extended")]
+    #[wasm_bindgen(js_name = outputChar)]
+    /// Take a char output (a character code) from the computer
+    ///
+    /// # Errors
+    /// If the computer was not waiting to output a char
+    pub fn output_char(&mut self) -> Result<u16, JsError> {
+        self.computer
+            .output_char()
+            .map(u16::from)
+            .map_err(|error| JsError::new(&error.to_string()))
+    }
+
+    #[wasm_bindgen(getter)]
+    #[must_use]
+    /// Get the memory (an array of 100 numbers)
+    pub fn memory(&self) -> Vec<u16> {
+        self.computer
+            .get_memory()
+            .iter()
+            .map(|number| u16::from(*number))
+            .collect()
+    }
+
+    #[wasm_bindgen(getter)]
+    #[must_use]
+    /// Get the register
+    pub fn register(&self) -> u16 {
+        self.computer.register().into()
+    }
+
+    #[wasm_bindgen(getter)]
+    #[must_use]
+    /// Get the counter, or 100 if it has run past the end of memory
+    pub fn counter(&self) -> usize {
+        self.computer.counter().map_or(100, usize::from)
+    }
+
+    #[wasm_bindgen(getter, js_name = negativeFlag)]
+    #[must_use]
+    /// Get the negative flag
+    pub fn negative_flag(&self) -> bool {
+        self.computer.negative_flag()
+    }
+
+    /// Reset the computer without resetting the memory
+    pub fn reset(&mut self) {
+        self.computer.reset();
+    }
+}
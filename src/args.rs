@@ -0,0 +1,466 @@
+use std::fmt::Write;
+
+use lminc::helper::case_insensitive::Str;
+
+use crate::error::Error;
+
+/// The column that option help starts at, after the option's name and value
+const OPTION_WIDTH: usize = 24;
+
+#[derive(Clone, Copy, Debug)]
+/// An option of a [Subcommand], like `--check` or `--to <format>`
+pub struct Opt {
+    /// The name, including the dashes
+    pub name: &'static str,
+    /// The name of the option's value, if it takes one
+    pub value: Option<&'static str>,
+    /// What the option does, on one or more lines
+    pub help: &'static str,
+}
+
+impl Opt {
+    #[must_use]
+    /// Create an option without a value
+    pub const fn flag(name: &'static str, help: &'static str) -> Self {
+        Self {
+            name,
+            value: None,
+            help,
+        }
+    }
+
+    #[must_use]
+    /// Create an option that takes a value
+    pub const fn value(name: &'static str, value: &'static str, help: &'static str) -> Self {
+        Self {
+            name,
+            value: Some(value),
+            help,
+        }
+    }
+
+    /// Write the option and its help, indented by `indent`
+    fn write_help(&self, out: &mut String, indent: usize) {
+        let mut name = self.name.to_owned();
+        if let Some(value) = self.value {
+            // Writing to a String does not fail
+            let _ = write!(name, " <{value}>");
+        }
+
+        // Long names get their help on the next line
+        let mut lines = self.help.lines();
+        if name.len() < OPTION_WIDTH {
+            let _ = writeln!(
+                out,
+                "{:indent$}{name:OPTION_WIDTH$}{}",
+                "",
+                lines.next().unwrap_or_default()
+            );
+        } else {
+            let _ = writeln!(out, "{:indent$}{name}", "");
+        }
+        for line in lines {
+            let _ = writeln!(out, "{:indent$}{:OPTION_WIDTH$}{line}", "", "");
+        }
+    }
+}
+
+/// A subcommand, with the arguments and options it takes
+pub struct Subcommand {
+    pub name: &'static str,
+    /// The names of the positional arguments, like `<path>`, where optional ones are
+    ///  in square brackets (like `[out path]`) and come after the others
+    pub arguments: &'static [&'static str],
+    pub options: &'static [Opt],
+    /// What the subcommand does, on one or more lines
+    pub description: &'static str,
+    pub run: fn(&Args) -> Result<(), Error>,
+}
+
+impl Subcommand {
+    #[must_use]
+    /// Find a subcommand by name, ignoring case
+    pub fn find<'a>(subcommands: &'a [Self], name: &str) -> Option<&'a Self> {
+        subcommands
+            .iter()
+            .find(|subcommand| Str::from(name) == subcommand.name)
+    }
+
+    #[must_use]
+    /// Get the usage line, like `lminc fmt <path> [options...]`
+    pub fn usage(&self, program: &str) -> String {
+        let mut usage = format!("{program} {}", self.signature());
+        if !self.options.is_empty() {
+            usage.push_str(" [options...]");
+        }
+        usage
+    }
+
+    /// Get the name and arguments, like `fmt <path>`
+    fn signature(&self) -> String {
+        let mut signature = self.name.to_owned();
+        for argument in self.arguments {
+            signature.push(' ');
+            signature.push_str(argument);
+        }
+        signature
+    }
+
+    #[must_use]
+    /// Get the help for the subcommand, with its usage, description and options
+    pub fn help(&self, program: &str) -> String {
+        let mut help = format!("Usage: {}\n\n{}\n", self.usage(program), self.description);
+
+        help.push_str("\nOptions:\n");
+        for option in self.options.iter().chain(GLOBAL_OPTIONS) {
+            option.write_help(&mut help, 4);
+        }
+        HELP_OPTION.write_help(&mut help, 4);
+
+        help
+    }
+
+    /// Write the subcommand for the list of subcommands, see [`help`]
+    fn write_summary(&self, out: &mut String) {
+        let _ = write!(out, "    {}", self.signature());
+        if !self.options.is_empty() {
+            out.push_str(" [options...]");
+        }
+        out.push('\n');
+
+        for line in self.description.lines() {
+            let _ = writeln!(out, "        {line}");
+        }
+        for option in self.options {
+            option.write_help(out, 12);
+        }
+    }
+}
+
+/// The option that every subcommand has, to print its help
+const HELP_OPTION: Opt = Opt::flag("-h, --help", "print this help");
+
+/// The options that every subcommand takes
+pub const GLOBAL_OPTIONS: &[Opt] = &[
+    Opt::flag(
+        "--no-color",
+        "do not color the output, like setting NO_COLOR",
+    ),
+    Opt::flag(
+        "--quiet",
+        "only print errors and the summary of test runs,\nso that the exit code gives the result",
+    ),
+    Opt::flag(
+        "--verbose",
+        "also print the number of cycles and the time taken",
+    ),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// How much a subcommand prints, from `--quiet` and `--verbose`
+pub enum Verbosity {
+    Quiet,
+    Normal,
+    Verbose,
+}
+
+#[must_use]
+/// Get the help for the program, with every subcommand and its options
+pub fn help(program: &str, subcommands: &[Subcommand]) -> String {
+    let mut help = format!("Usage: {program} <subcommand> <arguments...>\n\nSubcommands:\n");
+    for subcommand in subcommands {
+        subcommand.write_summary(&mut help);
+        help.push('\n');
+    }
+
+    help.push_str("Global options:\n");
+    for option in GLOBAL_OPTIONS.iter().chain([&HELP_OPTION]) {
+        option.write_help(&mut help, 4);
+    }
+    help.push('\n');
+
+    let _ = writeln!(
+        help,
+        "Use '{program} <subcommand> --help' for the help for one subcommand"
+    );
+    help
+}
+
+#[must_use]
+/// Check if the arguments ask for help
+pub fn wants_help(arguments: &[String]) -> bool {
+    arguments
+        .iter()
+        .any(|argument| argument == "-h" || argument == "--help")
+}
+
+/// The arguments given to a [Subcommand]
+pub struct Args<'a> {
+    program: &'a str,
+    subcommand: &'a Subcommand,
+    arguments: Vec<&'a str>,
+    options: Vec<(&'static str, Option<&'a str>)>,
+}
+
+impl<'a> Args<'a> {
+    /// Parse the arguments after the subcommand name.
+    /// Options start with `--`, and take their value from the next argument or after `=`
+    ///  (like `--to json` or `--to=json`)
+    ///
+    /// # Errors
+    /// [`Error::Usage`] - unknown or repeated options, a missing value, or the wrong
+    ///  number of positional arguments
+    pub fn parse(
+        program: &'a str,
+        subcommand: &'a Subcommand,
+        arguments: &'a [String],
+    ) -> Result<Self, Error> {
+        let mut args = Self {
+            program,
+            subcommand,
+            arguments: Vec::new(),
+            options: Vec::new(),
+        };
+
+        let mut arguments = arguments.iter();
+        while let Some(argument) = arguments.next() {
+            if !argument.starts_with("--") {
+                args.arguments.push(argument);
+                continue;
+            }
+
+            let (name, value) = match argument.split_once('=') {
+                Some((name, value)) => (name, Some(value)),
+                None => (argument.as_str(), None),
+            };
+            let Some(option) = subcommand
+                .options
+                .iter()
+                .chain(GLOBAL_OPTIONS)
+                .find(|option| option.name == name)
+            else {
+                return Err(args.usage_error(format!("Unknown option '{name}'!")));
+            };
+
+            let value = match (option.value, value) {
+                (Some(_), Some(value)) => Some(value),
+                (Some(value_name), None) => {
+                    Some(arguments.next().map(String::as_str).ok_or_else(|| {
+                        args.usage_error(format!("Missing the <{value_name}> for '{name}'!"))
+                    })?)
+                }
+                (None, Some(_)) => {
+                    return Err(args.usage_error(format!("'{name}' does not take a value!")))
+                }
+                (None, None) => None,
+            };
+
+            if args.options.iter().any(|(given, _)| *given == option.name) {
+                return Err(args.usage_error(format!("'{name}' was given more than once!")));
+            }
+            args.options.push((option.name, value));
+        }
+
+        if args.flag("--quiet") && args.flag("--verbose") {
+            return Err(args.usage_error("Give '--quiet' or '--verbose', not both!".to_owned()));
+        }
+
+        let required = subcommand
+            .arguments
+            .iter()
+            .filter(|argument| !argument.starts_with('['))
+            .count();
+        if let Some(missing) = subcommand.arguments.get(args.arguments.len()) {
+            if args.arguments.len() < required {
+                return Err(args.usage_error(format!("Missing the {missing} argument!")));
+            }
+        }
+        if let Some(extra) = args.arguments.get(subcommand.arguments.len()) {
+            return Err(args.usage_error(format!("Unexpected argument '{extra}'!")));
+        }
+
+        Ok(args)
+    }
+
+    /// Make an [`Error::Usage`] with the subcommand's usage
+    pub fn usage_error(&self, message: String) -> Error {
+        Error::Usage {
+            message,
+            usage: self.subcommand.usage(self.program),
+        }
+    }
+
+    #[must_use]
+    /// Get the name that the program was run with
+    pub const fn program(&self) -> &'a str {
+        self.program
+    }
+
+    #[must_use]
+    /// Get how much to print
+    pub fn verbosity(&self) -> Verbosity {
+        if self.flag("--quiet") {
+            Verbosity::Quiet
+        } else if self.flag("--verbose") {
+            Verbosity::Verbose
+        } else {
+            Verbosity::Normal
+        }
+    }
+
+    #[must_use]
+    /// Get a required positional argument
+    pub fn argument(&self, index: usize) -> &'a str {
+        self.arguments[index]
+    }
+
+    #[must_use]
+    /// Get an optional positional argument
+    pub fn optional_argument(&self, index: usize) -> Option<&'a str> {
+        self.arguments.get(index).copied()
+    }
+
+    #[must_use]
+    /// Check if an option was given
+    pub fn flag(&self, name: &str) -> bool {
+        self.options.iter().any(|(given, _)| *given == name)
+    }
+
+    #[must_use]
+    /// Get the value of an option, if it was given
+    pub fn value(&self, name: &str) -> Option<&'a str> {
+        self.options
+            .iter()
+            .find(|(given, _)| *given == name)
+            .and_then(|(_, value)| *value)
+    }
+
+    /// Get the value of an option that must be given
+    ///
+    /// # Errors
+    /// [`Error::Usage`] - the option was not given
+    pub fn required_value(&self, name: &str) -> Result<&'a str, Error> {
+        self.value(name)
+            .ok_or_else(|| self.usage_error(format!("Missing the '{name}' option!")))
+    }
+
+    /// Parse the value of an option, if it was given
+    ///
+    /// # Errors
+    /// [`Error::Usage`] - the value could not be parsed
+    pub fn parse_value<T: std::str::FromStr>(&self, name: &str) -> Result<Option<T>, Error> {
+        self.value(name)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| self.usage_error(format!("Invalid value '{value}' for '{name}'!")))
+            })
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Args, Opt, Subcommand, Verbosity};
+    use crate::error::Error;
+
+    const SUBCOMMAND: Subcommand = Subcommand {
+        name: "convert",
+        arguments: &["<in path>", "[out path]"],
+        options: &[
+            Opt::value("--to", "format", "the format"),
+            Opt::flag("--check", "only check"),
+        ],
+        description: "Convert a file",
+        run: |_| Ok(()),
+    };
+
+    fn parse(arguments: &[&str]) -> Result<Args<'static>, String> {
+        let arguments: Vec<String> = arguments.iter().map(ToString::to_string).collect();
+        Args::parse("lminc", &SUBCOMMAND, arguments.leak()).map_err(|error| match error {
+            Error::Usage { message, .. } => message,
+            error => panic!("got the wrong error: {error}"),
+        })
+    }
+
+    #[test]
+    fn parse_arguments() {
+        let args = parse(&["in", "--to", "json", "--check", "out"]).expect("failed to parse");
+        assert_eq!(
+            (args.argument(0), args.optional_argument(1)),
+            ("in", Some("out")),
+            "Got the wrong arguments!"
+        );
+        assert_eq!(args.value("--to"), Some("json"), "Got the wrong value!");
+        assert!(args.flag("--check"), "Did not get the flag!");
+
+        let args = parse(&["--to=hex", "in"]).expect("failed to parse");
+        assert_eq!(
+            (args.value("--to"), args.optional_argument(1)),
+            (Some("hex"), None),
+            "Failed to parse a value after '='!"
+        );
+        assert!(!args.flag("--check"), "Got a flag that was not given!");
+        assert_eq!(
+            args.verbosity(),
+            Verbosity::Normal,
+            "Got the wrong verbosity!"
+        );
+
+        let args = parse(&["--quiet", "in"]).expect("failed to parse a global option");
+        assert_eq!(
+            args.verbosity(),
+            Verbosity::Quiet,
+            "Got the wrong verbosity!"
+        );
+
+        for (arguments, message) in [
+            (&["in", "--bogus"][..], "Unknown option '--bogus'!"),
+            (&["in", "--to"], "Missing the <format> for '--to'!"),
+            (&["in", "--check=yes"], "'--check' does not take a value!"),
+            (
+                &["in", "--check", "--check"],
+                "'--check' was given more than once!",
+            ),
+            (&["--check"], "Missing the <in path> argument!"),
+            (&["in", "out", "more"], "Unexpected argument 'more'!"),
+            (
+                &["in", "--quiet", "--verbose"],
+                "Give '--quiet' or '--verbose', not both!",
+            ),
+        ] {
+            assert_eq!(
+                parse(arguments).err().as_deref(),
+                Some(message),
+                "Got the wrong error for {arguments:?}!"
+            );
+        }
+    }
+
+    #[test]
+    fn help() {
+        assert_eq!(
+            SUBCOMMAND.usage("lminc"),
+            "lminc convert <in path> [out path] [options...]",
+            "Got the wrong usage!"
+        );
+        assert_eq!(
+            SUBCOMMAND.help("lminc"),
+            "\
+Usage: lminc convert <in path> [out path] [options...]
+
+Convert a file
+
+Options:
+    --to <format>           the format
+    --check                 only check
+    --no-color              do not color the output, like setting NO_COLOR
+    --quiet                 only print errors and the summary of test runs,
+                            so that the exit code gives the result
+    --verbose               also print the number of cycles and the time taken
+    -h, --help              print this help
+",
+            "Got the wrong help!"
+        );
+    }
+}
@@ -0,0 +1,82 @@
+use std::{
+    env, fmt,
+    io::{self, IsTerminal},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+#[cfg(feature = "diagnostics")]
+use lminc::diagnostics::Diagnostic;
+use lminc::errors::Severity;
+
+/// Whether to color the output, see [`init`]
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Color the output if it is a terminal, unless `no_color` is set
+///  or the `NO_COLOR` environment variable is set (see <https://no-color.org/>)
+pub fn init(no_color: bool) {
+    let enabled = !no_color
+        && env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+        && io::stdout().is_terminal();
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Check if the output is colored
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+#[cfg(feature = "diagnostics")]
+/// Color a diagnostic, if the output is colored
+pub fn diagnostic(diagnostic: Diagnostic<'_>) -> Diagnostic<'_> {
+    if enabled() {
+        diagnostic.with_color()
+    } else {
+        diagnostic
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// A style for [`Color::paint`]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+}
+
+impl Color {
+    /// Get the ANSI escape code, which is bold as well
+    const fn code(self) -> &'static str {
+        match self {
+            Self::Red => "\x1b[1;31m",
+            Self::Green => "\x1b[1;32m",
+            Self::Yellow => "\x1b[1;33m",
+        }
+    }
+
+    /// Display `value` in this color, if the output is colored
+    pub const fn paint<T: fmt::Display>(self, value: T) -> Painted<T> {
+        Painted(self, value)
+    }
+}
+
+impl From<Severity> for Color {
+    fn from(value: Severity) -> Self {
+        match value {
+            Severity::Error => Self::Red,
+            Severity::Warning => Self::Yellow,
+        }
+    }
+}
+
+/// A value displayed in a [Color], see [`Color::paint`]
+pub struct Painted<T>(Color, T);
+
+impl<T: fmt::Display> fmt::Display for Painted<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if enabled() {
+            write!(f, "{}{}\x1b[0m", self.0.code(), self.1)
+        } else {
+            fmt::Display::fmt(&self.1, f)
+        }
+    }
+}
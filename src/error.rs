@@ -4,38 +4,69 @@ use std::{
     io,
 };
 
+#[cfg(feature = "toml")]
+use lminc::runner::tester::SuiteErrorWithTestNumber;
+#[cfg(feature = "json")]
+use lminc::runner::tester::{JSONError, JSONErrorWithTestNumber};
 use lminc::{
     assembler,
     errors::LineNumber,
-    file::FromFileError,
+    file::{DebugInfoErrorWithLineNumber, FromFileError},
     number_assembler, parser,
-    runner::{stdio, tester::CSVErrorWithLineNumber},
+    runner::{
+        stdio,
+        tester::{CSVErrorWithLineNumber, InlineTestErrorWithLineNumber},
+    },
 };
 
 #[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
 pub enum Error {
-    Usage(String),
+    Usage {
+        message: String,
+        usage: String,
+    },
     FileError(io::Error),
     ParseError(parser::ErrorWithLocation<LineNumber>),
     AssemblerError(assembler::ErrorWithInstructionNumber),
     NumberAssemblerError(number_assembler::ErrorWithLineNumber),
     LoadError(FromFileError),
+    InvalidDebugInfo(DebugInfoErrorWithLineNumber),
     RunnerError(stdio::Error),
     FromCSVError(CSVErrorWithLineNumber),
+    FromInlineTestError(InlineTestErrorWithLineNumber),
+    #[cfg(feature = "json")]
+    InvalidJSON(JSONError),
+    #[cfg(feature = "json")]
+    FromJSONError(JSONErrorWithTestNumber),
+    #[cfg(feature = "toml")]
+    InvalidTOML(toml::de::Error),
+    #[cfg(feature = "toml")]
+    FromSuiteError(SuiteErrorWithTestNumber),
     Custom(String),
 }
 
 impl Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::Usage(usage) => write!(f, "Usage: '{usage}'"),
+            Self::Usage { message, usage } => write!(f, "{message}\nUsage: '{usage}'"),
             Self::FileError(error) => write!(f, "File error: {error}"),
             Self::ParseError(error) => write!(f, "Error parsing file: {error}"),
             Self::AssemblerError(error) => write!(f, "Error assembling file: {error}"),
             Self::NumberAssemblerError(error) => write!(f, "Error assembling number file: {error}"),
             Self::LoadError(error) => write!(f, "Error loading binary file: {error}"),
+            Self::InvalidDebugInfo(error) => write!(f, "Error reading debug info: {error}"),
             Self::RunnerError(error) => fmt::Display::fmt(error, f),
             Self::FromCSVError(error) => write!(f, "Error reading CSV: {error}"),
+            Self::FromInlineTestError(error) => write!(f, "Error reading inline test: {error}"),
+            #[cfg(feature = "json")]
+            Self::InvalidJSON(error) => write!(f, "Error reading JSON: {error}"),
+            #[cfg(feature = "json")]
+            Self::FromJSONError(error) => write!(f, "Error reading JSON: {error}"),
+            #[cfg(feature = "toml")]
+            Self::InvalidTOML(error) => write!(f, "Error reading TOML: {error}"),
+            #[cfg(feature = "toml")]
+            Self::FromSuiteError(error) => write!(f, "Error reading TOML: {error}"),
             Self::Custom(message) => fmt::Display::fmt(message, f),
         }
     }
@@ -49,8 +80,18 @@ impl error::Error for Error {
             Self::AssemblerError(error) => Some(error),
             Self::NumberAssemblerError(error) => Some(error),
             Self::LoadError(error) => Some(error),
+            Self::InvalidDebugInfo(error) => Some(error),
             Self::RunnerError(error) => Some(error),
             Self::FromCSVError(error) => Some(error),
+            Self::FromInlineTestError(error) => Some(error),
+            #[cfg(feature = "json")]
+            Self::InvalidJSON(error) => Some(error),
+            #[cfg(feature = "json")]
+            Self::FromJSONError(error) => Some(error),
+            #[cfg(feature = "toml")]
+            Self::InvalidTOML(error) => Some(error),
+            #[cfg(feature = "toml")]
+            Self::FromSuiteError(error) => Some(error),
             _ => None,
         }
     }
@@ -74,8 +115,18 @@ from_impl!(
     Self::NumberAssemblerError
 );
 from_impl!(FromFileError, Self::LoadError);
+from_impl!(DebugInfoErrorWithLineNumber, Self::InvalidDebugInfo);
 from_impl!(stdio::Error, Self::RunnerError);
 from_impl!(CSVErrorWithLineNumber, Self::FromCSVError);
+from_impl!(InlineTestErrorWithLineNumber, Self::FromInlineTestError);
+#[cfg(feature = "json")]
+from_impl!(JSONError, Self::InvalidJSON);
+#[cfg(feature = "json")]
+from_impl!(JSONErrorWithTestNumber, Self::FromJSONError);
+#[cfg(feature = "toml")]
+from_impl!(toml::de::Error, Self::InvalidTOML);
+#[cfg(feature = "toml")]
+from_impl!(SuiteErrorWithTestNumber, Self::FromSuiteError);
 from_impl!(String, Self::Custom);
 
 impl From<&str> for Error {
@@ -6,117 +6,420 @@
     clippy::cargo
 )]
 
-use lminc::helper::case_insensitive::Str;
-use std::env;
+use lminc::file;
+use std::{env, process};
+
+mod args;
+use args::{Args, Opt, Subcommand};
+
+mod color;
+use color::Color;
 
 mod error;
 use error::Error;
 
 mod subcommands;
-use subcommands::{assemble, assemble_numbers, mem_dump, run, run_assembly, run_numbers, test};
+use subcommands::{
+    assemble, assemble_numbers, benchmark, bundle, check, convert, debug, disassemble, eval,
+    explain, fmt, init, mem_dump, run, run_assembly, run_numbers, symbols, test, test_assembly,
+    test_bundle,
+};
 
-macro_rules! HELP_TEXT {
-    () => {
-        "\
-Usage: {} <subcommand> <arguments...>
+/// The options of 'run', 'runAssembly' and 'runNumbers'
+const RUN_OPTIONS: &[Opt] = &[
+    Opt::value(
+        "--max-cycles",
+        "n",
+        "stop with an error after running n instructions,\nso that a program stuck in a loop does not run forever",
+    ),
+    Opt::value(
+        "--input",
+        "numbers",
+        "the number inputs, separated by commas (like '5,6,7')",
+    ),
+    Opt::value("--input-file", "path", "a file with one number input per line"),
+    #[cfg(feature = "extended")]
+    Opt::value("--char-input", "chars", "the char inputs (like 'abc')"),
+    #[cfg(feature = "extended")]
+    Opt::value(
+        "--char-input-file",
+        "path",
+        "a file with one char input per line",
+    ),
+];
 
-Subcommands:
-    help
-        Display this message
+const SUBCOMMANDS: &[Subcommand] = &[
+    Subcommand {
+        name: "help",
+        arguments: &["[subcommand]"],
+        options: &[],
+        description: "Display this message, or the help for one subcommand",
+        run: help,
+    },
+    Subcommand {
+        name: "assemble",
+        arguments: &["<in path>", "<out path>"],
+        options: &[
+            Opt::value(
+                "--listing",
+                "path",
+                "also write a listing (address, code and source) to path",
+            ),
+            Opt::flag(
+                "--debug-info",
+                "also write the debug info (source lines and labels)\nto a '.lmdbg' file next to the binary, for 'debug'",
+            ),
+        ],
+        description: "Assemble the assembly from an input and output a binary file",
+        run: assemble,
+    },
+    Subcommand {
+        name: "assembleNumbers",
+        arguments: &["<in path>", "<out path>"],
+        options: &[],
+        description: "Assemble the numbers from an input and output a binary file",
+        run: assemble_numbers,
+    },
+    Subcommand {
+        name: "run",
+        arguments: &["<path>"],
+        options: RUN_OPTIONS,
+        description: "\
+Run a binary file, or memory saved as JSON, Intel HEX or numbers.
+The inputs can be given with the options instead of typed, for scripts.
+When there is no char input, the char inputs are read from the number input",
+        run,
+    },
+    Subcommand {
+        name: "runAssembly",
+        arguments: &["<path>"],
+        options: RUN_OPTIONS,
+        description: "Run an assembly file, with the same options as 'run'",
+        run: run_assembly,
+    },
+    Subcommand {
+        name: "runNumbers",
+        arguments: &["<path>"],
+        options: RUN_OPTIONS,
+        description: "Run a number file, with the same options as 'run'",
+        run: run_numbers,
+    },
+    Subcommand {
+        name: "eval",
+        arguments: &["<assembly>"],
+        options: RUN_OPTIONS,
+        description: "\
+Assemble and run assembly given as an argument, with '\\n' between lines
+(like 'IN\\nOUT\\nHLT'), with the same options as 'run'",
+        run: eval,
+    },
+    Subcommand {
+        name: "debug",
+        arguments: &["<path>"],
+        options: &[Opt::value(
+            "--max-cycles",
+            "n",
+            "stop 'continue' after running n instructions,\nso that a program stuck in a loop does not run forever",
+        )],
+        description: "\
+Debug an assembly file, or memory in any format that 'run' reads,
+with breakpoints, stepping and inspection ('help' lists the commands).
+The source line is shown when stopped, using the '.lmdbg' file
+that 'assemble --debug-info' writes next to a binary",
+        run: debug,
+    },
+    Subcommand {
+        name: "memDump",
+        arguments: &["<path>"],
+        options: &[Opt::flag(
+            "--decode",
+            "show the instruction that each number runs as",
+        )],
+        description: "\
+Read the memory from a binary file and print it as a table,
+with '.' for zeros and '...' for rows of zeros, and the labels if the file has them",
+        run: mem_dump,
+    },
+    Subcommand {
+        name: "symbols",
+        arguments: &["<path>"],
+        options: &[],
+        description: "\
+Print the labels and their addresses, in address order, from an assembly file
+or from a binary file that was saved with them (by 'assemble')",
+        run: symbols,
+    },
+    Subcommand {
+        name: "fmt",
+        arguments: &["<path>"],
+        options: &[Opt::flag(
+            "--check",
+            "fail if the file is not already formatted, instead of rewriting it",
+        )],
+        description: "\
+Rewrite an assembly file in the canonical style (aligned columns, upper case instructions)",
+        run: fmt,
+    },
+    Subcommand {
+        name: "convert",
+        arguments: &["<in path>", "<out path>"],
+        options: &[
+            Opt::value(
+                "--to",
+                "format",
+                "the format to convert to (required): binary, container,\ncompressed (with the compression feature),\njson (with the json feature), hex (Intel HEX),\nnumbers or assembly",
+            ),
+            Opt::flag(
+                "--comments",
+                "with numbers, comment each number with its address\nand the instruction it runs as",
+            ),
+        ],
+        description: "\
+Convert memory in any format that 'run' reads (or assembly) to another format.
+Numbers can be assembled again with 'assembleNumbers'",
+        run: convert,
+    },
+    Subcommand {
+        name: "check",
+        arguments: &["<path>"],
+        options: &[],
+        description: "\
+Parse, assemble and lint an assembly file without running it,
+printing every error and warning (like unused labels), and failing if there are errors",
+        run: check,
+    },
+    Subcommand {
+        name: "disassemble",
+        arguments: &["<bin path>", "[out path]"],
+        options: &[],
+        description: "\
+Print the assembly of a binary file (or write it to the out path),
+with labels made up for the addresses that instructions use",
+        run: disassemble,
+    },
+    Subcommand {
+        name: "test",
+        arguments: &["<test path>", "<bin path>"],
+        options: &[
+            Opt::value(
+                "--filter",
+                "pattern",
+                "only run the tests with names matching the pattern",
+            ),
+            Opt::value(
+                "--skip",
+                "pattern",
+                "do not run the tests with names matching the pattern",
+            ),
+            Opt::flag("--fail-fast", "stop after the first test that fails"),
+            Opt::value(
+                "--report",
+                "format",
+                "print the report as json, junit (XML) or tap instead",
+            ),
+            Opt::value(
+                "--report-file",
+                "path",
+                "write the '--report' report to a file,\nand print the normal report",
+            ),
+        ],
+        description: "\
+Run the tests in a CSV file (or a JSON or TOML file, with the json or toml feature).
+Patterns with '*' (any characters) or '?' (one character) match whole names,
+other patterns match any name that contains them",
+        run: test,
+    },
+    Subcommand {
+        name: "benchmark",
+        arguments: &["<test path>", "<bin path>"],
+        options: &[],
+        description: "\
+Run the tests like 'test', and print the min, average and max cycles of each passing test",
+        run: benchmark,
+    },
+    Subcommand {
+        name: "testAssembly",
+        arguments: &["<path>"],
+        options: &[],
+        description: "Run the inline tests (';; test: in=5,6 out=11' comments) in an assembly file",
+        run: test_assembly,
+    },
+    Subcommand {
+        name: "bundle",
+        arguments: &["<assembly path>", "<test path>", "<out path>"],
+        options: &[],
+        description: "\
+Assemble the assembly and save it in a bundle with its source and the tests,
+so it can be shared as one file",
+        run: bundle,
+    },
+    Subcommand {
+        name: "testBundle",
+        arguments: &["<path>"],
+        options: &[],
+        description: "Run the tests in a bundle against its program",
+        run: test_bundle,
+    },
+    Subcommand {
+        name: "explain",
+        arguments: &["<instruction>"],
+        options: &[],
+        description: "\
+Explain what an instruction does, from its mnemonic (like 'STO'),
+its op-code (like '3') or the whole number (like '316')",
+        run: explain,
+    },
+    Subcommand {
+        name: "init",
+        arguments: &["<dir>"],
+        options: &[Opt::flag("--toml", "write the tests as TOML instead of CSV")],
+        description: "\
+Start an exercise in a directory, with an assembly file (with a header comment
+to describe the exercise) and a test file, both named after the directory",
+        run: init,
+    },
+    Subcommand {
+        name: "version",
+        arguments: &[],
+        options: &[Opt::flag(
+            "--json",
+            "print the version, the enabled features and the\nfile format versions as JSON, for other tools",
+        )],
+        description: "Print the version number",
+        run: version,
+    },
+    Subcommand {
+        name: "author",
+        arguments: &[],
+        options: &[],
+        description: "Information about the author",
+        run: author,
+    },
+];
 
-    assemble <in path> <out path>
-        Assemble the assembly from an input and output a binary file
+const AUTHOR_TEXT: &str = "\
+https://github.com/tomboddaert/lminc
+This program was created by:
 
-    assembleNumbers <in path> <out path>
-        Assemble the numbers from an input and output a binary file
+    Tom Boddaert
+        https://tomBoddaert.com/
+";
 
-    run <path>
-        Run a binary file
+const VERSION: Option<&str> = option_env!("CARGO_PKG_VERSION");
 
-    runAssembly <path>
-        Run an assembly file
+fn help(args: &Args) -> Result<(), Error> {
+    match args.optional_argument(0) {
+        Some(name) => {
+            let subcommand = Subcommand::find(SUBCOMMANDS, name)
+                .ok_or_else(|| unknown_subcommand(args.program(), name))?;
+            print!("{}", subcommand.help(args.program()));
+        }
+        None => print!("{}", args::help(args.program(), SUBCOMMANDS)),
+    }
 
-    runNumbers <path>
-        Run a number file
+    Ok(())
+}
 
-    memDump <path>
-        Read the memory from a binary file and print it out
+/// The cargo features that lminc was built with
+const FEATURES: &[&str] = &[
+    #[cfg(feature = "alloc")]
+    "alloc",
+    #[cfg(feature = "std")]
+    "std",
+    #[cfg(feature = "extended")]
+    "extended",
+    #[cfg(feature = "tui")]
+    "tui",
+    #[cfg(feature = "wasm")]
+    "wasm",
+    #[cfg(feature = "embedded-io")]
+    "embedded-io",
+    #[cfg(feature = "rayon")]
+    "rayon",
+    #[cfg(feature = "serde")]
+    "serde",
+    #[cfg(feature = "rand")]
+    "rand",
+    #[cfg(feature = "json")]
+    "json",
+    #[cfg(feature = "toml")]
+    "toml",
+    #[cfg(feature = "compression")]
+    "compression",
+    #[cfg(feature = "diagnostics")]
+    "diagnostics",
+    #[cfg(feature = "defmt")]
+    "defmt",
+];
 
-    test <test path> <bin path>
-        Run the tests in a CSV file
+// Subcommands return a result
+#[allow(clippy::unnecessary_wraps)]
+fn version(args: &Args) -> Result<(), Error> {
+    let version = VERSION.unwrap_or("unknown");
+    if !args.flag("--json") {
+        println!("LMinC version {version}");
+        return Ok(());
+    }
 
-    version
-        Print the version number
+    // The names and versions are all plain text, so they do not need escaping
+    let features = FEATURES
+        .iter()
+        .map(|feature| format!("\"{feature}\""))
+        .collect::<Vec<_>>()
+        .join(",");
+    println!(
+        "{{\"version\":\"{version}\",\"features\":[{features}],\"formats\":{{\"container\":{},\"bundle\":{},\"debug_info\":{}}}}}",
+        file::VERSION,
+        file::BUNDLE_VERSION,
+        file::DEBUG_INFO_VERSION
+    );
+    Ok(())
+}
 
-    author
-        Information about the author
-"
-    };
+#[allow(clippy::unnecessary_wraps)]
+fn author(_args: &Args) -> Result<(), Error> {
+    print!("{AUTHOR_TEXT}");
+    Ok(())
 }
 
-const AUTHOR_TEXT: &str = "\
-https://github.com/tomboddaert/lminc
-This program was created by:
+fn unknown_subcommand(program: &str, name: &str) -> Error {
+    format!("Unknown subcommand '{name}'!\n Use '{program} help' for the list of subcommands")
+        .into()
+}
 
-    Tom Boddaert
-        https://tomBoddaert.com/
-";
+/// Find the subcommand called `name`, then parse its arguments and run it
+fn run_subcommand(program: &str, name: &str, arguments: &[String]) -> Result<(), Error> {
+    if name == "-h" || name == "--help" {
+        print!("{}", args::help(program, SUBCOMMANDS));
+        return Ok(());
+    }
 
-const VERSION: Option<&str> = option_env!("CARGO_PKG_VERSION");
+    let subcommand =
+        Subcommand::find(SUBCOMMANDS, name).ok_or_else(|| unknown_subcommand(program, name))?;
+    if args::wants_help(arguments) {
+        print!("{}", subcommand.help(program));
+        return Ok(());
+    }
+
+    (subcommand.run)(&Args::parse(program, subcommand, arguments)?)
+}
 
 pub fn main() {
     // Get command line arguments
     let args: Vec<String> = env::args().collect();
+    let program = &args[0];
+
+    // Check for '--no-color' first, so that usage errors are not colored either
+    color::init(args.iter().any(|argument| argument == "--no-color"));
 
-    let Some(subcommand) = args.get(1) else {
+    let Some(name) = args.get(1) else {
         // If no command line arguments were given:
-        eprintln!(
-            "Usage '{} <subcommand> <arguments...>'\n Use '{} help' for help",
-            args[0], args[0]
-        );
+        eprintln!("Usage '{program} <subcommand> <arguments...>'\n Use '{program} help' for help");
         return;
     };
 
-    // Check the number of arguments and return a usage string if there are not the correct number
-    macro_rules! check_arguments {
-        ( $number:expr, $usage:expr, $fn:path ) => {
-            if args.len() != $number {
-                Err(Error::Usage(format!($usage, args[0])))
-            } else {
-                $fn(&args)
-            }
-        };
-    }
-
-    // Get the first command line argument, error if None
-    if let Err(error) = match Str::from(subcommand.as_str()) {
-        sc if sc == "help" => {
-            print!(HELP_TEXT!(), args[0]);
-            Ok(())
-        }
-        sc if sc == "assemble" => {
-            check_arguments!(4, "{} assemble <in path> <out path>", assemble)
-        }
-        sc if sc == "assembleNumbers" => check_arguments!(
-            4,
-            "{} assembleNumbers <in path> <out path>",
-            assemble_numbers
-        ),
-        sc if sc == "run" => check_arguments!(3, "{} run <path>", run),
-        sc if sc == "runAssembly" => check_arguments!(3, "{} runAssembly <path>", run_assembly),
-        sc if sc == "runNumbers" => check_arguments!(3, "{} runNumbers <path>", run_numbers),
-        sc if sc == "memDump" => check_arguments!(3, "{} memDump <path>", mem_dump),
-        sc if sc == "test" => check_arguments!(4, "{} test <test path> <bin path>", test),
-        sc if sc == "version" => {
-            println!("LMinC version {}", VERSION.unwrap_or("unknown"));
-            Ok(())
-        }
-        sc if sc == "author" => {
-            print!("{AUTHOR_TEXT}");
-            Ok(())
-        }
-        _ => Err("Unknown subcommand".into()),
-    } {
-        eprintln!("{error}");
+    if let Err(error) = run_subcommand(program, name, &args[2..]) {
+        eprintln!("{}", Color::Red.paint(error));
+        process::exit(1);
     }
 }
@@ -1,16 +1,81 @@
+#[cfg(feature = "diagnostics")]
+use lminc::diagnostics::Diagnostic;
+#[cfg(feature = "toml")]
+use lminc::runner::tester::TestSuite;
 use lminc::{
     assembler,
-    computer::Computer,
-    file, number_assembler,
-    runner::{stdio::Runner, tester::StdTest},
+    assembly::{Instruction, RawInstruction, MNEMONICS},
+    computer::Memory,
+    disassembler,
+    errors::{
+        Code, Diagnostics, Entry, ErrorWithLocation, InstructionNumber, LineNumber, Severity,
+    },
+    file, formatter,
+    helper::case_insensitive::Str,
+    lint,
+    num3::ThreeDigitNumber,
+    number_assembler, parser,
+    runner::{
+        debugger::Debugger,
+        stdio::{Retry, Runner},
+        tester::{AfterCycles, Filter, Outcome, Report, StdTest},
+    },
 };
 use std::{
+    ffi::OsStr,
+    fmt::{self, Write as _},
     fs::{self, File},
-    io::Read,
-    mem,
+    io::{self, BufRead, Read},
+    path::Path,
+    str,
+    time::{Duration, Instant},
 };
 
-use crate::error::Error;
+use crate::{
+    args::{Args, Verbosity},
+    color::Color,
+    error::Error,
+};
+
+#[cfg(feature = "diagnostics")]
+/// Make an error from a parse error, rendering it with its source
+fn parse_error(text: &str, error: parser::ErrorWithLocation<LineNumber>) -> Error {
+    Diagnostic::from_parse_error(text, &error)
+        .map(crate::color::diagnostic)
+        .map_or_else(|| error.into(), |diagnostic| diagnostic.to_string().into())
+}
+
+#[cfg(not(feature = "diagnostics"))]
+/// Make an error from a parse error
+fn parse_error(_text: &str, error: parser::ErrorWithLocation<LineNumber>) -> Error {
+    error.into()
+}
+
+/// Parse assembly, see [`parse_error`]
+fn parse_assembly(text: &str) -> Result<parser::Parser<'_>, Error> {
+    parser::Parser::parse_text(text).map_err(|error| parse_error(text, error))
+}
+
+#[cfg(feature = "diagnostics")]
+/// Assemble parsed assembly, rendering any error with its source
+fn assemble_parser(text: &str, parser: &parser::Parser) -> Result<Memory, Error> {
+    assembler::assemble_from_parser(*parser).map_err(|error| {
+        Diagnostic::from_assembler_error(text, &error)
+            .map(crate::color::diagnostic)
+            .map_or_else(|| error.into(), |diagnostic| diagnostic.to_string().into())
+    })
+}
+
+#[cfg(not(feature = "diagnostics"))]
+/// Assemble parsed assembly
+fn assemble_parser(_text: &str, parser: &parser::Parser) -> Result<Memory, Error> {
+    Ok(assembler::assemble_from_parser(*parser)?)
+}
+
+/// Parse and assemble assembly, see [`parse_assembly`] and [`assemble_parser`]
+fn assemble_text(text: &str) -> Result<Memory, Error> {
+    assemble_parser(text, &parse_assembly(text)?)
+}
 
 macro_rules! read_and_assemble {
     ( $path:expr, $fn:path ) => {{
@@ -24,142 +89,1067 @@ macro_rules! read_and_assemble {
     }};
 }
 
-pub fn assemble(args: &[String]) -> Result<(), Error> {
+/// Check if two paths are to the same file, so that an input is not overwritten
+fn same_file(a: &Path, b: &Path) -> bool {
+    a == b || matches!((fs::canonicalize(a), fs::canonicalize(b)), (Ok(a), Ok(b)) if a == b)
+}
+
+pub fn assemble(args: &Args) -> Result<(), Error> {
+    let in_path = Path::new(args.argument(0));
     // If <in path> == <out path>, error
-    if args[2] == args[3] {
+    if same_file(in_path, Path::new(args.argument(1))) {
         return Err("Cannot overwrite input assembly with output binary!".into());
     }
+    let listing_path = args.value("--listing");
+    if listing_path.is_some_and(|listing_path| same_file(in_path, Path::new(listing_path))) {
+        return Err("Cannot overwrite input assembly with the listing!".into());
+    }
+    let debug_info_path = args
+        .flag("--debug-info")
+        .then(|| Path::new(args.argument(1)).with_extension("lmdbg"));
+    if let Some(debug_info_path) = &debug_info_path {
+        if [args.argument(0), args.argument(1)]
+            .into_iter()
+            .chain(listing_path)
+            .any(|path| same_file(Path::new(path), debug_info_path))
+        {
+            return Err("Cannot overwrite another file with the debug info!".into());
+        }
+        // Only replace old debug info, not any other file
+        if fs::read_to_string(debug_info_path)
+            .is_ok_and(|text| file::DebugInfo::from_text(&text).is_err())
+        {
+            return Err(format!(
+                "'{}' already exists and is not debug info!",
+                debug_info_path.display()
+            )
+            .into());
+        }
+    }
 
-    // Load the file and assemble
-    let memory = read_and_assemble!(&args[2], assembler::assemble_from_text)??;
+    // Load the file and parse it
+    let mut file = File::open(in_path)?;
+    let mut buffer = String::new();
+    file.read_to_string(&mut buffer)?;
+    let parser = parse_assembly(&buffer)?;
 
-    // Write the assembled code to the output file
-    file::save(&args[3], memory)?;
+    // Keep the labels and the name of the program, then assemble
+    let mut metadata = file::Metadata::from_parser(&parser);
+    metadata.name = in_path
+        .file_stem()
+        .and_then(OsStr::to_str)
+        .map(ToString::to_string);
+    let memory = assemble_parser(&buffer, &parser)?;
+
+    // Write the assembled code to the output file, and the listing if asked for
+    if let Some(listing_path) = listing_path {
+        file::save_with_listing(args.argument(1), listing_path, memory, &metadata, &buffer)?;
+    } else {
+        file::save_with_metadata(args.argument(1), memory, &metadata)?;
+    }
+
+    // Write the debug info next to it if asked for
+    if let Some(debug_info_path) = debug_info_path {
+        let mut debug_info = file::DebugInfo::from_assembly(&buffer)?;
+        debug_info.source = Some(args.argument(0).to_owned());
+        fs::write(debug_info_path, debug_info.to_string())?;
+    }
 
     Ok(())
 }
 
-pub fn assemble_numbers(args: &[String]) -> Result<(), Error> {
+pub fn assemble_numbers(args: &Args) -> Result<(), Error> {
     // If <in path> == <out path>, error
-    if args[2] == args[3] {
+    if args.argument(0) == args.argument(1) {
         return Err("Cannot overwrite input numbers with output binary!".into());
     }
 
     // Load the file and assemble
     let memory = read_and_assemble!(
-        &args[2],
+        args.argument(0),
         number_assembler::NumberAssembler::assemble_from_text
     )?;
 
     // Write the assembled code to the output file
-    file::save(&args[3], memory)?;
+    file::save_container(args.argument(1), memory)?;
 
     Ok(())
 }
 
-pub fn run(args: &[String]) -> Result<(), Error> {
-    // Read the memory from the file
-    let memory = file::load(&args[2])?;
+/// Make an input that gives each of the values in turn
+fn scripted_input(values: impl Iterator<Item = impl AsRef<str>>) -> Box<dyn BufRead> {
+    let mut text = String::new();
+    for value in values {
+        text.push_str(value.as_ref());
+        text.push('\n');
+    }
+    Box::new(io::Cursor::new(text.into_bytes()))
+}
 
-    // Initialise the computer
-    let mut runner = Runner::new(memory);
+/// Open an input file, reading one input per line
+fn input_file(path: &str) -> Result<Box<dyn BufRead>, Error> {
+    Ok(Box::new(io::BufReader::new(File::open(path)?)))
+}
+
+/// Run memory with stdio, with the options:
+/// - `--input 5,6,7` or `--input-file <path>` (one per line) for the number inputs
+/// - `--char-input abc` or `--char-input-file <path>` (one per line) for the char inputs
+/// - `--max-cycles <n>` to stop after `n` cycles
+#[cfg_attr(not(feature = "extended"), allow(clippy::option_if_let_else))]
+fn run_with_options(memory: Memory, args: &Args) -> Result<(), Error> {
+    let max_cycles = args.parse_value::<u32>("--max-cycles")?;
+
+    let numbers = match (args.value("--input"), args.value("--input-file")) {
+        (Some(_), Some(_)) => {
+            return Err(args.usage_error("Give '--input' or '--input-file', not both!".to_owned()))
+        }
+        (Some(input), None) => Some(scripted_input(input.split(',').map(str::trim))),
+        (None, Some(path)) => Some(input_file(path)?),
+        (None, None) => None,
+    };
+    #[cfg(feature = "extended")]
+    let mut chars = match (args.value("--char-input"), args.value("--char-input-file")) {
+        (Some(_), Some(_)) => {
+            return Err(args
+                .usage_error("Give '--char-input' or '--char-input-file', not both!".to_owned()))
+        }
+        // A new line is an empty line
+        (Some(input), None) => Some(scripted_input(
+            input.chars().map(|char| char.to_string().replace('\n', "")),
+        )),
+        (None, Some(path)) => Some(input_file(path)?),
+        (None, None) => None,
+    };
+
+    #[cfg(not(feature = "extended"))]
+    let scripted = numbers.is_some();
+    #[cfg(feature = "extended")]
+    let scripted = numbers.is_some() || chars.is_some();
 
+    // Asking again would skip to the next scripted input
+    let retry = if scripted {
+        Retry::FailFast
+    } else {
+        Retry::Forever
+    };
+    let verbosity = args.verbosity();
+    let runner = || {
+        let mut runner = Runner::new(memory).with_retry(retry);
+        if verbosity == Verbosity::Quiet {
+            runner = runner.with_prompts(false);
+        }
+        match max_cycles {
+            Some(max_cycles) => runner.with_max_cycles(max_cycles),
+            None => runner,
+        }
+    };
+
+    match numbers {
+        Some(numbers) => run_runner(
+            runner().with_input(numbers),
+            #[cfg(feature = "extended")]
+            chars.as_mut(),
+            verbosity,
+        ),
+        None => run_runner(
+            runner(),
+            #[cfg(feature = "extended")]
+            chars.as_mut(),
+            verbosity,
+        ),
+    }
+}
+
+/// Run a [Runner] until it halts, reading the char inputs from `chars` if given,
+///  and printing the cycles and time taken if verbose
+#[cfg_attr(not(feature = "extended"), allow(clippy::elidable_lifetime_names))]
+fn run_runner<'a, R: BufRead>(
+    mut runner: Runner<'a, R>,
+    #[cfg(feature = "extended")] chars: Option<&'a mut Box<dyn BufRead>>,
+    verbosity: Verbosity,
+) -> Result<(), Error> {
+    #[cfg(feature = "extended")]
+    if let Some(chars) = chars {
+        runner = runner.with_char_input(chars);
+    }
+
+    let start = Instant::now();
     runner.run()?;
 
+    // On stderr, so that it is not mixed up with the program's output
+    if verbosity == Verbosity::Verbose {
+        eprintln!(
+            "Program stopped after {} fetch-execute cycles, in {:.2?}.",
+            runner.cycles(),
+            start.elapsed()
+        );
+    }
     Ok(())
 }
 
-pub fn run_assembly(args: &[String]) -> Result<(), Error> {
-    // Load the file and assemble
-    let memory = read_and_assemble!(&args[2], assembler::assemble_from_text)??;
+pub fn run(args: &Args) -> Result<(), Error> {
+    // Read the memory from the file, in any format
+    let memory = file::load_any(args.argument(0))?;
 
-    // Initialise the computer
-    let mut runner = Runner::new(memory);
+    // Initialise the computer and run it
+    run_with_options(memory, args)
+}
 
-    runner.run()?;
+pub fn run_assembly(args: &Args) -> Result<(), Error> {
+    // Load the file and assemble
+    let memory = read_and_assemble!(args.argument(0), assemble_text)?;
 
-    Ok(())
+    // Initialise the computer and run it
+    run_with_options(memory, args)
 }
 
-pub fn run_numbers(args: &[String]) -> Result<(), Error> {
+pub fn run_numbers(args: &Args) -> Result<(), Error> {
     // Load the file and assemble
     let memory = read_and_assemble!(
-        &args[2],
+        args.argument(0),
         number_assembler::NumberAssembler::assemble_from_text
     )?;
 
-    // Initialise the computer
-    let mut runner = Runner::new(memory);
+    // Initialise the computer and run it
+    run_with_options(memory, args)
+}
 
-    runner.run()?;
+pub fn eval(args: &Args) -> Result<(), Error> {
+    // Shells do not turn '\n' into a new line, so it is done here
+    let text = args.argument(0).replace("\\n", "\n");
+    let memory = assemble_text(&text)?;
+
+    // Initialise the computer and run it
+    run_with_options(memory, args)
+}
+
+/// Write an instruction's mnemonic, op-code, alternative mnemonics and what it does,
+///  replacing `xx` / `xxx` in the description with `data` if given
+fn explain_instruction(
+    out: &mut String,
+    instruction: Instruction<()>,
+    data: Option<ThreeDigitNumber>,
+) {
+    let op_code = match instruction {
+        Instruction::DAT(()) => "xxx".to_owned(),
+        _ if instruction.data().is_some() => {
+            format!("{}xx", u16::from(instruction.op_code()) / 100)
+        }
+        _ => format!("{:#}", instruction.op_code()),
+    };
+    let _ = write!(out, "{} ({op_code})", instruction.mnemonic());
+
+    let alternatives: Vec<&str> = MNEMONICS
+        .iter()
+        .filter(|(mnemonic, other)| *other == instruction && *mnemonic != instruction.mnemonic())
+        .map(|(mnemonic, _)| *mnemonic)
+        .collect();
+    if !alternatives.is_empty() {
+        let _ = write!(out, ", also written {}", alternatives.join(" or "));
+    }
+
+    let description = instruction.description();
+    match data {
+        Some(data) => {
+            let data = data.to_string();
+            let _ = writeln!(
+                out,
+                "\n    {}",
+                description.replace("xxx", &data).replace("xx", &data)
+            );
+        }
+        None => {
+            let _ = writeln!(out, "\n    {description}");
+        }
+    }
+}
+
+/// Explain an instruction, op-code or number, see [`explain`]
+fn explanation(query: &str) -> Result<String, Error> {
+    let mut out = String::new();
 
+    // One digit is an op-code, like '3' for '3xx'
+    if let &[digit @ b'0'..=b'9'] = query.as_bytes() {
+        let digit = u16::from(digit - b'0');
+        let mut instructions = MNEMONICS
+            .iter()
+            .filter(|(mnemonic, instruction)| {
+                *mnemonic == instruction.mnemonic()
+                    && !matches!(instruction, Instruction::DAT(()))
+                    && u16::from(instruction.op_code()) / 100 == digit
+            })
+            .peekable();
+
+        if instructions.peek().is_none() {
+            let _ = writeln!(
+                out,
+                "{digit}xx is not an instruction, running it stops the computer with an invalid instruction error"
+            );
+        }
+        for (_, instruction) in instructions {
+            explain_instruction(&mut out, *instruction, None);
+        }
+        match digit {
+            0 => {
+                let _ = writeln!(out, "The other 0xx numbers also run as HLT");
+            }
+            9 => {
+                let _ = writeln!(
+                    out,
+                    "The other 9xx numbers are not instructions, running them stops the computer with an invalid instruction error"
+                );
+            }
+            _ => (),
+        }
+        return Ok(out);
+    }
+
+    // More digits are a whole instruction, like '316'
+    if let Ok(number) = query.parse::<u16>() {
+        let number = ThreeDigitNumber::try_from(number)
+            .map_err(|_| Error::from(format!("'{query}' is larger than 999!")))?;
+
+        match RawInstruction::executed(number) {
+            Some(instruction) => {
+                let _ = writeln!(out, "{number:#} runs as '{instruction}'");
+                explain_instruction(
+                    &mut out,
+                    instruction.map_data(|_| ()),
+                    instruction.data().copied(),
+                );
+            }
+            None => {
+                let _ = writeln!(
+                    out,
+                    "{number:#} is not an instruction, running it stops the computer with an invalid instruction error"
+                );
+            }
+        }
+        return Ok(out);
+    }
+
+    let instruction = Instruction::try_from(query)
+        .map_err(|_| Error::from(format!("Unknown instruction '{query}'!")))?;
+    explain_instruction(&mut out, instruction, None);
+
+    Ok(out)
+}
+
+pub fn explain(args: &Args) -> Result<(), Error> {
+    print!("{}", explanation(args.argument(0))?);
     Ok(())
 }
 
-pub fn mem_dump(args: &[String]) -> Result<(), Error> {
-    // Read the memory from the file
-    let memory = file::load(&args[2])?;
+/// Load the debug info saved next to a binary, and the source it names
+fn load_source(path: &str) -> Result<Option<(String, file::DebugInfo)>, Error> {
+    let Ok(text) = fs::read_to_string(Path::new(path).with_extension("lmdbg")) else {
+        return Ok(None);
+    };
+    let debug_info = file::DebugInfo::from_text(&text)?;
+
+    Ok(debug_info
+        .source
+        .as_ref()
+        .and_then(|source| fs::read_to_string(source).ok())
+        .map(|source| (source, debug_info)))
+}
+
+/// Get the text of a file if it is assembly, which is text that is not a saved memory format
+fn as_assembly(buffer: &[u8]) -> Option<&str> {
+    str::from_utf8(buffer)
+        .ok()
+        .filter(|_| file::detect_format(buffer) == file::Format::Legacy)
+}
+
+pub fn debug(args: &Args) -> Result<(), Error> {
+    let buffer = fs::read(args.argument(0))?;
+
+    let (memory, source) = match as_assembly(&buffer) {
+        Some(text) => {
+            let memory = assemble_text(text)?;
+            let debug_info = file::DebugInfo::from_assembly(text)?;
+            (memory, Some((text.to_string(), debug_info)))
+        }
+        None => (
+            file::load_any_from_buffer(&buffer)?,
+            load_source(args.argument(0))?,
+        ),
+    };
+
+    println!("Type 'help' for the commands");
+    let mut debugger = Debugger::new(memory);
+    if let Some(max_cycles) = args.parse_value::<u32>("--max-cycles")? {
+        debugger = debugger.with_max_cycles(max_cycles);
+    }
+    let (input, output) = (io::stdin().lock(), io::stdout().lock());
+    match source {
+        Some((source, debug_info)) => {
+            debugger.run_interactive_with_source(input, output, &source, &debug_info)?;
+        }
+        None => debugger.run_interactive(input, output)?,
+    }
+
+    Ok(())
+}
+
+pub fn mem_dump(args: &Args) -> Result<(), Error> {
+    // Read the memory and any metadata from the file
+    let (memory, metadata) = file::load_with_metadata(args.argument(0))?;
+
+    let mut table = String::new();
+    // Writing to a String does not fail
+    let _ = if args.flag("--decode") {
+        disassembler::write_decoded_table(&mut table, memory)
+    } else {
+        disassembler::write_table(&mut table, memory)
+    };
+    print!("{table}");
+
+    // Print the labels, if the file has them
+    if let Some(metadata) = metadata.filter(|metadata| !metadata.symbols.is_empty()) {
+        println!("Labels:");
+        for (label, address) in &metadata.symbols {
+            println!("  {address:02} {label}");
+        }
+    }
+
+    Ok(())
+}
+
+pub fn symbols(args: &Args) -> Result<(), Error> {
+    let buffer = fs::read(args.argument(0))?;
+
+    // Find the labels in assembly, or read the ones saved in a binary
+    let symbols = match as_assembly(&buffer) {
+        Some(text) => file::Metadata::from_parser(&parse_assembly(text)?).symbols,
+        None => {
+            file::load_with_metadata_from_buffer(&buffer)
+                .map_err(file::FromFileError::from)?
+                .1
+                .ok_or("The binary has no symbols, assemble it with 'assemble' to keep them!")?
+                .symbols
+        }
+    };
+
+    // In address order, for looking up raw addresses
+    let mut symbols: Vec<(usize, String)> = symbols
+        .into_iter()
+        .map(|(label, address)| (address, label))
+        .collect();
+    symbols.sort();
+
+    let width = symbols
+        .iter()
+        .map(|(_, label)| label.chars().count())
+        .max()
+        .unwrap_or_default();
+    for (address, label) in symbols {
+        println!("{label:width$}  {address:02}");
+    }
+
+    Ok(())
+}
+
+pub fn fmt(args: &Args) -> Result<(), Error> {
+    let (check, path) = (args.flag("--check"), args.argument(0));
+
+    let text = fs::read_to_string(path)?;
+    let formatted = formatter::format(&text).map_err(|error| parse_error(&text, error))?;
+
+    if formatted == text {
+        return Ok(());
+    }
+    if check {
+        return Err(format!("{path} is not formatted!").into());
+    }
+
+    fs::write(path, formatted)?;
+
+    Ok(())
+}
+
+pub fn convert(args: &Args) -> Result<(), Error> {
+    let (in_path, out_path) = (args.argument(0), args.argument(1));
+    let format = args.required_value("--to")?;
+    let comments = args.flag("--comments");
+    if comments && Str::from(format) != "numbers" {
+        return Err(args.usage_error("'--comments' only works with '--to numbers'!".to_owned()));
+    }
+
+    // Read the memory in any format, or assemble it
+    let buffer = fs::read(in_path)?;
+    let memory = match as_assembly(&buffer) {
+        Some(text) => assemble_text(text)?,
+        None => file::load_any_from_buffer(&buffer)?,
+    };
+
+    match Str::from(format) {
+        format if format == "binary" => file::save(out_path, memory)?,
+        format if format == "container" => file::save_container(out_path, memory)?,
+        #[cfg(feature = "compression")]
+        format if format == "compressed" => {
+            file::save_compressed(out_path, memory, &file::Metadata::default())?;
+        }
+        #[cfg(feature = "json")]
+        format if format == "json" => fs::write(out_path, file::save_json(memory))?,
+        format if format == "hex" => fs::write(out_path, file::save_intel_hex(memory))?,
+        format if format == "numbers" && comments => {
+            fs::write(out_path, file::save_commented_numbers(memory))?;
+        }
+        format if format == "numbers" => fs::write(out_path, file::save_numbers(memory))?,
+        format if format == "assembly" => {
+            fs::write(out_path, disassembler::disassemble_with_labels(memory))?;
+        }
+        _ => return Err(format!("Unknown format '{format}'!").into()),
+    }
+
+    Ok(())
+}
+
+/// Print a problem found by [`check`] (rendered with its source if possible) and record it.
+/// Warnings are not printed if quiet
+fn report<E: Code + fmt::Display>(
+    diagnostics: &mut Diagnostics,
+    verbosity: Verbosity,
+    severity: Severity,
+    problem: &E,
+    rendered: Option<String>,
+) {
+    diagnostics.push(Entry::new(severity, problem));
+    if verbosity == Verbosity::Quiet && severity == Severity::Warning {
+        return;
+    }
+
+    match rendered {
+        Some(rendered) => println!("{rendered}"),
+        None => println!("{}: {problem}\n", Color::from(severity).paint(severity)),
+    }
+}
 
-    // Cast to a u16 array to fix formatting
-    let memory: [u16; 100] = unsafe { mem::transmute(memory) };
+pub fn check(args: &Args) -> Result<(), Error> {
+    let text = fs::read_to_string(args.argument(0))?;
+
+    // Render a problem with its source, if the diagnostics feature is enabled
+    macro_rules! render {
+        ( $from:ident, $problem:expr ) => {{
+            #[cfg(feature = "diagnostics")]
+            let rendered = Diagnostic::$from(&text, $problem)
+                .map(|diagnostic| crate::color::diagnostic(diagnostic).to_string());
+            #[cfg(not(feature = "diagnostics"))]
+            let rendered = None;
+            rendered
+        }};
+    }
 
-    println!("{memory:?}");
+    let verbosity = args.verbosity();
+    let mut diagnostics = Diagnostics::new();
+
+    // Parse every line, carrying on after errors to find them all
+    let mut parser = parser::Parser::new();
+    for (index, line) in text.lines().enumerate() {
+        if let Err(error) = parser.parse_line(line) {
+            let error = error.map_location(|_| LineNumber(index + 1));
+            let rendered = render!(from_parse_error, &error);
+            report(
+                &mut diagnostics,
+                verbosity,
+                Severity::Error,
+                &error,
+                rendered,
+            );
+        }
+    }
+
+    // Assembling and linting need every line to have parsed
+    if diagnostics.is_empty() {
+        for (index, instruction) in parser.iter().enumerate() {
+            if let Err(error) = assembler::assemble_instruction(instruction.instruction, &parser) {
+                let error = ErrorWithLocation(InstructionNumber(index + 1), error);
+                let rendered = render!(from_assembler_error, &error);
+                report(
+                    &mut diagnostics,
+                    verbosity,
+                    Severity::Error,
+                    &error,
+                    rendered,
+                );
+            }
+        }
+
+        for lint in lint::lint(&text)? {
+            let rendered = render!(from_lint, &lint);
+            report(
+                &mut diagnostics,
+                verbosity,
+                Severity::Warning,
+                &lint,
+                rendered,
+            );
+        }
+    }
+
+    if diagnostics.has_errors() {
+        return Err(diagnostics.summary().to_string().into());
+    }
+    if verbosity != Verbosity::Quiet {
+        println!("{}", diagnostics.summary());
+    }
+
+    Ok(())
+}
+
+pub fn disassemble(args: &Args) -> Result<(), Error> {
+    // If <bin path> == [out path], error
+    if args.optional_argument(1) == Some(args.argument(0)) {
+        return Err("Cannot overwrite input binary with output assembly!".into());
+    }
+
+    // Read the memory from the file, in any format
+    let memory = file::load_any(args.argument(0))?;
+    let assembly = disassembler::disassemble_with_labels(memory);
+
+    // Write the assembly to the output file, or print it
+    match args.optional_argument(1) {
+        Some(path) => fs::write(path, assembly)?,
+        None => print!("{assembly}"),
+    }
 
     Ok(())
 }
 
-pub fn test(args: &[String]) -> Result<(), Error> {
-    // Read the CSV file
-    let mut file = fs::File::open(&args[2])?;
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// The machine-readable formats that a test report can be written in
+enum ReportFormat {
+    Json,
+    JUnit,
+    Tap,
+}
+
+/// The options of the test subcommand
+struct TestOptions<'a> {
+    verbosity: Verbosity,
+    filter: Filter<'a>,
+    report: Option<ReportFormat>,
+    report_file: Option<&'a str>,
+}
+
+impl<'a> TestOptions<'a> {
+    /// Parse the options:
+    /// - `--filter <pattern>` to only run the tests with names matching the pattern
+    /// - `--skip <pattern>` to not run the tests with names matching the pattern
+    /// - `--fail-fast` to stop after the first test that fails
+    /// - `--report json|junit|tap` to write the report in a machine-readable format
+    /// - `--report-file <path>` to write that report to a file, instead of the normal output
+    fn parse(args: &Args<'a>) -> Result<Self, Error> {
+        let mut filter = Filter::new();
+        if let Some(pattern) = args.value("--filter") {
+            filter = filter.with_name(pattern);
+        }
+        if let Some(pattern) = args.value("--skip") {
+            filter = filter.with_skip(pattern);
+        }
+        if args.flag("--fail-fast") {
+            filter = filter.with_fail_fast();
+        }
+
+        let report = args
+            .value("--report")
+            .map(|value| match Str::from(value) {
+                format if format == "json" => Ok(ReportFormat::Json),
+                format if format == "junit" => Ok(ReportFormat::JUnit),
+                format if format == "tap" => Ok(ReportFormat::Tap),
+                _ => Err(args.usage_error(format!("Unknown report format '{value}'!"))),
+            })
+            .transpose()?;
+
+        let report_file = args.value("--report-file");
+        if report_file.is_some() && report.is_none() {
+            return Err(
+                args.usage_error("'--report-file' needs a format from '--report'!".to_owned())
+            );
+        }
+
+        Ok(Self {
+            verbosity: args.verbosity(),
+            filter,
+            report,
+            report_file,
+        })
+    }
+
+    /// Print the report, and write it in the machine-readable format (if there is one)
+    ///  to the report file or instead of printing it.
+    /// `suite_name` is the name of the suite in `JUnit` reports
+    fn write_report(
+        &self,
+        report: &Report,
+        elapsed: Duration,
+        suite_name: &str,
+    ) -> Result<(), Error> {
+        let Some(format) = self.report else {
+            print_report(report, self.verbosity, elapsed);
+            return Ok(());
+        };
+
+        let mut text = String::new();
+        // Writing to a String does not fail
+        let _ = match format {
+            ReportFormat::Json => report.write_json(&mut text),
+            ReportFormat::JUnit => report.write_junit(&mut text, suite_name),
+            ReportFormat::Tap => report.write_tap(&mut text),
+        };
+
+        match self.report_file {
+            Some(path) => {
+                print_report(report, self.verbosity, elapsed);
+                fs::write(path, text)?;
+            }
+            None => println!("{}", text.trim_end()),
+        }
+
+        Ok(())
+    }
+}
+
+/// Run the tests in the first argument that match the filter against the binary in the second,
+///  and give the report and the time taken to `f`
+fn run_tests<T>(
+    args: &Args,
+    filter: Filter,
+    f: impl FnOnce(&Report, Duration) -> T,
+) -> Result<T, Error> {
+    // Read the CSV (or JSON or TOML) file
+    let mut file = fs::File::open(args.argument(0))?;
     let mut buffer = String::new();
     file.read_to_string(&mut buffer)?;
 
-    let tests = StdTest::from_csv(&buffer);
-
     // Read the memory from the file
-    let memory = file::load(&args[3])?;
+    let memory = file::load(args.argument(1))?;
+
+    let format = file::TestsFormat::from_extension(
+        Path::new(args.argument(0))
+            .extension()
+            .and_then(OsStr::to_str)
+            .unwrap_or_default(),
+    );
+    run_tests_text(memory, &buffer, format, filter, f)
+}
+
+/// Run the tests in `text` that match the filter against the memory,
+///  and give the report and the time taken to `f`
+fn run_tests_text<T>(
+    memory: Memory,
+    text: &str,
+    format: file::TestsFormat,
+    filter: Filter,
+    f: impl FnOnce(&Report, Duration) -> T,
+) -> Result<T, Error> {
+    let start = Instant::now();
+    #[cfg(feature = "toml")]
+    let suite;
+    let report = match format {
+        file::TestsFormat::CSV => Report::run_matching(memory, StdTest::from_csv(text), filter)?,
+        #[cfg(feature = "json")]
+        file::TestsFormat::JSON => Report::run_matching(memory, StdTest::from_json(text)?, filter)?,
+        #[cfg(feature = "toml")]
+        file::TestsFormat::TOML => {
+            // Suites run by stage
+            suite = TestSuite::from_toml(text)?;
+            suite.run_matching(memory, filter)?
+        }
+        #[cfg(not(all(feature = "json", feature = "toml")))]
+        _ => return Err("The tests need the json or toml feature!".into()),
+    };
+
+    Ok(f(&report, start.elapsed()))
+}
+
+/// Fail if any of the tests failed, so that the exit code gives the result
+fn tests_result(report: &Report) -> Result<(), Error> {
+    if report.all_passed() {
+        Ok(())
+    } else {
+        Err("Some tests failed!".into())
+    }
+}
+
+pub fn test(args: &Args) -> Result<(), Error> {
+    let options = TestOptions::parse(args)?;
+    // Name the suite after the test file
+    let suite_name = Path::new(args.argument(0))
+        .file_stem()
+        .and_then(OsStr::to_str)
+        .unwrap_or("tests");
+
+    run_tests(args, options.filter, |report, elapsed| {
+        options.write_report(report, elapsed, suite_name)?;
+        tests_result(report)
+    })?
+}
+
+pub fn benchmark(args: &Args) -> Result<(), Error> {
+    run_tests(args, Filter::new(), |report, _| {
+        println!("{}", report.benchmark());
+
+        let failed = report.failed();
+        if failed != 0 {
+            println!("{failed} tests failed and were not counted!");
+        }
+    })
+}
+
+/// Check that a file for [`init`] does not exist, so that it is not overwritten
+fn check_free(path: &Path) -> Result<(), Error> {
+    if path.exists() {
+        return Err(format!("'{}' already exists!", path.display()).into());
+    }
+    Ok(())
+}
+
+pub fn init(args: &Args) -> Result<(), Error> {
+    let dir = Path::new(args.argument(0));
+    // Name the files after the directory
+    let name = dir.file_name().and_then(OsStr::to_str).ok_or_else(|| {
+        Error::from(format!(
+            "Cannot name an exercise after '{}'!",
+            dir.display()
+        ))
+    })?;
+
+    let (extension, tests) = if args.flag("--toml") {
+        (
+            "toml",
+            "\
+# The tests for the exercise, see the 'test' subcommand
+max_cycles = 100
+
+[[test]]
+name = \"echo\"
+description = \"Outputs its input\"
+inputs = [5]
+outputs = [5]
+",
+        )
+    } else {
+        // Each line is 'name;inputs;outputs;maximum cycles', see the 'test' subcommand
+        ("csv", "echo;5;5;100\n")
+    };
+    let program_path = dir.join(format!("{name}.txt"));
+    let tests_path = dir.join(format!("{name}_test.{extension}"));
+
+    let program = format!(
+        "\
+# {name}
+#
+# Describe the exercise here: what the program inputs, and what it should output.
+#
+# Check the program with:
+#   lminc check {name}.txt
+# Run the tests with:
+#   lminc assemble {name}.txt {name}.bin
+#   lminc test {name}_test.{extension} {name}.bin
+
+IN    # input a number
+OUT   # output it
+HLT   # stop
+"
+    );
+
+    // Check both files first, so that an error does not leave half an exercise
+    check_free(&program_path)?;
+    check_free(&tests_path)?;
+
+    fs::create_dir_all(dir)?;
+    fs::write(&program_path, program)?;
+    fs::write(&tests_path, tests)?;
+
+    if args.verbosity() != Verbosity::Quiet {
+        println!(
+            "Created '{}' and '{}'",
+            program_path.display(),
+            tests_path.display()
+        );
+    }
+
+    Ok(())
+}
+
+pub fn bundle(args: &Args) -> Result<(), Error> {
+    // If <out path> is one of the inputs, error
+    if args.argument(2) == args.argument(0) || args.argument(2) == args.argument(1) {
+        return Err("Cannot overwrite input files with output bundle!".into());
+    }
+
+    // Load the assembly and tests
+    let source = fs::read_to_string(args.argument(0))?;
+    let tests = fs::read_to_string(args.argument(1))?;
+
+    // Keep the labels and the name of the program, then assemble
+    let parser = parse_assembly(&source)?;
+    let mut metadata = file::Metadata::from_parser(&parser);
+    metadata.name = Path::new(args.argument(0))
+        .file_stem()
+        .and_then(OsStr::to_str)
+        .map(ToString::to_string);
+    let memory = assemble_parser(&source, &parser)?;
+
+    let format = file::TestsFormat::from_extension(
+        Path::new(args.argument(1))
+            .extension()
+            .and_then(OsStr::to_str)
+            .unwrap_or_default(),
+    );
+    let bundle = file::Bundle::new(memory, metadata)
+        .with_source(source)
+        .with_tests(file::Tests {
+            format,
+            text: tests,
+        });
+
+    file::save_bundle(args.argument(2), &bundle)?;
+
+    Ok(())
+}
+
+pub fn test_bundle(args: &Args) -> Result<(), Error> {
+    let bundle = file::load_bundle(args.argument(0))?;
 
-    // Initialise the computer
-    let mut computer = Computer::new(memory);
+    let Some(tests) = bundle.tests else {
+        return Err("The bundle has no tests!".into());
+    };
+    let verbosity = args.verbosity();
+    run_tests_text(
+        bundle.memory,
+        &tests.text,
+        tests.format,
+        Filter::new(),
+        |report, elapsed| {
+            print_report(report, verbosity, elapsed);
+            tests_result(report)
+        },
+    )?
+}
+
+pub fn test_assembly(args: &Args) -> Result<(), Error> {
+    // Load the file
+    let mut file = fs::File::open(args.argument(0))?;
+    let mut buffer = String::new();
+    file.read_to_string(&mut buffer)?;
 
-    let mut failed = 0;
-    let mut succeeded = 0;
+    // Assemble, then run the tests from the comments
+    let memory = assemble_text(&buffer)?;
+    let start = Instant::now();
+    let report = Report::run(memory, StdTest::from_assembly_comments(&buffer))?;
+    let elapsed = start.elapsed();
+
+    if report.tests.is_empty() {
+        return Err("No inline tests found!".into());
+    }
+    print_report(&report, args.verbosity(), elapsed);
+
+    tests_result(&report)
+}
 
+/// Print the report, with each test unless quiet, and the cycles and time taken if verbose
+fn print_report(report: &Report, verbosity: Verbosity, elapsed: Duration) {
+    let tests = match verbosity {
+        Verbosity::Quiet => &[][..],
+        Verbosity::Normal | Verbosity::Verbose => &report.tests,
+    };
     for test in tests {
-        let test = test?;
         test.name.map_or_else(
             || println!("Running test:"),
             |name| println!("Running test '{name}':"),
         );
 
-        // Reset the computer and the test
-        computer.reset();
-        let cycles = match test.run(&mut computer) {
-            Ok(cycles) => {
-                println!("  Test ran successfully.\n  Program {}", computer.state());
-                succeeded += 1;
-                cycles
+        match test.outcome {
+            Outcome::Passed(state) => println!(
+                "  {}\n  Program {state}",
+                Color::Green.paint("Test ran successfully.")
+            ),
+            Outcome::Failed(error) => println!(
+                "  {}: {}",
+                Color::Red.paint("Error"),
+                ErrorWithLocation(AfterCycles(test.cycles), error)
+            ),
+            Outcome::Skipped => {
+                // Skipped tests did not run, so they have no cycles
+                println!(
+                    "  {}\n",
+                    Color::Yellow.paint("Skipped, as a stage it needs did not pass.")
+                );
+                continue;
             }
-            Err(error) => {
-                println!("  Error: {}", error.1);
-                failed += 1;
-                error.1 .0 .0
-            }
-        };
+        }
+
+        if let (Outcome::Failed(_), Some(seed)) = (test.outcome, test.seed) {
+            println!("  Replay with seed {seed}.");
+        }
 
         // Print the number of cycles
-        println!("  Program stopped after {cycles} fetch-execute cycles.\n",);
+        println!(
+            "  Program stopped after {} fetch-execute cycles.\n",
+            test.cycles
+        );
     }
 
+    let succeeded = report.passed();
+    let failed = report.failed();
+
     // Print success and failure
     println!("{succeeded} tests ran successfully.\n{failed} tests failed.");
+    match report.skipped() {
+        0 => (),
+        skipped => println!("{skipped} tests were skipped."),
+    }
 
-    // Print successful
-    if failed == 0 {
-        println!("All tests run successfully!");
-    } else {
-        println!("Some tests failed!");
+    if verbosity == Verbosity::Verbose {
+        let cycles: u64 = report.tests.iter().map(|test| u64::from(test.cycles)).sum();
+        println!("The tests ran {cycles} fetch-execute cycles, in {elapsed:.2?}.");
     }
 
-    Ok(())
+    // Print successful, failures are returned as an error by the subcommand
+    if failed == 0 && verbosity != Verbosity::Quiet {
+        println!("{}", Color::Green.paint("All tests run successfully!"));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::explanation;
+
+    fn explain(query: &str) -> String {
+        explanation(query).expect("failed to explain")
+    }
+
+    #[test]
+    fn explain_numbers() {
+        assert!(
+            explain("316").starts_with("316 runs as 'STO 16'\nSTO (3xx)"),
+            "Explained the wrong instruction!"
+        );
+        assert!(
+            explain("001").starts_with("001 runs as 'HLT'\nHLT (000)"),
+            "Failed to explain that 001 halts!"
+        );
+        assert_eq!(
+            explain("405"),
+            "405 is not an instruction, running it stops the computer with an invalid instruction error\n",
+            "Failed to explain that 405 is invalid!"
+        );
+        assert_eq!(
+            explain("903"),
+            "903 is not an instruction, running it stops the computer with an invalid instruction error\n",
+            "Failed to explain that 903 is invalid!"
+        );
+        assert_eq!(
+            explain("4"),
+            "4xx is not an instruction, running it stops the computer with an invalid instruction error\n",
+            "Failed to explain that 4xx is invalid!"
+        );
+        assert!(
+            explanation("1000").is_err(),
+            "Failed to reject a number that is too large!"
+        );
+    }
 }